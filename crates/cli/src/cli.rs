@@ -27,3 +27,7 @@ pub enum CliResponse {
 /// When Zed started not as an *.app but as a binary (e.g. local development),
 /// there's a possibility to tell it to behave "regularly".
 pub const FORCE_CLI_MODE_ENV_VAR_NAME: &str = "ZED_FORCE_CLI_MODE";
+
+/// Starts Zed without loading any extensions, to check whether one of them
+/// is causing a problem.
+pub const DISABLE_EXTENSIONS_ARG: &str = "--disable-extensions";
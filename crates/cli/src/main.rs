@@ -36,6 +36,10 @@ struct Args {
     /// Custom Zed.app path
     #[clap(short, long)]
     bundle_path: Option<PathBuf>,
+    /// Start Zed without loading any extensions, to check whether one of them
+    /// is causing a problem. Has no effect if Zed is already running.
+    #[clap(long)]
+    disable_extensions: bool,
 }
 
 fn parse_path_with_position(
@@ -84,7 +88,7 @@ fn main() -> Result<()> {
         paths.push(canonicalized.to_string(|path| path.display().to_string()))
     }
 
-    let (tx, rx) = bundle.launch()?;
+    let (tx, rx) = bundle.launch(args.disable_extensions)?;
     let open_new_workspace = if args.new {
         Some(true)
     } else if args.add {
@@ -155,7 +159,10 @@ mod linux {
             unimplemented!()
         }
 
-        pub fn launch(&self) -> anyhow::Result<(IpcSender<CliRequest>, IpcReceiver<CliResponse>)> {
+        pub fn launch(
+            &self,
+            _disable_extensions: bool,
+        ) -> anyhow::Result<(IpcSender<CliRequest>, IpcReceiver<CliResponse>)> {
             unimplemented!()
         }
 
@@ -188,7 +195,10 @@ mod windows {
             unimplemented!()
         }
 
-        pub fn launch(&self) -> anyhow::Result<(IpcSender<CliRequest>, IpcReceiver<CliResponse>)> {
+        pub fn launch(
+            &self,
+            _disable_extensions: bool,
+        ) -> anyhow::Result<(IpcSender<CliRequest>, IpcReceiver<CliResponse>)> {
             unimplemented!()
         }
 
@@ -209,7 +219,10 @@ mod mac_os {
     use core_services::{kLSLaunchDefaults, LSLaunchURLSpec, LSOpenFromURLSpec, TCFType};
     use std::{fs, path::Path, ptr};
 
-    use cli::{CliRequest, CliResponse, IpcHandshake, FORCE_CLI_MODE_ENV_VAR_NAME};
+    use cli::{
+        CliRequest, CliResponse, IpcHandshake, DISABLE_EXTENSIONS_ARG,
+        FORCE_CLI_MODE_ENV_VAR_NAME,
+    };
     use ipc_channel::ipc::{IpcOneShotServer, IpcReceiver, IpcSender};
 
     use crate::{locate_bundle, Bundle, InfoPlist};
@@ -268,12 +281,32 @@ mod mac_os {
             }
         }
 
-        pub fn launch(&self) -> anyhow::Result<(IpcSender<CliRequest>, IpcReceiver<CliResponse>)> {
+        pub fn launch(
+            &self,
+            disable_extensions: bool,
+        ) -> anyhow::Result<(IpcSender<CliRequest>, IpcReceiver<CliResponse>)> {
             let (server, server_name) =
                 IpcOneShotServer::<IpcHandshake>::new().context("Handshake before Zed spawn")?;
             let url = format!("zed-cli://{server_name}");
 
             match self {
+                // Launch Services can't pass arguments to the app, so use `open`,
+                // which only passes them on when the app isn't already running.
+                Self::App { app_bundle, .. } if disable_extensions => {
+                    let status = std::process::Command::new("open")
+                        .arg("-a")
+                        .arg(app_bundle)
+                        .arg(&url)
+                        .args(["--args", DISABLE_EXTENSIONS_ARG])
+                        .status()
+                        .context("running open")?;
+                    anyhow::ensure!(
+                        status.success(),
+                        "cannot start app bundle {}",
+                        self.zed_version_string()
+                    );
+                }
+
                 Self::App { app_bundle, .. } => {
                     let app_path = app_bundle;
 
@@ -325,8 +358,11 @@ mod mac_os {
                     let command = command
                         .env(FORCE_CLI_MODE_ENV_VAR_NAME, "")
                         .stderr(subprocess_stdout_file)
-                        .stdout(subprocess_stdin_file)
-                        .arg(url);
+                        .stdout(subprocess_stdin_file);
+                    if disable_extensions {
+                        command.arg(DISABLE_EXTENSIONS_ARG);
+                    }
+                    command.arg(url);
 
                     command
                         .spawn()
@@ -373,6 +373,15 @@ CREATE TABLE extension_versions (
     authors TEXT NOT NULL,
     repository TEXT NOT NULL,
     description TEXT NOT NULL,
+    license TEXT,
+    themes TEXT NOT NULL DEFAULT '',
+    languages TEXT NOT NULL DEFAULT '',
+    grammars TEXT NOT NULL DEFAULT '',
+    language_servers TEXT NOT NULL DEFAULT '',
+    archive_sha256 TEXT,
+    signature_key_id TEXT,
+    signature TEXT,
+    dependencies TEXT NOT NULL DEFAULT '{}',
     download_count INTEGER NOT NULL DEFAULT 0,
     PRIMARY KEY (extension_id, version)
 );
@@ -380,6 +389,16 @@ CREATE TABLE extension_versions (
 CREATE UNIQUE INDEX "index_extensions_external_id" ON "extensions" ("external_id");
 CREATE INDEX "index_extensions_total_download_count" ON "extensions" ("total_download_count");
 
+CREATE TABLE extension_reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    extension_id INTEGER NOT NULL REFERENCES extensions(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    details TEXT NOT NULL,
+    reported_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "index_extension_reports_extension_id" ON "extension_reports" ("extension_id");
+
 CREATE TABLE hosted_projects (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     channel_id INTEGER NOT NULL REFERENCES channels(id),
@@ -0,0 +1 @@
+ALTER TABLE extension_versions ADD COLUMN themes TEXT NOT NULL DEFAULT '';
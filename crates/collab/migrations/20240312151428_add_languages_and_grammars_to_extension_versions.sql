@@ -0,0 +1,2 @@
+ALTER TABLE extension_versions ADD COLUMN languages TEXT NOT NULL DEFAULT '';
+ALTER TABLE extension_versions ADD COLUMN grammars TEXT NOT NULL DEFAULT '';
@@ -0,0 +1 @@
+ALTER TABLE extension_versions ADD COLUMN language_servers TEXT NOT NULL DEFAULT '';
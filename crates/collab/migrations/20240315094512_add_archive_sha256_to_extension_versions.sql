@@ -0,0 +1 @@
+ALTER TABLE extension_versions ADD COLUMN archive_sha256 TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE extension_versions ADD COLUMN signature_key_id TEXT;
+ALTER TABLE extension_versions ADD COLUMN signature TEXT;
@@ -0,0 +1 @@
+ALTER TABLE extension_versions ADD COLUMN dependencies TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS extension_reports (
+    id SERIAL PRIMARY KEY,
+    extension_id INTEGER NOT NULL REFERENCES extensions(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    details TEXT NOT NULL,
+    reported_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX "index_extension_reports_extension_id" ON "extension_reports" ("extension_id");
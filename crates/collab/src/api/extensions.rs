@@ -1,35 +1,57 @@
 use crate::{
-    db::{ExtensionMetadata, NewExtensionVersion},
+    db::{ExtensionMetadata, ExtensionSignature, NewExtensionVersion},
     executor::Executor,
     AppState, Error, Result,
 };
 use anyhow::{anyhow, Context as _};
-use aws_sdk_s3::presigning::PresigningConfig;
+use async_compression::futures::bufread::{GzipDecoder, GzipEncoder};
+use aws_sdk_s3::{presigning::PresigningConfig, primitives::ByteStream};
 use axum::{
     extract::{Path, Query},
     http::StatusCode,
     response::Redirect,
-    routing::get,
+    routing::{get, post},
     Extension, Json, Router,
 };
-use collections::HashMap;
-use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
+use collections::{BTreeMap, HashMap, HashSet};
+use futures::{io::BufReader, AsyncReadExt as _, StreamExt as _};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{iter, sync::Arc, time::Duration};
 use time::PrimitiveDateTime;
 use util::ResultExt;
 
 pub fn router() -> Router {
     Router::new()
         .route("/extensions", get(get_extensions))
+        .route("/extensions/collections", get(get_extension_collections))
+        .route("/extensions/blocklist", get(get_extension_blocklist))
+        .route("/extensions/updates", post(get_extension_updates))
+        .route("/extensions/:extension_id/reports", post(report_extension))
+        .route(
+            "/extensions/:extension_id/:version",
+            get(get_extension_version),
+        )
         .route(
             "/extensions/:extension_id/:version/download",
             get(download_extension),
         )
+        .route(
+            "/extensions/:extension_id/:version/delta",
+            get(download_extension_delta),
+        )
+        .route(
+            "/extensions/:extension_id/:version/themes",
+            get(get_extension_themes),
+        )
 }
 
 #[derive(Debug, Deserialize)]
 struct GetExtensionsParams {
     filter: Option<String>,
+    /// A comma-separated list of the extensions to return, such as the
+    /// members of a collection.
+    ids: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -38,11 +60,145 @@ struct DownloadExtensionParams {
     version: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct DownloadExtensionDeltaParams {
+    /// The version that the delta upgrades from.
+    from: String,
+}
+
 #[derive(Debug, Serialize)]
 struct GetExtensionsResponse {
     pub data: Vec<ExtensionMetadata>,
 }
 
+#[derive(Debug, Serialize)]
+struct GetExtensionVersionResponse {
+    pub data: ExtensionMetadata,
+}
+
+#[derive(Debug, Serialize)]
+struct GetExtensionThemesResponse {
+    pub data: Vec<ExtensionThemeFile>,
+}
+
+/// One of the theme files in an extension's archive.
+#[derive(Debug, Serialize)]
+struct ExtensionThemeFile {
+    path: String,
+    content: String,
+}
+
+/// The installed versions of extensions that a client is checking for
+/// updates.
+#[derive(Debug, Deserialize)]
+struct GetExtensionUpdatesBody {
+    extensions: Vec<InstalledExtensionVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstalledExtensionVersion {
+    id: String,
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportExtensionParams {
+    extension_id: String,
+}
+
+/// Why an extension was reported.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ExtensionReportReason {
+    Malware,
+    Broken,
+    Impersonation,
+}
+
+impl ExtensionReportReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Malware => "malware",
+            Self::Broken => "broken",
+            Self::Impersonation => "impersonation",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportExtensionBody {
+    reason: ExtensionReportReason,
+    #[serde(default)]
+    details: String,
+}
+
+/// A set of extensions curated by the operators of the registry, such as the
+/// extensions that a team uses.
+#[derive(Debug, Serialize, Deserialize)]
+struct ExtensionCollection {
+    id: String,
+    name: String,
+    description: Option<String>,
+    extension_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtensionCollectionsFile {
+    collections: Vec<ExtensionCollection>,
+}
+
+#[derive(Debug, Serialize)]
+struct GetExtensionCollectionsResponse {
+    pub data: Vec<ExtensionCollection>,
+}
+
+/// A version of an extension that the operators of the registry have
+/// blocked, such as one found to be malicious.
+#[derive(Debug, Serialize, Deserialize)]
+struct BlockedExtensionVersion {
+    extension_id: String,
+    version: String,
+    reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    safe_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtensionBlocklistFile {
+    blocked_versions: Vec<BlockedExtensionVersion>,
+}
+
+#[derive(Debug, Serialize)]
+struct GetExtensionBlocklistResponse {
+    pub data: Vec<BlockedExtensionVersion>,
+}
+
+/// The manifest of a delta between two versions of an extension, listing
+/// every file of the newer version and where the delta gets its content from.
+#[derive(Debug, Serialize)]
+struct ExtensionDeltaManifest {
+    files: Vec<ExtensionDeltaFile>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExtensionDeltaFile {
+    path: String,
+    sha256: String,
+    source: ExtensionDeltaSource,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ExtensionDeltaSource {
+    /// The file is the same as in the earlier version.
+    Unchanged,
+    /// The file's content is in the delta, under `files/`.
+    Replaced,
+    /// A binary diff from the earlier version of the file is in the delta,
+    /// under `patches/`.
+    Patched,
+}
+
 #[derive(Deserialize)]
 struct ExtensionManifest {
     name: String,
@@ -50,16 +206,418 @@ struct ExtensionManifest {
     description: Option<String>,
     authors: Vec<String>,
     repository: String,
+    license: Option<String>,
+    #[serde(default)]
+    themes: Vec<String>,
+    #[serde(default)]
+    languages: Vec<String>,
+    #[serde(default)]
+    grammars: BTreeMap<String, IgnoredAny>,
+    #[serde(default)]
+    language_servers: BTreeMap<String, IgnoredAny>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
 }
 
 async fn get_extensions(
     Extension(app): Extension<Arc<AppState>>,
     Query(params): Query<GetExtensionsParams>,
 ) -> Result<Json<GetExtensionsResponse>> {
-    let extensions = app.db.get_extensions(params.filter.as_deref(), 500).await?;
+    let mut extensions = if let Some(ids) = params.ids.as_deref() {
+        let ids = ids
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .collect::<Vec<_>>();
+        app.db.get_extensions_by_ids(&ids).await?
+    } else {
+        app.db.get_extensions(params.filter.as_deref(), 500).await?
+    };
+    for extension in &mut extensions {
+        set_extension_mirrors(&app, extension);
+    }
     Ok(Json(GetExtensionsResponse { data: extensions }))
 }
 
+/// Returns the latest version of each of the given extensions that is newer
+/// than the installed one, so that clients can check for updates without
+/// fetching the whole listing.
+async fn get_extension_updates(
+    Extension(app): Extension<Arc<AppState>>,
+    Json(body): Json<GetExtensionUpdatesBody>,
+) -> Result<Json<GetExtensionsResponse>> {
+    if body.extensions.len() > MAX_EXTENSION_UPDATES_PER_REQUEST {
+        Err(Error::Http(
+            StatusCode::BAD_REQUEST,
+            "too many extensions".into(),
+        ))?
+    }
+
+    let installed_versions = body
+        .extensions
+        .iter()
+        .map(|extension| (extension.id.as_str(), extension.version.as_str()))
+        .collect::<HashMap<_, _>>();
+    let ids = installed_versions.keys().copied().collect::<Vec<_>>();
+    let mut extensions = app.db.get_extensions_by_ids(&ids).await?;
+    extensions.retain(|extension| {
+        let Some(installed_version) = installed_versions.get(extension.id.as_str()) else {
+            return false;
+        };
+        match (
+            semver::Version::parse(&extension.version),
+            semver::Version::parse(installed_version),
+        ) {
+            (Ok(latest_version), Ok(installed_version)) => latest_version > installed_version,
+            _ => extension.version != *installed_version,
+        }
+    });
+    for extension in &mut extensions {
+        set_extension_mirrors(&app, extension);
+    }
+    Ok(Json(GetExtensionsResponse { data: extensions }))
+}
+
+/// Returns the metadata of a single version of an extension, including the
+/// checksum and signature of its archive. Unlike downloading the archive,
+/// this isn't counted as a download.
+async fn get_extension_version(
+    Extension(app): Extension<Arc<AppState>>,
+    Path(params): Path<DownloadExtensionParams>,
+) -> Result<Json<GetExtensionVersionResponse>> {
+    let Some(mut extension) = app
+        .db
+        .get_extension_version(&params.extension_id, &params.version)
+        .await?
+    else {
+        Err(Error::Http(
+            StatusCode::NOT_FOUND,
+            "unknown extension version".into(),
+        ))?
+    };
+    set_extension_mirrors(&app, &mut extension);
+    Ok(Json(GetExtensionVersionResponse { data: extension }))
+}
+
+/// Returns the theme files of a version of an extension, so that its themes
+/// can be previewed without downloading the whole archive. This isn't counted
+/// as a download.
+async fn get_extension_themes(
+    Extension(app): Extension<Arc<AppState>>,
+    Path(params): Path<DownloadExtensionParams>,
+) -> Result<Json<GetExtensionThemesResponse>> {
+    let Some((blob_store_client, bucket)) = app
+        .blob_store_client
+        .clone()
+        .zip(app.config.blob_store_bucket.clone())
+    else {
+        Err(Error::Http(
+            StatusCode::NOT_IMPLEMENTED,
+            "not supported".into(),
+        ))?
+    };
+
+    let DownloadExtensionParams {
+        extension_id,
+        version,
+    } = params;
+    let Some(extension) = app
+        .db
+        .get_extension_version(&extension_id, &version)
+        .await?
+    else {
+        Err(Error::Http(
+            StatusCode::NOT_FOUND,
+            "unknown extension version".into(),
+        ))?
+    };
+    if extension.themes.is_empty() {
+        return Ok(Json(GetExtensionThemesResponse { data: Vec::new() }));
+    }
+
+    let archive_bytes =
+        fetch_extension_archive(&blob_store_client, &bucket, &extension_id, &version).await?;
+    let themes = read_theme_files(&archive_bytes, &extension.themes)
+        .await
+        .with_context(|| {
+            format!("failed to read themes of extension {extension_id} version {version}")
+        })?;
+
+    Ok(Json(GetExtensionThemesResponse { data: themes }))
+}
+
+async fn fetch_extension_archive(
+    blob_store_client: &aws_sdk_s3::Client,
+    bucket: &str,
+    extension_id: &str,
+    version: &str,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let object = blob_store_client
+        .get_object()
+        .bucket(bucket)
+        .key(format!(
+            "extensions/{extension_id}/{version}/archive.tar.gz"
+        ))
+        .send()
+        .await
+        .map_err(|e| anyhow!("failed to fetch extension archive {e}"))?;
+    let archive_bytes = object
+        .body
+        .collect()
+        .await
+        .map(|data| data.into_bytes())
+        .with_context(|| {
+            format!("failed to download archive for extension {extension_id} version {version}")
+        })?;
+    Ok(archive_bytes.to_vec())
+}
+
+/// Reads the theme files at the given paths from a gzipped extension archive.
+async fn read_theme_files(
+    archive_bytes: &[u8],
+    theme_paths: &[String],
+) -> Result<Vec<ExtensionThemeFile>, anyhow::Error> {
+    let mut remaining_paths = theme_paths
+        .iter()
+        .map(|path| path.trim_start_matches("./"))
+        .collect::<HashSet<_>>();
+    let archive = async_tar::Archive::new(GzipDecoder::new(archive_bytes));
+    let mut entries = archive.entries()?;
+    let mut theme_files = Vec::new();
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let path = path.trim_start_matches("./");
+        if !remaining_paths.remove(path) {
+            continue;
+        }
+
+        let mut content = String::new();
+        entry.read_to_string(&mut content).await?;
+        theme_files.push(ExtensionThemeFile {
+            path: path.to_string(),
+            content,
+        });
+        if remaining_paths.is_empty() {
+            break;
+        }
+    }
+    Ok(theme_files)
+}
+
+/// Reads every file in a gzipped extension archive, keyed by its path.
+async fn read_archive_files(
+    archive_bytes: &[u8],
+) -> Result<BTreeMap<String, Vec<u8>>, anyhow::Error> {
+    let archive = async_tar::Archive::new(GzipDecoder::new(archive_bytes));
+    let mut entries = archive.entries()?;
+    let mut files = BTreeMap::default();
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content).await?;
+        files.insert(path.trim_start_matches("./").to_string(), content);
+    }
+    Ok(files)
+}
+
+/// Builds a delta that upgrades an extension from the version in the first
+/// archive to the version in the second. Changed files are sent as binary
+/// diffs, unless the diff is no smaller than the file.
+async fn build_extension_delta(
+    from_archive: &[u8],
+    to_archive: &[u8],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let from_files = read_archive_files(from_archive).await?;
+    let to_files = read_archive_files(to_archive).await?;
+
+    let (manifest, contents) = tokio::task::spawn_blocking(move || {
+        let mut files = Vec::new();
+        let mut contents = Vec::new();
+        for (path, content) in to_files {
+            let sha256 = format!("{:x}", Sha256::digest(&content));
+            let source = match from_files.get(&path) {
+                Some(from_content) if *from_content == content => ExtensionDeltaSource::Unchanged,
+                Some(from_content) => {
+                    let mut patch = Vec::new();
+                    bsdiff::diff(from_content, &content, &mut patch)?;
+                    if patch.len() < content.len() {
+                        contents.push((format!("patches/{path}"), patch));
+                        ExtensionDeltaSource::Patched
+                    } else {
+                        contents.push((format!("files/{path}"), content));
+                        ExtensionDeltaSource::Replaced
+                    }
+                }
+                None => {
+                    contents.push((format!("files/{path}"), content));
+                    ExtensionDeltaSource::Replaced
+                }
+            };
+            files.push(ExtensionDeltaFile {
+                path,
+                sha256,
+                source,
+            });
+        }
+        anyhow::Ok((ExtensionDeltaManifest { files }, contents))
+    })
+    .await??;
+
+    let manifest = serde_json::to_vec(&manifest)?;
+    let mut tar_bytes = Vec::new();
+    let mut archive = async_tar::Builder::new(&mut tar_bytes);
+    for (path, content) in iter::once(("delta.json".to_string(), manifest)).chain(contents) {
+        let mut header = async_tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        archive
+            .append_data(&mut header, path, content.as_slice())
+            .await?;
+    }
+    archive.into_inner().await?;
+
+    let mut delta_bytes = Vec::new();
+    GzipEncoder::new(BufReader::new(tar_bytes.as_slice()))
+        .read_to_end(&mut delta_bytes)
+        .await?;
+    Ok(delta_bytes)
+}
+
+/// Lists the configured mirrors that the extension's archive can also be
+/// downloaded from.
+fn set_extension_mirrors(app: &AppState, extension: &mut ExtensionMetadata) {
+    if let Some(mirror_urls) = app.config.extension_mirror_urls.as_deref() {
+        extension.mirrors = mirror_urls
+            .split(',')
+            .map(|mirror_url| mirror_url.trim().trim_end_matches('/'))
+            .filter(|mirror_url| !mirror_url.is_empty())
+            .map(|mirror_url| {
+                format!(
+                    "{mirror_url}/extensions/{}/{}/archive.tar.gz",
+                    extension.id, extension.version
+                )
+            })
+            .collect();
+    }
+}
+
+async fn get_extension_collections(
+    Extension(app): Extension<Arc<AppState>>,
+) -> Result<Json<GetExtensionCollectionsResponse>> {
+    let Some((blob_store_client, bucket)) = app
+        .blob_store_client
+        .clone()
+        .zip(app.config.blob_store_bucket.clone())
+    else {
+        return Ok(Json(GetExtensionCollectionsResponse { data: Vec::new() }));
+    };
+
+    let object = match blob_store_client
+        .get_object()
+        .bucket(bucket)
+        .key(EXTENSION_COLLECTIONS_KEY)
+        .send()
+        .await
+    {
+        Ok(object) => object,
+        Err(error) => {
+            let error = error.into_service_error();
+            if error.is_no_such_key() {
+                return Ok(Json(GetExtensionCollectionsResponse { data: Vec::new() }));
+            }
+            Err(anyhow!("failed to fetch extension collections: {error}"))?
+        }
+    };
+    let collections_bytes = object
+        .body
+        .collect()
+        .await
+        .map(|data| data.into_bytes())
+        .context("failed to download extension collections")?
+        .to_vec();
+    let collections = serde_json::from_slice::<ExtensionCollectionsFile>(&collections_bytes)
+        .context("invalid extension collections")?;
+
+    Ok(Json(GetExtensionCollectionsResponse {
+        data: collections.collections,
+    }))
+}
+
+async fn get_extension_blocklist(
+    Extension(app): Extension<Arc<AppState>>,
+) -> Result<Json<GetExtensionBlocklistResponse>> {
+    let Some((blob_store_client, bucket)) = app
+        .blob_store_client
+        .clone()
+        .zip(app.config.blob_store_bucket.clone())
+    else {
+        return Ok(Json(GetExtensionBlocklistResponse { data: Vec::new() }));
+    };
+
+    let object = match blob_store_client
+        .get_object()
+        .bucket(bucket)
+        .key(EXTENSION_BLOCKLIST_KEY)
+        .send()
+        .await
+    {
+        Ok(object) => object,
+        Err(error) => {
+            let error = error.into_service_error();
+            if error.is_no_such_key() {
+                return Ok(Json(GetExtensionBlocklistResponse { data: Vec::new() }));
+            }
+            Err(anyhow!("failed to fetch extension blocklist: {error}"))?
+        }
+    };
+    let blocklist_bytes = object
+        .body
+        .collect()
+        .await
+        .map(|data| data.into_bytes())
+        .context("failed to download extension blocklist")?
+        .to_vec();
+    let blocklist = serde_json::from_slice::<ExtensionBlocklistFile>(&blocklist_bytes)
+        .context("invalid extension blocklist")?;
+
+    Ok(Json(GetExtensionBlocklistResponse {
+        data: blocklist.blocked_versions,
+    }))
+}
+
+/// Stores a report of a problem with an extension for the operators of the
+/// registry to review.
+async fn report_extension(
+    Extension(app): Extension<Arc<AppState>>,
+    Path(params): Path<ReportExtensionParams>,
+    Json(body): Json<ReportExtensionBody>,
+) -> Result<()> {
+    if body.details.len() > MAX_EXTENSION_REPORT_DETAILS_LEN {
+        Err(Error::Http(
+            StatusCode::BAD_REQUEST,
+            "report details are too long".into(),
+        ))?
+    }
+
+    let extension_exists = app
+        .db
+        .create_extension_report(&params.extension_id, body.reason.as_str(), &body.details)
+        .await?;
+    if !extension_exists {
+        Err(Error::Http(
+            StatusCode::NOT_FOUND,
+            "unknown extension".into(),
+        ))?
+    }
+
+    Ok(())
+}
+
 async fn download_extension(
     Extension(app): Extension<Arc<AppState>>,
     Path(params): Path<DownloadExtensionParams>,
@@ -105,6 +663,100 @@ async fn download_extension(
     Ok(Redirect::temporary(url.uri()))
 }
 
+/// Redirects to a delta that upgrades an extension from an earlier version to
+/// the given one, so that clients don't need to download the whole archive.
+/// Deltas are built from the versions' archives when they're first
+/// requested, and kept in the blob store. Like downloading the archive, this
+/// is counted as a download.
+async fn download_extension_delta(
+    Extension(app): Extension<Arc<AppState>>,
+    Path(params): Path<DownloadExtensionParams>,
+    Query(delta_params): Query<DownloadExtensionDeltaParams>,
+) -> Result<Redirect> {
+    let Some((blob_store_client, bucket)) = app
+        .blob_store_client
+        .clone()
+        .zip(app.config.blob_store_bucket.clone())
+    else {
+        Err(Error::Http(
+            StatusCode::NOT_IMPLEMENTED,
+            "not supported".into(),
+        ))?
+    };
+
+    let DownloadExtensionParams {
+        extension_id,
+        version,
+    } = params;
+    let from_version = delta_params.from;
+    for version in [&from_version, &version] {
+        if app
+            .db
+            .get_extension_version(&extension_id, version)
+            .await?
+            .is_none()
+        {
+            Err(Error::Http(
+                StatusCode::NOT_FOUND,
+                "unknown extension version".into(),
+            ))?
+        }
+    }
+
+    let delta_key = format!("extensions/{extension_id}/{version}/deltas/{from_version}.tar.gz");
+    let delta_exists = blob_store_client
+        .head_object()
+        .bucket(&bucket)
+        .key(&delta_key)
+        .send()
+        .await
+        .is_ok();
+    if !delta_exists {
+        let from_archive =
+            fetch_extension_archive(&blob_store_client, &bucket, &extension_id, &from_version)
+                .await?;
+        let to_archive =
+            fetch_extension_archive(&blob_store_client, &bucket, &extension_id, &version).await?;
+        let delta = build_extension_delta(&from_archive, &to_archive)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to build delta for extension {extension_id} \
+                    from version {from_version} to {version}"
+                )
+            })?;
+        blob_store_client
+            .put_object()
+            .bucket(&bucket)
+            .key(&delta_key)
+            .body(ByteStream::from(delta))
+            .send()
+            .await
+            .map_err(|e| anyhow!("failed to store extension delta {e}"))?;
+    }
+
+    app.db
+        .record_extension_download(&extension_id, &version)
+        .await?;
+
+    let url = blob_store_client
+        .get_object()
+        .bucket(bucket)
+        .key(delta_key)
+        .presigned(PresigningConfig::expires_in(EXTENSION_DOWNLOAD_URL_LIFETIME).unwrap())
+        .await
+        .map_err(|e| anyhow!("failed to create presigned extension delta url {e}"))?;
+
+    Ok(Redirect::temporary(url.uri()))
+}
+
+/// The blob store key of the file that defines the registry's extension collections.
+const EXTENSION_COLLECTIONS_KEY: &str = "extension-collections.json";
+/// The blob store key of the file that lists the extension versions that the
+/// registry has blocked.
+const EXTENSION_BLOCKLIST_KEY: &str = "extension-blocklist.json";
+const MAX_EXTENSION_UPDATES_PER_REQUEST: usize = 1000;
+const MAX_EXTENSION_REPORT_DETAILS_LEN: usize = 4096;
 const EXTENSION_FETCH_INTERVAL: Duration = Duration::from_secs(5 * 60);
 const EXTENSION_DOWNLOAD_URL_LIFETIME: Duration = Duration::from_secs(3 * 60);
 
@@ -152,6 +804,7 @@ async fn fetch_extensions_from_blob_store(
     let objects = list.contents.unwrap_or_default();
 
     let mut published_versions = HashMap::<&str, Vec<&str>>::default();
+    let mut signed_versions = HashSet::<(&str, &str)>::default();
     for object in &objects {
         let Some(key) = object.key.as_ref() else {
             continue;
@@ -166,11 +819,15 @@ async fn fetch_extensions_from_blob_store(
         let Some(version) = parts.next() else {
             continue;
         };
-        if parts.next() == Some("manifest.json") {
-            published_versions
+        match parts.next() {
+            Some("manifest.json") => published_versions
                 .entry(extension_id)
                 .or_default()
-                .push(version);
+                .push(version),
+            Some("signature.json") => {
+                signed_versions.insert((extension_id, version));
+            }
+            _ => {}
         }
     }
 
@@ -191,6 +848,7 @@ async fn fetch_extensions_from_blob_store(
                     blob_store_bucket,
                     extension_id,
                     published_version,
+                    signed_versions.contains(&(extension_id, published_version)),
                 )
                 .await
                 .log_err()
@@ -222,6 +880,7 @@ async fn fetch_extension_manifest(
     blob_store_bucket: &String,
     extension_id: &str,
     version: &str,
+    signed: bool,
 ) -> Result<NewExtensionVersion, anyhow::Error> {
     let object = blob_store_client
         .get_object()
@@ -250,6 +909,17 @@ async fn fetch_extension_manifest(
     })?;
     let published_at = time::OffsetDateTime::from_unix_timestamp_nanos(published_at.as_nanos())?;
     let published_at = PrimitiveDateTime::new(published_at.date(), published_at.time());
+    let archive_sha256 =
+        fetch_extension_archive_sha256(blob_store_client, blob_store_bucket, extension_id, version)
+            .await?;
+    let signature = if signed {
+        Some(
+            fetch_extension_signature(blob_store_client, blob_store_bucket, extension_id, version)
+                .await?,
+        )
+    } else {
+        None
+    };
     let version = semver::Version::parse(&manifest.version).with_context(|| {
         format!("invalid version for extension {extension_id} version {version}")
     })?;
@@ -259,6 +929,70 @@ async fn fetch_extension_manifest(
         description: manifest.description.unwrap_or_default(),
         authors: manifest.authors,
         repository: manifest.repository,
+        license: manifest.license,
+        themes: manifest.themes,
+        languages: manifest.languages,
+        grammars: manifest.grammars.into_keys().collect(),
+        language_servers: manifest.language_servers.into_keys().collect(),
+        archive_sha256: Some(archive_sha256),
+        signature,
+        dependencies: manifest.dependencies,
         published_at,
     })
 }
+
+/// Downloads the archive for the given extension version and returns its
+/// hex-encoded SHA-256, which clients use to verify their downloads.
+async fn fetch_extension_archive_sha256(
+    blob_store_client: &aws_sdk_s3::Client,
+    blob_store_bucket: &String,
+    extension_id: &str,
+    version: &str,
+) -> Result<String, anyhow::Error> {
+    let object = blob_store_client
+        .get_object()
+        .bucket(blob_store_bucket)
+        .key(format!(
+            "extensions/{extension_id}/{version}/archive.tar.gz"
+        ))
+        .send()
+        .await?;
+    let archive_bytes = object
+        .body
+        .collect()
+        .await
+        .map(|data| data.into_bytes())
+        .with_context(|| {
+            format!("failed to download archive for extension {extension_id} version {version}")
+        })?;
+    Ok(hex::encode(Sha256::digest(&archive_bytes)))
+}
+
+/// Downloads the signature that the publisher uploaded alongside the given
+/// extension version's archive.
+async fn fetch_extension_signature(
+    blob_store_client: &aws_sdk_s3::Client,
+    blob_store_bucket: &String,
+    extension_id: &str,
+    version: &str,
+) -> Result<ExtensionSignature, anyhow::Error> {
+    let object = blob_store_client
+        .get_object()
+        .bucket(blob_store_bucket)
+        .key(format!(
+            "extensions/{extension_id}/{version}/signature.json"
+        ))
+        .send()
+        .await?;
+    let signature_bytes = object
+        .body
+        .collect()
+        .await
+        .map(|data| data.into_bytes())
+        .with_context(|| {
+            format!("failed to download signature for extension {extension_id} version {version}")
+        })?;
+    serde_json::from_slice(&signature_bytes).with_context(|| {
+        format!("invalid signature for extension {extension_id} version {version}")
+    })
+}
@@ -727,6 +727,14 @@ pub struct NewExtensionVersion {
     pub description: String,
     pub authors: Vec<String>,
     pub repository: String,
+    pub license: Option<String>,
+    pub themes: Vec<String>,
+    pub languages: Vec<String>,
+    pub grammars: Vec<String>,
+    pub language_servers: Vec<String>,
+    pub archive_sha256: Option<String>,
+    pub signature: Option<ExtensionSignature>,
+    pub dependencies: BTreeMap<String, String>,
     pub published_at: PrimitiveDateTime,
 }
 
@@ -738,11 +746,36 @@ pub struct ExtensionMetadata {
     pub authors: Vec<String>,
     pub description: String,
     pub repository: String,
+    pub license: Option<String>,
+    pub themes: Vec<String>,
+    pub languages: Vec<String>,
+    pub grammars: Vec<String>,
+    pub language_servers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ExtensionSignature>,
+    /// The extensions that the version depends on, and the versions of them
+    /// that it requires.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+    /// Other URLs that the version's archive can be downloaded from.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<String>,
     #[serde(serialize_with = "serialize_iso8601")]
     pub published_at: PrimitiveDateTime,
     pub download_count: u64,
 }
 
+/// A publisher's signature over the SHA-256 of an extension version's archive.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExtensionSignature {
+    /// The id of the key that produced the signature.
+    pub key_id: String,
+    /// The base64-encoded signature.
+    pub signature: String,
+}
+
 pub fn serialize_iso8601<S: Serializer>(
     datetime: &PrimitiveDateTime,
     serializer: S,
@@ -86,6 +86,7 @@ id_type!(UserId);
 id_type!(ChannelBufferCollaboratorId);
 id_type!(FlagId);
 id_type!(ExtensionId);
+id_type!(ExtensionReportId);
 id_type!(NotificationId);
 id_type!(NotificationKindId);
 id_type!(HostedProjectId);
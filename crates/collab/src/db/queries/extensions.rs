@@ -31,27 +31,61 @@ impl Database {
             Ok(extensions
                 .into_iter()
                 .filter_map(|(extension, latest_version)| {
-                    let version = latest_version?;
-                    Some(ExtensionMetadata {
-                        id: extension.external_id,
-                        name: extension.name,
-                        version: version.version,
-                        authors: version
-                            .authors
-                            .split(',')
-                            .map(|author| author.trim().to_string())
-                            .collect::<Vec<_>>(),
-                        description: version.description,
-                        repository: version.repository,
-                        published_at: version.published_at,
-                        download_count: extension.total_download_count as u64,
-                    })
+                    Some(extension_metadata(extension, latest_version?))
                 })
                 .collect())
         })
         .await
     }
 
+    /// Returns the latest version of each of the given extensions, skipping
+    /// any that don't exist.
+    pub async fn get_extensions_by_ids(&self, ids: &[&str]) -> Result<Vec<ExtensionMetadata>> {
+        self.transaction(|tx| async move {
+            let extensions = extension::Entity::find()
+                .filter(extension::Column::ExternalId.is_in(ids.iter().copied()))
+                .filter(
+                    extension::Column::LatestVersion
+                        .into_expr()
+                        .eq(extension_version::Column::Version.into_expr()),
+                )
+                .order_by_asc(extension::Column::Name)
+                .inner_join(extension_version::Entity)
+                .select_also(extension_version::Entity)
+                .all(&*tx)
+                .await?;
+
+            Ok(extensions
+                .into_iter()
+                .filter_map(|(extension, latest_version)| {
+                    Some(extension_metadata(extension, latest_version?))
+                })
+                .collect())
+        })
+        .await
+    }
+
+    /// Returns the metadata of the given version of an extension, if it exists.
+    pub async fn get_extension_version(
+        &self,
+        extension_id: &str,
+        version: &str,
+    ) -> Result<Option<ExtensionMetadata>> {
+        self.transaction(|tx| async move {
+            let extension = extension::Entity::find()
+                .filter(extension::Column::ExternalId.eq(extension_id))
+                .filter(extension_version::Column::Version.eq(version))
+                .inner_join(extension_version::Entity)
+                .select_also(extension_version::Entity)
+                .one(&*tx)
+                .await?;
+
+            Ok(extension
+                .and_then(|(extension, version)| Some(extension_metadata(extension, version?))))
+        })
+        .await
+    }
+
     pub async fn get_known_extension_versions<'a>(&self) -> Result<HashMap<String, Vec<String>>> {
         self.transaction(|tx| async move {
             let mut extension_external_ids_by_id = HashMap::default();
@@ -135,6 +169,27 @@ impl Database {
                         authors: ActiveValue::Set(version.authors.join(", ")),
                         repository: ActiveValue::Set(version.repository.clone()),
                         description: ActiveValue::Set(version.description.clone()),
+                        license: ActiveValue::Set(version.license.clone()),
+                        themes: ActiveValue::Set(version.themes.join(",")),
+                        languages: ActiveValue::Set(version.languages.join(",")),
+                        grammars: ActiveValue::Set(version.grammars.join(",")),
+                        language_servers: ActiveValue::Set(version.language_servers.join(",")),
+                        archive_sha256: ActiveValue::Set(version.archive_sha256.clone()),
+                        signature_key_id: ActiveValue::Set(
+                            version
+                                .signature
+                                .as_ref()
+                                .map(|signature| signature.key_id.clone()),
+                        ),
+                        signature: ActiveValue::Set(
+                            version
+                                .signature
+                                .as_ref()
+                                .map(|signature| signature.signature.clone()),
+                        ),
+                        dependencies: ActiveValue::Set(
+                            serde_json::to_string(&version.dependencies).unwrap_or_default(),
+                        ),
                         download_count: ActiveValue::NotSet,
                     }
                 }))
@@ -203,4 +258,94 @@ impl Database {
         })
         .await
     }
+
+    /// Stores a report of a problem with an extension. Returns false if the
+    /// extension doesn't exist.
+    pub async fn create_extension_report(
+        &self,
+        extension: &str,
+        reason: &str,
+        details: &str,
+    ) -> Result<bool> {
+        self.transaction(|tx| async move {
+            let Some(extension) = extension::Entity::find()
+                .filter(extension::Column::ExternalId.eq(extension))
+                .one(&*tx)
+                .await?
+            else {
+                return Ok(false);
+            };
+
+            extension_report::Entity::insert(extension_report::ActiveModel {
+                id: ActiveValue::NotSet,
+                extension_id: ActiveValue::Set(extension.id),
+                reason: ActiveValue::Set(reason.to_string()),
+                details: ActiveValue::Set(details.to_string()),
+                reported_at: ActiveValue::NotSet,
+            })
+            .exec_without_returning(&*tx)
+            .await?;
+
+            Ok(true)
+        })
+        .await
+    }
+
+    /// Returns the reports of problems with an extension, oldest first.
+    pub async fn get_extension_reports(
+        &self,
+        extension: &str,
+    ) -> Result<Vec<extension_report::Model>> {
+        self.transaction(|tx| async move {
+            Ok(extension_report::Entity::find()
+                .inner_join(extension::Entity)
+                .filter(extension::Column::ExternalId.eq(extension))
+                .order_by_asc(extension_report::Column::Id)
+                .all(&*tx)
+                .await?)
+        })
+        .await
+    }
+}
+
+fn extension_metadata(
+    extension: extension::Model,
+    version: extension_version::Model,
+) -> ExtensionMetadata {
+    ExtensionMetadata {
+        id: extension.external_id,
+        name: extension.name,
+        version: version.version,
+        authors: version
+            .authors
+            .split(',')
+            .map(|author| author.trim().to_string())
+            .collect::<Vec<_>>(),
+        description: version.description,
+        repository: version.repository,
+        license: version.license,
+        themes: split_list(&version.themes),
+        languages: split_list(&version.languages),
+        grammars: split_list(&version.grammars),
+        language_servers: split_list(&version.language_servers),
+        archive_sha256: version.archive_sha256,
+        signature: version
+            .signature_key_id
+            .zip(version.signature)
+            .map(|(key_id, signature)| ExtensionSignature { key_id, signature }),
+        dependencies: serde_json::from_str(&version.dependencies).unwrap_or_default(),
+        mirrors: Vec::new(),
+        published_at: version.published_at,
+        download_count: extension.total_download_count as u64,
+    }
+}
+
+/// Splits a comma-separated list stored in a single column.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect()
 }
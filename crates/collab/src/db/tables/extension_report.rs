@@ -0,0 +1,34 @@
+use crate::db::{ExtensionId, ExtensionReportId};
+use sea_orm::entity::prelude::*;
+use time::PrimitiveDateTime;
+
+/// A report of a problem with an extension, such as malware, sent to the
+/// operators of the registry.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "extension_reports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: ExtensionReportId,
+    pub extension_id: ExtensionId,
+    pub reason: String,
+    pub details: String,
+    pub reported_at: PrimitiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::extension::Entity",
+        from = "Column::ExtensionId",
+        to = "super::extension::Column::Id"
+    )]
+    Extension,
+}
+
+impl Related<super::extension::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Extension.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -13,6 +13,17 @@ pub struct Model {
     pub authors: String,
     pub repository: String,
     pub description: String,
+    pub license: Option<String>,
+    pub themes: String,
+    pub languages: String,
+    pub grammars: String,
+    pub language_servers: String,
+    pub archive_sha256: Option<String>,
+    pub signature_key_id: Option<String>,
+    pub signature: Option<String>,
+    /// The extensions that the version depends on, as a JSON object from
+    /// extension ids to version requirements.
+    pub dependencies: String,
     pub download_count: i64,
 }
 
@@ -1,6 +1,6 @@
 use super::Database;
 use crate::{
-    db::{ExtensionMetadata, NewExtensionVersion},
+    db::{ExtensionMetadata, ExtensionSignature, NewExtensionVersion},
     test_both_dbs,
 };
 use std::sync::Arc;
@@ -33,6 +33,14 @@ async fn test_extensions(db: &Arc<Database>) {
                         description: "an extension".into(),
                         authors: vec!["max".into()],
                         repository: "ext1/repo".into(),
+                        license: None,
+                        themes: Vec::new(),
+                        languages: Vec::new(),
+                        grammars: Vec::new(),
+                        language_servers: Vec::new(),
+                        archive_sha256: None,
+                        signature: None,
+                        dependencies: Default::default(),
                         published_at: t0,
                     },
                     NewExtensionVersion {
@@ -41,6 +49,14 @@ async fn test_extensions(db: &Arc<Database>) {
                         description: "a good extension".into(),
                         authors: vec!["max".into(), "marshall".into()],
                         repository: "ext1/repo".into(),
+                        license: None,
+                        themes: Vec::new(),
+                        languages: Vec::new(),
+                        grammars: Vec::new(),
+                        language_servers: Vec::new(),
+                        archive_sha256: None,
+                        signature: None,
+                        dependencies: Default::default(),
                         published_at: t0,
                     },
                 ],
@@ -53,6 +69,14 @@ async fn test_extensions(db: &Arc<Database>) {
                     description: "a great extension".into(),
                     authors: vec!["marshall".into()],
                     repository: "ext2/repo".into(),
+                    license: None,
+                    themes: Vec::new(),
+                    languages: Vec::new(),
+                    grammars: Vec::new(),
+                    language_servers: Vec::new(),
+                    archive_sha256: None,
+                    signature: None,
+                    dependencies: Default::default(),
                     published_at: t0,
                 }],
             ),
@@ -86,6 +110,15 @@ async fn test_extensions(db: &Arc<Database>) {
                 authors: vec!["max".into(), "marshall".into()],
                 description: "a good extension".into(),
                 repository: "ext1/repo".into(),
+                license: None,
+                themes: Vec::new(),
+                languages: Vec::new(),
+                grammars: Vec::new(),
+                language_servers: Vec::new(),
+                archive_sha256: None,
+                signature: None,
+                dependencies: Default::default(),
+                mirrors: Vec::new(),
                 published_at: t0,
                 download_count: 0,
             },
@@ -96,6 +129,15 @@ async fn test_extensions(db: &Arc<Database>) {
                 authors: vec!["marshall".into()],
                 description: "a great extension".into(),
                 repository: "ext2/repo".into(),
+                license: None,
+                themes: Vec::new(),
+                languages: Vec::new(),
+                grammars: Vec::new(),
+                language_servers: Vec::new(),
+                archive_sha256: None,
+                signature: None,
+                dependencies: Default::default(),
+                mirrors: Vec::new(),
                 published_at: t0,
                 download_count: 0
             },
@@ -133,6 +175,15 @@ async fn test_extensions(db: &Arc<Database>) {
                 authors: vec!["marshall".into()],
                 description: "a great extension".into(),
                 repository: "ext2/repo".into(),
+                license: None,
+                themes: Vec::new(),
+                languages: Vec::new(),
+                grammars: Vec::new(),
+                language_servers: Vec::new(),
+                archive_sha256: None,
+                signature: None,
+                dependencies: Default::default(),
+                mirrors: Vec::new(),
                 published_at: t0,
                 download_count: 7
             },
@@ -143,6 +194,15 @@ async fn test_extensions(db: &Arc<Database>) {
                 authors: vec!["max".into(), "marshall".into()],
                 description: "a good extension".into(),
                 repository: "ext1/repo".into(),
+                license: None,
+                themes: Vec::new(),
+                languages: Vec::new(),
+                grammars: Vec::new(),
+                language_servers: Vec::new(),
+                archive_sha256: None,
+                signature: None,
+                dependencies: Default::default(),
+                mirrors: Vec::new(),
                 published_at: t0,
                 download_count: 5,
             },
@@ -161,6 +221,17 @@ async fn test_extensions(db: &Arc<Database>) {
                     description: "a real good extension".into(),
                     authors: vec!["max".into(), "marshall".into()],
                     repository: "ext1/repo".into(),
+                    license: Some("MIT".into()),
+                    themes: vec!["themes/ext1.json".into()],
+                    languages: vec!["languages/ext1".into()],
+                    grammars: vec!["ext1".into()],
+                    language_servers: vec!["ext1-lsp".into()],
+                    archive_sha256: Some("abc123".into()),
+                    signature: Some(ExtensionSignature {
+                        key_id: "ext1-key".into(),
+                        signature: "c2lnbmF0dXJl".into(),
+                    }),
+                    dependencies: [("ext2".into(), "^0.2".into())].into_iter().collect(),
                     published_at: t0,
                 }],
             ),
@@ -172,6 +243,14 @@ async fn test_extensions(db: &Arc<Database>) {
                     description: "an old extension".into(),
                     authors: vec!["marshall".into()],
                     repository: "ext2/repo".into(),
+                    license: None,
+                    themes: Vec::new(),
+                    languages: Vec::new(),
+                    grammars: Vec::new(),
+                    language_servers: Vec::new(),
+                    archive_sha256: None,
+                    signature: None,
+                    dependencies: Default::default(),
                     published_at: t0,
                 }],
             ),
@@ -207,6 +286,15 @@ async fn test_extensions(db: &Arc<Database>) {
                 authors: vec!["marshall".into()],
                 description: "a great extension".into(),
                 repository: "ext2/repo".into(),
+                license: None,
+                themes: Vec::new(),
+                languages: Vec::new(),
+                grammars: Vec::new(),
+                language_servers: Vec::new(),
+                archive_sha256: None,
+                signature: None,
+                dependencies: Default::default(),
+                mirrors: Vec::new(),
                 published_at: t0,
                 download_count: 7
             },
@@ -217,9 +305,134 @@ async fn test_extensions(db: &Arc<Database>) {
                 authors: vec!["max".into(), "marshall".into()],
                 description: "a real good extension".into(),
                 repository: "ext1/repo".into(),
+                license: Some("MIT".into()),
+                themes: vec!["themes/ext1.json".into()],
+                languages: vec!["languages/ext1".into()],
+                grammars: vec!["ext1".into()],
+                language_servers: vec!["ext1-lsp".into()],
+                archive_sha256: Some("abc123".into()),
+                signature: Some(ExtensionSignature {
+                    key_id: "ext1-key".into(),
+                    signature: "c2lnbmF0dXJl".into(),
+                }),
+                dependencies: [("ext2".into(), "^0.2".into())].into_iter().collect(),
+                mirrors: Vec::new(),
                 published_at: t0,
                 download_count: 5,
             },
         ]
     );
+
+    // Earlier versions can be looked up by their version, which doesn't count
+    // as a download.
+    let extension = db
+        .get_extension_version("ext1", "0.0.1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(extension.version, "0.0.1");
+    assert_eq!(extension.description, "an extension");
+    assert_eq!(extension.download_count, 5);
+    let extension = db
+        .get_extension_version("ext1", "0.0.3")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(extension.archive_sha256.as_deref(), Some("abc123"));
+    assert_eq!(
+        extension.dependencies,
+        [("ext2".into(), "^0.2".into())].into_iter().collect()
+    );
+    assert!(db
+        .get_extension_version("ext1", "0.0.4")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(db
+        .get_extension_version("no-such-extension", "0.0.1")
+        .await
+        .unwrap()
+        .is_none());
+
+    // Extensions can be looked up by their ids, such as the members of a
+    // collection, skipping any that don't exist.
+    let extensions = db
+        .get_extensions_by_ids(&["ext2", "no-such-extension", "ext1"])
+        .await
+        .unwrap();
+    assert_eq!(
+        extensions
+            .iter()
+            .map(|extension| (extension.id.as_str(), extension.version.as_str()))
+            .collect::<Vec<_>>(),
+        [("ext1", "0.0.3"), ("ext2", "0.2.0")]
+    );
+    let extensions = db.get_extensions_by_ids(&["ext2"]).await.unwrap();
+    assert_eq!(extensions.len(), 1);
+    assert_eq!(extensions[0].download_count, 7);
+    assert!(db.get_extensions_by_ids(&[]).await.unwrap().is_empty());
+}
+
+test_both_dbs!(
+    test_extension_reports,
+    test_extension_reports_postgres,
+    test_extension_reports_sqlite
+);
+
+async fn test_extension_reports(db: &Arc<Database>) {
+    let t0 = OffsetDateTime::from_unix_timestamp_nanos(0).unwrap();
+    let t0 = PrimitiveDateTime::new(t0.date(), t0.time());
+
+    db.insert_extension_versions(
+        &[(
+            "ext1",
+            vec![NewExtensionVersion {
+                name: "Extension 1".into(),
+                version: semver::Version::parse("0.0.1").unwrap(),
+                description: "an extension".into(),
+                authors: vec!["max".into()],
+                repository: "ext1/repo".into(),
+                license: None,
+                themes: Vec::new(),
+                languages: Vec::new(),
+                grammars: Vec::new(),
+                language_servers: Vec::new(),
+                archive_sha256: None,
+                signature: None,
+                dependencies: Default::default(),
+                published_at: t0,
+            }],
+        )]
+        .into_iter()
+        .collect(),
+    )
+    .await
+    .unwrap();
+
+    assert!(db
+        .create_extension_report("ext1", "malware", "it reads my ssh keys")
+        .await
+        .unwrap());
+    assert!(db
+        .create_extension_report("ext1", "broken", "")
+        .await
+        .unwrap());
+    assert!(!db
+        .create_extension_report("no-such-extension", "broken", "")
+        .await
+        .unwrap());
+
+    let reports = db.get_extension_reports("ext1").await.unwrap();
+    assert_eq!(
+        reports
+            .iter()
+            .map(|report| (report.reason.as_str(), report.details.as_str()))
+            .collect::<Vec<_>>(),
+        [("malware", "it reads my ssh keys"), ("broken", "")]
+    );
+    assert!(db
+        .get_extension_reports("no-such-extension")
+        .await
+        .unwrap()
+        .is_empty());
 }
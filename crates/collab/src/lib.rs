@@ -125,6 +125,9 @@ pub struct Config {
     pub blob_store_access_key: Option<String>,
     pub blob_store_secret_key: Option<String>,
     pub blob_store_bucket: Option<String>,
+    /// A comma-separated list of the base URLs of mirrors of the blob store's
+    /// extension archives.
+    pub extension_mirror_urls: Option<String>,
     pub zed_environment: Arc<str>,
     pub zed_client_checksum_seed: Option<String>,
     pub slack_panics_webhook: Option<String>,
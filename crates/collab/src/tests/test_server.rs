@@ -506,6 +506,7 @@ impl TestServer {
                 blob_store_access_key: None,
                 blob_store_secret_key: None,
                 blob_store_bucket: None,
+                extension_mirror_urls: None,
                 clickhouse_url: None,
                 clickhouse_user: None,
                 clickhouse_password: None,
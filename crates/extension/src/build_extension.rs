@@ -1,4 +1,5 @@
 use crate::ExtensionManifest;
+use crate::{build_log::parse_ansi, BuildLog};
 use crate::{extension_manifest::ExtensionLibraryKind, GrammarManifestEntry};
 use anyhow::{anyhow, bail, Context as _, Result};
 use async_compression::futures::bufread::GzipDecoder;
@@ -6,6 +7,7 @@ use async_tar::Archive;
 use futures::io::BufReader;
 use futures::AsyncReadExt;
 use serde::Deserialize;
+use std::io::{BufRead, BufReader as StdBufReader};
 use std::mem;
 use std::{
     env, fs,
@@ -49,6 +51,8 @@ pub struct ExtensionBuilder {
 
 pub struct CompileExtensionOptions {
     pub release: bool,
+    /// Receives the output of the build as it progresses.
+    pub log: Option<BuildLog>,
 }
 
 #[derive(Deserialize)]
@@ -83,12 +87,17 @@ impl ExtensionBuilder {
         if extension_toml.lib.kind == Some(ExtensionLibraryKind::Rust)
             || fs::metadata(&cargo_toml_path)?.is_file()
         {
-            self.compile_rust_extension(extension_dir, options).await?;
+            self.compile_rust_extension(extension_dir, &options).await?;
         }
 
         for (grammar_name, grammar_metadata) in extension_toml.grammars {
-            self.compile_grammar(extension_dir, grammar_name, grammar_metadata)
-                .await?;
+            self.compile_grammar(
+                extension_dir,
+                grammar_name,
+                grammar_metadata,
+                options.log.as_ref(),
+            )
+            .await?;
         }
 
         log::info!("finished compiling extension {}", extension_dir.display());
@@ -98,7 +107,7 @@ impl ExtensionBuilder {
     async fn compile_rust_extension(
         &self,
         extension_dir: &Path,
-        options: CompileExtensionOptions,
+        options: &CompileExtensionOptions,
     ) -> Result<(), anyhow::Error> {
         self.install_rust_wasm_target_if_needed()?;
         let adapter_bytes = self.install_wasi_preview1_adapter_if_needed().await?;
@@ -107,19 +116,40 @@ impl ExtensionBuilder {
         let cargo_toml: CargoToml = toml::from_str(&cargo_toml_content)?;
 
         log::info!("compiling rust extension {}", extension_dir.display());
-        let output = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
             .args(["build", "--target", RUST_TARGET])
             .args(options.release.then_some("--release"))
             .arg("--target-dir")
             .arg(extension_dir.join("target"))
-            .current_dir(&extension_dir)
-            .output()
-            .context("failed to run `cargo`")?;
-        if !output.status.success() {
-            bail!(
-                "failed to build extension {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
+            .current_dir(&extension_dir);
+        if let Some(build_log) = &options.log {
+            // Stream cargo's progress into the log, keeping its colors.
+            command
+                .args(["--color", "always"])
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped());
+            let mut child = command.spawn().context("failed to run `cargo`")?;
+            let mut stderr = String::new();
+            if let Some(child_stderr) = child.stderr.take() {
+                for line in StdBufReader::new(child_stderr).lines() {
+                    let line = line?;
+                    build_log.push_line(&line);
+                    stderr.push_str(&line);
+                    stderr.push('\n');
+                }
+            }
+            if !child.wait()?.success() {
+                bail!("failed to build extension {}", parse_ansi(&stderr).0);
+            }
+        } else {
+            let output = command.output().context("failed to run `cargo`")?;
+            if !output.status.success() {
+                bail!(
+                    "failed to build extension {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
         }
 
         let mut wasm_path = PathBuf::from(extension_dir);
@@ -159,6 +189,7 @@ impl ExtensionBuilder {
         extension_dir: &Path,
         grammar_name: Arc<str>,
         grammar_metadata: GrammarManifestEntry,
+        build_log: Option<&BuildLog>,
     ) -> Result<()> {
         let clang_path = self.install_wasi_sdk_if_needed().await?;
 
@@ -180,6 +211,9 @@ impl ExtensionBuilder {
         let scanner_path = src_path.join("scanner.c");
 
         log::info!("compiling {grammar_name} parser");
+        if let Some(build_log) = build_log {
+            build_log.push_line(&format!("Compiling {grammar_name} parser"));
+        }
         let clang_output = Command::new(&clang_path)
             .args(["-fPIC", "-shared", "-Os"])
             .arg(format!("-Wl,--export=tree_sitter_{grammar_name}"))
@@ -191,6 +225,11 @@ impl ExtensionBuilder {
             .args(scanner_path.exists().then_some(scanner_path))
             .output()
             .context("failed to run clang")?;
+        if let Some(build_log) = build_log {
+            for line in String::from_utf8_lossy(&clang_output.stderr).lines() {
+                build_log.push_line(line);
+            }
+        }
         if !clang_output.status.success() {
             bail!(
                 "failed to compile {} parser with clang: {}",
@@ -202,6 +241,90 @@ impl ExtensionBuilder {
         Ok(())
     }
 
+    /// Checks out the latest commit on the default branch of the repository at
+    /// `url` into `directory`, cloning the repository first if needed, and
+    /// returns the SHA of the checked-out commit.
+    pub fn checkout_latest(&self, directory: &Path, url: &str) -> Result<String> {
+        let git_dir = directory.join(".git");
+
+        if directory.exists() {
+            let fetch_output = Command::new("git")
+                .arg("--git-dir")
+                .arg(&git_dir)
+                .args(["fetch", "--depth", "1", "origin", "HEAD"])
+                .output()
+                .context("failed to execute `git fetch`")?;
+            if !fetch_output.status.success() {
+                bail!(
+                    "failed to fetch {url}: {}",
+                    String::from_utf8_lossy(&fetch_output.stderr)
+                );
+            }
+
+            let checkout_output = Command::new("git")
+                .arg("--git-dir")
+                .arg(&git_dir)
+                .args(["checkout", "--force", "FETCH_HEAD"])
+                .current_dir(&directory)
+                .output()
+                .context("failed to execute `git checkout`")?;
+            if !checkout_output.status.success() {
+                bail!(
+                    "failed to checkout the latest commit in directory '{}'",
+                    directory.display()
+                );
+            }
+        } else {
+            let clone_output = Command::new("git")
+                .args(["clone", "--depth", "1", "--", url])
+                .arg(&directory)
+                .output()
+                .context("failed to execute `git clone`")?;
+            if !clone_output.status.success() {
+                bail!(
+                    "failed to clone {url}: {}",
+                    String::from_utf8_lossy(&clone_output.stderr)
+                );
+            }
+        }
+
+        let rev_parse_output = Command::new("git")
+            .arg("--git-dir")
+            .arg(&git_dir)
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .context("failed to execute `git rev-parse`")?;
+        if !rev_parse_output.status.success() {
+            bail!(
+                "failed to read the checked-out commit in directory '{}'",
+                directory.display()
+            );
+        }
+        Ok(String::from_utf8_lossy(&rev_parse_output.stdout)
+            .trim()
+            .to_string())
+    }
+
+    /// Returns the SHA of the latest commit on the default branch of the
+    /// repository at `url`, without checking it out.
+    pub fn latest_revision(&self, url: &str) -> Result<String> {
+        let ls_remote_output = Command::new("git")
+            .args(["ls-remote", "--", url, "HEAD"])
+            .output()
+            .context("failed to execute `git ls-remote`")?;
+        if !ls_remote_output.status.success() {
+            bail!(
+                "failed to read the latest commit of {url}: {}",
+                String::from_utf8_lossy(&ls_remote_output.stderr)
+            );
+        }
+        String::from_utf8_lossy(&ls_remote_output.stdout)
+            .split_whitespace()
+            .next()
+            .map(ToString::to_string)
+            .ok_or_else(|| anyhow!("{url} has no commits"))
+    }
+
     fn checkout_repo(&self, directory: &Path, url: &str, rev: &str) -> Result<()> {
         let git_dir = directory.join(".git");
 
@@ -0,0 +1,192 @@
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use parking_lot::Mutex;
+use std::{ops::Range, sync::Arc};
+
+/// Collects the output of the tools run while building an extension, so that
+/// it can be displayed while the build is still in progress.
+#[derive(Clone)]
+pub struct BuildLog {
+    text: Arc<Mutex<String>>,
+    updates_tx: UnboundedSender<()>,
+    mirror: Option<Box<BuildLog>>,
+}
+
+impl BuildLog {
+    /// Creates a log that appends to the given text. The returned receiver
+    /// yields whenever output is added, and ends once every copy of the log
+    /// has been dropped.
+    pub fn new(text: Arc<Mutex<String>>) -> (Self, UnboundedReceiver<()>) {
+        let (updates_tx, updates_rx) = unbounded();
+        (
+            Self {
+                text,
+                updates_tx,
+                mirror: None,
+            },
+            updates_rx,
+        )
+    }
+
+    /// Returns a log that also appends its output to the given log.
+    pub fn mirrored_to(mut self, mirror: BuildLog) -> Self {
+        self.mirror = Some(Box::new(mirror));
+        self
+    }
+
+    pub fn push_line(&self, line: &str) {
+        let mut text = self.text.lock();
+        text.push_str(line);
+        text.push('\n');
+        drop(text);
+        self.updates_tx.unbounded_send(()).ok();
+        if let Some(mirror) = &self.mirror {
+            mirror.push_line(line);
+        }
+    }
+}
+
+/// One of the 16 standard colors that can be set by an ANSI escape sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnsiColor {
+    /// The index of the color, from 0 (black) to 7 (white).
+    pub index: u8,
+    pub bright: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    pub foreground: Option<AnsiColor>,
+    pub bold: bool,
+}
+
+/// Removes the ANSI escape sequences from the given text, returning the
+/// remaining text along with the style of each of its ranges that isn't
+/// displayed with the default style.
+pub fn parse_ansi(text: &str) -> (String, Vec<(Range<usize>, AnsiStyle)>) {
+    let mut output = String::with_capacity(text.len());
+    let mut styles = Vec::new();
+    let mut style = AnsiStyle::default();
+    let mut style_start = 0;
+
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            output.push(ch);
+            continue;
+        }
+
+        // Only Select Graphic Rendition sequences affect the text's style.
+        // Any other escape sequence is discarded.
+        if chars.next_if_eq(&'[').is_none() {
+            continue;
+        }
+        let mut parameters = String::new();
+        let mut command = None;
+        for ch in chars.by_ref() {
+            if ch.is_ascii_digit() || ch == ';' {
+                parameters.push(ch);
+            } else {
+                command = Some(ch);
+                break;
+            }
+        }
+        if command != Some('m') {
+            continue;
+        }
+
+        let mut new_style = style;
+        for parameter in parameters.split(';') {
+            match parameter.parse::<u8>().unwrap_or(0) {
+                0 => new_style = AnsiStyle::default(),
+                1 => new_style.bold = true,
+                22 => new_style.bold = false,
+                index @ 30..=37 => {
+                    new_style.foreground = Some(AnsiColor {
+                        index: index - 30,
+                        bright: false,
+                    })
+                }
+                39 => new_style.foreground = None,
+                index @ 90..=97 => {
+                    new_style.foreground = Some(AnsiColor {
+                        index: index - 90,
+                        bright: true,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        if new_style != style {
+            if style != AnsiStyle::default() && style_start < output.len() {
+                styles.push((style_start..output.len(), style));
+            }
+            style = new_style;
+            style_start = output.len();
+        }
+    }
+
+    if style != AnsiStyle::default() && style_start < output.len() {
+        styles.push((style_start..output.len(), style));
+    }
+
+    (output, styles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirrored_build_log() {
+        let text = Arc::new(Mutex::new(String::new()));
+        let mirror_text = Arc::new(Mutex::new(String::from("Rebuilding a\n")));
+        let (mirror, _mirror_updates) = BuildLog::new(mirror_text.clone());
+        let (log, _updates) = BuildLog::new(text.clone());
+        let log = log.mirrored_to(mirror);
+
+        log.push_line("Compiling a");
+        assert_eq!(text.lock().as_str(), "Compiling a\n");
+        assert_eq!(mirror_text.lock().as_str(), "Rebuilding a\nCompiling a\n");
+    }
+
+    #[test]
+    fn test_parse_ansi() {
+        let (text, styles) = parse_ansi(
+            "\x1b[0m\x1b[1m\x1b[31merror[E0425]\x1b[0m\x1b[1m: cannot find value\x1b[0m\n\x1b[94m-->\x1b[39m src/lib.rs\x1b[K\n",
+        );
+        assert_eq!(text, "error[E0425]: cannot find value\n--> src/lib.rs\n");
+        assert_eq!(
+            styles,
+            [
+                (
+                    0..12,
+                    AnsiStyle {
+                        foreground: Some(AnsiColor {
+                            index: 1,
+                            bright: false
+                        }),
+                        bold: true,
+                    }
+                ),
+                (
+                    12..31,
+                    AnsiStyle {
+                        foreground: None,
+                        bold: true,
+                    }
+                ),
+                (
+                    32..35,
+                    AnsiStyle {
+                        foreground: Some(AnsiColor {
+                            index: 4,
+                            bright: true
+                        }),
+                        bold: false,
+                    }
+                ),
+            ]
+        );
+    }
+}
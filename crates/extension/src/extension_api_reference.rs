@@ -0,0 +1,224 @@
+use crate::extension_compatibility::{MAX_SUPPORTED_API_VERSION, MIN_SUPPORTED_API_VERSION};
+use util::SemanticVersion;
+
+/// The WIT world that extensions are loaded with, which defines every
+/// capability of the extension API supported by this version of Zed.
+const EXTENSION_WIT: &str = include_str!("../../extension_api/wit/extension.wit");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionApiItemKind {
+    /// A function that Zed provides to extensions.
+    Import,
+    /// A function that extensions implement, and that Zed calls.
+    Export,
+    /// A handle to a resource owned by Zed, along with its methods.
+    Resource,
+    /// A type used by the imports, exports, and resources.
+    Type,
+}
+
+impl ExtensionApiItemKind {
+    pub const ALL: [Self; 4] = [Self::Import, Self::Export, Self::Resource, Self::Type];
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionApiItem {
+    pub kind: ExtensionApiItemKind,
+    pub name: String,
+    /// The WIT definition of the item, without its doc comment.
+    pub definition: String,
+    pub docs: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionApiReference {
+    /// The WIT package and world that define the extension API.
+    pub package: String,
+    pub world: String,
+    pub min_version: SemanticVersion,
+    pub max_version: SemanticVersion,
+    pub items: Vec<ExtensionApiItem>,
+}
+
+/// Returns the capabilities of the extension API that are available in this
+/// version of Zed.
+pub fn extension_api_reference() -> ExtensionApiReference {
+    let (package, world, items) = parse_wit_world(EXTENSION_WIT);
+    ExtensionApiReference {
+        package,
+        world,
+        min_version: MIN_SUPPORTED_API_VERSION,
+        max_version: MAX_SUPPORTED_API_VERSION,
+        items,
+    }
+}
+
+/// Lists the items of the worlds in the given WIT package, along with the name
+/// of its first world. This only handles the subset of WIT used by the
+/// extension API, where later worlds only add optional exports to the first,
+/// and each item starts on its own line.
+fn parse_wit_world(source: &str) -> (String, String, Vec<ExtensionApiItem>) {
+    let mut package = String::new();
+    let mut world = String::new();
+    let mut items = Vec::new();
+    let mut docs = Vec::new();
+    let mut current_item: Option<ExtensionApiItem> = None;
+    let mut depth = 0;
+
+    for line in source.lines() {
+        let line = line.trim();
+        let depth_before = depth;
+        depth += line.matches('{').count();
+        depth -= line.matches('}').count().min(depth);
+
+        if let Some(item) = current_item.as_mut() {
+            item.definition.push('\n');
+            if depth > 1 {
+                item.definition.push_str("    ");
+            }
+            item.definition.push_str(line);
+            if depth <= 1 {
+                items.extend(current_item.take());
+            }
+            continue;
+        }
+
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.trim().to_string());
+            continue;
+        }
+        if line.is_empty() {
+            docs.clear();
+            continue;
+        }
+
+        if depth_before == 0 {
+            if let Some(name) = line.strip_prefix("package ") {
+                package = name.trim_end_matches(';').trim().to_string();
+            } else if let Some(name) = line.strip_prefix("world ").filter(|_| world.is_empty()) {
+                world = name.trim_end_matches('{').trim().to_string();
+            }
+            docs.clear();
+            continue;
+        }
+        if depth_before != 1 {
+            continue;
+        }
+
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let kind = match keyword {
+            "import" => ExtensionApiItemKind::Import,
+            "export" => ExtensionApiItemKind::Export,
+            "resource" => ExtensionApiItemKind::Resource,
+            "record" | "enum" | "variant" | "flags" | "type" => ExtensionApiItemKind::Type,
+            _ => {
+                docs.clear();
+                continue;
+            }
+        };
+        let name = rest
+            .split(|c: char| c == ':' || c == '=' || c == '{' || c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let item = ExtensionApiItem {
+            kind,
+            name,
+            definition: line.to_string(),
+            docs: docs.join(" "),
+        };
+        docs.clear();
+
+        if depth > 1 {
+            current_item = Some(item);
+        } else {
+            items.push(item);
+        }
+    }
+
+    (package, world, items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wit_world() {
+        let (package, world, items) = parse_wit_world(
+            r#"
+            package zed:extension;
+
+            world extension {
+                export init-extension: func();
+
+                enum os {
+                    mac,
+                    linux,
+                }
+
+                /// Gets the current operating system.
+                import current-platform: func() -> os;
+
+                type env-vars = list<tuple<string, string>>;
+
+                resource worktree {
+                    /// Reads a file.
+                    read-text-file: func(path: string) -> result<string, string>;
+                }
+            }
+            "#,
+        );
+
+        assert_eq!(package, "zed:extension");
+        assert_eq!(world, "extension");
+        assert_eq!(
+            items,
+            [
+                ExtensionApiItem {
+                    kind: ExtensionApiItemKind::Export,
+                    name: "init-extension".into(),
+                    definition: "export init-extension: func();".into(),
+                    docs: "".into(),
+                },
+                ExtensionApiItem {
+                    kind: ExtensionApiItemKind::Type,
+                    name: "os".into(),
+                    definition: "enum os {\n    mac,\n    linux,\n}".into(),
+                    docs: "".into(),
+                },
+                ExtensionApiItem {
+                    kind: ExtensionApiItemKind::Import,
+                    name: "current-platform".into(),
+                    definition: "import current-platform: func() -> os;".into(),
+                    docs: "Gets the current operating system.".into(),
+                },
+                ExtensionApiItem {
+                    kind: ExtensionApiItemKind::Type,
+                    name: "env-vars".into(),
+                    definition: "type env-vars = list<tuple<string, string>>;".into(),
+                    docs: "".into(),
+                },
+                ExtensionApiItem {
+                    kind: ExtensionApiItemKind::Resource,
+                    name: "worktree".into(),
+                    definition: "resource worktree {\n    /// Reads a file.\n    read-text-file: func(path: string) -> result<string, string>;\n}".into(),
+                    docs: "".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extension_api_reference() {
+        let reference = extension_api_reference();
+        assert_eq!(reference.package, "zed:extension");
+        assert_eq!(reference.world, "extension");
+        assert!(reference.items.iter().any(|item| {
+            item.kind == ExtensionApiItemKind::Import && item.name == "latest-github-release"
+        }));
+        assert!(reference.items.iter().any(|item| {
+            item.kind == ExtensionApiItemKind::Export && item.name == "on-installed"
+        }));
+    }
+}
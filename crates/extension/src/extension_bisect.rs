@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A search for the extension that causes a problem. The first round disables
+/// all of the candidates, to check that one of them causes the problem, and
+/// each later round disables half of the remaining candidates until only one
+/// is left.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ExtensionBisect {
+    /// The extensions that may still be causing the problem.
+    candidates: Vec<Arc<str>>,
+    /// How many of the candidates, from the start of the list, are loaded in
+    /// the current round. The rest of the candidates are disabled.
+    enabled_count: usize,
+    /// Whether the problem is known to go away with all of the candidates
+    /// disabled.
+    #[serde(default)]
+    confirmed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExtensionBisectStep {
+    /// Another round is needed, with a different set of extensions disabled.
+    Continue(ExtensionBisect),
+    /// The problem was narrowed down to a single extension.
+    Found(Arc<str>),
+    /// The problem was still present with all of the candidates disabled, so
+    /// it isn't caused by any of them.
+    NotFound,
+}
+
+impl ExtensionBisect {
+    pub fn new(candidates: Vec<Arc<str>>) -> Self {
+        Self {
+            candidates,
+            enabled_count: 0,
+            confirmed: false,
+        }
+    }
+
+    fn halve(candidates: Vec<Arc<str>>) -> Self {
+        Self {
+            enabled_count: candidates.len() / 2,
+            candidates,
+            confirmed: true,
+        }
+    }
+
+    /// The candidates that are loaded in the current round.
+    pub fn enabled_extensions(&self) -> &[Arc<str>] {
+        &self.candidates[..self.enabled_count]
+    }
+
+    /// The candidates that are disabled in the current round.
+    pub fn disabled_extensions(&self) -> &[Arc<str>] {
+        &self.candidates[self.enabled_count..]
+    }
+
+    pub fn candidate_count(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// The number of rounds left, including the current one, if every answer
+    /// keeps the larger half of the candidates.
+    pub fn remaining_rounds(&self) -> usize {
+        let mut rounds = 0;
+        let mut candidates = self.candidates.len();
+        while candidates > 1 {
+            candidates -= candidates / 2;
+            rounds += 1;
+        }
+        if !self.confirmed {
+            rounds += 1;
+        }
+        rounds.max(1)
+    }
+
+    pub(crate) fn is_disabled(&self, extension_id: &str) -> bool {
+        self.disabled_extensions()
+            .iter()
+            .any(|candidate| candidate.as_ref() == extension_id)
+    }
+
+    /// Narrows down the candidates based on whether the problem was still
+    /// present during the current round.
+    pub fn next(&self, problem_present: bool) -> ExtensionBisectStep {
+        if !self.confirmed {
+            return match (problem_present, self.candidates.as_slice()) {
+                (true, _) | (false, []) => ExtensionBisectStep::NotFound,
+                (false, [culprit]) => ExtensionBisectStep::Found(culprit.clone()),
+                (false, candidates) => {
+                    ExtensionBisectStep::Continue(Self::halve(candidates.to_vec()))
+                }
+            };
+        }
+
+        // If the problem went away, it's caused by one of the extensions that
+        // were disabled. Otherwise, it's caused by one that was loaded.
+        let candidates = if problem_present {
+            self.enabled_extensions()
+        } else {
+            self.disabled_extensions()
+        };
+        match candidates {
+            [] => ExtensionBisectStep::NotFound,
+            [culprit] => ExtensionBisectStep::Found(culprit.clone()),
+            candidates => ExtensionBisectStep::Continue(Self::halve(candidates.to_vec())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bisect(candidates: &[&str]) -> ExtensionBisect {
+        ExtensionBisect::new(candidates.iter().map(|&id| id.into()).collect())
+    }
+
+    #[test]
+    fn test_bisect_finds_culprit() {
+        let round = bisect(&["a", "b", "c", "d", "e"]);
+        assert!(round.enabled_extensions().is_empty());
+        assert_eq!(round.remaining_rounds(), 4);
+
+        // The problem went away with all of the extensions disabled.
+        let ExtensionBisectStep::Continue(round) = round.next(false) else {
+            panic!("expected another round");
+        };
+        assert_eq!(
+            round.enabled_extensions(),
+            [Arc::<str>::from("a"), Arc::<str>::from("b")]
+        );
+        assert!(round.is_disabled("c"));
+        assert!(!round.is_disabled("a"));
+        assert_eq!(round.remaining_rounds(), 3);
+
+        // The problem went away with c, d and e disabled.
+        let ExtensionBisectStep::Continue(round) = round.next(false) else {
+            panic!("expected another round");
+        };
+        assert_eq!(round.enabled_extensions(), [Arc::<str>::from("c")]);
+        assert_eq!(
+            round.disabled_extensions(),
+            [Arc::<str>::from("d"), Arc::<str>::from("e")]
+        );
+
+        // The problem went away again with d and e disabled.
+        let ExtensionBisectStep::Continue(round) = round.next(false) else {
+            panic!("expected another round");
+        };
+        assert_eq!(round.remaining_rounds(), 1);
+
+        // The problem was still present with only d loaded.
+        assert_eq!(round.next(true), ExtensionBisectStep::Found("d".into()));
+    }
+
+    #[test]
+    fn test_bisect_single_candidate() {
+        let round = bisect(&["a"]);
+        assert!(round.enabled_extensions().is_empty());
+        assert_eq!(round.remaining_rounds(), 1);
+        assert_eq!(round.next(false), ExtensionBisectStep::Found("a".into()));
+        assert_eq!(round.next(true), ExtensionBisectStep::NotFound);
+    }
+
+    #[test]
+    fn test_bisect_problem_always_present() {
+        let round = bisect(&["a", "b", "c", "d"]);
+
+        // The problem was still present with all of the extensions disabled,
+        // so none of them are blamed.
+        assert_eq!(round.next(true), ExtensionBisectStep::NotFound);
+
+        // Once the problem is known to go away with all of them disabled, it's
+        // narrowed down among the ones that were loaded.
+        let ExtensionBisectStep::Continue(mut round) = round.next(false) else {
+            panic!("expected another round");
+        };
+        let mut steps = 0;
+        let culprit = loop {
+            steps += 1;
+            match round.next(true) {
+                ExtensionBisectStep::Continue(next_round) => round = next_round,
+                ExtensionBisectStep::Found(culprit) => break culprit,
+                ExtensionBisectStep::NotFound => panic!("expected a culprit"),
+            }
+        };
+        assert_eq!(culprit.as_ref(), "a");
+        assert_eq!(steps, 2);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::wasm_host::parse_wasm_extension_version;
+use std::sync::Arc;
+use util::SemanticVersion;
+
+/// Notes for extension authors on migrating between versions of the extension API.
+pub const EXTENSION_API_MIGRATION_NOTES_URL: &str =
+    "https://github.com/zed-industries/zed/tree/main/crates/extension_api";
+
+/// The oldest and newest versions of `zed_extension_api` that this version of Zed
+/// is able to load. Patch releases of the newest version are also supported.
+pub(crate) const MIN_SUPPORTED_API_VERSION: SemanticVersion = SemanticVersion {
+    major: 0,
+    minor: 1,
+    patch: 0,
+};
+pub(crate) const MAX_SUPPORTED_API_VERSION: SemanticVersion = SemanticVersion {
+    major: 0,
+    minor: 1,
+    patch: 0,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompatibilityIssue {
+    /// The compiled extension does not declare which extension API version it uses.
+    MissingApiVersion,
+    /// The extension was compiled against an extension API version that this
+    /// version of Zed does not support.
+    UnsupportedApiVersion(SemanticVersion),
+    /// The extension's most recent build referred to an item of the extension
+    /// API that no longer exists.
+    MissingApiItem(String),
+}
+
+impl CompatibilityIssue {
+    pub fn message(&self) -> String {
+        match self {
+            Self::MissingApiVersion => "The extension does not declare an API version.".into(),
+            Self::UnsupportedApiVersion(version) => format!(
+                "The extension uses API version {version}, but this version of Zed supports {MIN_SUPPORTED_API_VERSION} through {}.{}.x.",
+                MAX_SUPPORTED_API_VERSION.major, MAX_SUPPORTED_API_VERSION.minor,
+            ),
+            Self::MissingApiItem(name) => {
+                format!("`{name}` is no longer part of the extension API.")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompatibilityReport {
+    pub extension_id: Arc<str>,
+    pub api_version: Option<SemanticVersion>,
+    pub issues: Vec<CompatibilityIssue>,
+}
+
+impl CompatibilityReport {
+    pub fn is_compatible(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+pub(crate) fn check_compatibility(
+    extension_id: Arc<str>,
+    wasm_bytes: Option<&[u8]>,
+    build_error: Option<&str>,
+) -> CompatibilityReport {
+    let mut api_version = None;
+    let mut issues = Vec::new();
+
+    if let Some(wasm_bytes) = wasm_bytes {
+        match parse_wasm_extension_version(&extension_id, wasm_bytes) {
+            Ok(version) => {
+                if !is_supported_api_version(version) {
+                    issues.push(CompatibilityIssue::UnsupportedApiVersion(version));
+                }
+                api_version = Some(version);
+            }
+            Err(_) => issues.push(CompatibilityIssue::MissingApiVersion),
+        }
+    }
+
+    if let Some(build_error) = build_error {
+        issues.extend(
+            missing_api_items(build_error)
+                .into_iter()
+                .map(CompatibilityIssue::MissingApiItem),
+        );
+    }
+
+    CompatibilityReport {
+        extension_id,
+        api_version,
+        issues,
+    }
+}
+
+fn is_supported_api_version(version: SemanticVersion) -> bool {
+    version >= MIN_SUPPORTED_API_VERSION
+        && (version.major, version.minor)
+            <= (
+                MAX_SUPPORTED_API_VERSION.major,
+                MAX_SUPPORTED_API_VERSION.minor,
+            )
+}
+
+/// Finds the extension API items that `rustc` could not resolve, based on its
+/// error output.
+///
+/// Extensions usually import the API crate as `zed`, so errors referring to either
+/// `zed` or `zed_extension_api` are considered.
+fn missing_api_items(build_error: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    for line in build_error.lines() {
+        let line = line.trim();
+        if !line.starts_with("error[") {
+            continue;
+        }
+        if !line.contains("zed_extension_api")
+            && !line.contains("`zed`")
+            && !line.contains("`zed::")
+        {
+            continue;
+        }
+
+        let Some(item) = line.split('`').nth(1) else {
+            continue;
+        };
+        let item = item.to_string();
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_api_items() {
+        let build_error = r#"
+            failed to build extension    Compiling my-extension v0.0.1
+            error[E0425]: cannot find function `latest_github_release` in crate `zed`
+              --> src/lib.rs:10:20
+            error[E0432]: unresolved import `zed_extension_api::GithubRelease`
+            error[E0425]: cannot find function `latest_github_release` in crate `zed`
+            error[E0425]: cannot find value `unrelated` in this scope
+        "#;
+
+        assert_eq!(
+            missing_api_items(build_error),
+            ["latest_github_release", "zed_extension_api::GithubRelease"]
+        );
+    }
+
+    #[test]
+    fn test_is_supported_api_version() {
+        assert!(is_supported_api_version(SemanticVersion::new(0, 1, 0)));
+        assert!(is_supported_api_version(SemanticVersion::new(0, 1, 3)));
+        assert!(!is_supported_api_version(SemanticVersion::new(0, 0, 9)));
+        assert!(!is_supported_api_version(SemanticVersion::new(0, 2, 0)));
+        assert!(!is_supported_api_version(SemanticVersion::new(1, 0, 0)));
+    }
+}
@@ -0,0 +1,215 @@
+use futures::{channel::mpsc::UnboundedSender, AsyncRead, AsyncReadExt as _};
+use gpui::BackgroundExecutor;
+use std::{
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// The size of the chunks that throttled downloads are read in.
+const THROTTLED_CHUNK_SIZE: usize = 16 * 1024;
+
+/// How much of an extension's archive has been downloaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub downloaded_bytes: u64,
+    /// The size of the archive, if the server reported it.
+    pub total_bytes: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// Returns the fraction of the archive that has been downloaded, if its size is known.
+    pub fn fraction(&self) -> Option<f32> {
+        let total_bytes = self.total_bytes.filter(|total_bytes| *total_bytes > 0)?;
+        Some((self.downloaded_bytes as f64 / total_bytes as f64).min(1.) as f32)
+    }
+}
+
+/// Wraps the body of a download, reporting the number of bytes read so far,
+/// and failing once the download has been cancelled.
+pub(crate) struct DownloadReader<R> {
+    reader: R,
+    downloaded_bytes: u64,
+    progress_tx: UnboundedSender<u64>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<R> DownloadReader<R> {
+    pub fn new(reader: R, progress_tx: UnboundedSender<u64>, cancelled: Arc<AtomicBool>) -> Self {
+        Self {
+            reader,
+            downloaded_bytes: 0,
+            progress_tx,
+            cancelled,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for DownloadReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::Other,
+                "download cancelled",
+            )));
+        }
+
+        let this = &mut *self;
+        let poll = Pin::new(&mut this.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(read_len)) = &poll {
+            this.downloaded_bytes += *read_len as u64;
+            this.progress_tx.unbounded_send(this.downloaded_bytes).ok();
+        }
+        poll
+    }
+}
+
+/// A bandwidth limit that is shared equally between the downloads running
+/// under it at the same time.
+#[derive(Clone, Default)]
+pub(crate) struct SharedBandwidth {
+    active_downloads: Arc<AtomicUsize>,
+}
+
+impl SharedBandwidth {
+    /// Starts a download under the given limit, in bytes per second, which
+    /// counts towards the shares of other downloads until it is dropped.
+    pub fn start(&self, bytes_per_second: u64) -> ThrottledDownload {
+        self.active_downloads.fetch_add(1, Ordering::SeqCst);
+        ThrottledDownload {
+            bytes_per_second: bytes_per_second.max(1),
+            active_downloads: self.active_downloads.clone(),
+        }
+    }
+}
+
+/// A limit on the bandwidth that a kind of traffic may use, which is shared
+/// between the requests made under it at the same time.
+#[derive(Clone)]
+pub(crate) struct BandwidthLimit {
+    pub bandwidth: SharedBandwidth,
+    pub bytes_per_second: u64,
+}
+
+/// Reads the rest of the given reader, staying within the given bandwidth
+/// limit if there is one.
+pub(crate) async fn read_to_end_within(
+    limit: Option<&BandwidthLimit>,
+    reader: &mut (impl AsyncRead + Unpin),
+    bytes: &mut Vec<u8>,
+    executor: &BackgroundExecutor,
+) -> io::Result<()> {
+    match limit {
+        Some(limit) => {
+            limit
+                .bandwidth
+                .start(limit.bytes_per_second)
+                .read_to_end(reader, bytes, executor)
+                .await
+        }
+        None => reader.read_to_end(bytes).await.map(drop),
+    }
+}
+
+/// A download whose reads are paced to its share of a [`SharedBandwidth`].
+pub(crate) struct ThrottledDownload {
+    bytes_per_second: u64,
+    active_downloads: Arc<AtomicUsize>,
+}
+
+impl ThrottledDownload {
+    /// Returns how long to wait after reading the given number of bytes, so
+    /// that the download stays within its share of the bandwidth.
+    pub fn delay_for(&self, bytes: usize) -> Duration {
+        let active_downloads = self.active_downloads.load(Ordering::SeqCst).max(1) as u64;
+        let share = (self.bytes_per_second / active_downloads).max(1);
+        Duration::from_secs_f64(bytes as f64 / share as f64)
+    }
+
+    /// Reads the rest of the given reader, waiting between chunks to stay
+    /// within the download's share of the bandwidth.
+    pub async fn read_to_end(
+        &self,
+        reader: &mut (impl AsyncRead + Unpin),
+        bytes: &mut Vec<u8>,
+        executor: &BackgroundExecutor,
+    ) -> io::Result<()> {
+        let mut chunk = vec![0; THROTTLED_CHUNK_SIZE];
+        loop {
+            let read_len = reader.read(&mut chunk).await?;
+            if read_len == 0 {
+                return Ok(());
+            }
+            bytes.extend_from_slice(&chunk[..read_len]);
+            executor.timer(self.delay_for(read_len)).await;
+        }
+    }
+}
+
+impl Drop for ThrottledDownload {
+    fn drop(&mut self) {
+        self.active_downloads.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{channel::mpsc::unbounded, AsyncReadExt, StreamExt};
+
+    #[gpui::test]
+    async fn test_download_reader() {
+        let (progress_tx, progress_rx) = unbounded();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let mut reader = DownloadReader::new(&b"0123456789"[..], progress_tx, cancelled.clone());
+
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf).await.unwrap();
+        reader.read_exact(&mut buf).await.unwrap();
+
+        cancelled.store(true, Ordering::SeqCst);
+        let error = reader.read_exact(&mut buf).await.unwrap_err();
+        assert_eq!(error.to_string(), "download cancelled");
+
+        drop(reader);
+        assert_eq!(progress_rx.collect::<Vec<_>>().await, [4, 8]);
+    }
+
+    #[test]
+    fn test_shared_bandwidth() {
+        let bandwidth = SharedBandwidth::default();
+        let first = bandwidth.start(1000);
+        assert_eq!(first.delay_for(500), Duration::from_millis(500));
+
+        let second = bandwidth.start(1000);
+        assert_eq!(first.delay_for(500), Duration::from_secs(1));
+        assert_eq!(second.delay_for(500), Duration::from_secs(1));
+
+        drop(first);
+        assert_eq!(second.delay_for(500), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_download_progress_fraction() {
+        let progress = DownloadProgress {
+            downloaded_bytes: 25,
+            total_bytes: Some(100),
+        };
+        assert_eq!(progress.fraction(), Some(0.25));
+
+        let progress = DownloadProgress {
+            downloaded_bytes: 25,
+            total_bytes: None,
+        };
+        assert_eq!(progress.fraction(), None);
+    }
+}
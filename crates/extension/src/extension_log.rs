@@ -0,0 +1,116 @@
+use collections::VecDeque;
+use std::{fmt, sync::Arc};
+
+/// The number of lines that are kept in each extension's log. Older lines are
+/// dropped as new ones are logged.
+pub const EXTENSION_LOG_LINE_LIMIT: usize = 2000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExtensionLogSource {
+    /// Output that the extension's wasm module printed.
+    Wasm,
+    /// Output that one of the extension's language servers wrote to stderr.
+    LanguageServer(Arc<str>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionLogLine {
+    pub source: ExtensionLogSource,
+    pub text: String,
+}
+
+impl fmt::Display for ExtensionLogLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            ExtensionLogSource::Wasm => write!(f, "[wasm] {}", self.text),
+            ExtensionLogSource::LanguageServer(name) => write!(f, "[{name}] {}", self.text),
+        }
+    }
+}
+
+/// The most recent output of an extension.
+#[derive(Default)]
+pub struct ExtensionLog {
+    lines: VecDeque<ExtensionLogLine>,
+    total_line_count: usize,
+}
+
+impl ExtensionLog {
+    pub(crate) fn push(&mut self, line: ExtensionLogLine) {
+        if self.lines.len() == EXTENSION_LOG_LINE_LIMIT {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        self.total_line_count += 1;
+    }
+
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &ExtensionLogLine> + ExactSizeIterator {
+        self.lines.iter()
+    }
+
+    /// The number of lines that have ever been logged, including the ones
+    /// that have since been dropped, so that viewers can tell which of the
+    /// lines are new.
+    pub fn total_line_count(&self) -> usize {
+        self.total_line_count
+    }
+
+    /// Returns the lines that were logged after the first `seen_line_count`
+    /// lines, as far as they are still kept.
+    pub fn lines_after(&self, seen_line_count: usize) -> impl Iterator<Item = &ExtensionLogLine> {
+        let new_line_count = self
+            .total_line_count
+            .saturating_sub(seen_line_count)
+            .min(self.lines.len());
+        self.lines.iter().skip(self.lines.len() - new_line_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> ExtensionLogLine {
+        ExtensionLogLine {
+            source: ExtensionLogSource::Wasm,
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn test_extension_log_limit() {
+        let mut log = ExtensionLog::default();
+        for ix in 0..EXTENSION_LOG_LINE_LIMIT + 5 {
+            log.push(line(&ix.to_string()));
+        }
+
+        assert_eq!(log.lines().len(), EXTENSION_LOG_LINE_LIMIT);
+        assert_eq!(log.total_line_count(), EXTENSION_LOG_LINE_LIMIT + 5);
+        assert_eq!(log.lines().next().unwrap().text, "5");
+
+        let new_lines = log
+            .lines_after(EXTENSION_LOG_LINE_LIMIT + 3)
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            new_lines,
+            [
+                (EXTENSION_LOG_LINE_LIMIT + 3).to_string(),
+                (EXTENSION_LOG_LINE_LIMIT + 4).to_string()
+            ]
+        );
+
+        // Lines that were dropped before they were seen can't be returned.
+        assert_eq!(log.lines_after(0).count(), EXTENSION_LOG_LINE_LIMIT);
+    }
+
+    #[test]
+    fn test_extension_log_line_display() {
+        assert_eq!(line("ready").to_string(), "[wasm] ready");
+        let server_line = ExtensionLogLine {
+            source: ExtensionLogSource::LanguageServer("ruby-lsp".into()),
+            text: "ready".into(),
+        };
+        assert_eq!(server_line.to_string(), "[ruby-lsp] ready");
+    }
+}
@@ -1,22 +1,48 @@
 use crate::wasm_host::{wit::LanguageServerConfig, WasmExtension, WasmHost};
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use futures::{Future, FutureExt};
-use gpui::AsyncAppContext;
-use language::{Language, LanguageServerName, LspAdapter, LspAdapterDelegate};
+use collections::HashMap;
+use futures::{channel::mpsc::UnboundedSender, Future, FutureExt};
+use gpui::{AppContext, AsyncAppContext};
+use language::{
+    Language, LanguageRegistry, LanguageServerBinaryStatus, LanguageServerName, LspAdapter,
+    LspAdapterDelegate,
+};
 use lsp::LanguageServerBinary;
+use parking_lot::Mutex;
 use std::{
     any::Any,
+    ffi::OsStr,
     path::{Path, PathBuf},
     pin::Pin,
     sync::Arc,
+    time::{Duration, Instant},
 };
+use util::http::HttpClient;
 use wasmtime_wasi::preview2::WasiView as _;
 
 pub struct ExtensionLspAdapter {
     pub(crate) extension: WasmExtension,
     pub(crate) config: LanguageServerConfig,
     pub(crate) host: Arc<WasmHost>,
+    /// Reports the use of the extension when its language server is started.
+    pub(crate) activation_tx: UnboundedSender<Arc<str>>,
+    /// Reports the extension's crashes, with a description of each.
+    pub(crate) crash_tx: UnboundedSender<(Arc<str>, String)>,
+    /// When the language server was last started for each worktree.
+    pub(crate) started_at: Mutex<HashMap<PathBuf, Instant>>,
+}
+
+/// A language server that is started again this soon after it was last
+/// started is assumed to have crashed.
+const SERVER_RESTART_CRASH_INTERVAL: Duration = Duration::from_secs(30);
+
+impl ExtensionLspAdapter {
+    fn report_crash(&self, description: String) {
+        self.crash_tx
+            .unbounded_send((self.extension.manifest.id.clone(), description))
+            .ok();
+    }
 }
 
 #[async_trait(?Send)]
@@ -34,6 +60,24 @@ impl LspAdapter for ExtensionLspAdapter {
         _: &'a mut AsyncAppContext,
     ) -> Pin<Box<dyn 'a + Future<Output = Result<LanguageServerBinary>>>> {
         async move {
+            self.activation_tx
+                .unbounded_send(self.extension.manifest.id.clone())
+                .ok();
+            let now = Instant::now();
+            let previous_start = self
+                .started_at
+                .lock()
+                .insert(delegate.worktree_root_path().to_path_buf(), now);
+            if previous_start.map_or(false, |previous_start| {
+                now - previous_start < SERVER_RESTART_CRASH_INTERVAL
+            }) {
+                self.report_crash(format!(
+                    "language server {} was restarted within {} seconds",
+                    self.config.name,
+                    SERVER_RESTART_CRASH_INTERVAL.as_secs()
+                ));
+            }
+
             let command = self
                 .extension
                 .call({
@@ -50,7 +94,13 @@ impl LspAdapter for ExtensionLspAdapter {
                         .boxed()
                     }
                 })
-                .await?;
+                .await
+                .map_err(|error| {
+                    if error.downcast_ref::<wasmtime::Trap>().is_some() {
+                        self.report_crash(format!("{error:#}"));
+                    }
+                    error
+                })?;
 
             let path = self
                 .host
@@ -93,3 +143,63 @@ impl LspAdapter for ExtensionLspAdapter {
         None
     }
 }
+
+/// Runs an extension's `language-server-command` before its language server
+/// is first started, so that the extension fetches the server's binary.
+pub(crate) async fn prefetch_language_server(
+    extension: &WasmExtension,
+    config: LanguageServerConfig,
+    delegate: Arc<dyn LspAdapterDelegate>,
+) -> Result<()> {
+    extension
+        .call(|extension, store| {
+            async move {
+                let resource = store.data_mut().table().push(delegate)?;
+                extension
+                    .call_language_server_command(store, &config, resource)
+                    .await?
+                    .map_err(|e| anyhow!("{}", e))?;
+                anyhow::Ok(())
+            }
+            .boxed()
+        })
+        .await
+}
+
+/// The delegate used to prefetch language servers, which has no worktree, so
+/// the extension's work directory stands in for one. Binaries on the `PATH`
+/// aren't used, so that the extension fetches its own.
+pub(crate) struct PrefetchLspAdapterDelegate {
+    pub(crate) http_client: Arc<dyn HttpClient>,
+    pub(crate) language_registry: Arc<LanguageRegistry>,
+    pub(crate) work_dir: PathBuf,
+}
+
+#[async_trait]
+impl LspAdapterDelegate for PrefetchLspAdapterDelegate {
+    fn show_notification(&self, _: &str, _: &mut AppContext) {}
+
+    fn http_client(&self) -> Arc<dyn HttpClient> {
+        self.http_client.clone()
+    }
+
+    fn update_status(&self, language: LanguageServerName, status: LanguageServerBinaryStatus) {
+        self.language_registry.update_lsp_status(language, status);
+    }
+
+    fn worktree_root_path(&self) -> &Path {
+        &self.work_dir
+    }
+
+    async fn which(&self, _: &OsStr) -> Option<PathBuf> {
+        None
+    }
+
+    async fn shell_env(&self) -> HashMap<String, String> {
+        HashMap::default()
+    }
+
+    async fn read_text_file(&self, path: PathBuf) -> Result<String> {
+        Err(anyhow!("no such path {path:?}"))
+    }
+}
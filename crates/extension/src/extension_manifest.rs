@@ -37,6 +37,11 @@ pub struct ExtensionManifest {
     #[serde(default)]
     pub authors: Vec<String>,
     #[serde(default)]
+    pub license: Option<String>,
+    /// A URL where users can support the extension's authors.
+    #[serde(default)]
+    pub funding: Option<String>,
+    #[serde(default)]
     pub lib: LibManifestEntry,
 
     #[serde(default)]
@@ -47,6 +52,10 @@ pub struct ExtensionManifest {
     pub grammars: BTreeMap<Arc<str>, GrammarManifestEntry>,
     #[serde(default)]
     pub language_servers: BTreeMap<LanguageServerName, LanguageServerManifestEntry>,
+    /// The other extensions that this extension requires, as a map from
+    /// extension ids to semver requirements on their versions.
+    #[serde(default)]
+    pub dependencies: BTreeMap<Arc<str>, String>,
 }
 
 #[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
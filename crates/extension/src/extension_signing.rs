@@ -0,0 +1,113 @@
+use anyhow::{anyhow, bail, Context as _, Result};
+use collections::BTreeMap;
+use rsa::{PublicKey as _, RSAPublicKey};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// The keys that Zed trusts to sign extensions published to the registry, as
+/// pairs of key ids and base64-encoded PKCS#1 public keys.
+///
+/// Additional keys can be trusted with the `trusted_signing_keys` setting.
+pub const REGISTRY_SIGNING_KEYS: &[(&str, &str)] = &[];
+
+/// A publisher's signature over the SHA-256 of an extension's archive.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ExtensionSignature {
+    /// The id of the key that produced the signature.
+    pub key_id: String,
+    /// The base64-encoded PKCS#1 v1.5 signature.
+    pub signature: String,
+}
+
+/// Where a trusted signing key comes from.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningKeySource {
+    /// One of the keys that ship with Zed.
+    Registry,
+    /// A key that the user trusted in their settings.
+    User,
+}
+
+/// The key that signed an installed extension.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ExtensionSigner {
+    pub key_id: Arc<str>,
+    pub source: SigningKeySource,
+}
+
+/// Why an extension's signature couldn't be verified.
+#[derive(Debug)]
+pub enum SignatureError {
+    /// The signature was made with a key that isn't trusted.
+    UntrustedKey(String),
+    /// The signature doesn't match the archive.
+    Invalid(anyhow::Error),
+}
+
+impl std::fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureError::UntrustedKey(key_id) => {
+                write!(f, "signed with untrusted key {key_id}")
+            }
+            SignatureError::Invalid(error) => write!(f, "invalid signature: {error:#}"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+/// Fails when no key is trusted to verify signatures, because Zed ships no
+/// registry keys and none are set in `trusted_signing_keys`. This is checked
+/// whenever signed extensions are required, so that installs fail with a
+/// clear error instead of being rejected as untrusted one by one.
+pub fn ensure_signing_keys_trusted(trusted_keys: &BTreeMap<String, String>) -> Result<()> {
+    if REGISTRY_SIGNING_KEYS.is_empty() && trusted_keys.is_empty() {
+        bail!(
+            "signed extensions are required, but no signing keys are trusted. \
+            Add the keys of the registry's publishers to the `trusted_signing_keys` setting."
+        );
+    }
+    Ok(())
+}
+
+/// Verifies a signature over an archive's SHA-256 digest against the keys
+/// that ship with Zed and the ones the user trusts.
+pub fn verify_archive_signature(
+    archive_sha256: &[u8],
+    signature: &ExtensionSignature,
+    trusted_keys: &BTreeMap<String, String>,
+) -> Result<ExtensionSigner, SignatureError> {
+    let (public_key, source) = REGISTRY_SIGNING_KEYS
+        .iter()
+        .find(|(key_id, _)| *key_id == signature.key_id)
+        .map(|(_, public_key)| (*public_key, SigningKeySource::Registry))
+        .or_else(|| {
+            trusted_keys
+                .get(&signature.key_id)
+                .map(|public_key| (public_key.as_str(), SigningKeySource::User))
+        })
+        .ok_or_else(|| SignatureError::UntrustedKey(signature.key_id.clone()))?;
+
+    verify_signature(archive_sha256, &signature.signature, public_key)
+        .map_err(SignatureError::Invalid)?;
+
+    Ok(ExtensionSigner {
+        key_id: signature.key_id.as_str().into(),
+        source,
+    })
+}
+
+fn verify_signature(digest: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let public_key = base64::decode(public_key).context("failed to base64-decode public key")?;
+    let public_key = RSAPublicKey::from_pkcs1(&public_key).context("failed to parse public key")?;
+    let signature = base64::decode(signature).context("failed to base64-decode signature")?;
+    public_key
+        .verify(
+            rsa::PaddingScheme::new_pkcs1v15_sign(Some(rsa::Hash::SHA2_256)),
+            digest,
+            &signature,
+        )
+        .map_err(|_| anyhow!("signature does not match the archive"))
+}
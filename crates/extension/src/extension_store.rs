@@ -1,59 +1,274 @@
 mod build_extension;
+mod build_log;
+mod extension_api_reference;
+mod extension_bisect;
+mod extension_compatibility;
+mod extension_download;
+mod extension_log;
 mod extension_lsp_adapter;
 mod extension_manifest;
+mod extension_settings;
+mod extension_signing;
 mod wasm_host;
 
 #[cfg(test)]
 mod extension_store_test;
 
-use crate::{extension_lsp_adapter::ExtensionLspAdapter, wasm_host::wit};
+use crate::{
+    extension_lsp_adapter::{
+        prefetch_language_server, ExtensionLspAdapter, PrefetchLspAdapterDelegate,
+    },
+    wasm_host::wit,
+};
 use anyhow::{anyhow, bail, Context as _, Result};
 use async_compression::futures::bufread::GzipDecoder;
 use async_tar::Archive;
 use build_extension::{CompileExtensionOptions, ExtensionBuilder};
-use collections::{hash_map, BTreeMap, HashMap, HashSet};
+use collections::{hash_map, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use extension_download::{read_to_end_within, BandwidthLimit, DownloadReader, SharedBandwidth};
 use extension_manifest::ExtensionLibraryKind;
+use extension_signing::SignatureError;
 use fs::{Fs, RemoveOptions};
 use futures::{
     channel::{
         mpsc::{unbounded, UnboundedSender},
         oneshot,
     },
+    future::Shared,
     io::BufReader,
     select_biased, AsyncReadExt as _, Future, FutureExt as _, StreamExt as _,
 };
-use gpui::{actions, AppContext, Context, EventEmitter, Global, Model, ModelContext, Task};
+use gpui::{
+    actions, AppContext, Context, EventEmitter, Global, Model, ModelContext, SharedString, Task,
+};
 use language::{
-    LanguageConfig, LanguageMatcher, LanguageQueries, LanguageRegistry, QUERY_FILENAME_PREFIXES,
+    LanguageConfig, LanguageMatcher, LanguageQueries, LanguageRegistry, LanguageServerName,
+    LspAdapterDelegate, QUERY_FILENAME_PREFIXES,
 };
+use lsp::{IoKind, LanguageServerId};
 use node_runtime::NodeRuntime;
+use parking_lot::Mutex;
+use project::{project_settings::ProjectSettings, Project};
+use rand::Rng as _;
+use semver::VersionReq;
 use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use sha2::{Digest, Sha256};
 use std::{
     cmp::Ordering,
     ffi::OsStr,
+    io::{self, Read as _},
+    iter,
     path::{self, Path, PathBuf},
-    sync::Arc,
-    time::{Duration, Instant},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    time::{Duration, Instant, SystemTime},
 };
+use sysinfo::{ProcessRefreshKind, System, UpdateKind};
 use theme::{ThemeRegistry, ThemeSettings};
+use time::OffsetDateTime;
 use util::{
-    http::{AsyncBody, HttpClient, HttpClientWithUrl},
+    github::latest_github_release,
+    http::{self, AsyncBody, Configurable as _, HttpClient, HttpClientWithUrl, Uri},
     paths::EXTENSIONS_DIR,
     ResultExt,
 };
 use wasm_host::{WasmExtension, WasmHost};
 
+pub use build_log::{parse_ansi, AnsiColor, AnsiStyle, BuildLog};
+pub use extension_api_reference::{
+    extension_api_reference, ExtensionApiItem, ExtensionApiItemKind, ExtensionApiReference,
+};
+pub use extension_bisect::{ExtensionBisect, ExtensionBisectStep};
+pub use extension_compatibility::{
+    CompatibilityIssue, CompatibilityReport, EXTENSION_API_MIGRATION_NOTES_URL,
+};
+pub use extension_download::DownloadProgress;
+pub use extension_log::{
+    ExtensionLog, ExtensionLogLine, ExtensionLogSource, EXTENSION_LOG_LINE_LIMIT,
+};
 pub use extension_manifest::{ExtensionManifest, GrammarManifestEntry, OldExtensionManifest};
+pub use extension_settings::{ExtensionRegistrySettings, ExtensionSettings, SyncedExtension};
+pub use extension_signing::{
+    ExtensionSignature, ExtensionSigner, SigningKeySource, REGISTRY_SIGNING_KEYS,
+};
 
 const RELOAD_DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
 const FS_WATCH_LATENCY: Duration = Duration::from_millis(100);
+const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+const AUTO_UPDATE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
 
-#[derive(Deserialize)]
+const METADATA_PREFETCH_DELAY: Duration = Duration::from_secs(10);
+const DOWNLOAD_RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+const DELTA_MANIFEST_FILE: &str = "delta.json";
+const REGISTRY_MAX_RETRIES: u32 = 4;
+const REGISTRY_RETRY_DELAY: Duration = Duration::from_secs(1);
+const REGISTRY_MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+const REQUIRED_EXTENSIONS_RETRY_DELAY: Duration = Duration::from_secs(30);
+const REQUIRED_EXTENSIONS_MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+const EXTENSION_ACTIVITY_RESOLUTION: Duration = Duration::from_secs(60 * 60);
+const EXTENSION_CRASH_WINDOW: Duration = Duration::from_secs(10 * 60);
+/// How long an installed extension must have gone without being used to be
+/// suggested for removal.
+pub const UNUSED_EXTENSION_THRESHOLD: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+
+const REPLACED_DIR_SUFFIX: &str = ".replaced";
+
+const CHECKSUMS_FILE_NAME: &str = ".checksums.json";
+
+/// A registry's metadata for a single version of an extension.
+#[derive(Deserialize, Serialize)]
+pub struct ExtensionVersionApiResponse {
+    pub data: ExtensionApiResponse,
+}
+
+#[derive(Deserialize, Serialize)]
 pub struct ExtensionsApiResponse {
     pub data: Vec<ExtensionApiResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<usize>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Serialize)]
+struct ExtensionUpdatesRequest {
+    extensions: Vec<InstalledExtensionVersion>,
+}
+
+#[derive(Serialize)]
+struct InstalledExtensionVersion {
+    id: Arc<str>,
+    version: Arc<str>,
+}
+
+#[derive(Deserialize)]
+struct ExtensionDeltaManifest {
+    files: Vec<ExtensionDeltaFile>,
+}
+
+#[derive(Deserialize)]
+struct ExtensionDeltaFile {
+    path: String,
+    sha256: String,
+    source: ExtensionDeltaSource,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ExtensionDeltaSource {
+    Unchanged,
+    Replaced,
+    Patched,
+}
+
+/// A query for the extensions listed by the registries.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExtensionQuery {
+    pub search: Option<String>,
+    pub category: Option<ExtensionCategory>,
+    pub sort: Option<ExtensionSort>,
+    pub page: usize,
+    pub page_size: Option<usize>,
+    pub ids: Vec<Arc<str>>,
+}
+
+impl ExtensionQuery {
+    fn is_full_listing(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn query_string(&self) -> String {
+        let mut parameters = Vec::new();
+        if let Some(search) = &self.search {
+            parameters.push(format!("filter={search}"));
+        }
+        if let Some(category) = self.category {
+            parameters.push(format!("category={}", category.as_str()));
+        }
+        if let Some(sort) = self.sort {
+            parameters.push(format!("sort={}", sort.as_str()));
+        }
+        if let Some(page_size) = self.page_size {
+            parameters.push(format!("page={}&page_size={page_size}", self.page));
+        }
+        if !self.ids.is_empty() {
+            parameters.push(format!("ids={}", self.ids.join(",")));
+        }
+        if parameters.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", parameters.join("&"))
+        }
+    }
+}
+
+/// A kind of extension that registries can filter their listing by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionCategory {
+    Themes,
+    Languages,
+    LanguageServers,
+}
+
+impl ExtensionCategory {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Themes => "themes",
+            Self::Languages => "languages",
+            Self::LanguageServers => "language-servers",
+        }
+    }
+}
+
+/// An order that registries can list extensions in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionSort {
+    Downloads,
+    RecentlyUpdated,
+    Name,
+}
+
+impl ExtensionSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Downloads => "downloads",
+            Self::RecentlyUpdated => "updated",
+            Self::Name => "name",
+        }
+    }
+}
+
+/// A page of the extensions listed by the registries.
+pub struct ExtensionsPage {
+    pub extensions: Vec<ExtensionApiResponse>,
+    pub total_count: usize,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RegistryCache {
+    #[serde(default)]
+    fetched_at: Option<SystemTime>,
+    data: Vec<ExtensionApiResponse>,
+    #[serde(default)]
+    listings: BTreeMap<String, CachedRegistryListing>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+struct CachedRegistryListing {
+    etag: String,
+    data: Vec<ExtensionApiResponse>,
+}
+
+impl RegistryCache {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        self.fetched_at
+            .and_then(|fetched_at| SystemTime::now().duration_since(fetched_at).ok())
+            .map_or(false, |age| age < ttl)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct ExtensionApiResponse {
     pub id: Arc<str>,
     pub name: String,
@@ -61,7 +276,98 @@ pub struct ExtensionApiResponse {
     pub description: Option<String>,
     pub authors: Vec<String>,
     pub repository: String,
+    pub license: Option<String>,
+    #[serde(default)]
+    pub funding: Option<String>,
+    #[serde(default)]
+    pub themes: Vec<String>,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    #[serde(default)]
+    pub grammars: Vec<String>,
+    #[serde(default)]
+    pub language_servers: Vec<String>,
     pub download_count: usize,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub published_at: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub publisher: Option<ExtensionPublisher>,
+    #[serde(default)]
+    pub archive_sha256: Option<String>,
+    #[serde(default)]
+    pub signature: Option<ExtensionSignature>,
+    #[serde(default)]
+    pub archive_size: Option<u64>,
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+    #[serde(default)]
+    pub dependencies: BTreeMap<Arc<str>, String>,
+    #[serde(default)]
+    pub registry: Option<Arc<str>>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ExtensionPublisher {
+    pub name: String,
+    #[serde(default)]
+    pub verified: bool,
+}
+
+/// Why an extension is reported to the operators of the extension registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionReportReason {
+    Malware,
+    Broken,
+    Impersonation,
+}
+
+impl ExtensionReportReason {
+    pub const ALL: [Self; 3] = [Self::Malware, Self::Broken, Self::Impersonation];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Malware => "Malware",
+            Self::Broken => "Broken",
+            Self::Impersonation => "Impersonation",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExtensionReportRequest<'a> {
+    reason: ExtensionReportReason,
+    details: &'a str,
+}
+
+/// A set of extensions curated by the operators of the extension registry.
+#[derive(Clone, Deserialize)]
+pub struct ExtensionCollection {
+    pub id: Arc<str>,
+    pub name: String,
+    pub description: Option<String>,
+    pub extension_ids: Vec<Arc<str>>,
+}
+
+#[derive(Deserialize)]
+pub struct ExtensionCollectionsApiResponse {
+    pub data: Vec<ExtensionCollection>,
+}
+
+/// A release of an extension that the registry has blocked, because it is
+/// malicious or badly broken.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct BlockedExtensionVersion {
+    pub extension_id: Arc<str>,
+    pub version: Arc<str>,
+    pub reason: String,
+    #[serde(default)]
+    pub safe_version: Option<Arc<str>>,
+}
+
+#[derive(Deserialize)]
+pub struct ExtensionBlocklistApiResponse {
+    pub data: Vec<BlockedExtensionVersion>,
 }
 
 pub struct ExtensionStore {
@@ -69,19 +375,133 @@ pub struct ExtensionStore {
     extension_index: ExtensionIndex,
     fs: Arc<dyn Fs>,
     http_client: Arc<HttpClientWithUrl>,
+    proxied_http_client: Option<(String, Arc<dyn HttpClient>)>,
     reload_tx: UnboundedSender<Option<Arc<str>>>,
     reload_complete_senders: Vec<oneshot::Sender<()>>,
     installed_dir: PathBuf,
+    downloads_dir: PathBuf,
+    previous_versions_dir: PathBuf,
+    retained_previous_versions: Option<usize>,
     outstanding_operations: HashMap<Arc<str>, ExtensionOperation>,
+    pending_installs: HashMap<Arc<str>, (Arc<str>, Shared<Task<Result<(), Arc<anyhow::Error>>>>)>,
+    downloads: HashMap<Arc<str>, ExtensionDownload>,
+    active_downloads: HashSet<Arc<str>>,
+    download_queue: VecDeque<(Arc<str>, oneshot::Sender<()>)>,
+    failed_operations: HashMap<Arc<str>, FailedOperation>,
+    slow_operations: HashSet<Arc<str>>,
+    operation_watchdogs: HashMap<Arc<str>, Task<()>>,
+    build_cancellations: HashMap<Arc<str>, oneshot::Sender<()>>,
     index_path: PathBuf,
+    registry_cache_path: PathBuf,
+    registries: Vec<Registry>,
+    recently_upgraded_extensions: BTreeMap<Arc<str>, UpgradedExtension>,
+    recently_upgraded_path: PathBuf,
+    pinned_extensions: BTreeMap<Arc<str>, Arc<str>>,
+    pinned_extensions_path: PathBuf,
+    extension_signers: BTreeMap<Arc<str>, ExtensionSigner>,
+    extension_signers_path: PathBuf,
+    extension_sources: BTreeMap<Arc<str>, ExtensionSource>,
+    extension_sources_path: PathBuf,
+    pending_dir: PathBuf,
+    pending_changes: BTreeMap<Arc<str>, PendingExtensionChange>,
+    pending_changes_path: PathBuf,
+    blocklist: Vec<BlockedExtensionVersion>,
+    blocklist_path: PathBuf,
+    extension_activity: BTreeMap<Arc<str>, ExtensionActivity>,
+    extension_activity_path: PathBuf,
+    activation_tx: UnboundedSender<Arc<str>>,
+    crash_tx: UnboundedSender<(Arc<str>, String)>,
+    extension_crashes: HashMap<Arc<str>, Vec<Instant>>,
+    crashed_extensions: HashMap<Arc<str>, SharedString>,
+    log_tx: UnboundedSender<(Arc<str>, ExtensionLogLine)>,
+    extension_logs: HashMap<Arc<str>, ExtensionLog>,
+    language_server_log_subscriptions: HashMap<LanguageServerId, lsp::Subscription>,
+    registry_busy_until: Option<Instant>,
+    required_extensions: Vec<String>,
+    required_extensions_task: Option<Task<()>>,
+    offered_synced_extensions: HashSet<Arc<str>>,
+    synced_extensions_offer: Option<ExtensionList>,
+    tracked_dir: PathBuf,
+    tracked_extensions: BTreeMap<Arc<str>, String>,
+    tracked_revisions: HashMap<Arc<str>, String>,
+    tracked_extension_poll_interval: Duration,
+    tracking_task: Option<Task<()>>,
+    auto_update_task: Option<Task<()>>,
+    auto_updating_extensions: HashSet<Arc<str>>,
+    background_bandwidth: SharedBandwidth,
     language_registry: Arc<LanguageRegistry>,
     theme_registry: Arc<ThemeRegistry>,
     modified_extensions: HashSet<Arc<str>>,
+    dev_extension_build_errors: HashMap<Arc<str>, String>,
+    dev_extension_build_logs: HashMap<Arc<str>, Arc<Mutex<String>>>,
+    dev_extensions_rebuild_log: Option<Arc<Mutex<String>>>,
+    dev_extension_compatibility: HashMap<Arc<str>, CompatibilityReport>,
+    is_rebuilding_dev_extensions: bool,
+    remote_extensions: HashMap<Arc<str>, ExtensionApiResponse>,
+    extension_collections: Vec<ExtensionCollection>,
+    extension_data_sizes: HashMap<Arc<str>, u64>,
+    extension_load_times: HashMap<Arc<str>, Duration>,
+    server_process_usage: HashMap<Arc<str>, ExtensionResourceUsage>,
+    process_monitor: Arc<Mutex<Option<System>>>,
+    installed_extension_sizes: HashMap<Arc<str>, u64>,
+    extension_work_dir_sizes: HashMap<Arc<str>, u64>,
+    disabled_extensions: HashMap<Arc<str>, DisabledExtension>,
+    damaged_extensions: HashMap<Arc<str>, SharedString>,
+    unloaded_extensions: HashSet<Arc<str>>,
+    extensions_requiring_restart: HashSet<Arc<str>>,
+    safe_mode: bool,
+    safe_mode_path: PathBuf,
+    bisect: Option<ExtensionBisect>,
+    bisect_path: PathBuf,
     wasm_host: Arc<WasmHost>,
     wasm_extensions: Vec<(Arc<ExtensionManifest>, WasmExtension)>,
     tasks: Vec<Task<()>>,
 }
 
+/// An install or upgrade that has been downloaded and unpacked, but that won't
+/// be applied until the extensions are next reloaded.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PendingExtensionChange {
+    pub version: Arc<str>,
+    pub previous_version: Option<Arc<str>>,
+    signer: Option<ExtensionSigner>,
+    #[serde(default)]
+    source: Option<ExtensionSource>,
+}
+
+/// Where an extension that wasn't installed from the registry came from, so
+/// that it can be updated from the same place.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExtensionSource {
+    Git {
+        repository_url: String,
+        revision: String,
+    },
+    GithubRelease {
+        repository: String,
+        tag: String,
+    },
+}
+
+/// What a directory found by [`ExtensionStore::find_orphaned_directories`]
+/// was left over from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrphanedDirectoryKind {
+    BrokenInstall,
+    InterruptedDownload,
+    PreviousVersions,
+    TrackedCheckout,
+}
+
+/// A directory within the extensions directory that nothing refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrphanedDirectory {
+    pub path: PathBuf,
+    pub kind: OrphanedDirectoryKind,
+    pub size: u64,
+}
+
 #[derive(Clone)]
 pub enum ExtensionStatus {
     NotInstalled,
@@ -91,16 +511,179 @@ pub enum ExtensionStatus {
     Removing,
 }
 
-enum ExtensionOperation {
+/// How long an extension remains disabled before being re-enabled automatically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisabledUntil {
+    Time(Instant),
+    Restart,
+    Reenabled,
+}
+
+struct DisabledExtension {
+    until: DisabledUntil,
+    _enable_task: Option<Task<()>>,
+}
+
+struct ExtensionDownload {
+    progress: DownloadProgress,
+    cancelled: Arc<AtomicBool>,
+    cancel_request: Option<oneshot::Sender<()>>,
+    stage: DownloadStage,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DownloadStage {
+    Queued,
+    Requesting,
+    Downloading,
+    Loading,
+}
+
+/// The installed extensions that provide support for a language.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LanguageProviders {
+    pub language: Option<Arc<str>>,
+    pub grammar: Option<Arc<str>>,
+    pub language_servers: Vec<(LanguageServerName, Arc<str>)>,
+}
+
+/// The resources that an installed extension is using.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExtensionResourceUsage {
+    pub wasm_memory: u64,
+    pub wasm_busy_time: Duration,
+    pub server_processes: usize,
+    pub server_memory: u64,
+    pub server_cpu_usage: f32,
+}
+
+/// When an installed extension was last used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ExtensionActivity {
+    pub tracked_since: SystemTime,
+    pub last_used: Option<SystemTime>,
+}
+
+/// An extension that was upgraded and whose upgrade hasn't been acknowledged.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UpgradedExtension {
+    pub previous_version: Option<Arc<str>>,
+    pub version: Arc<str>,
+}
+
+/// A registry that extensions are fetched from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Registry {
+    pub name: Arc<str>,
+    url: Option<String>,
+}
+
+impl Registry {
+    fn zed() -> Self {
+        Self {
+            name: "Zed".into(),
+            url: None,
+        }
+    }
+}
+
+/// A list of installed extensions, which can be exported and then imported
+/// elsewhere to install the same extensions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ExtensionList {
+    pub extensions: Vec<ExtensionListEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ExtensionListEntry {
+    pub id: Arc<str>,
+    pub version: Arc<str>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// What installing a version of an extension would do, as reported by
+/// [`ExtensionStore::preview_install`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstallPreview {
+    pub extension_id: Arc<str>,
+    pub version: Arc<str>,
+    pub download_size: u64,
+    pub files: Vec<(PathBuf, u64)>,
+    pub languages: Vec<Arc<str>>,
+    pub themes: Vec<Arc<str>>,
+    pub grammars: Vec<Arc<str>>,
+    pub language_servers: Vec<LanguageServerName>,
+}
+
+impl InstallPreview {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|(_, size)| size).sum()
+    }
+}
+
+struct FailedOperation {
+    operation: ExtensionOperation,
+    version: Arc<str>,
+    error: SharedString,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionOperation {
     Upgrade,
     Install,
     Remove,
 }
 
-#[derive(Copy, Clone)]
+/// An install, upgrade, or removal that is in progress or has failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtensionOperationEntry {
+    pub extension_id: Arc<str>,
+    pub operation: ExtensionOperation,
+    pub state: ExtensionOperationState,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtensionOperationState {
+    Queued(usize),
+    Pending,
+    Downloading(DownloadProgress),
+    Loading,
+    Failed(SharedString),
+}
+
+#[derive(Clone)]
 pub enum Event {
     ExtensionsUpdated,
     StartedReloading,
+    ExtensionInstalled {
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        operation: ExtensionOperation,
+    },
+    ExtensionOperationFailed {
+        extension_id: Arc<str>,
+        operation: ExtensionOperation,
+        error: SharedString,
+    },
+    ExtensionsAutoUpdated {
+        upgraded: Vec<(Arc<str>, Arc<str>)>,
+        failed: Vec<Arc<str>>,
+    },
+    RegistryChanged,
+    SyncedExtensionsAvailable {
+        extensions: ExtensionList,
+    },
+    InstalledVersionsBlocked {
+        extension_ids: Vec<Arc<str>>,
+    },
+    ExtensionDisabledAfterCrashes {
+        extension_id: Arc<str>,
+        error: SharedString,
+    },
+    ExtensionLogged {
+        extension_id: Arc<str>,
+    },
 }
 
 impl EventEmitter<Event> for ExtensionStore {}
@@ -109,6 +692,10 @@ struct GlobalExtensionStore(Model<ExtensionStore>);
 
 impl Global for GlobalExtensionStore {}
 
+struct ExtensionsDisabledForLaunch;
+
+impl Global for ExtensionsDisabledForLaunch {}
+
 #[derive(Debug, Deserialize, Serialize, Default, PartialEq, Eq)]
 pub struct ExtensionIndex {
     pub extensions: BTreeMap<Arc<str>, ExtensionIndexEntry>,
@@ -144,8 +731,14 @@ pub fn init(
     node_runtime: Arc<dyn NodeRuntime>,
     language_registry: Arc<LanguageRegistry>,
     theme_registry: Arc<ThemeRegistry>,
+    disable_extensions: bool,
     cx: &mut AppContext,
 ) {
+    ExtensionSettings::register(cx);
+    if disable_extensions {
+        cx.set_global(ExtensionsDisabledForLaunch);
+    }
+
     let store = cx.new_model(move |cx| {
         ExtensionStore::new(
             EXTENSIONS_DIR.clone(),
@@ -159,6 +752,36 @@ pub fn init(
         )
     });
 
+    store.update(cx, |store, cx| {
+        store.sync_tracked_extensions(cx);
+        store.sync_auto_update(cx);
+        store.sync_retained_previous_versions(cx);
+        store.sync_synced_extensions(cx);
+        store.sync_registries(cx);
+        store.sync_proxy(cx);
+        store.sync_required_extensions(cx);
+        if ExtensionSettings::get_global(cx).prefetch_metadata {
+            store.prefetch_metadata(cx);
+        }
+    });
+    cx.observe_global::<SettingsStore>({
+        let store = store.clone();
+        move |cx| {
+            store.update(cx, |store, cx| {
+                store.sync_tracked_extensions(cx);
+                store.sync_disabled_extensions(cx);
+                store.sync_auto_update(cx);
+                store.sync_retained_previous_versions(cx);
+                store.sync_synced_extensions(cx);
+                store.sync_registries(cx);
+                store.sync_proxy(cx);
+                store.sync_required_extensions(cx);
+                store.record_active_theme(cx);
+            })
+        }
+    })
+    .detach();
+
     cx.on_action(|_: &ReloadExtensions, cx| {
         let store = cx.global::<GlobalExtensionStore>().0.clone();
         store.update(cx, |store, cx| drop(store.reload(None, cx)));
@@ -186,16 +809,102 @@ impl ExtensionStore {
         let work_dir = extensions_dir.join("work");
         let build_dir = build_dir.unwrap_or_else(|| extensions_dir.join("build"));
         let installed_dir = extensions_dir.join("installed");
+        let downloads_dir = extensions_dir.join("downloads");
+        let previous_versions_dir = extensions_dir.join("previous_versions");
         let index_path = extensions_dir.join("index.json");
+        let registry_cache_path = extensions_dir.join("registry_cache.json");
+        let recently_upgraded_path = extensions_dir.join("recently_upgraded.json");
+        let pinned_extensions_path = extensions_dir.join("pinned_extensions.json");
+        let extension_signers_path = extensions_dir.join("extension_signers.json");
+        let extension_sources_path = extensions_dir.join("extension_sources.json");
+        let pending_dir = extensions_dir.join("pending");
+        let pending_changes_path = extensions_dir.join("pending_changes.json");
+        let blocklist_path = extensions_dir.join("blocklist.json");
+        let extension_activity_path = extensions_dir.join("activity.json");
+        let safe_mode_path = extensions_dir.join("safe_mode");
+        let bisect_path = extensions_dir.join("bisect.json");
+        let tracked_dir = extensions_dir.join("tracked");
 
         let (reload_tx, mut reload_rx) = unbounded();
+        let (activation_tx, mut activation_rx) = unbounded();
+        let (crash_tx, mut crash_rx) = unbounded();
+        let (log_tx, mut log_rx) = unbounded();
         let mut this = Self {
             extension_index: Default::default(),
             installed_dir,
+            downloads_dir,
+            previous_versions_dir,
+            retained_previous_versions: None,
             index_path,
+            registry_cache_path,
+            registries: vec![Registry::zed()],
+            recently_upgraded_extensions: Default::default(),
+            recently_upgraded_path,
+            pinned_extensions: Default::default(),
+            pinned_extensions_path,
+            extension_signers: Default::default(),
+            extension_signers_path,
+            extension_sources: Default::default(),
+            extension_sources_path,
+            pending_dir,
+            pending_changes: Default::default(),
+            pending_changes_path,
+            blocklist: Default::default(),
+            blocklist_path,
+            extension_activity: Default::default(),
+            extension_activity_path,
+            activation_tx,
+            crash_tx,
+            extension_crashes: Default::default(),
+            crashed_extensions: Default::default(),
+            log_tx: log_tx.clone(),
+            extension_logs: Default::default(),
+            language_server_log_subscriptions: Default::default(),
+            registry_busy_until: None,
+            required_extensions: Vec::new(),
+            required_extensions_task: None,
+            offered_synced_extensions: Default::default(),
+            synced_extensions_offer: None,
+            tracked_dir,
+            tracked_extensions: Default::default(),
+            tracked_revisions: Default::default(),
+            tracked_extension_poll_interval: Duration::ZERO,
+            tracking_task: None,
+            auto_update_task: None,
+            auto_updating_extensions: Default::default(),
+            background_bandwidth: Default::default(),
             builder: Arc::new(ExtensionBuilder::new(build_dir)),
             outstanding_operations: Default::default(),
+            pending_installs: Default::default(),
+            downloads: Default::default(),
+            active_downloads: Default::default(),
+            download_queue: Default::default(),
+            failed_operations: Default::default(),
+            slow_operations: Default::default(),
+            operation_watchdogs: Default::default(),
+            build_cancellations: Default::default(),
             modified_extensions: Default::default(),
+            dev_extension_build_errors: Default::default(),
+            dev_extension_build_logs: Default::default(),
+            dev_extensions_rebuild_log: None,
+            dev_extension_compatibility: Default::default(),
+            is_rebuilding_dev_extensions: false,
+            remote_extensions: Default::default(),
+            extension_collections: Default::default(),
+            extension_data_sizes: Default::default(),
+            extension_load_times: Default::default(),
+            server_process_usage: Default::default(),
+            process_monitor: Default::default(),
+            installed_extension_sizes: Default::default(),
+            extension_work_dir_sizes: Default::default(),
+            disabled_extensions: Default::default(),
+            damaged_extensions: Default::default(),
+            unloaded_extensions: Default::default(),
+            extensions_requiring_restart: Default::default(),
+            safe_mode: false,
+            safe_mode_path,
+            bisect: None,
+            bisect_path,
             reload_complete_senders: Vec::new(),
             wasm_host: WasmHost::new(
                 fs.clone(),
@@ -203,10 +912,12 @@ impl ExtensionStore {
                 node_runtime,
                 language_registry.clone(),
                 work_dir,
+                log_tx,
             ),
             wasm_extensions: Vec::new(),
             fs,
             http_client,
+            proxied_http_client: None,
             language_registry,
             theme_registry,
             reload_tx,
@@ -216,14 +927,96 @@ impl ExtensionStore {
         // The extensions store maintains an index file, which contains a complete
         // list of the installed extensions and the resources that they provide.
         // This index is loaded synchronously on startup.
-        let (index_content, index_metadata, extensions_metadata) =
-            cx.background_executor().block(async {
-                futures::join!(
-                    this.fs.load(&this.index_path),
-                    this.fs.metadata(&this.index_path),
-                    this.fs.metadata(&this.installed_dir),
-                )
-            });
+        let (
+            index_content,
+            index_metadata,
+            extensions_metadata,
+            recently_upgraded_content,
+            pinned_extensions_content,
+            extension_signers_content,
+            extension_sources_content,
+            pending_changes_content,
+            blocklist_content,
+            extension_activity_content,
+            safe_mode_marker_exists,
+            bisect_content,
+        ) = cx.background_executor().block(async {
+            futures::join!(
+                this.fs.load(&this.index_path),
+                this.fs.metadata(&this.index_path),
+                this.fs.metadata(&this.installed_dir),
+                this.fs.load(&this.recently_upgraded_path),
+                this.fs.load(&this.pinned_extensions_path),
+                this.fs.load(&this.extension_signers_path),
+                this.fs.load(&this.extension_sources_path),
+                this.fs.load(&this.pending_changes_path),
+                this.fs.load(&this.blocklist_path),
+                this.fs.load(&this.extension_activity_path),
+                this.fs.is_file(&this.safe_mode_path),
+                this.fs.load(&this.bisect_path),
+            )
+        });
+        this.safe_mode = safe_mode_marker_exists || cx.has_global::<ExtensionsDisabledForLaunch>();
+        if this.safe_mode {
+            log::info!("starting with all extensions disabled");
+        }
+        if let Ok(bisect_content) = bisect_content {
+            if let Some(bisect) = serde_json::from_str::<ExtensionBisect>(&bisect_content).log_err()
+            {
+                log::info!(
+                    "bisecting extensions, with {} of {} candidates disabled",
+                    bisect.disabled_extensions().len(),
+                    bisect.candidate_count()
+                );
+                this.bisect = Some(bisect);
+            }
+        }
+        if let Ok(recently_upgraded_content) = recently_upgraded_content {
+            if let Some(recently_upgraded) =
+                serde_json::from_str(&recently_upgraded_content).log_err()
+            {
+                this.recently_upgraded_extensions = recently_upgraded;
+            }
+        }
+        if let Ok(pinned_extensions_content) = pinned_extensions_content {
+            if let Some(pinned_extensions) =
+                serde_json::from_str(&pinned_extensions_content).log_err()
+            {
+                this.pinned_extensions = pinned_extensions;
+            }
+        }
+        if let Ok(extension_signers_content) = extension_signers_content {
+            if let Some(extension_signers) =
+                serde_json::from_str(&extension_signers_content).log_err()
+            {
+                this.extension_signers = extension_signers;
+            }
+        }
+        if let Ok(extension_sources_content) = extension_sources_content {
+            if let Some(extension_sources) =
+                serde_json::from_str(&extension_sources_content).log_err()
+            {
+                this.extension_sources = extension_sources;
+            }
+        }
+        if let Ok(pending_changes_content) = pending_changes_content {
+            if let Some(pending_changes) = serde_json::from_str(&pending_changes_content).log_err()
+            {
+                this.pending_changes = pending_changes;
+            }
+        }
+        if let Ok(blocklist_content) = blocklist_content {
+            if let Some(blocklist) = serde_json::from_str(&blocklist_content).log_err() {
+                this.blocklist = blocklist;
+            }
+        }
+        if let Ok(extension_activity_content) = extension_activity_content {
+            if let Some(extension_activity) =
+                serde_json::from_str(&extension_activity_content).log_err()
+            {
+                this.extension_activity = extension_activity;
+            }
+        }
 
         // Normally, there is no need to rebuild the index. But if the index file
         // is invalid or is out-of-date according to the filesystem mtimes, then
@@ -243,6 +1036,17 @@ impl ExtensionStore {
             }
         }
 
+        this.apply_disabled_extensions_setting(cx);
+
+        // Check the installed extensions before loading them, so that
+        // corrupted or partially deleted ones are reported as damaged rather
+        // than failing to load.
+        this.damaged_extensions = cx.background_executor().block(check_installed_extensions(
+            this.fs.clone(),
+            this.installed_dir.clone(),
+            &extension_index,
+        ));
+
         // Immediately load all of the extensions in the initial manifest. If the
         // index needs to be rebuild, then enqueue
         let load_initial_extensions = this.extensions_updated(extension_index, cx);
@@ -250,6 +1054,16 @@ impl ExtensionStore {
             let _ = this.reload(None, cx);
         }
 
+        // Restore the extensions whose replacement was interrupted by a crash,
+        // then apply the changes that were staged during the previous session.
+        let recover = this.recover_interrupted_installs(cx);
+        this.tasks.push(cx.spawn(|this, mut cx| async move {
+            recover.await.log_err();
+            if let Ok(apply) = this.update(&mut cx, |this, cx| this.apply_pending_changes(cx)) {
+                apply.await.log_err();
+            }
+        }));
+
         // Perform all extension loading in a single task to ensure that we
         // never attempt to simultaneously load/unload extensions from multiple
         // parallel tasks.
@@ -288,6 +1102,49 @@ impl ExtensionStore {
             .map(drop)
         }));
 
+        this.tasks.push(cx.spawn(|this, mut cx| async move {
+            while let Some(extension_id) = activation_rx.next().await {
+                if this
+                    .update(&mut cx, |this, cx| {
+                        this.record_extension_activation(extension_id, cx)
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }));
+
+        this.tasks.push(cx.spawn(|this, mut cx| async move {
+            while let Some((extension_id, description)) = crash_rx.next().await {
+                if this
+                    .update(&mut cx, |this, cx| {
+                        this.record_extension_crash(extension_id, description, cx)
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }));
+
+        this.tasks.push(cx.spawn(|this, mut cx| async move {
+            while let Some((extension_id, line)) = log_rx.next().await {
+                if this
+                    .update(&mut cx, |this, cx| {
+                        this.extension_logs
+                            .entry(extension_id.clone())
+                            .or_default()
+                            .push(line);
+                        cx.emit(Event::ExtensionLogged { extension_id });
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }));
+
         // Watch the installed extensions directory for changes. Whenever changes are
         // detected, rebuild the extension index, and load/unload any extensions that
         // have been added, removed, or modified.
@@ -350,85 +1207,4381 @@ impl ExtensionStore {
         }
     }
 
-    pub fn dev_extensions(&self) -> impl Iterator<Item = &Arc<ExtensionManifest>> {
-        self.extension_index
-            .extensions
-            .values()
-            .filter_map(|extension| extension.dev.then_some(&extension.manifest))
+    pub fn download_progress(&self, extension_id: &str) -> Option<DownloadProgress> {
+        self.downloads
+            .get(extension_id)
+            .map(|download| download.progress)
     }
 
-    pub fn fetch_extensions(
-        &self,
-        search: Option<&str>,
+    pub fn download_queue_position(&self, extension_id: &str) -> Option<usize> {
+        self.download_queue
+            .iter()
+            .position(|(queued_id, _)| queued_id.as_ref() == extension_id)
+    }
+
+    fn acquire_download_slot(
+        &mut self,
+        extension_id: &Arc<str>,
         cx: &mut ModelContext<Self>,
-    ) -> Task<Result<Vec<ExtensionApiResponse>>> {
-        let url = self.http_client.build_zed_api_url(&format!(
-            "/extensions{query}",
-            query = search
-                .map(|search| format!("?filter={search}"))
-                .unwrap_or_default()
-        ));
-        let http_client = self.http_client.clone();
-        cx.spawn(move |_, _| async move {
-            let mut response = http_client.get(&url, AsyncBody::empty(), true).await?;
+    ) -> Option<oneshot::Receiver<()>> {
+        let max_concurrent_downloads = ExtensionSettings::get_global(cx)
+            .max_concurrent_downloads
+            .max(1);
+        if self.active_downloads.len() < max_concurrent_downloads {
+            self.active_downloads.insert(extension_id.clone());
+            return None;
+        }
 
-            let mut body = Vec::new();
-            response
-                .body_mut()
-                .read_to_end(&mut body)
-                .await
-                .context("error reading extensions")?;
+        let (slot_tx, slot_rx) = oneshot::channel();
+        self.download_queue
+            .push_back((extension_id.clone(), slot_tx));
+        if let Some(download) = self.downloads.get_mut(extension_id) {
+            download.stage = DownloadStage::Queued;
+        }
+        cx.notify();
+        Some(slot_rx)
+    }
 
-            if response.status().is_client_error() {
-                let text = String::from_utf8_lossy(body.as_slice());
-                bail!(
-                    "status error {}, response: {text:?}",
-                    response.status().as_u16()
-                );
+    fn release_download_slot(&mut self, extension_id: &str, cx: &mut ModelContext<Self>) {
+        self.active_downloads.remove(extension_id);
+        self.download_queue
+            .retain(|(queued_id, _)| queued_id.as_ref() != extension_id);
+
+        let max_concurrent_downloads = ExtensionSettings::get_global(cx)
+            .max_concurrent_downloads
+            .max(1);
+        while self.active_downloads.len() < max_concurrent_downloads {
+            let Some((queued_id, slot_tx)) = self.download_queue.pop_front() else {
+                break;
+            };
+            if slot_tx.send(()).is_ok() {
+                if let Some(download) = self.downloads.get_mut(&queued_id) {
+                    download.stage = DownloadStage::Requesting;
+                }
+                self.active_downloads.insert(queued_id);
             }
+        }
+        cx.notify();
+    }
 
-            let response: ExtensionsApiResponse = serde_json::from_slice(&body)?;
+    pub fn cancel_download(&mut self, extension_id: &str) {
+        if let Some(download) = self.downloads.get_mut(extension_id) {
+            download.cancelled.store(true, atomic::Ordering::SeqCst);
+            if let Some(cancel_request) = download.cancel_request.take() {
+                cancel_request.send(()).ok();
+            }
+        }
+    }
 
-            Ok(response.data)
-        })
+    pub fn cancel_operation(&mut self, extension_id: &str) {
+        self.cancel_download(extension_id);
+        if let Some(cancel_build) = self.build_cancellations.remove(extension_id) {
+            cancel_build.send(()).ok();
+        }
     }
 
-    pub fn install_extension(
-        &mut self,
+    pub fn is_operation_slow(&self, extension_id: &str) -> bool {
+        self.slow_operations.contains(extension_id)
+    }
+
+    pub fn keep_waiting(&mut self, extension_id: Arc<str>, cx: &mut ModelContext<Self>) {
+        if self.slow_operations.remove(&extension_id) {
+            self.start_watchdog(extension_id, cx);
+            cx.notify();
+        }
+    }
+
+    fn start_watchdog(&mut self, extension_id: Arc<str>, cx: &mut ModelContext<Self>) {
+        let timeout = ExtensionSettings::get_global(cx).slow_operation_timeout();
+        let watchdog = cx.spawn({
+            let extension_id = extension_id.clone();
+            |this, mut cx| async move {
+                cx.background_executor().timer(timeout).await;
+                this.update(&mut cx, |this, cx| {
+                    let Some(operation) = this.outstanding_operations.get(&extension_id) else {
+                        return;
+                    };
+                    log::warn!(
+                        "extension {extension_id} has not finished {operation:?} after {} seconds",
+                        timeout.as_secs()
+                    );
+                    this.slow_operations.insert(extension_id);
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
+        self.operation_watchdogs.insert(extension_id, watchdog);
+    }
+
+    fn start_cancellable_build(&mut self, extension_id: Arc<str>) -> oneshot::Receiver<()> {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        self.build_cancellations.insert(extension_id, cancel_tx);
+        cancel_rx
+    }
+
+    fn finish_operation(&mut self, extension_id: &str) {
+        self.outstanding_operations.remove(extension_id);
+        self.operation_watchdogs.remove(extension_id);
+        self.slow_operations.remove(extension_id);
+        self.build_cancellations.remove(extension_id);
+    }
+
+    /// Returns the operations that are in progress, followed by the installs
+    /// and upgrades that have failed.
+    pub fn operations(&self) -> Vec<ExtensionOperationEntry> {
+        let mut operations = self
+            .outstanding_operations
+            .iter()
+            .map(|(extension_id, operation)| {
+                let state = match self.downloads.get(extension_id) {
+                    Some(download) => match download.stage {
+                        DownloadStage::Queued => ExtensionOperationState::Queued(
+                            self.download_queue_position(extension_id)
+                                .unwrap_or_default(),
+                        ),
+                        DownloadStage::Requesting => ExtensionOperationState::Pending,
+                        DownloadStage::Downloading => {
+                            ExtensionOperationState::Downloading(download.progress)
+                        }
+                        DownloadStage::Loading => ExtensionOperationState::Loading,
+                    },
+                    None => ExtensionOperationState::Pending,
+                };
+                ExtensionOperationEntry {
+                    extension_id: extension_id.clone(),
+                    operation: *operation,
+                    state,
+                }
+            })
+            .collect::<Vec<_>>();
+        operations.sort_by(|a, b| a.extension_id.cmp(&b.extension_id));
+
+        let mut failed_operations = self
+            .failed_operations
+            .iter()
+            .map(|(extension_id, failed)| ExtensionOperationEntry {
+                extension_id: extension_id.clone(),
+                operation: failed.operation,
+                state: ExtensionOperationState::Failed(failed.error.clone()),
+            })
+            .collect::<Vec<_>>();
+        failed_operations.sort_by(|a, b| a.extension_id.cmp(&b.extension_id));
+
+        operations.extend(failed_operations);
+        operations
+    }
+
+    pub fn retry_operation(&mut self, extension_id: Arc<str>, cx: &mut ModelContext<Self>) {
+        if let Some(failed) = self.failed_operations.remove(&extension_id) {
+            self.install_or_upgrade_extension(extension_id, failed.version, failed.operation, cx)
+                .detach_and_log_err(cx);
+        }
+    }
+
+    pub fn dismiss_failed_operation(&mut self, extension_id: &str, cx: &mut ModelContext<Self>) {
+        if self.failed_operations.remove(extension_id).is_some() {
+            cx.notify();
+        }
+    }
+
+    pub fn loaded_extensions(&self) -> impl Iterator<Item = &Arc<ExtensionManifest>> {
+        self.extension_index
+            .extensions
+            .iter()
+            .filter(|(extension_id, _)| !self.unloaded_extensions.contains(*extension_id))
+            .map(|(_, extension)| &extension.manifest)
+    }
+
+    pub fn installed_extension(&self, extension_id: &str) -> Option<&Arc<ExtensionManifest>> {
+        self.extension_index
+            .extensions
+            .get(extension_id)
+            .map(|extension| &extension.manifest)
+    }
+
+    pub fn installed_extension_dir(&self, extension_id: &str) -> Option<PathBuf> {
+        self.extension_index
+            .extensions
+            .contains_key(extension_id)
+            .then(|| self.installed_dir.join(extension_id))
+    }
+
+    pub fn dev_extensions(&self) -> impl Iterator<Item = &Arc<ExtensionManifest>> {
+        self.extension_index
+            .extensions
+            .values()
+            .filter_map(|extension| extension.dev.then_some(&extension.manifest))
+    }
+
+    pub fn recently_upgraded_extensions(
+        &self,
+    ) -> impl Iterator<Item = (&Arc<str>, &UpgradedExtension)> {
+        self.recently_upgraded_extensions.iter()
+    }
+
+    pub fn is_recently_upgraded(&self, extension_id: &str) -> bool {
+        self.recently_upgraded_extensions.contains_key(extension_id)
+    }
+
+    pub fn acknowledge_upgrade(&mut self, extension_id: &str, cx: &mut ModelContext<Self>) {
+        if self
+            .recently_upgraded_extensions
+            .remove(extension_id)
+            .is_some()
+        {
+            self.save_recently_upgraded_extensions(cx);
+        }
+    }
+
+    pub fn acknowledge_all_upgrades(&mut self, cx: &mut ModelContext<Self>) {
+        if !self.recently_upgraded_extensions.is_empty() {
+            self.recently_upgraded_extensions.clear();
+            self.save_recently_upgraded_extensions(cx);
+        }
+    }
+
+    fn save_recently_upgraded_extensions(&mut self, cx: &mut ModelContext<Self>) {
+        cx.notify();
+        let fs = self.fs.clone();
+        let path = self.recently_upgraded_path.clone();
+        let content = serde_json::to_string_pretty(&self.recently_upgraded_extensions);
+        cx.background_executor()
+            .spawn(async move { fs.atomic_write(path, content?).await })
+            .detach_and_log_err(cx);
+    }
+
+    pub fn pending_changes(&self) -> &BTreeMap<Arc<str>, PendingExtensionChange> {
+        &self.pending_changes
+    }
+
+    fn save_pending_changes(&mut self, cx: &mut ModelContext<Self>) {
+        cx.notify();
+        let fs = self.fs.clone();
+        let path = self.pending_changes_path.clone();
+        let content = serde_json::to_string_pretty(&self.pending_changes);
+        cx.background_executor()
+            .spawn(async move { fs.atomic_write(path, content?).await })
+            .detach_and_log_err(cx);
+    }
+
+    fn discard_pending_change(&mut self, extension_id: &Arc<str>, cx: &mut ModelContext<Self>) {
+        if self.pending_changes.remove(extension_id).is_none() {
+            return;
+        }
+
+        self.save_pending_changes(cx);
+        let fs = self.fs.clone();
+        let staged_dir = self.pending_dir.join(extension_id.as_ref());
+        cx.background_executor()
+            .spawn(async move {
+                fs.remove_dir(
+                    &staged_dir,
+                    RemoveOptions {
+                        recursive: true,
+                        ignore_if_not_exists: true,
+                    },
+                )
+                .await
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn recover_interrupted_installs(&self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let downloads_dir = self.downloads_dir.clone();
+        let installed_dir = self.installed_dir.clone();
+
+        cx.spawn(|this, mut cx| async move {
+            let Ok(mut entries) = fs.read_dir(&downloads_dir).await else {
+                return Ok(());
+            };
+            let mut restored = Vec::new();
+            while let Some(entry) = entries.next().await {
+                let replaced_dir = entry?;
+                let Some(extension_id) = replaced_dir
+                    .file_name()
+                    .and_then(OsStr::to_str)
+                    .and_then(|name| name.strip_suffix(REPLACED_DIR_SUFFIX))
+                else {
+                    continue;
+                };
+
+                let extension_dir = installed_dir.join(extension_id);
+                if fs.metadata(&extension_dir).await?.is_some() {
+                    fs.remove_dir(
+                        &replaced_dir,
+                        RemoveOptions {
+                            recursive: true,
+                            ignore_if_not_exists: true,
+                        },
+                    )
+                    .await?;
+                } else {
+                    log::info!("restoring extension {extension_id} after an interrupted install");
+                    fs.rename(&replaced_dir, &extension_dir, Default::default())
+                        .await?;
+                    restored.push(Arc::<str>::from(extension_id));
+                }
+            }
+
+            for extension_id in restored {
+                this.update(&mut cx, |this, cx| this.reload(Some(extension_id), cx))?
+                    .await;
+            }
+            Ok(())
+        })
+    }
+
+    /// Applies the staged installs and upgrades, and reloads the extensions.
+    pub fn apply_pending_changes(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let changes = self
+            .pending_changes
+            .iter()
+            .filter(|(extension_id, _)| !self.outstanding_operations.contains_key(*extension_id))
+            .map(|(extension_id, change)| (extension_id.clone(), change.clone()))
+            .collect::<Vec<_>>();
+        if changes.is_empty() {
+            return Task::ready(Ok(()));
+        }
+
+        let extensions_dir = self.extensions_dir();
+        let pending_dir = self.pending_dir.clone();
+        let downloads_dir = self.downloads_dir.clone();
+        let previous_versions_dir = self.previous_versions_dir.clone();
+        let retained_previous_versions =
+            ExtensionSettings::get_global(cx).retained_previous_versions;
+        let fs = self.fs.clone();
+
+        cx.spawn(|this, mut cx| async move {
+            let remove_options = RemoveOptions {
+                recursive: true,
+                ignore_if_not_exists: true,
+            };
+            let mut applied = Vec::new();
+            let mut failed = Vec::new();
+            for (extension_id, change) in changes {
+                let staged_dir = pending_dir.join(extension_id.as_ref());
+                let extension_dir = extensions_dir.join(extension_id.as_ref());
+                let result = async {
+                    if let Some(previous_version) = change
+                        .previous_version
+                        .as_ref()
+                        .filter(|_| retained_previous_versions > 0)
+                    {
+                        let retained_dir = previous_versions_dir
+                            .join(extension_id.as_ref())
+                            .join(previous_version.as_ref());
+                        fs.remove_dir(&retained_dir, remove_options).await?;
+                        fs.create_dir(&previous_versions_dir.join(extension_id.as_ref()))
+                            .await?;
+                        fs.rename(&extension_dir, &retained_dir, Default::default())
+                            .await?;
+                    }
+                    publish_extension_dir(fs.as_ref(), &staged_dir, &extension_dir, &downloads_dir)
+                        .await
+                }
+                .await;
+                match result {
+                    Ok(()) => applied.push((extension_id, change)),
+                    Err(error) => {
+                        log::error!("failed to apply the staged change to {extension_id}: {error}");
+                        fs.remove_dir(&staged_dir, remove_options).await.log_err();
+                        failed.push(extension_id);
+                    }
+                }
+            }
+
+            this.update(&mut cx, |this, cx| {
+                for extension_id in &failed {
+                    this.pending_changes.remove(extension_id);
+                }
+                for (extension_id, change) in &applied {
+                    this.pending_changes.remove(extension_id);
+                    this.set_extension_signer(extension_id, change.signer.clone(), cx);
+                    this.set_extension_source(extension_id, change.source.clone(), cx);
+                    this.damaged_extensions.remove(extension_id);
+                    if change.previous_version.is_some() {
+                        this.recently_upgraded_extensions.insert(
+                            extension_id.clone(),
+                            UpgradedExtension {
+                                previous_version: change.previous_version.clone(),
+                                version: change.version.clone(),
+                            },
+                        );
+                    }
+                }
+                this.save_pending_changes(cx);
+                this.save_recently_upgraded_extensions(cx);
+                this.reload(None, cx)
+            })?
+            .await;
+
+            this.update(&mut cx, |this, cx| {
+                for (extension_id, change) in applied {
+                    let operation = if change.previous_version.is_some() {
+                        ExtensionOperation::Upgrade
+                    } else {
+                        ExtensionOperation::Install
+                    };
+                    this.run_lifecycle_hook(&extension_id, change.previous_version, cx)
+                        .detach();
+                    cx.emit(Event::ExtensionInstalled {
+                        extension_id,
+                        version: change.version,
+                        operation,
+                    });
+                }
+                this.record_synced_extensions(cx);
+                this.prune_previous_versions(cx)
+            })?
+            .await
+            .log_err();
+            Ok(())
+        })
+    }
+
+    pub fn sync_retained_previous_versions(&mut self, cx: &mut ModelContext<Self>) {
+        let retained_previous_versions =
+            ExtensionSettings::get_global(cx).retained_previous_versions;
+        if self.retained_previous_versions == Some(retained_previous_versions) {
+            return;
+        }
+        self.retained_previous_versions = Some(retained_previous_versions);
+        self.prune_previous_versions(cx).detach_and_log_err(cx);
+    }
+
+    /// Removes all but the most recent previous versions of each extension,
+    /// keeping as many as the `retained_previous_versions` setting allows.
+    pub fn prune_previous_versions(&self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let retained_previous_versions =
+            ExtensionSettings::get_global(cx).retained_previous_versions;
+        let previous_versions_dir = self.previous_versions_dir.clone();
+        let fs = self.fs.clone();
+        cx.background_executor().spawn(async move {
+            let Ok(mut extension_dirs) = fs.read_dir(&previous_versions_dir).await else {
+                return Ok(());
+            };
+            let remove_options = RemoveOptions {
+                recursive: true,
+                ignore_if_not_exists: true,
+            };
+            while let Some(extension_dir) = extension_dirs.next().await {
+                let extension_dir = extension_dir?;
+                let mut versions = Vec::new();
+                let mut version_dirs = fs.read_dir(&extension_dir).await?;
+                while let Some(version_dir) = version_dirs.next().await {
+                    let version_dir = version_dir?;
+                    if let Some(metadata) = fs.metadata(&version_dir).await? {
+                        versions.push((metadata.mtime, version_dir));
+                    }
+                }
+
+                // The most recently retained versions are kept.
+                versions.sort_by(|(a, _), (b, _)| b.cmp(a));
+                let retained_count = versions.len().min(retained_previous_versions);
+                for (_, version_dir) in versions.drain(retained_count..) {
+                    fs.remove_dir(&version_dir, remove_options).await?;
+                }
+                if versions.is_empty() {
+                    fs.remove_dir(&extension_dir, remove_options).await?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Finds the directories within the extensions directory that were left
+    /// behind by crashes, failed installs, or extensions that have since
+    /// been uninstalled or stopped being tracked.
+    pub fn find_orphaned_directories(
+        &self,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<Vec<OrphanedDirectory>>> {
+        let fs = self.fs.clone();
+        let installed_ids = self
+            .extension_index
+            .extensions
+            .keys()
+            .cloned()
+            .collect::<HashSet<_>>();
+        let tracked_ids = self
+            .tracked_extensions
+            .keys()
+            .cloned()
+            .collect::<HashSet<_>>();
+        // While an operation is running, its archive may be being unpacked.
+        let has_outstanding_operations = !self.outstanding_operations.is_empty();
+        let dirs = [
+            (
+                self.installed_dir.clone(),
+                OrphanedDirectoryKind::BrokenInstall,
+            ),
+            (
+                self.downloads_dir.clone(),
+                OrphanedDirectoryKind::InterruptedDownload,
+            ),
+            (
+                self.previous_versions_dir.clone(),
+                OrphanedDirectoryKind::PreviousVersions,
+            ),
+            (
+                self.tracked_dir.clone(),
+                OrphanedDirectoryKind::TrackedCheckout,
+            ),
+        ];
+        cx.background_executor().spawn(async move {
+            let mut orphans = Vec::new();
+            for (dir, kind) in dirs {
+                let Ok(mut entries) = fs.read_dir(&dir).await else {
+                    continue;
+                };
+                while let Some(entry) = entries.next().await {
+                    let entry = entry?;
+                    if !fs.is_dir(&entry).await {
+                        continue;
+                    }
+                    let name = entry
+                        .file_name()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or_default();
+                    let is_orphaned = match kind {
+                        OrphanedDirectoryKind::BrokenInstall => {
+                            !installed_ids.contains(name)
+                                && Self::load_extension_manifest(fs.clone(), &entry)
+                                    .await
+                                    .is_err()
+                        }
+                        OrphanedDirectoryKind::InterruptedDownload => !has_outstanding_operations,
+                        OrphanedDirectoryKind::PreviousVersions => !installed_ids.contains(name),
+                        OrphanedDirectoryKind::TrackedCheckout => !tracked_ids.contains(name),
+                    };
+                    if is_orphaned {
+                        let size = directory_size(fs.as_ref(), entry.clone()).await?;
+                        orphans.push(OrphanedDirectory {
+                            path: entry,
+                            kind,
+                            size,
+                        });
+                    }
+                }
+            }
+            orphans.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(orphans)
+        })
+    }
+
+    /// Removes the directories found by [`Self::find_orphaned_directories`],
+    /// returning the ones that were removed.
+    pub fn remove_orphaned_directories(
+        &mut self,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<Vec<OrphanedDirectory>>> {
+        if !self.outstanding_operations.is_empty() {
+            return Task::ready(Err(anyhow!(
+                "extensions can't be cleaned up while they are being installed, upgraded, or removed"
+            )));
+        }
+
+        let find = self.find_orphaned_directories(cx);
+        let fs = self.fs.clone();
+        cx.background_executor().spawn(async move {
+            let orphans = find.await?;
+            for orphan in &orphans {
+                log::info!("removing orphaned extension directory {:?}", orphan.path);
+                fs.remove_dir(
+                    &orphan.path,
+                    RemoveOptions {
+                        recursive: true,
+                        ignore_if_not_exists: true,
+                    },
+                )
+                .await?;
+            }
+            Ok(orphans)
+        })
+    }
+
+    pub fn pinned_version(&self, extension_id: &str) -> Option<&Arc<str>> {
+        self.pinned_extensions.get(extension_id)
+    }
+
+    /// Pins an installed extension to its current version, so that it isn't
+    /// offered upgrades or upgraded automatically until it is unpinned.
+    pub fn pin_extension(&mut self, extension_id: &str, cx: &mut ModelContext<Self>) {
+        let Some(version) = self
+            .installed_extension(extension_id)
+            .map(|manifest| manifest.version.clone())
+        else {
+            return;
+        };
+        self.pinned_extensions.insert(extension_id.into(), version);
+        self.save_pinned_extensions(cx);
+        self.record_synced_extensions(cx);
+    }
+
+    pub fn unpin_extension(&mut self, extension_id: &str, cx: &mut ModelContext<Self>) {
+        if self.pinned_extensions.remove(extension_id).is_some() {
+            self.save_pinned_extensions(cx);
+            self.record_synced_extensions(cx);
+        }
+    }
+
+    fn save_pinned_extensions(&mut self, cx: &mut ModelContext<Self>) {
+        cx.notify();
+        let fs = self.fs.clone();
+        let path = self.pinned_extensions_path.clone();
+        let content = serde_json::to_string_pretty(&self.pinned_extensions);
+        cx.background_executor()
+            .spawn(async move { fs.atomic_write(path, content?).await })
+            .detach_and_log_err(cx);
+    }
+
+    pub fn extension_last_used(&self, extension_id: &str) -> Option<SystemTime> {
+        self.extension_activity
+            .get(extension_id)
+            .and_then(|activity| activity.last_used)
+    }
+
+    /// Returns the installed extensions that haven't been used for at least
+    /// [`UNUSED_EXTENSION_THRESHOLD`], other than dev extensions and the
+    /// extensions required by the `required_extensions` setting.
+    pub fn unused_extensions(&self, cx: &AppContext) -> Vec<Arc<str>> {
+        let settings = ExtensionSettings::get_global(cx);
+        let now = SystemTime::now();
+        self.extension_activity
+            .iter()
+            .filter(|(extension_id, activity)| {
+                let Some(extension) = self.extension_index.extensions.get(*extension_id) else {
+                    return false;
+                };
+                let unused_since = activity.last_used.unwrap_or(activity.tracked_since);
+                !extension.dev
+                    && !settings.requires_extension(extension_id)
+                    && now
+                        .duration_since(unused_since)
+                        .map_or(false, |elapsed| elapsed >= UNUSED_EXTENSION_THRESHOLD)
+            })
+            .map(|(extension_id, _)| extension_id.clone())
+            .collect()
+    }
+
+    /// Records that the given extension was just used, unless its use was
+    /// already recorded within the last hour.
+    pub fn record_extension_activation(
+        &mut self,
+        extension_id: Arc<str>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        if !self.extension_index.extensions.contains_key(&extension_id) {
+            return;
+        }
+        let now = SystemTime::now();
+        let activity = self
+            .extension_activity
+            .entry(extension_id)
+            .or_insert(ExtensionActivity {
+                tracked_since: now,
+                last_used: None,
+            });
+        if let Some(last_used) = activity.last_used {
+            if now
+                .duration_since(last_used)
+                .map_or(true, |elapsed| elapsed < EXTENSION_ACTIVITY_RESOLUTION)
+            {
+                return;
+            }
+        }
+        activity.last_used = Some(now);
+        self.save_extension_activity(cx);
+    }
+
+    fn track_installed_extensions(&mut self, cx: &mut ModelContext<Self>) {
+        let now = SystemTime::now();
+        let mut changed = false;
+        for extension_id in self.extension_index.extensions.keys() {
+            if !self.extension_activity.contains_key(extension_id) {
+                self.extension_activity.insert(
+                    extension_id.clone(),
+                    ExtensionActivity {
+                        tracked_since: now,
+                        last_used: None,
+                    },
+                );
+                changed = true;
+            }
+        }
+        if changed {
+            self.save_extension_activity(cx);
+        }
+    }
+
+    fn record_active_theme(&mut self, cx: &mut ModelContext<Self>) {
+        let theme_name = ThemeSettings::get_global(cx).active_theme.name.clone();
+        if let Some(entry) = self.extension_index.themes.get(theme_name.as_ref()) {
+            let extension_id = entry.extension.clone();
+            self.record_extension_activation(extension_id, cx);
+        }
+    }
+
+    pub fn provides_active_theme(&self, extension_id: &str, cx: &AppContext) -> bool {
+        let theme_name = &ThemeSettings::get_global(cx).active_theme.name;
+        self.extension_index
+            .themes
+            .get(theme_name.as_ref())
+            .map_or(false, |entry| entry.extension.as_ref() == extension_id)
+    }
+
+    fn forget_extension_activity(&mut self, extension_id: &str, cx: &mut ModelContext<Self>) {
+        if self.extension_activity.remove(extension_id).is_some() {
+            self.save_extension_activity(cx);
+        }
+    }
+
+    fn save_extension_activity(&mut self, cx: &mut ModelContext<Self>) {
+        cx.notify();
+        let fs = self.fs.clone();
+        let path = self.extension_activity_path.clone();
+        let content = serde_json::to_string_pretty(&self.extension_activity);
+        cx.background_executor()
+            .spawn(async move { fs.atomic_write(path, content?).await })
+            .detach_and_log_err(cx);
+    }
+
+    pub fn extension_signer(&self, extension_id: &str) -> Option<&ExtensionSigner> {
+        self.extension_signers.get(extension_id)
+    }
+
+    fn set_extension_signer(
+        &mut self,
+        extension_id: &Arc<str>,
+        signer: Option<ExtensionSigner>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let changed = match signer {
+            Some(signer) => {
+                self.extension_signers
+                    .insert(extension_id.clone(), signer.clone())
+                    != Some(signer)
+            }
+            None => self.extension_signers.remove(extension_id).is_some(),
+        };
+        if !changed {
+            return;
+        }
+
+        cx.notify();
+        let fs = self.fs.clone();
+        let path = self.extension_signers_path.clone();
+        let content = serde_json::to_string_pretty(&self.extension_signers);
+        cx.background_executor()
+            .spawn(async move { fs.atomic_write(path, content?).await })
+            .detach_and_log_err(cx);
+    }
+
+    pub fn extension_source(&self, extension_id: &str) -> Option<&ExtensionSource> {
+        self.extension_sources.get(extension_id)
+    }
+
+    fn set_extension_source(
+        &mut self,
+        extension_id: &Arc<str>,
+        source: Option<ExtensionSource>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let changed = match source {
+            Some(source) => {
+                self.extension_sources
+                    .insert(extension_id.clone(), source.clone())
+                    != Some(source)
+            }
+            None => self.extension_sources.remove(extension_id).is_some(),
+        };
+        if !changed {
+            return;
+        }
+
+        cx.notify();
+        let fs = self.fs.clone();
+        let path = self.extension_sources_path.clone();
+        let content = serde_json::to_string_pretty(&self.extension_sources);
+        cx.background_executor()
+            .spawn(async move { fs.atomic_write(path, content?).await })
+            .detach_and_log_err(cx);
+    }
+
+    pub fn remote_extension(&self, extension_id: &str) -> Option<&ExtensionApiResponse> {
+        self.remote_extensions.get(extension_id)
+    }
+
+    pub fn extension_collections(&self) -> &[ExtensionCollection] {
+        &self.extension_collections
+    }
+
+    pub fn is_rebuilding_dev_extensions(&self) -> bool {
+        self.is_rebuilding_dev_extensions
+    }
+
+    pub fn extension_requires_restart(&self, extension_id: &str) -> bool {
+        self.extensions_requiring_restart.contains(extension_id)
+    }
+
+    pub fn has_extensions_requiring_restart(&self) -> bool {
+        !self.extensions_requiring_restart.is_empty()
+    }
+
+    pub fn damaged_extension(&self, extension_id: &str) -> Option<&SharedString> {
+        self.damaged_extensions.get(extension_id)
+    }
+
+    pub fn extension_disabled_until(&self, extension_id: &str) -> Option<DisabledUntil> {
+        self.disabled_extensions
+            .get(extension_id)
+            .map(|disabled| disabled.until)
+    }
+
+    /// Unloads an installed extension without uninstalling it.
+    pub fn disable_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        until: DisabledUntil,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let enable_task = match until {
+            DisabledUntil::Time(time) => {
+                let extension_id = extension_id.clone();
+                let timer = cx
+                    .background_executor()
+                    .timer(time.saturating_duration_since(Instant::now()));
+                Some(cx.spawn(|this, mut cx| async move {
+                    timer.await;
+                    this.update(&mut cx, |this, cx| this.enable_extension(&extension_id, cx))
+                        .ok();
+                }))
+            }
+            DisabledUntil::Restart => None,
+            DisabledUntil::Reenabled => {
+                let extension_id = extension_id.clone();
+                settings::update_settings_file::<ExtensionSettings>(
+                    self.fs.clone(),
+                    cx,
+                    move |settings| {
+                        let disabled_extensions =
+                            settings.disabled_extensions.get_or_insert_with(Vec::new);
+                        if !disabled_extensions
+                            .iter()
+                            .any(|id| id.as_str() == extension_id.as_ref())
+                        {
+                            disabled_extensions.push(extension_id.to_string());
+                        }
+                    },
+                );
+                None
+            }
+        };
+
+        self.disabled_extensions.insert(
+            extension_id,
+            DisabledExtension {
+                until,
+                _enable_task: enable_task,
+            },
+        );
+        let _ = self.reload(None, cx);
+        cx.notify();
+    }
+
+    pub fn enable_extension(&mut self, extension_id: &str, cx: &mut ModelContext<Self>) {
+        let Some(disabled) = self.disabled_extensions.remove(extension_id) else {
+            return;
+        };
+        self.crashed_extensions.remove(extension_id);
+        self.extension_crashes.remove(extension_id);
+        if disabled.until == DisabledUntil::Reenabled {
+            let extension_id = extension_id.to_string();
+            settings::update_settings_file::<ExtensionSettings>(
+                self.fs.clone(),
+                cx,
+                move |settings| {
+                    if let Some(disabled_extensions) = settings.disabled_extensions.as_mut() {
+                        disabled_extensions.retain(|id| *id != extension_id);
+                    }
+                },
+            );
+        }
+        let _ = self.reload(None, cx);
+        cx.notify();
+    }
+
+    pub fn is_in_safe_mode(&self) -> bool {
+        self.safe_mode
+    }
+
+    /// Sets whether Zed starts with all extensions disabled the next time it
+    /// is launched.
+    pub fn set_safe_mode_on_restart(
+        &mut self,
+        safe_mode: bool,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let safe_mode_path = self.safe_mode_path.clone();
+        cx.background_executor().spawn(async move {
+            if safe_mode {
+                fs.atomic_write(safe_mode_path, String::new()).await
+            } else {
+                fs.remove_file(
+                    &safe_mode_path,
+                    RemoveOptions {
+                        ignore_if_not_exists: true,
+                        ..Default::default()
+                    },
+                )
+                .await
+            }
+        })
+    }
+
+    fn is_suspended(&self, extension_id: &str) -> bool {
+        self.safe_mode
+            || self
+                .bisect
+                .as_ref()
+                .map_or(false, |bisect| bisect.is_disabled(extension_id))
+    }
+
+    pub fn extension_bisect(&self) -> Option<&ExtensionBisect> {
+        self.bisect.as_ref()
+    }
+
+    /// Starts a bisect of the loaded extensions, which disables half of them
+    /// from the next launch.
+    pub fn start_bisect(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<ExtensionBisect>> {
+        let candidates = self
+            .extension_index
+            .extensions
+            .iter()
+            .filter(|(extension_id, entry)| {
+                !entry.dev && !self.unloaded_extensions.contains(*extension_id)
+            })
+            .map(|(extension_id, _)| extension_id.clone())
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Task::ready(Err(anyhow!("there are no loaded extensions to bisect")));
+        }
+
+        let bisect = ExtensionBisect::new(candidates);
+        let save = self.save_bisect(Some(bisect.clone()), cx);
+        cx.background_executor().spawn(async move {
+            save.await?;
+            Ok(bisect)
+        })
+    }
+
+    /// Narrows down the bisect that is in progress, based on whether the
+    /// problem was still present with the current set of extensions.
+    pub fn continue_bisect(
+        &mut self,
+        problem_present: bool,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<ExtensionBisectStep>> {
+        let Some(bisect) = &self.bisect else {
+            return Task::ready(Err(anyhow!("no extension bisect is in progress")));
+        };
+
+        let step = bisect.next(problem_present);
+        let next_bisect = match &step {
+            ExtensionBisectStep::Continue(next_bisect) => Some(next_bisect.clone()),
+            ExtensionBisectStep::Found(_) | ExtensionBisectStep::NotFound => None,
+        };
+        let save = self.save_bisect(next_bisect, cx);
+        cx.background_executor().spawn(async move {
+            save.await?;
+            Ok(step)
+        })
+    }
+
+    pub fn stop_bisect(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        self.save_bisect(None, cx)
+    }
+
+    fn save_bisect(
+        &self,
+        bisect: Option<ExtensionBisect>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let bisect_path = self.bisect_path.clone();
+        cx.background_executor().spawn(async move {
+            match bisect {
+                Some(bisect) => {
+                    fs.atomic_write(bisect_path, serde_json::to_string(&bisect)?)
+                        .await
+                }
+                None => {
+                    fs.remove_file(
+                        &bisect_path,
+                        RemoveOptions {
+                            ignore_if_not_exists: true,
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                }
+            }
+        })
+    }
+
+    pub fn extension_log(&self, extension_id: &str) -> Option<&ExtensionLog> {
+        self.extension_logs.get(extension_id)
+    }
+
+    /// Captures the stderr of the language servers that extensions provide in
+    /// the given project, as they are started.
+    pub fn capture_language_server_logs(
+        &mut self,
+        project: &Model<Project>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        cx.subscribe(project, |this, project, event, cx| match event {
+            project::Event::LanguageServerAdded(server_id) => {
+                this.capture_language_server_log(&project, *server_id, cx)
+            }
+            project::Event::LanguageServerRemoved(server_id) => {
+                this.language_server_log_subscriptions.remove(server_id);
+            }
+            _ => {}
+        })
+        .detach();
+    }
+
+    fn capture_language_server_log(
+        &mut self,
+        project: &Model<Project>,
+        server_id: LanguageServerId,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let project = project.read(cx);
+        let Some((server, adapter)) = project
+            .language_server_for_id(server_id)
+            .zip(project.language_server_adapter_for_id(server_id))
+        else {
+            return;
+        };
+        let Some(extension_id) = self
+            .extension_index
+            .extensions
+            .iter()
+            .find(|(_, entry)| entry.manifest.language_servers.contains_key(&adapter.name))
+            .map(|(extension_id, _)| extension_id.clone())
+        else {
+            return;
+        };
+
+        let log_tx = self.log_tx.clone();
+        let server_name: Arc<str> = adapter.name.0.clone();
+        let subscription = server.on_io(move |io_kind, message| {
+            if matches!(io_kind, IoKind::StdErr) {
+                log_tx
+                    .unbounded_send((
+                        extension_id.clone(),
+                        ExtensionLogLine {
+                            source: ExtensionLogSource::LanguageServer(server_name.clone()),
+                            text: message.trim_end().to_string(),
+                        },
+                    ))
+                    .ok();
+            }
+        });
+        self.language_server_log_subscriptions
+            .insert(server_id, subscription);
+    }
+
+    pub fn extension_crash_error(&self, extension_id: &str) -> Option<&SharedString> {
+        self.crashed_extensions.get(extension_id)
+    }
+
+    /// Records a crash of the given extension, disabling it if it has crashed
+    /// as many times as the `crash_limit` setting allows.
+    pub fn record_extension_crash(
+        &mut self,
+        extension_id: Arc<str>,
+        description: String,
+        cx: &mut ModelContext<Self>,
+    ) {
+        log::error!("extension {extension_id} crashed: {description}");
+        let crash_limit = ExtensionSettings::get_global(cx).crash_limit;
+        if crash_limit == 0 || self.disabled_extensions.contains_key(&extension_id) {
+            return;
+        }
+
+        let now = Instant::now();
+        let crashes = self
+            .extension_crashes
+            .entry(extension_id.clone())
+            .or_default();
+        crashes.retain(|crashed_at| now.duration_since(*crashed_at) < EXTENSION_CRASH_WINDOW);
+        crashes.push(now);
+        if crashes.len() < crash_limit {
+            return;
+        }
+
+        self.extension_crashes.remove(&extension_id);
+        let error = SharedString::from(description);
+        self.crashed_extensions
+            .insert(extension_id.clone(), error.clone());
+        self.disable_extension(extension_id.clone(), DisabledUntil::Reenabled, cx);
+        cx.emit(Event::ExtensionDisabledAfterCrashes {
+            extension_id,
+            error,
+        });
+    }
+
+    pub fn sync_disabled_extensions(&mut self, cx: &mut ModelContext<Self>) {
+        if self.apply_disabled_extensions_setting(cx) {
+            let _ = self.reload(None, cx);
+            cx.notify();
+        }
+    }
+
+    fn apply_disabled_extensions_setting(&mut self, cx: &AppContext) -> bool {
+        let disabled_in_settings = ExtensionSettings::get_global(cx)
+            .disabled_extensions
+            .iter()
+            .map(|extension_id| Arc::<str>::from(extension_id.as_str()))
+            .collect::<HashSet<_>>();
+
+        let mut changed = false;
+        self.disabled_extensions.retain(|extension_id, disabled| {
+            let keep = disabled.until != DisabledUntil::Reenabled
+                || disabled_in_settings.contains(extension_id);
+            changed |= !keep;
+            keep
+        });
+        for extension_id in disabled_in_settings {
+            let until = self
+                .disabled_extensions
+                .get(&extension_id)
+                .map(|disabled| disabled.until);
+            if until != Some(DisabledUntil::Reenabled) {
+                self.disabled_extensions.insert(
+                    extension_id,
+                    DisabledExtension {
+                        until: DisabledUntil::Reenabled,
+                        _enable_task: None,
+                    },
+                );
+                changed = true;
+            }
+        }
+        let disabled_extensions = &self.disabled_extensions;
+        self.crashed_extensions
+            .retain(|extension_id, _| disabled_extensions.contains_key(extension_id));
+        changed
+    }
+
+    pub fn extension_language_names(&self, extension_id: &str) -> Vec<Arc<str>> {
+        self.extension_index
+            .languages
+            .iter()
+            .filter(|(_, entry)| entry.extension.as_ref() == extension_id)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Returns the installed extensions that provide a language, theme,
+    /// grammar, or language server whose name contains the given query,
+    /// ignoring case.
+    pub fn extensions_with_matching_contributions(&self, query: &str) -> Vec<Arc<str>> {
+        let query = query.to_lowercase();
+        let matches = |name: &str| name.to_lowercase().contains(&query);
+
+        let mut extension_ids = BTreeSet::new();
+        for (name, entry) in &self.extension_index.languages {
+            if matches(name) {
+                extension_ids.insert(entry.extension.clone());
+            }
+        }
+        for (name, entry) in &self.extension_index.themes {
+            if matches(name) {
+                extension_ids.insert(entry.extension.clone());
+            }
+        }
+        for (extension_id, extension) in &self.extension_index.extensions {
+            let manifest = &extension.manifest;
+            if manifest.grammars.keys().any(|name| matches(name))
+                || manifest
+                    .language_servers
+                    .keys()
+                    .any(|name| matches(&name.0))
+            {
+                extension_ids.insert(extension_id.clone());
+            }
+        }
+        extension_ids.into_iter().collect()
+    }
+
+    /// Returns the installed extensions that provide support for the given
+    /// language.
+    pub fn language_providers(&self, language_name: &str) -> LanguageProviders {
+        let language_entry = self.extension_index.languages.get(language_name);
+        let grammar = language_entry.and_then(|entry| {
+            let grammar_name = entry.grammar.as_ref()?;
+            self.extension_index
+                .extensions
+                .iter()
+                .find(|(_, extension)| extension.manifest.grammars.contains_key(grammar_name))
+                .map(|(extension_id, _)| extension_id.clone())
+        });
+        let language_servers = self
+            .extension_index
+            .extensions
+            .iter()
+            .flat_map(|(extension_id, extension)| {
+                extension
+                    .manifest
+                    .language_servers
+                    .iter()
+                    .filter(|(_, entry)| entry.language.as_ref() == language_name)
+                    .map(|(name, _)| (name.clone(), extension_id.clone()))
+            })
+            .collect();
+
+        LanguageProviders {
+            language: language_entry.map(|entry| entry.extension.clone()),
+            grammar,
+            language_servers,
+        }
+    }
+
+    pub fn extension_data_dir(&self, extension_id: &str) -> PathBuf {
+        self.wasm_host.extension_data_dir(extension_id)
+    }
+
+    pub fn extension_load_time(&self, extension_id: &str) -> Option<Duration> {
+        self.extension_load_times.get(extension_id).copied()
+    }
+
+    /// Returns the resources that the given installed extension is using.
+    pub fn extension_resource_usage(&self, extension_id: &str) -> Option<ExtensionResourceUsage> {
+        if !self.extension_index.extensions.contains_key(extension_id) {
+            return None;
+        }
+        let mut usage = self
+            .server_process_usage
+            .get(extension_id)
+            .copied()
+            .unwrap_or_default();
+        if let Some((_, wasm_extension)) = self
+            .wasm_extensions
+            .iter()
+            .find(|(manifest, _)| manifest.id.as_ref() == extension_id)
+        {
+            usage.wasm_memory = wasm_extension.memory_usage() as u64;
+            usage.wasm_busy_time = wasm_extension.busy_time();
+        }
+        Some(usage)
+    }
+
+    /// Measures the memory and CPU used by the processes that each installed
+    /// extension started, which are the ones whose executable or arguments
+    /// are within the extension's work directory.
+    pub fn refresh_resource_usage(&mut self, cx: &mut ModelContext<Self>) -> Task<()> {
+        let extension_dirs = self
+            .extension_index
+            .extensions
+            .keys()
+            .map(|extension_id| {
+                (
+                    extension_id.clone(),
+                    self.wasm_host.work_dir.join(extension_id.as_ref()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let process_monitor = self.process_monitor.clone();
+        cx.spawn(|this, mut cx| async move {
+            let usage = cx
+                .background_executor()
+                .spawn(async move {
+                    let mut process_monitor = process_monitor.lock();
+                    let system = process_monitor.get_or_insert_with(System::new);
+                    system.refresh_processes_specifics(
+                        ProcessRefreshKind::new()
+                            .with_cpu()
+                            .with_memory()
+                            .with_exe(UpdateKind::OnlyIfNotSet)
+                            .with_cmd(UpdateKind::OnlyIfNotSet),
+                    );
+
+                    let mut usage = HashMap::<Arc<str>, ExtensionResourceUsage>::default();
+                    for process in system.processes().values() {
+                        let Some((extension_id, _)) = extension_dirs.iter().find(|(_, dir)| {
+                            process.exe().map_or(false, |exe| exe.starts_with(dir))
+                                || process
+                                    .cmd()
+                                    .iter()
+                                    .any(|arg| Path::new(arg).starts_with(dir))
+                        }) else {
+                            continue;
+                        };
+                        let usage = usage.entry(extension_id.clone()).or_default();
+                        usage.server_processes += 1;
+                        usage.server_memory += process.memory();
+                        usage.server_cpu_usage += process.cpu_usage();
+                    }
+                    usage
+                })
+                .await;
+            this.update(&mut cx, |this, cx| {
+                this.server_process_usage = usage;
+                cx.notify();
+            })
+            .ok();
+        })
+    }
+
+    pub fn extension_data_size(&self, extension_id: &str) -> Option<u64> {
+        self.extension_data_sizes.get(extension_id).copied()
+    }
+
+    /// Recomputes the size of the data stored by each of the given extensions.
+    pub fn refresh_extension_data_sizes(
+        &mut self,
+        extension_ids: Vec<Arc<str>>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let data_dirs = extension_ids
+            .into_iter()
+            .map(|extension_id| {
+                let data_dir = self.extension_data_dir(&extension_id);
+                (extension_id, data_dir)
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn(|this, mut cx| async move {
+            let mut sizes = Vec::with_capacity(data_dirs.len());
+            for (extension_id, data_dir) in data_dirs {
+                if let Some(size) = directory_size(fs.as_ref(), data_dir).await.log_err() {
+                    sizes.push((extension_id, size));
+                }
+            }
+
+            this.update(&mut cx, |this, cx| {
+                this.extension_data_sizes.extend(sizes);
+                cx.notify();
+            })
+        })
+    }
+
+    pub fn extension_disk_usage(&self, extension_id: &str) -> Option<u64> {
+        let installed_size = self.installed_extension_sizes.get(extension_id)?;
+        let work_dir_size = self
+            .extension_work_dir_sizes
+            .get(extension_id)
+            .copied()
+            .unwrap_or_default();
+        Some(installed_size + work_dir_size)
+    }
+
+    pub fn total_disk_usage(&self) -> u64 {
+        self.extension_index
+            .extensions
+            .keys()
+            .filter_map(|extension_id| self.extension_disk_usage(extension_id))
+            .sum()
+    }
+
+    pub fn disk_quota(&self, cx: &AppContext) -> Option<u64> {
+        ExtensionSettings::get_global(cx)
+            .disk_quota_mb
+            .map(|quota| quota * 1024 * 1024)
+    }
+
+    pub fn would_exceed_disk_quota(&self, additional_bytes: u64, cx: &AppContext) -> bool {
+        self.disk_quota(cx).map_or(false, |quota| {
+            self.total_disk_usage() + additional_bytes > quota
+        })
+    }
+
+    /// Recomputes the disk space used by each installed extension and by the
+    /// data it has stored.
+    pub fn refresh_disk_usage(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let dirs = self
+            .extension_index
+            .extensions
+            .keys()
+            .map(|extension_id| {
+                (
+                    extension_id.clone(),
+                    self.installed_dir.join(extension_id.as_ref()),
+                    self.wasm_host.work_dir.join(extension_id.as_ref()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn(|this, mut cx| async move {
+            let mut sizes = Vec::with_capacity(dirs.len());
+            for (extension_id, installed_dir, work_dir) in dirs {
+                let installed_size = directory_size(fs.as_ref(), installed_dir).await?;
+                let work_dir_size = directory_size(fs.as_ref(), work_dir).await?;
+                sizes.push((extension_id, installed_size, work_dir_size));
+            }
+
+            this.update(&mut cx, |this, cx| {
+                for (extension_id, installed_size, work_dir_size) in sizes {
+                    this.installed_extension_sizes
+                        .insert(extension_id.clone(), installed_size);
+                    this.extension_work_dir_sizes
+                        .insert(extension_id, work_dir_size);
+                }
+                cx.notify();
+            })
+        })
+    }
+
+    /// Removes everything that the given extension has stored in its data
+    /// directory.
+    pub fn clear_extension_data(
+        &mut self,
+        extension_id: &str,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let extension_id: Arc<str> = extension_id.into();
+        let data_dir = self.extension_data_dir(&extension_id);
+        let clear = cx.background_executor().spawn(async move {
+            if !fs.is_dir(&data_dir).await {
+                return Ok(());
+            }
+
+            let mut entries = fs.read_dir(&data_dir).await?;
+            while let Some(entry) = entries.next().await {
+                let entry = entry?;
+                let is_dir = fs
+                    .metadata(&entry)
+                    .await?
+                    .map_or(false, |metadata| metadata.is_dir && !metadata.is_symlink);
+                if is_dir {
+                    fs.remove_dir(
+                        &entry,
+                        RemoveOptions {
+                            recursive: true,
+                            ignore_if_not_exists: true,
+                        },
+                    )
+                    .await?;
+                } else {
+                    fs.remove_file(
+                        &entry,
+                        RemoveOptions {
+                            recursive: false,
+                            ignore_if_not_exists: true,
+                        },
+                    )
+                    .await?;
+                }
+            }
+            anyhow::Ok(())
+        });
+
+        cx.spawn(|this, mut cx| async move {
+            clear.await?;
+            this.update(&mut cx, |this, cx| {
+                this.extension_data_sizes.insert(extension_id, 0);
+                cx.notify();
+            })
+        })
+    }
+
+    /// Removes the given extension's working directory, including its data
+    /// and the language servers it has downloaded, along with the settings
+    /// that only apply to it: its entry in the tracked extensions and the
+    /// configuration of the language servers it provides.
+    pub fn purge_extension_data(
+        &mut self,
+        extension_id: &str,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let extension_id: Arc<str> = extension_id.into();
+        let language_servers = self
+            .extension_index
+            .extensions
+            .get(&extension_id)
+            .map(|extension| {
+                extension
+                    .manifest
+                    .language_servers
+                    .keys()
+                    .map(|name| name.0.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let is_tracked = ExtensionSettings::get_global(cx)
+            .tracked_extensions
+            .contains_key(extension_id.as_ref());
+        let lsp_settings = &ProjectSettings::get_global(cx).lsp;
+        let configured_language_servers = language_servers
+            .into_iter()
+            .filter(|name| lsp_settings.contains_key(name))
+            .collect::<Vec<_>>();
+        if is_tracked {
+            let extension_id = extension_id.clone();
+            settings::update_settings_file::<ExtensionSettings>(
+                self.fs.clone(),
+                cx,
+                move |settings| {
+                    if let Some(tracked_extensions) = settings.tracked_extensions.as_mut() {
+                        tracked_extensions.remove(extension_id.as_ref());
+                    }
+                },
+            );
+        }
+        if !configured_language_servers.is_empty() {
+            settings::update_settings_file::<ProjectSettings>(
+                self.fs.clone(),
+                cx,
+                move |settings| {
+                    for name in &configured_language_servers {
+                        settings.lsp.remove(name);
+                    }
+                },
+            );
+        }
+
+        let fs = self.fs.clone();
+        let work_dir = self.wasm_host.work_dir.join(extension_id.as_ref());
+        cx.spawn(|this, mut cx| async move {
+            fs.remove_dir(
+                &work_dir,
+                RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: true,
+                },
+            )
+            .await?;
+            this.update(&mut cx, |this, cx| {
+                this.extension_data_sizes.remove(&extension_id);
+                this.extension_work_dir_sizes.remove(&extension_id);
+                cx.notify();
+            })
+        })
+    }
+
+    pub fn dev_extension_build_error(&self, extension_id: &str) -> Option<&str> {
+        self.dev_extension_build_errors
+            .get(extension_id)
+            .map(String::as_str)
+    }
+
+    pub fn dev_extension_build_log(&self, extension_id: &str) -> Option<String> {
+        self.dev_extension_build_logs
+            .get(extension_id)
+            .map(|text| text.lock().clone())
+    }
+
+    pub fn dev_extensions_rebuild_log(&self) -> Option<String> {
+        self.dev_extensions_rebuild_log
+            .as_ref()
+            .map(|text| text.lock().clone())
+    }
+
+    fn start_build_log(&mut self, extension_id: Arc<str>, cx: &mut ModelContext<Self>) -> BuildLog {
+        let text = Arc::new(Mutex::new(String::new()));
+        self.dev_extension_build_logs
+            .insert(extension_id, text.clone());
+        Self::notify_on_build_output(text, cx)
+    }
+
+    fn notify_on_build_output(text: Arc<Mutex<String>>, cx: &mut ModelContext<Self>) -> BuildLog {
+        cx.notify();
+        let (build_log, mut updates) = BuildLog::new(text);
+        cx.spawn(|this, mut cx| async move {
+            while updates.next().await.is_some() {
+                if this.update(&mut cx, |_, cx| cx.notify()).is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+        build_log
+    }
+
+    pub fn dev_extension_compatibility(&self, extension_id: &str) -> Option<&CompatibilityReport> {
+        self.dev_extension_compatibility.get(extension_id)
+    }
+
+    /// Checks whether each dev extension is compatible with this version of the
+    /// extension API, based on the API version that its compiled `extension.wasm`
+    /// declares and the errors from its most recent build.
+    pub fn check_dev_extensions_compatibility(
+        &self,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let installed_dir = self.installed_dir.clone();
+        let extensions = self
+            .dev_extensions()
+            .map(|manifest| {
+                (
+                    manifest.clone(),
+                    self.dev_extension_build_errors.get(&manifest.id).cloned(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let check = cx.background_executor().spawn(async move {
+            let mut reports = Vec::with_capacity(extensions.len());
+            for (manifest, build_error) in extensions {
+                let mut wasm_bytes = None;
+                if manifest.lib.kind.is_some() {
+                    let mut path = installed_dir.clone();
+                    path.extend([manifest.id.as_ref(), "extension.wasm"]);
+                    if let Some(mut wasm_file) = fs.open_sync(&path).await.log_err() {
+                        let mut bytes = Vec::new();
+                        if wasm_file.read_to_end(&mut bytes).log_err().is_some() {
+                            wasm_bytes = Some(bytes);
+                        }
+                    }
+                }
+
+                reports.push(extension_compatibility::check_compatibility(
+                    manifest.id.clone(),
+                    wasm_bytes.as_deref(),
+                    build_error.as_deref(),
+                ));
+            }
+            reports
+        });
+
+        cx.spawn(|this, mut cx| async move {
+            let reports = check.await;
+            this.update(&mut cx, |this, cx| {
+                this.dev_extension_compatibility = reports
+                    .into_iter()
+                    .map(|report| (report.extension_id.clone(), report))
+                    .collect();
+                cx.notify();
+            })
+        })
+    }
+
+    pub fn is_registry_busy(&self) -> bool {
+        self.registry_busy_until
+            .map_or(false, |busy_until| Instant::now() < busy_until)
+    }
+
+    pub fn registries(&self) -> &[Registry] {
+        &self.registries
+    }
+
+    pub fn registry_base_url(&self, registry: &Registry) -> String {
+        match &registry.url {
+            Some(url) => url.clone(),
+            None => self.http_client.build_zed_api_url(""),
+        }
+    }
+
+    pub fn registry_url(&self) -> String {
+        match self.registries.first() {
+            Some(registry) => self.registry_base_url(registry),
+            None => self.http_client.build_zed_api_url(""),
+        }
+    }
+
+    fn build_registry_url(&self, path: &str) -> String {
+        format!("{}{path}", self.registry_url())
+    }
+
+    fn build_extension_url(&self, extension_id: &str, path: &str) -> String {
+        let registry = self
+            .remote_extensions
+            .get(extension_id)
+            .and_then(|extension| extension.registry.as_ref())
+            .and_then(|name| {
+                self.registries
+                    .iter()
+                    .find(|registry| registry.name == *name)
+            });
+        match registry {
+            Some(registry) => format!("{}{path}", self.registry_base_url(registry)),
+            None => self.build_registry_url(path),
+        }
+    }
+
+    /// Switches to the registries in the `registries` setting, or to the one
+    /// in the `registry_url` setting if there are none, forgetting the
+    /// metadata that was fetched from the previous ones.
+    pub fn sync_registries(&mut self, cx: &mut ModelContext<Self>) {
+        let settings = ExtensionSettings::get_global(cx);
+        let registries = if settings.registries.is_empty() {
+            let registry_url = settings
+                .registry_url
+                .as_ref()
+                .map(|registry_url| registry_url.trim_end_matches('/').to_string())
+                .filter(|registry_url| !registry_url.is_empty());
+            vec![match registry_url {
+                Some(registry_url) => Registry {
+                    name: registry_url.as_str().into(),
+                    url: Some(registry_url),
+                },
+                None => Registry::zed(),
+            }]
+        } else {
+            settings
+                .registries
+                .iter()
+                .filter(|registry| registry.enabled)
+                .map(|registry| Registry {
+                    name: registry.name.as_str().into(),
+                    url: Some(registry.url.trim_end_matches('/').to_string()),
+                })
+                .collect()
+        };
+        if registries == self.registries {
+            return;
+        }
+
+        self.registry_cache_path = if registries == [Registry::zed()] {
+            self.index_path.with_file_name("registry_cache.json")
+        } else {
+            let urls = registries
+                .iter()
+                .map(|registry| registry.url.as_deref().unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join("\n");
+            self.index_path.with_file_name(format!(
+                "registry_cache-{:x}.json",
+                Sha256::digest(urls.as_bytes())
+            ))
+        };
+        self.registries = registries;
+        self.remote_extensions.clear();
+        self.extension_collections.clear();
+        cx.emit(Event::RegistryChanged);
+        cx.notify();
+    }
+
+    fn registry_http_client(&self) -> Arc<dyn HttpClient> {
+        match &self.proxied_http_client {
+            Some((_, http_client)) => http_client.clone(),
+            None => self.http_client.clone(),
+        }
+    }
+
+    /// Sends registry queries and downloads through the proxy in the `proxy`
+    /// setting, or through the proxy from the environment if it isn't set.
+    pub fn sync_proxy(&mut self, cx: &mut ModelContext<Self>) {
+        let proxy = ExtensionSettings::get_global(cx)
+            .proxy
+            .clone()
+            .filter(|proxy| !proxy.is_empty());
+        if proxy.as_ref() == self.proxied_http_client.as_ref().map(|(proxy, _)| proxy) {
+            return;
+        }
+
+        self.proxied_http_client = proxy.and_then(|proxy| {
+            let uri = proxy
+                .parse::<Uri>()
+                .with_context(|| format!("invalid extension proxy {proxy:?}"))
+                .log_err()?;
+            Some((proxy, http::client_with_proxy(Some(uri))))
+        });
+    }
+
+    /// Fetches the extensions from every enabled registry, merging them in
+    /// order of precedence.
+    pub fn fetch_extensions(
+        &self,
+        search: Option<&str>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<Vec<ExtensionApiResponse>>> {
+        let query = ExtensionQuery {
+            search: search.map(str::to_string),
+            ..Default::default()
+        };
+        let page = self.query_extensions(query, cx);
+        cx.background_executor()
+            .spawn(async move { Ok(page.await?.extensions) })
+    }
+
+    pub fn query_extensions(
+        &self,
+        query: ExtensionQuery,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<ExtensionsPage>> {
+        self.query_extensions_within(query, None, cx)
+    }
+
+    fn query_extensions_within(
+        &self,
+        query: ExtensionQuery,
+        bandwidth_limit: Option<BandwidthLimit>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<ExtensionsPage>> {
+        let is_full_listing = query.is_full_listing();
+        if !is_full_listing && self.is_registry_busy() {
+            return Task::ready(Err(anyhow!(
+                "the extension registry is busy, try again shortly"
+            )));
+        }
+        let path = format!("/extensions{}", query.query_string());
+        let requests = self
+            .registries
+            .iter()
+            .map(|registry| {
+                (
+                    registry.name.clone(),
+                    format!("{}{path}", self.registry_base_url(registry)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let http_client = self.registry_http_client();
+        let fs = self.fs.clone();
+        let registry_cache_path = self.registry_cache_path.clone();
+        let cache_ttl = ExtensionSettings::get_global(cx)
+            .registry_cache_ttl()
+            .filter(|_| is_full_listing);
+        cx.spawn(move |this, mut cx| async move {
+            let cached = if is_full_listing {
+                load_registry_cache(&fs, &registry_cache_path).await.ok()
+            } else {
+                None
+            };
+            if let Some((cached, _)) = cached
+                .as_ref()
+                .zip(cache_ttl)
+                .filter(|(cached, cache_ttl)| cached.is_fresh(*cache_ttl))
+            {
+                this.update(&mut cx, |this, cx| {
+                    for extension in &cached.data {
+                        this.remote_extensions
+                            .insert(extension.id.clone(), extension.clone());
+                    }
+                    cx.notify();
+                })?;
+                return Ok(ExtensionsPage {
+                    extensions: cached.data.clone(),
+                    total_count: cached.data.len(),
+                });
+            }
+            let mut cached_listings = cached.map(|cached| cached.listings).unwrap_or_default();
+
+            let mut attempt = 0;
+            let responses = loop {
+                let responses =
+                    futures::future::join_all(requests.iter().map(|(registry, url)| {
+                        let http_client = http_client.clone();
+                        let etag = cached_listings.get(url).map(|listing| listing.etag.clone());
+                        let bandwidth_limit = bandwidth_limit.as_ref();
+                        let executor = cx.background_executor().clone();
+                        async move {
+                            let result = fetch_registry_extensions(
+                                &http_client,
+                                url,
+                                etag.as_deref(),
+                                bandwidth_limit,
+                                &executor,
+                            )
+                            .await;
+                            (registry.clone(), url.clone(), result)
+                        }
+                    }))
+                    .await;
+
+                let fetched_any = responses.iter().any(|(_, _, result)| result.is_ok());
+                let busy_error = responses
+                    .iter()
+                    .filter_map(|(_, _, result)| {
+                        result.as_ref().err()?.downcast_ref::<RegistryBusyError>()
+                    })
+                    .next()
+                    .cloned()
+                    .filter(|_| !fetched_any);
+                let Some(busy_error) = busy_error else {
+                    this.update(&mut cx, |this, cx| {
+                        if this.registry_busy_until.take().is_some() {
+                            cx.notify();
+                        }
+                    })?;
+                    break responses;
+                };
+
+                let delay = busy_error.retry_delay(attempt);
+                this.update(&mut cx, |this, cx| {
+                    this.registry_busy_until = Some(Instant::now() + delay);
+                    cx.notify();
+                })?;
+                if !is_full_listing || attempt >= REGISTRY_MAX_RETRIES {
+                    break responses;
+                }
+                log::info!("{busy_error}, retrying in {delay:?}");
+                attempt += 1;
+                cx.background_executor().timer(delay).await;
+            };
+
+            let mut extensions = Vec::new();
+            let mut total_count = 0;
+            let mut listings = BTreeMap::default();
+            let mut seen_extension_ids = HashSet::default();
+            let mut fetched_any = false;
+            let mut first_error = None;
+            for (registry, url, result) in responses {
+                let result = result.and_then(|listing| match listing {
+                    RegistryListing::Modified {
+                        data,
+                        etag,
+                        total_count: registry_total_count,
+                    } => {
+                        total_count += registry_total_count.unwrap_or(data.len());
+                        if let Some(etag) = etag {
+                            listings.insert(
+                                url,
+                                CachedRegistryListing {
+                                    etag,
+                                    data: data.clone(),
+                                },
+                            );
+                        }
+                        Ok(data)
+                    }
+                    RegistryListing::NotModified => {
+                        let listing = cached_listings
+                            .remove(&url)
+                            .context("the registry's listing isn't cached")?;
+                        let data = listing.data.clone();
+                        total_count += data.len();
+                        listings.insert(url, listing);
+                        Ok(data)
+                    }
+                });
+                match result {
+                    Ok(registry_extensions) => {
+                        fetched_any = true;
+                        for mut extension in registry_extensions {
+                            if seen_extension_ids.insert(extension.id.clone()) {
+                                extension.registry = Some(registry.clone());
+                                extensions.push(extension);
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        log::error!(
+                            "failed to fetch extensions from registry {registry}: {error:#}"
+                        );
+                        first_error.get_or_insert(error);
+                    }
+                }
+            }
+            if let (false, Some(error)) = (fetched_any, first_error) {
+                return Err(error);
+            }
+
+            if is_full_listing {
+                // Full listings aren't paginated, so their total count is how
+                // many distinct extensions they list.
+                total_count = extensions.len();
+                let cache = RegistryCache {
+                    fetched_at: Some(SystemTime::now()),
+                    data: extensions,
+                    listings,
+                };
+                if let Some(body) = serde_json::to_string(&cache).log_err() {
+                    fs.atomic_write(registry_cache_path, body)
+                        .await
+                        .context("failed to cache the extension listing")
+                        .log_err();
+                }
+                extensions = cache.data;
+            }
+
+            this.update(&mut cx, |this, cx| {
+                for extension in &extensions {
+                    this.remote_extensions
+                        .insert(extension.id.clone(), extension.clone());
+                }
+                cx.notify();
+            })?;
+
+            Ok(ExtensionsPage {
+                extensions,
+                total_count,
+            })
+        })
+    }
+
+    /// Returns the extensions from the most recent full listing of the
+    /// registry, for when the registry can't be reached.
+    pub fn cached_extensions(
+        &self,
+        search: Option<&str>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<Vec<ExtensionApiResponse>>> {
+        let fs = self.fs.clone();
+        let registry_cache_path = self.registry_cache_path.clone();
+        let search = search.map(|search| search.to_lowercase());
+        cx.spawn(move |this, mut cx| async move {
+            let mut response = load_registry_cache(&fs, &registry_cache_path).await?;
+
+            if let Some(search) = search {
+                response.data.retain(|extension| {
+                    extension.id.to_lowercase().contains(&search)
+                        || extension.name.to_lowercase().contains(&search)
+                        || extension.description.as_ref().map_or(false, |description| {
+                            description.to_lowercase().contains(&search)
+                        })
+                });
+            }
+
+            this.update(&mut cx, |this, cx| {
+                for extension in &response.data {
+                    this.remote_extensions
+                        .entry(extension.id.clone())
+                        .or_insert_with(|| extension.clone());
+                }
+                cx.notify();
+            })?;
+
+            Ok(response.data)
+        })
+    }
+
+    /// Fetches the registry's listing and collections once some time has
+    /// passed, so that they are ready before they are first needed.
+    pub fn prefetch_metadata(&mut self, cx: &mut ModelContext<Self>) {
+        let prefetch = cx.spawn(|this, mut cx| async move {
+            cx.background_executor()
+                .timer(METADATA_PREFETCH_DELAY)
+                .await;
+            let Ok((fetch, fetch_collections, fetch_blocklist)) =
+                this.update(&mut cx, |this, cx| {
+                    let bandwidth_limit = this.background_bandwidth_limit(cx);
+                    (
+                        this.query_extensions_within(
+                            ExtensionQuery::default(),
+                            bandwidth_limit.clone(),
+                            cx,
+                        ),
+                        this.fetch_extension_collections_within(bandwidth_limit, cx),
+                        this.fetch_blocklist(cx),
+                    )
+                })
+            else {
+                return;
+            };
+            fetch
+                .await
+                .context("failed to prefetch the extension listing")
+                .log_err();
+            fetch_collections
+                .await
+                .context("failed to prefetch the extension collections")
+                .log_err();
+            fetch_blocklist
+                .await
+                .context("failed to prefetch the extension blocklist")
+                .log_err();
+        });
+        self.tasks.push(prefetch);
+    }
+
+    pub fn fetch_extension_collections(&self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        self.fetch_extension_collections_within(None, cx)
+    }
+
+    fn fetch_extension_collections_within(
+        &self,
+        bandwidth_limit: Option<BandwidthLimit>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let url = self.build_registry_url("/extensions/collections");
+        let http_client = self.registry_http_client();
+        cx.spawn(move |this, mut cx| async move {
+            let mut response = http_client.get(&url, AsyncBody::empty(), true).await?;
+
+            let mut body = Vec::new();
+            read_to_end_within(
+                bandwidth_limit.as_ref(),
+                response.body_mut(),
+                &mut body,
+                cx.background_executor(),
+            )
+            .await
+            .context("error reading extension collections")?;
+
+            if response.status().is_client_error() {
+                let text = String::from_utf8_lossy(body.as_slice());
+                bail!(
+                    "status error {}, response: {text:?}",
+                    response.status().as_u16()
+                );
+            }
+
+            let response: ExtensionCollectionsApiResponse = serde_json::from_slice(&body)?;
+
+            this.update(&mut cx, |this, cx| {
+                this.extension_collections = response.data;
+                cx.notify();
+            })
+        })
+    }
+
+    /// Fetches the releases that the registry has blocked.
+    pub fn fetch_blocklist(&self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let url = self.build_registry_url("/extensions/blocklist");
+        let http_client = self.registry_http_client();
+        let bandwidth_limit = self.background_bandwidth_limit(cx);
+        cx.spawn(move |this, mut cx| async move {
+            let mut response = http_client.get(&url, AsyncBody::empty(), true).await?;
+
+            let mut body = Vec::new();
+            read_to_end_within(
+                bandwidth_limit.as_ref(),
+                response.body_mut(),
+                &mut body,
+                cx.background_executor(),
+            )
+            .await
+            .context("error reading extension blocklist")?;
+
+            if !response.status().is_success() {
+                let text = String::from_utf8_lossy(body.as_slice());
+                bail!(
+                    "status error {} from {url}, response: {text:?}",
+                    response.status().as_u16()
+                );
+            }
+            let blocklist = serde_json::from_slice::<ExtensionBlocklistApiResponse>(&body)
+                .context("invalid extension blocklist")?
+                .data;
+
+            this.update(&mut cx, |this, cx| this.set_blocklist(blocklist, cx))
+        })
+    }
+
+    fn set_blocklist(
+        &mut self,
+        blocklist: Vec<BlockedExtensionVersion>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        if self.blocklist == blocklist {
+            return;
+        }
+
+        let previously_blocked = self
+            .extension_index
+            .extensions
+            .keys()
+            .filter(|extension_id| self.installed_version_block(extension_id).is_some())
+            .cloned()
+            .collect::<HashSet<_>>();
+        self.blocklist = blocklist;
+        let newly_blocked = self
+            .extension_index
+            .extensions
+            .keys()
+            .filter(|extension_id| {
+                self.installed_version_block(extension_id).is_some()
+                    && !previously_blocked.contains(*extension_id)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        cx.notify();
+        let fs = self.fs.clone();
+        let path = self.blocklist_path.clone();
+        let content = serde_json::to_string_pretty(&self.blocklist);
+        cx.background_executor()
+            .spawn(async move { fs.atomic_write(path, content?).await })
+            .detach_and_log_err(cx);
+
+        if !newly_blocked.is_empty() {
+            cx.emit(Event::InstalledVersionsBlocked {
+                extension_ids: newly_blocked,
+            });
+        }
+    }
+
+    pub fn blocked_version(
+        &self,
+        extension_id: &str,
+        version: &str,
+    ) -> Option<&BlockedExtensionVersion> {
+        self.blocklist.iter().find(|blocked| {
+            blocked.extension_id.as_ref() == extension_id && blocked.version.as_ref() == version
+        })
+    }
+
+    pub fn installed_version_block(&self, extension_id: &str) -> Option<&BlockedExtensionVersion> {
+        let extension = self.extension_index.extensions.get(extension_id)?;
+        if extension.dev || self.extension_sources.contains_key(extension_id) {
+            return None;
+        }
+        self.blocked_version(extension_id, &extension.manifest.version)
+    }
+
+    /// Replaces the blocked installed version of an extension with the
+    /// version that the registry recommends instead.
+    pub fn downgrade_blocked_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let Some(blocked) = self.installed_version_block(&extension_id) else {
+            return Task::ready(Err(anyhow!(
+                "the installed version of {extension_id} isn't blocked"
+            )));
+        };
+        let Some(safe_version) = blocked.safe_version.clone() else {
+            return Task::ready(Err(anyhow!(
+                "the registry offers no version of {extension_id} to downgrade to"
+            )));
+        };
+        let was_pinned = self.pinned_extensions.contains_key(&extension_id);
+        let install = self.install_or_upgrade_extension(
+            extension_id.clone(),
+            safe_version,
+            ExtensionOperation::Install,
+            cx,
+        );
+        cx.spawn(|this, mut cx| async move {
+            install.await?;
+            if was_pinned {
+                this.update(&mut cx, |this, cx| this.pin_extension(&extension_id, cx))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Reports an extension in the registry to its operators, along with the
+    /// reporter's description of the problem.
+    pub fn report_extension(
+        &self,
+        extension_id: &str,
+        reason: ExtensionReportReason,
+        details: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let url = self.build_extension_url(
+            &extension_id,
+            &format!("/extensions/{extension_id}/reports"),
+        );
+        let http_client = self.registry_http_client();
+        cx.background_executor().spawn(async move {
+            let body = serde_json::to_string(&ExtensionReportRequest {
+                reason,
+                details: &details,
+            })?;
+            let mut response = http_client.post_json(&url, body.into()).await?;
+            if !response.status().is_success() {
+                let mut body = String::new();
+                response.body_mut().read_to_string(&mut body).await.ok();
+                bail!(
+                    "status error {}, response: {body:?}",
+                    response.status().as_u16()
+                );
+            }
+            Ok(())
+        })
+    }
+
+    /// Installs the latest version of each extension in the given collection
+    /// that isn't already installed.
+    pub fn install_extension_collection(
+        &mut self,
+        collection: &ExtensionCollection,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let extension_ids = collection.extension_ids.clone();
+        if extension_ids.is_empty() {
+            return Task::ready(Ok(()));
+        }
+
+        // Only the collection's extensions are fetched, rather than the whole
+        // listing, which may not include all of them.
+        let query = ExtensionQuery {
+            ids: extension_ids.clone(),
+            ..Default::default()
+        };
+        let fetch = self.query_extensions(query, cx);
+        cx.spawn(move |this, mut cx| async move {
+            let extensions = fetch.await?.extensions;
+            this.update(&mut cx, |this, cx| {
+                for extension_id in extension_ids {
+                    if !matches!(
+                        this.extension_status(&extension_id),
+                        ExtensionStatus::NotInstalled
+                    ) {
+                        continue;
+                    }
+
+                    let Some(extension) = extensions
+                        .iter()
+                        .find(|extension| extension.id == extension_id)
+                    else {
+                        log::error!(
+                            "extension {extension_id} in collection is not in the registry"
+                        );
+                        continue;
+                    };
+                    this.install_extension(extension_id, extension.version.clone(), cx);
+                }
+            })
+        })
+    }
+
+    pub fn install_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        self.install_or_upgrade_with_dependencies(
+            extension_id,
+            version,
+            ExtensionOperation::Install,
+            cx,
+        )
+        .detach_and_log_err(cx);
+    }
+
+    /// Lists the installed extensions, other than dev extensions, so that they
+    /// can be installed elsewhere with [`Self::import_extension_list`].
+    pub fn export_extension_list(&self) -> ExtensionList {
+        ExtensionList {
+            extensions: self
+                .extension_index
+                .extensions
+                .iter()
+                .filter(|(_, extension)| !extension.dev)
+                .map(|(extension_id, extension)| ExtensionListEntry {
+                    id: extension_id.clone(),
+                    version: extension.manifest.version.clone(),
+                    pinned: self.pinned_extensions.contains_key(extension_id),
+                })
+                .collect(),
+        }
+    }
+
+    /// Installs the extensions in an exported list that aren't installed yet,
+    /// resolving to the outcome for each of them once they have all finished.
+    pub fn import_extension_list(
+        &mut self,
+        list: ExtensionList,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Vec<(Arc<str>, Result<()>)>> {
+        let fetch = self.fetch_extensions(None, cx);
+        cx.spawn(|this, mut cx| async move {
+            let fetch_error = fetch.await.err().map(|error| error.to_string());
+            let Ok(operations) = this.update(&mut cx, |this, cx| {
+                list.extensions
+                    .into_iter()
+                    .filter_map(|entry| {
+                        let installed_version = this
+                            .installed_extension(&entry.id)
+                            .map(|manifest| manifest.version.clone());
+                        let version = if entry.pinned {
+                            entry.version.clone()
+                        } else if installed_version.is_some() {
+                            return None;
+                        } else if let Some(extension) = this.remote_extension(&entry.id) {
+                            extension.version.clone()
+                        } else {
+                            let error = match &fetch_error {
+                                Some(error) => anyhow!("couldn't fetch the registry: {error}"),
+                                None => anyhow!("extension {} is not in the registry", entry.id),
+                            };
+                            return Some((entry.id, Task::ready(Err(error))));
+                        };
+                        if installed_version.as_ref() == Some(&version)
+                            || this.pinned_version(&entry.id).is_some()
+                        {
+                            return None;
+                        }
+
+                        let operation = if installed_version.is_some() {
+                            ExtensionOperation::Upgrade
+                        } else {
+                            ExtensionOperation::Install
+                        };
+                        let install = this.install_or_upgrade_with_dependencies(
+                            entry.id.clone(),
+                            version,
+                            operation,
+                            cx,
+                        );
+                        let extension_id = entry.id.clone();
+                        let task = cx.spawn(|this, mut cx| async move {
+                            install.await?;
+                            if entry.pinned {
+                                this.update(&mut cx, |this, cx| this.pin_extension(&entry.id, cx))?;
+                            }
+                            Ok(())
+                        });
+                        Some((extension_id, task))
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return Vec::new();
+            };
+
+            futures::future::join_all(
+                operations
+                    .into_iter()
+                    .map(|(extension_id, task)| async move { (extension_id, task.await) }),
+            )
+            .await
+        })
+    }
+
+    /// Includes an extension in, or excludes it from, the extensions that are
+    /// synced through the `synced_extensions` setting.
+    pub fn set_extension_synced(
+        &mut self,
+        extension_id: &str,
+        synced: bool,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let synced_extension = self.synced_extension(extension_id);
+        let extension_id = extension_id.to_string();
+        settings::update_settings_file::<ExtensionSettings>(self.fs.clone(), cx, move |settings| {
+            let unsynced_extensions = settings.unsynced_extensions.get_or_insert_with(Vec::new);
+            unsynced_extensions.retain(|id| *id != extension_id);
+            let synced_extensions = settings
+                .synced_extensions
+                .get_or_insert_with(Default::default);
+            if synced {
+                if let Some(synced_extension) = synced_extension {
+                    synced_extensions.insert(extension_id, synced_extension);
+                }
+            } else {
+                synced_extensions.remove(&extension_id);
+                unsynced_extensions.push(extension_id);
+            }
+        });
+    }
+
+    pub fn sync_synced_extensions(&mut self, cx: &mut ModelContext<Self>) {
+        if !ExtensionSettings::get_global(cx).sync_extensions {
+            self.offered_synced_extensions.clear();
+            self.synced_extensions_offer = None;
+            return;
+        }
+        self.record_synced_extensions(cx);
+        self.offer_synced_extensions(cx);
+    }
+
+    fn synced_extension(&self, extension_id: &str) -> Option<SyncedExtension> {
+        let extension = self.extension_index.extensions.get(extension_id)?;
+        if extension.dev {
+            return None;
+        }
+        Some(SyncedExtension {
+            version: extension.manifest.version.to_string(),
+            pinned: self.pinned_extensions.contains_key(extension_id),
+        })
+    }
+
+    fn record_synced_extensions(&self, cx: &mut ModelContext<Self>) {
+        let settings = ExtensionSettings::get_global(cx);
+        if !settings.sync_extensions {
+            return;
+        }
+        let changed = self
+            .extension_index
+            .extensions
+            .keys()
+            .filter(|extension_id| {
+                settings.syncs(extension_id)
+                    && self.outstanding_operations.get(*extension_id)
+                        != Some(&ExtensionOperation::Remove)
+            })
+            .filter_map(|extension_id| {
+                let synced_extension = self.synced_extension(extension_id)?;
+                let is_recorded = settings
+                    .synced_extensions
+                    .get(extension_id.as_ref())
+                    .map_or(false, |recorded| {
+                        recorded.pinned == synced_extension.pinned
+                            && (!recorded.pinned || recorded.version == synced_extension.version)
+                    });
+                (!is_recorded).then(|| (extension_id.to_string(), synced_extension))
+            })
+            .collect::<Vec<_>>();
+        if changed.is_empty() {
+            return;
+        }
+
+        settings::update_settings_file::<ExtensionSettings>(self.fs.clone(), cx, move |settings| {
+            settings
+                .synced_extensions
+                .get_or_insert_with(Default::default)
+                .extend(changed);
+        });
+    }
+
+    fn forget_synced_extension(&self, extension_id: &str, cx: &mut ModelContext<Self>) {
+        let settings = ExtensionSettings::get_global(cx);
+        if !settings.sync_extensions || !settings.synced_extensions.contains_key(extension_id) {
+            return;
+        }
+        let extension_id = extension_id.to_string();
+        settings::update_settings_file::<ExtensionSettings>(self.fs.clone(), cx, move |settings| {
+            if let Some(synced_extensions) = settings.synced_extensions.as_mut() {
+                synced_extensions.remove(&extension_id);
+            }
+        });
+    }
+
+    fn offer_synced_extensions(&mut self, cx: &mut ModelContext<Self>) {
+        let settings = ExtensionSettings::get_global(cx);
+        let extensions = settings
+            .synced_extensions
+            .iter()
+            .filter(|(extension_id, _)| {
+                settings.syncs(extension_id)
+                    && !self
+                        .offered_synced_extensions
+                        .contains(extension_id.as_str())
+                    && !self
+                        .outstanding_operations
+                        .contains_key(extension_id.as_str())
+            })
+            .filter(|(extension_id, synced_extension)| {
+                match self.installed_extension(extension_id) {
+                    None => true,
+                    Some(manifest) => {
+                        synced_extension.pinned
+                            && self.pinned_version(extension_id).is_none()
+                            && manifest.version.as_ref() != synced_extension.version
+                    }
+                }
+            })
+            .map(|(extension_id, synced_extension)| ExtensionListEntry {
+                id: extension_id.as_str().into(),
+                version: synced_extension.version.as_str().into(),
+                pinned: synced_extension.pinned,
+            })
+            .collect::<Vec<_>>();
+        if extensions.is_empty() {
+            return;
+        }
+
+        self.offered_synced_extensions
+            .extend(extensions.iter().map(|entry| entry.id.clone()));
+        let offer = self
+            .synced_extensions_offer
+            .get_or_insert_with(Default::default);
+        offer.extensions.extend(extensions);
+        cx.emit(Event::SyncedExtensionsAvailable {
+            extensions: offer.clone(),
+        });
+    }
+
+    pub fn synced_extensions_offer(&self) -> Option<&ExtensionList> {
+        self.synced_extensions_offer.as_ref()
+    }
+
+    pub fn install_synced_extensions(
+        &mut self,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Vec<(Arc<str>, Result<()>)>> {
+        match self.synced_extensions_offer.take() {
+            Some(offer) => self.import_extension_list(offer, cx),
+            None => Task::ready(Vec::new()),
+        }
+    }
+
+    /// Installs the registry's latest versions of the given extensions that
+    /// aren't installed yet, resolving to the outcome for each of them.
+    pub fn install_latest_extensions(
+        &mut self,
+        extension_ids: Vec<Arc<str>>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Vec<(Arc<str>, Result<()>)>> {
+        let list = ExtensionList {
+            extensions: extension_ids
+                .into_iter()
+                .map(|id| ExtensionListEntry {
+                    id,
+                    // The versions of extensions that aren't pinned are
+                    // resolved from the registry.
+                    version: "".into(),
+                    pinned: false,
+                })
+                .collect(),
+        };
+        self.import_extension_list(list, cx)
+    }
+
+    /// Installs the extensions in the `required_extensions` setting that
+    /// aren't installed yet, whenever the setting changes.
+    pub fn sync_required_extensions(&mut self, cx: &mut ModelContext<Self>) {
+        let required_extensions = &ExtensionSettings::get_global(cx).required_extensions;
+        if *required_extensions == self.required_extensions {
+            return;
+        }
+        self.required_extensions = required_extensions.clone();
+
+        self.required_extensions_task = Some(cx.spawn(|this, mut cx| async move {
+            let mut attempt = 0;
+            loop {
+                let install = this.update(&mut cx, |this, cx| {
+                    let missing_extensions = this.missing_required_extensions();
+                    (!missing_extensions.is_empty())
+                        .then(|| this.install_latest_extensions(missing_extensions, cx))
+                });
+                let Ok(Some(install)) = install else {
+                    return;
+                };
+
+                let mut failed = false;
+                for (extension_id, result) in install.await {
+                    if let Err(error) = result {
+                        log::error!("failed to install required extension {extension_id}: {error}");
+                        failed = true;
+                    }
+                }
+                if !failed {
+                    return;
+                }
+
+                let delay = (REQUIRED_EXTENSIONS_RETRY_DELAY * 2u32.saturating_pow(attempt))
+                    .min(REQUIRED_EXTENSIONS_MAX_RETRY_DELAY);
+                attempt += 1;
+                log::info!("retrying the installs of required extensions in {delay:?}");
+                cx.background_executor().timer(delay).await;
+            }
+        }));
+    }
+
+    fn missing_required_extensions(&self) -> Vec<Arc<str>> {
+        self.required_extensions
+            .iter()
+            .filter(|extension_id| {
+                !self
+                    .extension_index
+                    .extensions
+                    .contains_key(extension_id.as_str())
+                    && !self
+                        .outstanding_operations
+                        .contains_key(extension_id.as_str())
+            })
+            .map(|extension_id| Arc::<str>::from(extension_id.as_str()))
+            .collect()
+    }
+
+    /// Downloads a version of an extension without installing it, and
+    /// reports what installing it would do.
+    pub fn preview_install(
+        &self,
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<InstallPreview>> {
+        let url = self.build_extension_url(
+            &extension_id,
+            &format!("/extensions/{extension_id}/{version}/download"),
+        );
+        let http_client = self.registry_http_client();
+        cx.background_executor().spawn(async move {
+            let mut response = http_client
+                .get(&url, Default::default(), true)
+                .await
+                .map_err(|err| anyhow!("error downloading extension: {}", err))?;
+            if !response.status().is_success() {
+                bail!(
+                    "failed to download extension {extension_id} {version}: {}",
+                    response.status()
+                );
+            }
+            let mut archive_bytes = Vec::new();
+            response.body_mut().read_to_end(&mut archive_bytes).await?;
+
+            #[derive(Deserialize)]
+            struct Named {
+                name: Arc<str>,
+            }
+            #[derive(Deserialize)]
+            struct ThemeFamily {
+                themes: Vec<Named>,
+            }
+
+            let mut files = Vec::new();
+            let mut contents = HashMap::default();
+            let archive = Archive::new(GzipDecoder::new(BufReader::new(archive_bytes.as_slice())));
+            let mut entries = archive.entries()?;
+            while let Some(entry) = entries.next().await {
+                let mut entry = entry?;
+                if !entry.header().entry_type().is_file() {
+                    continue;
+                }
+                let path = entry.path()?.into_owned();
+                let path = path.strip_prefix(".").unwrap_or(&path).to_path_buf();
+                let size = entry.header().size()?;
+                // Only the manifest and the configuration files it refers to
+                // are needed, and those are small.
+                if path.extension().map_or(false, |extension| {
+                    extension == "toml" || extension == "json"
+                }) {
+                    let mut content = String::new();
+                    entry.read_to_string(&mut content).await?;
+                    contents.insert(path.clone(), content);
+                }
+                files.push((path, size));
+            }
+            files.sort();
+
+            let manifest = if let Some(content) = contents.get(Path::new("extension.toml")) {
+                toml::from_str::<ExtensionManifest>(content).context("invalid extension.toml")?
+            } else if let Some(content) = contents.get(Path::new("extension.json")) {
+                manifest_from_old_manifest(
+                    serde_json::from_str(content).context("invalid extension.json")?,
+                    &extension_id,
+                )
+            } else {
+                bail!("extension {extension_id} {version} has no manifest");
+            };
+
+            let mut languages = Vec::new();
+            for language_path in &manifest.languages {
+                let config = contents
+                    .get(&language_path.join("config.toml"))
+                    .and_then(|content| toml::from_str::<Named>(content).log_err());
+                if let Some(config) = config {
+                    languages.push(config.name);
+                }
+            }
+            let mut themes = Vec::new();
+            for theme_path in &manifest.themes {
+                let family = contents
+                    .get(theme_path)
+                    .and_then(|content| serde_json::from_str::<ThemeFamily>(content).log_err());
+                if let Some(family) = family {
+                    themes.extend(family.themes.into_iter().map(|theme| theme.name));
+                }
+            }
+
+            Ok(InstallPreview {
+                extension_id,
+                version,
+                download_size: archive_bytes.len() as u64,
+                files,
+                languages,
+                themes,
+                grammars: manifest.grammars.into_keys().collect(),
+                language_servers: manifest.language_servers.into_keys().collect(),
+            })
+        })
+    }
+
+    pub fn upgrade_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        self.install_or_upgrade_with_dependencies(
+            extension_id,
+            version,
+            ExtensionOperation::Upgrade,
+            cx,
+        )
+        .detach_and_log_err(cx);
+    }
+
+    /// Downloads the installed version of an extension from the registry again,
+    /// and replaces the installed files with it, to repair an extension whose
+    /// files were corrupted.
+    pub fn reinstall_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let Some(extension) = self.extension_index.extensions.get(&extension_id) else {
+            return Task::ready(Err(anyhow!("extension {extension_id} is not installed")));
+        };
+        if extension.dev {
+            return Task::ready(Err(anyhow!(
+                "extension {extension_id} is a dev extension, which is rebuilt instead"
+            )));
+        }
+        if self.extension_sources.contains_key(&extension_id) {
+            return Task::ready(Err(anyhow!(
+                "extension {extension_id} wasn't installed from the registry"
+            )));
+        }
+        let version = extension.manifest.version.clone();
+        self.install_or_upgrade_extension(extension_id, version, ExtensionOperation::Install, cx)
+    }
+
+    /// Reinstalls each of the extensions installed from the registry, as
+    /// [`Self::reinstall_extension`] does, resolving to the outcome for each
+    /// extension once they have all finished.
+    pub fn reinstall_all_extensions(
+        &mut self,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Vec<(Arc<str>, Result<()>)>> {
+        let extension_ids = self
+            .extension_index
+            .extensions
+            .iter()
+            .filter(|(extension_id, extension)| {
+                !extension.dev && !self.extension_sources.contains_key(*extension_id)
+            })
+            .map(|(extension_id, _)| extension_id.clone())
+            .collect::<Vec<_>>();
+        let operations = extension_ids
+            .into_iter()
+            .map(|extension_id| {
+                let task = self.reinstall_extension(extension_id.clone(), cx);
+                async move { (extension_id, task.await) }
+            })
+            .collect::<Vec<_>>();
+        cx.background_executor()
+            .spawn(futures::future::join_all(operations))
+    }
+
+    /// Performs the given operation on each of the given extensions at once,
+    /// resolving to the outcome for each extension once they have all finished.
+    pub fn run_batch_operation(
+        &mut self,
+        operation: ExtensionOperation,
+        extensions: Vec<(Arc<str>, Arc<str>)>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Vec<(Arc<str>, Result<()>)>> {
+        let operations = extensions
+            .into_iter()
+            .map(|(extension_id, version)| {
+                let task = match operation {
+                    ExtensionOperation::Install | ExtensionOperation::Upgrade => self
+                        .install_or_upgrade_with_dependencies(
+                            extension_id.clone(),
+                            version,
+                            operation,
+                            cx,
+                        ),
+                    ExtensionOperation::Remove => self.remove_extension(extension_id.clone(), cx),
+                };
+                async move { (extension_id, task.await) }
+            })
+            .collect::<Vec<_>>();
+        cx.background_executor()
+            .spawn(futures::future::join_all(operations))
+    }
+
+    fn install_or_upgrade_with_dependencies(
+        &mut self,
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        operation: ExtensionOperation,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let remote_extension = self.fetch_remote_extension_version(&extension_id, &version, cx);
+        cx.spawn(|this, mut cx| async move {
+            let remote_extension = remote_extension.await?;
+            let dependencies = remote_extension
+                .as_ref()
+                .map(|extension| extension.dependencies.clone())
+                .unwrap_or_default();
+            this.update(&mut cx, |this, cx| {
+                this.install_package_with_dependencies(
+                    extension_id,
+                    version,
+                    operation,
+                    ExtensionPackage::Registry(remote_extension),
+                    dependencies,
+                    cx,
+                )
+            })?
+            .await
+        })
+    }
+
+    fn install_package_with_dependencies(
+        &mut self,
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        operation: ExtensionOperation,
+        package: ExtensionPackage,
+        dependencies: BTreeMap<Arc<str>, String>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        cx.spawn(|this, mut cx| async move {
+            let mut plan = HashMap::default();
+            plan.insert(
+                extension_id.clone(),
+                PlannedInstall {
+                    version: version.clone(),
+                    operation,
+                    dependencies: dependencies.keys().cloned().collect(),
+                    package,
+                },
+            );
+
+            let mut requirements = Vec::new();
+            let mut pending = vec![(extension_id.clone(), dependencies)];
+            while let Some((dependent_id, dependencies)) = pending.pop() {
+                for (dependency_id, requirement) in dependencies {
+                    let requirement = VersionReq::parse(&requirement).with_context(|| {
+                        format!(
+                            "extension {dependent_id} has an invalid requirement \
+                            {requirement:?} on {dependency_id}"
+                        )
+                    })?;
+                    requirements.push((
+                        dependent_id.clone(),
+                        dependency_id.clone(),
+                        requirement.clone(),
+                    ));
+                    if plan.contains_key(&dependency_id) {
+                        continue;
+                    }
+
+                    let (installed_version, is_pinned) = this.update(&mut cx, |this, _| {
+                        (
+                            this.installed_extension(&dependency_id)
+                                .map(|manifest| manifest.version.clone()),
+                            this.pinned_version(&dependency_id).is_some(),
+                        )
+                    })?;
+                    if installed_version
+                        .as_deref()
+                        .map_or(false, |version| version_satisfies(&requirement, version))
+                    {
+                        continue;
+                    }
+                    if is_pinned {
+                        bail!(
+                            "extension {dependent_id} requires {dependency_id} {requirement}, \
+                            but {dependency_id} is pinned to an incompatible version"
+                        );
+                    }
+
+                    let latest_version =
+                        |this: &Self| this.remote_extension(&dependency_id).cloned();
+                    let mut latest = this.update(&mut cx, |this, _| latest_version(this))?;
+                    if latest.is_none() {
+                        this.update(&mut cx, |this, cx| this.fetch_extensions(None, cx))?
+                            .await?;
+                        latest = this.update(&mut cx, |this, _| latest_version(this))?;
+                    }
+                    let Some(latest) = latest else {
+                        bail!(
+                            "extension {dependent_id} depends on {dependency_id}, \
+                            which is not in the registry"
+                        );
+                    };
+
+                    pending.push((dependency_id.clone(), latest.dependencies.clone()));
+                    plan.insert(
+                        dependency_id,
+                        PlannedInstall {
+                            version: latest.version.clone(),
+                            operation: if installed_version.is_some() {
+                                ExtensionOperation::Upgrade
+                            } else {
+                                ExtensionOperation::Install
+                            },
+                            dependencies: latest.dependencies.keys().cloned().collect(),
+                            package: ExtensionPackage::Registry(Some(latest)),
+                        },
+                    );
+                }
+            }
+
+            // A dependency that's installed or upgraded must still satisfy the
+            // requirements that other extensions have on it.
+            for (dependent_id, dependency_id, requirement) in &requirements {
+                let Some(planned) = plan.get(dependency_id) else {
+                    continue;
+                };
+                if !version_satisfies(requirement, &planned.version) {
+                    bail!(
+                        "extension {dependent_id} requires {dependency_id} {requirement}, \
+                        but its latest version is {}",
+                        planned.version
+                    );
+                }
+            }
+
+            for install_id in install_order(&extension_id, &plan) {
+                let planned = &plan[&install_id];
+                let install = this.update(&mut cx, |this, cx| {
+                    if install_id != extension_id {
+                        // Another extension that depends on it may have
+                        // installed it in the meantime.
+                        if this
+                            .installed_extension(&install_id)
+                            .map_or(false, |manifest| manifest.version == planned.version)
+                        {
+                            return None;
+                        }
+                        log::info!(
+                            "installing {install_id} {} for {extension_id}",
+                            planned.version
+                        );
+                    }
+                    Some(this.shared_install(
+                        install_id.clone(),
+                        planned.version.clone(),
+                        planned.operation,
+                        planned.package.clone(),
+                        cx,
+                    ))
+                })?;
+                let Some(install) = install else {
+                    continue;
+                };
+                install.await.map_err(|error| {
+                    if install_id == extension_id {
+                        anyhow!("{error:#}")
+                    } else {
+                        anyhow!("failed to install dependency {install_id}: {error:#}")
+                    }
+                })?;
+            }
+            Ok(())
+        })
+    }
+
+    fn shared_install(
+        &mut self,
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        operation: ExtensionOperation,
+        package: ExtensionPackage,
+        cx: &mut ModelContext<Self>,
+    ) -> Shared<Task<Result<(), Arc<anyhow::Error>>>> {
+        if let Some((pending_version, install)) = self.pending_installs.get(&extension_id) {
+            if *pending_version == version {
+                return install.clone();
+            }
+        }
+
+        let install = self.install_extension_version(
+            extension_id.clone(),
+            version.clone(),
+            operation,
+            package,
+            cx,
+        );
+        let install = cx
+            .spawn({
+                let extension_id = extension_id.clone();
+                |this, mut cx| async move {
+                    let result = install.await.map_err(Arc::new);
+                    this.update(&mut cx, |this, _| {
+                        this.pending_installs.remove(&extension_id);
+                    })
+                    .ok();
+                    result
+                }
+            })
+            .shared();
+        self.pending_installs
+            .insert(extension_id, (version, install.clone()));
+        install
+    }
+
+    fn fetch_remote_extension_version(
+        &self,
+        extension_id: &Arc<str>,
+        version: &Arc<str>,
+        cx: &ModelContext<Self>,
+    ) -> Task<Result<Option<ExtensionApiResponse>>> {
+        if let Some(extension) = self
+            .remote_extensions
+            .get(extension_id)
+            .filter(|extension| extension.version == *version)
+        {
+            return Task::ready(Ok(Some(extension.clone())));
+        }
+        let http_client = self.registry_http_client();
+        let url = self.build_extension_url(
+            extension_id,
+            &format!("/extensions/{extension_id}/{version}"),
+        );
+        let extension_id = extension_id.clone();
+        let version = version.clone();
+        cx.background_executor().spawn(async move {
+            fetch_extension_version_metadata(&http_client, &url)
+                .await
+                .with_context(|| format!("failed to fetch metadata for {extension_id} {version}"))
+        })
+    }
+
+    /// Returns the registry's latest versions of the extensions that would be
+    /// installed along with the given one: its dependencies, and theirs in
+    /// turn, that aren't installed at a version satisfying the requirements on
+    /// them.
+    pub fn dependencies_to_install(
+        &self,
+        extension: &ExtensionApiResponse,
+    ) -> Vec<ExtensionApiResponse> {
+        let mut dependencies = Vec::new();
+        let mut visited = HashSet::default();
+        visited.insert(extension.id.clone());
+        let mut pending = vec![extension];
+        while let Some(dependent) = pending.pop() {
+            for (dependency_id, requirement) in &dependent.dependencies {
+                let is_satisfied = VersionReq::parse(requirement)
+                    .ok()
+                    .zip(self.installed_extension(dependency_id))
+                    .map_or(false, |(requirement, manifest)| {
+                        version_satisfies(&requirement, &manifest.version)
+                    });
+                if is_satisfied || !visited.insert(dependency_id.clone()) {
+                    continue;
+                }
+                if let Some(latest) = self.remote_extensions.get(dependency_id) {
+                    dependencies.push(latest.clone());
+                    pending.push(latest);
+                }
+            }
+        }
+        dependencies
+    }
+
+    /// Returns the installed extensions that depend on the given extension.
+    pub fn dependents(&self, extension_id: &str) -> Vec<Arc<str>> {
+        self.extension_index
+            .extensions
+            .iter()
+            .filter(|(id, extension)| {
+                id.as_ref() != extension_id
+                    && extension.manifest.dependencies.contains_key(extension_id)
+            })
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    fn install_or_upgrade_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        operation: ExtensionOperation,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let remote_extension = self
+            .remote_extensions
+            .get(&extension_id)
+            .filter(|extension| extension.version == version)
+            .cloned();
+        self.install_extension_version(
+            extension_id,
+            version,
+            operation,
+            ExtensionPackage::Registry(remote_extension),
+            cx,
+        )
+    }
+
+    fn install_extension_version(
+        &mut self,
+        extension_id: Arc<str>,
+        version: Arc<str>,
+        operation: ExtensionOperation,
+        package: ExtensionPackage,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        log::info!("installing extension {extension_id} {version}");
+        let url = self.build_extension_url(
+            &extension_id,
+            &format!("/extensions/{extension_id}/{version}/download"),
+        );
+
+        let extensions_dir = self.extensions_dir();
+        let downloads_dir = self.downloads_dir.clone();
+        let previous_versions_dir = self.previous_versions_dir.clone();
+        let retained_previous_versions =
+            ExtensionSettings::get_global(cx).retained_previous_versions;
+        // The checksum, signature and mirrors of the version come from
+        // its registry metadata.
+        let metadata_url = self.build_extension_url(
+            &extension_id,
+            &format!("/extensions/{extension_id}/{version}"),
+        );
+        let settings = ExtensionSettings::get_global(cx);
+        let require_signed_extensions = settings.require_signed_extensions;
+        let trusted_signing_keys = settings.trusted_signing_keys.clone();
+        // Reinstalling the installed version repairs it right away, and
+        // doesn't retain the installed files as a previous version. Nor are
+        // versions that the registry has blocked retained.
+        let is_reinstall = self
+            .installed_extension(&extension_id)
+            .map_or(false, |manifest| manifest.version == version);
+        let retain_installed_version =
+            !is_reinstall && self.installed_version_block(&extension_id).is_none();
+        let stage_changes = settings.stage_extension_changes && !is_reinstall;
+        let pending_dir = self.pending_dir.clone();
+        let background_bandwidth_limit = self
+            .background_bandwidth_limit(cx)
+            .filter(|_| self.auto_updating_extensions.contains(&extension_id));
+
+        let http_client = self.registry_http_client();
+        let fs = self.fs.clone();
+
+        if !ExtensionSettings::get_global(cx).allows_extension(&extension_id) {
+            return Task::ready(Err(anyhow!(
+                "extension {extension_id} is blocked by your organization"
+            )));
+        }
+        if require_signed_extensions {
+            if let Err(error) =
+                extension_signing::ensure_signing_keys_trusted(&trusted_signing_keys)
+            {
+                return Task::ready(Err(error));
+            }
+        }
+        if let Some(blocked) = self.blocked_version(&extension_id, &version) {
+            return Task::ready(Err(anyhow!(
+                "{extension_id} {version} has been blocked by the registry: {}",
+                blocked.reason
+            )));
+        }
+        match self.outstanding_operations.entry(extension_id.clone()) {
+            hash_map::Entry::Occupied(_) => {
+                return Task::ready(Err(anyhow!(
+                    "extension {extension_id} is already being modified"
+                )))
+            }
+            hash_map::Entry::Vacant(e) => e.insert(operation),
+        };
+
+        self.failed_operations.remove(&extension_id);
+        let previous_version = self
+            .installed_extension(&extension_id)
+            .map(|manifest| manifest.version.clone());
+        // Upgrades are first downloaded as a delta from the installed
+        // version, if the registry offers one.
+        let delta_url = previous_version
+            .as_ref()
+            .filter(|_| operation == ExtensionOperation::Upgrade)
+            .map(|previous_version| {
+                self.build_extension_url(
+                    &extension_id,
+                    &format!("/extensions/{extension_id}/{version}/delta?from={previous_version}"),
+                )
+            });
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (cancel_request_tx, cancel_request_rx) = oneshot::channel();
+        let queued_download = if let ExtensionPackage::Registry(_) = &package {
+            self.downloads.insert(
+                extension_id.clone(),
+                ExtensionDownload {
+                    progress: DownloadProgress::default(),
+                    cancelled: cancelled.clone(),
+                    cancel_request: Some(cancel_request_tx),
+                    stage: DownloadStage::Requesting,
+                },
+            );
+            self.acquire_download_slot(&extension_id, cx)
+        } else {
+            None
+        };
+        self.start_watchdog(extension_id.clone(), cx);
+        cx.notify();
+
+        cx.spawn(move |this, mut cx| async move {
+            let _finish = util::defer({
+                let this = this.clone();
+                let mut cx = cx.clone();
+                let extension_id = extension_id.clone();
+                move || {
+                    this.update(&mut cx, |this, cx| {
+                        this.finish_operation(&extension_id);
+                        this.downloads.remove(extension_id.as_ref());
+                        cx.notify();
+                    })
+                    .ok();
+                }
+            });
+
+            let mut staged = false;
+            let result = async {
+                let remove_options = RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: true,
+                };
+                let (package_dir, signer, source) = match package {
+                    ExtensionPackage::Registry(remote_extension) => {
+                        // Wait for a download slot, which is released once the
+                        // archive has been downloaded.
+                        let mut cancel_request_rx = cancel_request_rx.fuse();
+                        let download_slot = util::defer({
+                            let this = this.clone();
+                            let mut cx = cx.clone();
+                            let extension_id = extension_id.clone();
+                            move || {
+                                this.update(&mut cx, |this, cx| {
+                                    this.release_download_slot(&extension_id, cx)
+                                })
+                                .ok();
+                            }
+                        });
+                        if let Some(queued_download) = queued_download {
+                            select_biased! {
+                                _ = cancel_request_rx => {
+                                    log::info!("cancelled installing extension {extension_id}");
+                                    return Ok(());
+                                }
+                                _ = queued_download.fuse() => {}
+                            }
+                        }
+
+                        // Unpack the archive separately, so that a failed or cancelled
+                        // download doesn't affect the installed version of the extension.
+                        let download_dir = downloads_dir.join(extension_id.as_ref());
+                        fs.remove_dir(&download_dir, remove_options).await?;
+
+                        let remote_extension = match remote_extension {
+                            Some(remote_extension) => Some(remote_extension),
+                            None => fetch_extension_version_metadata(&http_client, &metadata_url)
+                                .await
+                                .with_context(|| {
+                                    format!("failed to fetch metadata for {extension_id} {version}")
+                                })?,
+                        };
+                        let expected_sha256 = remote_extension
+                            .as_ref()
+                            .and_then(|extension| extension.archive_sha256.clone());
+                        let signature = remote_extension
+                            .as_ref()
+                            .and_then(|extension| extension.signature.clone());
+
+                        // Only whole archives are signed, so signed extensions, and
+                        // every extension when signatures are required, aren't
+                        // upgraded with deltas.
+                        let delta_url =
+                            delta_url.filter(|_| signature.is_none() && !require_signed_extensions);
+                        let download_urls = delta_url
+                            .into_iter()
+                            .map(|url| (url, true))
+                            .chain(iter::once((url, false)))
+                            .chain(
+                                remote_extension
+                                    .into_iter()
+                                    .flat_map(|extension| extension.mirrors)
+                                    .map(|url| (url, false)),
+                            )
+                            .collect::<Vec<_>>();
+
+                        // Download the whole archive before unpacking it, so that its
+                        // checksum can be verified first. If the download fails or
+                        // doesn't start in time, the archive's mirrors are tried in turn.
+                        // A delta is applied as soon as it's downloaded, falling back
+                        // to the whole archive if that fails.
+                        let mut archive_bytes = None;
+                        let mut applied_delta = false;
+                        let mut last_error = None;
+                        for (url, is_delta) in &download_urls {
+                            if let Some(error) = &last_error {
+                                log::warn!(
+                                    "failed to download extension {extension_id} {version}, \
+                                    trying {url}: {error:#}"
+                                );
+                            }
+                            let mut timeout = cx
+                                .background_executor()
+                                .timer(DOWNLOAD_RESPONSE_TIMEOUT)
+                                .fuse();
+                            let mut request = http_client.get(url, Default::default(), true).fuse();
+                            let mut response = select_biased! {
+                                _ = cancel_request_rx => {
+                                    log::info!("cancelled installing extension {extension_id}");
+                                    return Ok(());
+                                }
+                                response = request => {
+                                    match response {
+                                        Ok(response) if response.status().is_success() => {
+                                            response
+                                        }
+                                        Ok(response) => {
+                                            last_error = Some(anyhow!(
+                                                "error downloading extension: status {}",
+                                                response.status()
+                                            ));
+                                            continue;
+                                        }
+                                        Err(error) => {
+                                            last_error = Some(anyhow!(
+                                                "error downloading extension: {error}"
+                                            ));
+                                            continue;
+                                        }
+                                    }
+                                }
+                                _ = timeout => {
+                                    last_error = Some(anyhow!(
+                                        "error downloading extension: no response after {:?}",
+                                        DOWNLOAD_RESPONSE_TIMEOUT
+                                    ));
+                                    continue;
+                                }
+                            };
+                            let total_bytes = response
+                                .headers()
+                                .get("content-length")
+                                .and_then(|value| value.to_str().ok()?.parse().ok());
+
+                            let (progress_tx, mut progress_rx) = unbounded();
+                            this.update(&mut cx, |this, cx| {
+                                if let Some(download) = this.downloads.get_mut(&extension_id) {
+                                    download.progress = DownloadProgress {
+                                        downloaded_bytes: 0,
+                                        total_bytes,
+                                    };
+                                    download.stage = DownloadStage::Downloading;
+                                }
+                                cx.notify();
+
+                                let extension_id = extension_id.clone();
+                                cx.spawn(|this, mut cx| async move {
+                                    while let Some(downloaded_bytes) = progress_rx.next().await {
+                                        let updated = this.update(&mut cx, |this, cx| {
+                                            if let Some(download) =
+                                                this.downloads.get_mut(&extension_id)
+                                            {
+                                                download.progress.downloaded_bytes =
+                                                    downloaded_bytes;
+                                                cx.notify();
+                                            }
+                                        });
+                                        if updated.is_err() {
+                                            break;
+                                        }
+                                    }
+                                })
+                                .detach();
+                            })?;
+
+                            let mut body = DownloadReader::new(
+                                response.body_mut(),
+                                progress_tx,
+                                cancelled.clone(),
+                            );
+                            let mut bytes = Vec::new();
+                            let read = read_to_end_within(
+                                background_bandwidth_limit.as_ref(),
+                                &mut body,
+                                &mut bytes,
+                                cx.background_executor(),
+                            )
+                            .await;
+                            if let Err(error) = read {
+                                if cancelled.load(atomic::Ordering::SeqCst) {
+                                    log::info!("cancelled installing extension {extension_id}");
+                                    return Ok(());
+                                }
+                                last_error = Some(error.into());
+                                continue;
+                            }
+                            if *is_delta {
+                                let applied = cx
+                                    .background_executor()
+                                    .spawn(apply_extension_delta(
+                                        fs.clone(),
+                                        extensions_dir.join(extension_id.as_ref()),
+                                        download_dir.clone(),
+                                        bytes,
+                                        version.clone(),
+                                    ))
+                                    .await;
+                                if let Err(error) = applied {
+                                    fs.remove_dir(&download_dir, remove_options).await.log_err();
+                                    last_error = Some(error.context("failed to apply delta"));
+                                    continue;
+                                }
+                                applied_delta = true;
+                                break;
+                            }
+                            archive_bytes = Some(bytes);
+                            break;
+                        }
+                        drop(download_slot);
+                        let signer = if applied_delta {
+                            None
+                        } else {
+                            let Some(archive_bytes) = archive_bytes else {
+                                return Err(last_error.unwrap_or_else(|| {
+                                    anyhow!("no URL to download extension {extension_id}")
+                                }));
+                            };
+                            let signer = verify_extension_archive(
+                                &extension_id,
+                                &version,
+                                &archive_bytes,
+                                expected_sha256.as_deref(),
+                                signature.as_ref(),
+                                require_signed_extensions,
+                                &trusted_signing_keys,
+                            )?;
+
+                            let decompressed_bytes =
+                                GzipDecoder::new(BufReader::new(archive_bytes.as_slice()));
+                            let archive = Archive::new(decompressed_bytes);
+                            if let Err(error) = archive.unpack(&download_dir).await {
+                                fs.remove_dir(&download_dir, remove_options).await.log_err();
+                                if cancelled.load(atomic::Ordering::SeqCst) {
+                                    log::info!("cancelled installing extension {extension_id}");
+                                    return Ok(());
+                                }
+                                return Err(error.into());
+                            }
+                            signer
+                        };
+                        (download_dir, signer, None)
+                    }
+                    ExtensionPackage::Unpacked {
+                        dir,
+                        signer,
+                        source,
+                    } => {
+                        if require_signed_extensions && signer.is_none() {
+                            bail!(
+                                "cannot install extension {extension_id} {version}: \
+                                it is not signed"
+                            );
+                        }
+                        let extension_dir = extensions_dir.join(extension_id.as_ref());
+                        if let Some(metadata) = fs.metadata(&extension_dir).await? {
+                            if metadata.is_symlink {
+                                bail!("extension {extension_id} is installed as a dev extension");
+                            }
+                        }
+                        // Without a confirmation step, in which the quota
+                        // would be shown, an install that exceeds it fails.
+                        let size = directory_size(fs.as_ref(), dir.clone()).await?;
+                        let exceeds_disk_quota = this.update(&mut cx, |this, cx| {
+                            let installed_size = this
+                                .installed_extension_sizes
+                                .get(&extension_id)
+                                .copied()
+                                .unwrap_or_default();
+                            this.would_exceed_disk_quota(size.saturating_sub(installed_size), cx)
+                        })?;
+                        if exceeds_disk_quota {
+                            bail!(
+                                "cannot install extension {extension_id} {version}: \
+                                it would exceed the disk quota for extensions"
+                            );
+                        }
+                        (dir, signer, source)
+                    }
+                };
+
+                this.update(&mut cx, |this, cx| {
+                    if let Some(download) = this.downloads.get_mut(&extension_id) {
+                        download.stage = DownloadStage::Loading;
+                    }
+                    cx.notify();
+                })?;
+
+                if stage_changes {
+                    let staged_dir = pending_dir.join(extension_id.as_ref());
+                    fs.remove_dir(&staged_dir, remove_options).await?;
+                    fs.create_dir(&pending_dir).await?;
+                    fs.rename(&package_dir, &staged_dir, Default::default())
+                        .await?;
+                    this.update(&mut cx, |this, cx| {
+                        this.pending_changes.insert(
+                            extension_id.clone(),
+                            PendingExtensionChange {
+                                version: version.clone(),
+                                previous_version: previous_version.clone(),
+                                signer,
+                                source,
+                            },
+                        );
+                        this.save_pending_changes(cx);
+                    })?;
+                    staged = true;
+                    return Ok(());
+                }
+
+                let extension_dir = extensions_dir.join(extension_id.as_ref());
+                if let Some(previous_version) = previous_version
+                    .as_ref()
+                    .filter(|_| retained_previous_versions > 0 && retain_installed_version)
+                {
+                    let retained_dir = previous_versions_dir
+                        .join(extension_id.as_ref())
+                        .join(previous_version.as_ref());
+                    fs.remove_dir(&retained_dir, remove_options).await?;
+                    fs.create_dir(&previous_versions_dir.join(extension_id.as_ref()))
+                        .await?;
+                    fs.rename(&extension_dir, &retained_dir, Default::default())
+                        .await?;
+                }
+                publish_extension_dir(fs.as_ref(), &package_dir, &extension_dir, &downloads_dir)
+                    .await?;
+
+                this.update(&mut cx, |this, cx| {
+                    this.set_extension_signer(&extension_id, signer, cx);
+                    this.set_extension_source(&extension_id, source, cx);
+                    this.damaged_extensions.remove(&extension_id);
+                    this.reload(Some(extension_id.clone()), cx)
+                })?
+                .await;
+                this.update(&mut cx, |this, cx| this.prune_previous_versions(cx))?
+                    .await
+                    .log_err();
+                anyhow::Ok(())
+            }
+            .await;
+
+            this.update(&mut cx, |this, cx| match &result {
+                Ok(()) => {
+                    // Staged changes are reported once they're applied.
+                    if !cancelled.load(atomic::Ordering::SeqCst) && !staged {
+                        let hook_previous_version = match operation {
+                            ExtensionOperation::Upgrade => previous_version.clone(),
+                            _ => None,
+                        };
+                        this.run_lifecycle_hook(&extension_id, hook_previous_version, cx)
+                            .detach();
+                        if operation == ExtensionOperation::Upgrade {
+                            this.recently_upgraded_extensions.insert(
+                                extension_id.clone(),
+                                UpgradedExtension {
+                                    previous_version,
+                                    version: version.clone(),
+                                },
+                            );
+                            this.save_recently_upgraded_extensions(cx);
+                        }
+                        if ExtensionSettings::get_global(cx).warm_up_on_install {
+                            this.warm_up_extension(&extension_id, cx).detach();
+                        }
+                        this.record_synced_extensions(cx);
+                        cx.emit(Event::ExtensionInstalled {
+                            extension_id: extension_id.clone(),
+                            version,
+                            operation,
+                        });
+                    }
+                }
+                Err(error) => {
+                    let error = SharedString::from(error.to_string());
+                    this.failed_operations.insert(
+                        extension_id.clone(),
+                        FailedOperation {
+                            operation,
+                            version,
+                            error: error.clone(),
+                        },
+                    );
+                    cx.emit(Event::ExtensionOperationFailed {
+                        extension_id: extension_id.clone(),
+                        operation,
+                        error,
+                    });
+                }
+            })
+            .ok();
+
+            if cancelled.load(atomic::Ordering::SeqCst) {
+                bail!("installing extension {extension_id} was cancelled");
+            }
+            result
+        })
+    }
+
+    pub fn is_tracked(&self, extension_id: &str) -> bool {
+        self.tracked_extensions.contains_key(extension_id)
+    }
+
+    /// Starts or stops tracking extensions so that they match the
+    /// `tracked_extensions` setting.
+    pub fn sync_tracked_extensions(&mut self, cx: &mut ModelContext<Self>) {
+        let settings = ExtensionSettings::get_global(cx);
+        let tracked_extensions = settings
+            .tracked_extensions
+            .iter()
+            .map(|(extension_id, repository_url)| {
+                (
+                    Arc::<str>::from(extension_id.as_str()),
+                    repository_url.clone(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        let poll_interval = settings.tracked_extension_poll_interval();
+        if tracked_extensions == self.tracked_extensions
+            && poll_interval == self.tracked_extension_poll_interval
+        {
+            return;
+        }
+
+        let untracked_extension_ids = self
+            .tracked_extensions
+            .keys()
+            .filter(|extension_id| !tracked_extensions.contains_key(*extension_id))
+            .cloned()
+            .collect::<Vec<_>>();
+        for extension_id in untracked_extension_ids {
+            self.tracked_revisions.remove(&extension_id);
+            self.uninstall_dev_extension(extension_id, false, cx);
+        }
+
+        let newly_tracked_extensions = tracked_extensions
+            .iter()
+            .filter(|(extension_id, repository_url)| {
+                self.tracked_extensions.get(*extension_id) != Some(repository_url)
+            })
+            .map(|(extension_id, repository_url)| (extension_id.clone(), repository_url.clone()))
+            .collect::<Vec<_>>();
+        for (extension_id, repository_url) in newly_tracked_extensions {
+            self.tracked_revisions.remove(&extension_id);
+            self.update_tracked_extension(extension_id, repository_url, cx)
+                .detach_and_log_err(cx);
+        }
+
+        self.tracked_extensions = tracked_extensions;
+        self.tracked_extension_poll_interval = poll_interval;
+        self.tracking_task = if self.tracked_extensions.is_empty() {
+            None
+        } else {
+            Some(cx.spawn(|this, mut cx| async move {
+                loop {
+                    cx.background_executor().timer(poll_interval).await;
+                    let Ok(updates) = this.update(&mut cx, |this, cx| {
+                        this.tracked_extensions
+                            .clone()
+                            .into_iter()
+                            .map(|(extension_id, repository_url)| {
+                                this.update_tracked_extension(extension_id, repository_url, cx)
+                            })
+                            .collect::<Vec<_>>()
+                    }) else {
+                        break;
+                    };
+                    for result in futures::future::join_all(updates).await {
+                        result.log_err();
+                    }
+                }
+            }))
+        };
+        cx.notify();
+    }
+
+    /// Starts or stops periodically upgrading the installed extensions,
+    /// according to the `auto_update_extensions` and `auto_update_overrides`
+    /// settings.
+    pub fn sync_auto_update(&mut self, cx: &mut ModelContext<Self>) {
+        let auto_update = ExtensionSettings::get_global(cx).auto_updates_any();
+        if auto_update == self.auto_update_task.is_some() {
+            return;
+        }
+
+        self.auto_update_task = auto_update.then(|| {
+            cx.spawn(|this, mut cx| async move {
+                loop {
+                    let Ok(update) =
+                        this.update(&mut cx, |this, cx| this.auto_update_extensions(cx))
+                    else {
+                        break;
+                    };
+                    update.await.log_err();
+                    let Ok(update) =
+                        this.update(&mut cx, |this, cx| this.update_sourced_extensions(cx))
+                    else {
+                        break;
+                    };
+                    update.await.log_err();
+                    cx.background_executor().timer(AUTO_UPDATE_INTERVAL).await;
+                }
+            })
+        });
+    }
+
+    fn background_bandwidth_limit(&self, cx: &AppContext) -> Option<BandwidthLimit> {
+        let limit = ExtensionSettings::get_global(cx).background_bandwidth_limit_kb_per_sec?;
+        Some(BandwidthLimit {
+            bandwidth: self.background_bandwidth.clone(),
+            bytes_per_second: limit * 1024,
+        })
+    }
+
+    /// Asks every enabled registry which of the installed extensions have
+    /// newer versions, in a single request to each, and records the newer
+    /// versions as the extensions' latest ones.
+    pub fn check_for_updates(&self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let installed = self
+            .extension_index
+            .extensions
+            .iter()
+            .filter(|(_, extension)| !extension.dev)
+            .map(|(extension_id, extension)| InstalledExtensionVersion {
+                id: extension_id.clone(),
+                version: extension.manifest.version.clone(),
+            })
+            .collect::<Vec<_>>();
+        if installed.is_empty() {
+            return Task::ready(Ok(()));
+        }
+        let installed_ids = installed
+            .iter()
+            .map(|extension| extension.id.clone())
+            .collect::<HashSet<_>>();
+        let body = match serde_json::to_string(&ExtensionUpdatesRequest {
+            extensions: installed,
+        }) {
+            Ok(body) => body,
+            Err(error) => return Task::ready(Err(error.into())),
+        };
+        let requests = self
+            .registries
+            .iter()
+            .map(|registry| {
+                let base_url = self.registry_base_url(registry);
+                (
+                    registry.name.clone(),
+                    format!("{base_url}/extensions/updates"),
+                    format!("{base_url}/extensions"),
+                )
+            })
+            .collect::<Vec<_>>();
+        let http_client = self.registry_http_client();
+        let bandwidth_limit = self.background_bandwidth_limit(cx);
+
+        cx.spawn(|this, mut cx| async move {
+            let responses = futures::future::join_all(requests.iter().map(
+                |(registry, updates_url, listing_url)| {
+                    let http_client = http_client.clone();
+                    let body = body.clone();
+                    let installed_ids = &installed_ids;
+                    let bandwidth_limit = bandwidth_limit.as_ref();
+                    let executor = cx.background_executor().clone();
+                    async move {
+                        let result = match fetch_extension_updates(
+                            &http_client,
+                            updates_url,
+                            body,
+                            bandwidth_limit,
+                            &executor,
+                        )
+                        .await
+                        {
+                            Ok(Some(extensions)) => Ok(extensions),
+                            Ok(None) => fetch_registry_extensions(
+                                &http_client,
+                                listing_url,
+                                None,
+                                bandwidth_limit,
+                                &executor,
+                            )
+                            .await
+                            .map(|listing| match listing {
+                                RegistryListing::Modified { data, .. } => data
+                                    .into_iter()
+                                    .filter(|extension| installed_ids.contains(&extension.id))
+                                    .collect(),
+                                RegistryListing::NotModified => Vec::new(),
+                            }),
+                            Err(error) => Err(error),
+                        };
+                        (registry.clone(), result)
+                    }
+                },
+            ))
+            .await;
+
+            let mut updates = Vec::new();
+            let mut seen_extension_ids = HashSet::default();
+            let mut checked_any = false;
+            let mut first_error = None;
+            for (registry, result) in responses {
+                match result {
+                    Ok(extensions) => {
+                        checked_any = true;
+                        for mut extension in extensions {
+                            if seen_extension_ids.insert(extension.id.clone()) {
+                                extension.registry = Some(registry.clone());
+                                updates.push(extension);
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        log::error!(
+                            "failed to check registry {registry} for extension updates: {error:#}"
+                        );
+                        first_error.get_or_insert(error);
+                    }
+                }
+            }
+            if let (false, Some(error)) = (checked_any, first_error) {
+                return Err(error);
+            }
+
+            this.update(&mut cx, |this, cx| {
+                for extension in updates {
+                    this.remote_extensions
+                        .insert(extension.id.clone(), extension);
+                }
+                cx.notify();
+            })
+        })
+    }
+
+    fn auto_update_candidates(&self, cx: &AppContext) -> Vec<(Arc<str>, Arc<str>)> {
+        let settings = ExtensionSettings::get_global(cx);
+        self.extension_index
+            .extensions
+            .iter()
+            .filter(|(extension_id, extension)| {
+                settings.auto_updates(extension_id)
+                    && !self.pinned_extensions.contains_key(*extension_id)
+                    && !extension.dev
+                    && !self.tracked_extensions.contains_key(*extension_id)
+                    && !self.extension_sources.contains_key(*extension_id)
+                    && !self.outstanding_operations.contains_key(*extension_id)
+            })
+            .filter_map(|(extension_id, extension)| {
+                let latest = self.remote_extensions.get(extension_id)?;
+                (latest.version != extension.manifest.version
+                    && self
+                        .blocked_version(extension_id, &latest.version)
+                        .is_none())
+                .then(|| (extension_id.clone(), latest.version.clone()))
+            })
+            .collect()
+    }
+
+    /// Checks the registry for updates and upgrades every installed extension
+    /// that has a newer version, emitting a summary of what changed.
+    pub fn auto_update_extensions(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let fetch = self.check_for_updates(cx);
+        let fetch_blocklist = self.fetch_blocklist(cx);
+        cx.spawn(|this, mut cx| async move {
+            fetch.await?;
+            fetch_blocklist
+                .await
+                .context("failed to fetch the extension blocklist")
+                .log_err();
+            let (upgrades, batch) = this.update(&mut cx, |this, cx| {
+                let upgrades = this.auto_update_candidates(cx);
+                this.auto_updating_extensions.extend(
+                    upgrades
+                        .iter()
+                        .map(|(extension_id, _)| extension_id.clone()),
+                );
+                let batch =
+                    this.run_batch_operation(ExtensionOperation::Upgrade, upgrades.clone(), cx);
+                (upgrades, batch)
+            })?;
+            let outcomes = batch.await;
+
+            this.update(&mut cx, |this, cx| {
+                let mut upgraded = Vec::new();
+                let mut failed = Vec::new();
+                for ((extension_id, version), (_, result)) in upgrades.into_iter().zip(outcomes) {
+                    this.auto_updating_extensions.remove(&extension_id);
+                    match result {
+                        Ok(()) => upgraded.push((extension_id, version)),
+                        Err(error) => {
+                            log::error!("failed to auto-update extension {extension_id}: {error}");
+                            failed.push(extension_id);
+                        }
+                    }
+                }
+                if !upgraded.is_empty() || !failed.is_empty() {
+                    cx.emit(Event::ExtensionsAutoUpdated { upgraded, failed });
+                }
+            })
+        })
+    }
+
+    /// Reinstalls each extension installed from a git repository that has new
+    /// commits, or from a GitHub repository that has a newer release, emitting
+    /// a summary of what changed.
+    pub fn update_sourced_extensions(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let settings = ExtensionSettings::get_global(cx);
+        let sources = self
+            .extension_sources
+            .iter()
+            .filter(|(extension_id, _)| {
+                settings.auto_updates(extension_id)
+                    && !self.pinned_extensions.contains_key(*extension_id)
+                    && !self.outstanding_operations.contains_key(*extension_id)
+            })
+            .map(|(extension_id, source)| (extension_id.clone(), source.clone()))
+            .collect::<Vec<_>>();
+        let builder = self.builder.clone();
+        let http_client = self.registry_http_client();
+
+        cx.spawn(|this, mut cx| async move {
+            let mut upgraded = Vec::new();
+            let mut failed = Vec::new();
+            for (extension_id, source) in sources {
+                let result = match source {
+                    ExtensionSource::Git {
+                        repository_url,
+                        revision,
+                    } => {
+                        let latest_revision = cx.background_executor().spawn({
+                            let builder = builder.clone();
+                            let repository_url = repository_url.clone();
+                            async move { builder.latest_revision(&repository_url) }
+                        });
+                        match latest_revision.await {
+                            Ok(latest_revision) if latest_revision == revision => continue,
+                            Ok(_) => {
+                                this.update(&mut cx, |this, cx| {
+                                    this.auto_updating_extensions.insert(extension_id.clone());
+                                    this.install_from_git(repository_url, cx)
+                                })?
+                                .await
+                            }
+                            Err(error) => Err(error),
+                        }
+                    }
+                    ExtensionSource::GithubRelease { repository, tag } => {
+                        match latest_github_release(&repository, true, false, http_client.clone())
+                            .await
+                        {
+                            Ok(release) if release.tag_name == tag => continue,
+                            Ok(_) => {
+                                this.update(&mut cx, |this, cx| {
+                                    this.auto_updating_extensions.insert(extension_id.clone());
+                                    this.install_from_github_release(repository, cx)
+                                })?
+                                .await
+                            }
+                            Err(error) => Err(error),
+                        }
+                    }
+                };
+                this.update(&mut cx, |this, _| {
+                    this.auto_updating_extensions.remove(&extension_id);
+                    match result {
+                        Ok(()) => {
+                            if let Some(extension) =
+                                this.extension_index.extensions.get(&extension_id)
+                            {
+                                upgraded.push((extension_id, extension.manifest.version.clone()));
+                            }
+                        }
+                        Err(error) => {
+                            log::error!(
+                                "failed to update extension {extension_id} from its source: {error}"
+                            );
+                            failed.push(extension_id);
+                        }
+                    }
+                })?;
+            }
+
+            this.update(&mut cx, |_, cx| {
+                if !upgraded.is_empty() || !failed.is_empty() {
+                    cx.emit(Event::ExtensionsAutoUpdated { upgraded, failed });
+                }
+            })
+        })
+    }
+
+    pub fn is_auto_updating(&self, extension_id: &str) -> bool {
+        self.auto_updating_extensions.contains(extension_id)
+    }
+
+    fn update_tracked_extension(
+        &mut self,
         extension_id: Arc<str>,
-        version: Arc<str>,
+        repository_url: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        if self.outstanding_operations.contains_key(&extension_id) {
+            return Task::ready(Ok(()));
+        }
+
+        let checkout_dir = self.tracked_dir.join(extension_id.as_ref());
+        let installed_path = self.installed_dir.join(extension_id.as_ref());
+        let builder = self.builder.clone();
+        let fs = self.fs.clone();
+        cx.spawn(|this, mut cx| async move {
+            let revision = cx
+                .background_executor()
+                .spawn({
+                    let checkout_dir = checkout_dir.clone();
+                    async move { builder.checkout_latest(&checkout_dir, &repository_url) }
+                })
+                .await
+                .with_context(|| format!("failed to update tracked extension {extension_id}"))?;
+
+            let (is_built, installed_as_dev) = this.update(&mut cx, |this, _| {
+                let previous_revision = this
+                    .tracked_revisions
+                    .insert(extension_id.clone(), revision.clone());
+                let installed_as_dev = this
+                    .extension_index
+                    .extensions
+                    .get(&extension_id)
+                    .map(|extension| extension.dev);
+                (previous_revision == Some(revision), installed_as_dev)
+            })?;
+
+            match installed_as_dev {
+                Some(true) => {
+                    if fs.read_link(&installed_path).await? != checkout_dir {
+                        bail!(
+                            "extension {extension_id} is installed as a dev extension from another directory"
+                        );
+                    }
+                    if is_built {
+                        return Ok(());
+                    }
+                    this.update(&mut cx, |this, cx| {
+                        this.rebuild_dev_extension(extension_id.clone(), cx)
+                    })?
+                    .await
+                }
+                Some(false) => {
+                    // Replace the published release with the tracked checkout.
+                    this.update(&mut cx, |this, cx| {
+                        this.remove_extension(extension_id.clone(), cx)
+                    })?
+                    .await?;
+                    this.update(&mut cx, |this, cx| {
+                        this.install_dev_extension(checkout_dir, cx)
+                    })?
+                    .await
+                }
+                None => {
+                    this.update(&mut cx, |this, cx| {
+                        this.install_dev_extension(checkout_dir, cx)
+                    })?
+                    .await
+                }
+            }
+        })
+    }
+
+    fn warm_up_extension(&self, extension_id: &str, cx: &mut ModelContext<Self>) -> Task<()> {
+        let language_names = self
+            .extension_index
+            .languages
+            .iter()
+            .filter(|(_, language)| language.extension.as_ref() == extension_id)
+            .map(|(language_name, _)| language_name.clone())
+            .collect::<Vec<_>>();
+        let language_servers = self
+            .wasm_extensions
+            .iter()
+            .filter(|(manifest, _)| manifest.id.as_ref() == extension_id)
+            .flat_map(|(manifest, wasm_extension)| {
+                manifest.language_servers.iter().map(|(name, config)| {
+                    (
+                        wasm_extension.clone(),
+                        wit::LanguageServerConfig {
+                            name: name.0.to_string(),
+                            language_name: config.language.to_string(),
+                        },
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        let delegate: Arc<dyn LspAdapterDelegate> = Arc::new(PrefetchLspAdapterDelegate {
+            http_client: self.http_client.clone(),
+            language_registry: self.language_registry.clone(),
+            work_dir: self.wasm_host.work_dir.join(extension_id),
+        });
+        let language_registry = self.language_registry.clone();
+        cx.spawn(|_, _| async move {
+            for language_name in language_names {
+                language_registry
+                    .language_for_name(&language_name)
+                    .await
+                    .log_err();
+            }
+            for (wasm_extension, config) in language_servers {
+                let name = config.name.clone();
+                prefetch_language_server(&wasm_extension, config, delegate.clone())
+                    .await
+                    .with_context(|| format!("failed to fetch language server {name}"))
+                    .log_err();
+            }
+        })
+    }
+
+    fn run_lifecycle_hook(
+        &self,
+        extension_id: &str,
+        previous_version: Option<Arc<str>>,
         cx: &mut ModelContext<Self>,
-    ) {
-        self.install_or_upgrade_extension(extension_id, version, ExtensionOperation::Install, cx)
+    ) -> Task<()> {
+        let Some(wasm_extension) = self
+            .wasm_extensions
+            .iter()
+            .find(|(manifest, _)| manifest.id.as_ref() == extension_id)
+            .map(|(_, wasm_extension)| wasm_extension.clone())
+        else {
+            return Task::ready(());
+        };
+        let extension_id = extension_id.to_string();
+        cx.background_executor().spawn(async move {
+            if let Err(error) = wasm_extension.run_lifecycle_hook(previous_version).await {
+                log::error!("lifecycle hook of extension {extension_id} failed: {error:#}");
+            }
+        })
     }
 
-    pub fn upgrade_extension(
+    /// Downloads the themes provided by the given version of an extension and
+    /// adds them to the theme registry, without installing the extension.
+    pub fn load_theme_preview(
         &mut self,
         extension_id: Arc<str>,
         version: Arc<str>,
+        theme_paths: Vec<String>,
         cx: &mut ModelContext<Self>,
-    ) {
-        self.install_or_upgrade_extension(extension_id, version, ExtensionOperation::Upgrade, cx)
+    ) -> Task<Result<Vec<SharedString>>> {
+        let url = self.build_extension_url(
+            &extension_id,
+            &format!("/extensions/{extension_id}/{version}/themes"),
+        );
+        let http_client = self.registry_http_client();
+        let theme_registry = self.theme_registry.clone();
+
+        cx.spawn(move |_, cx| async move {
+            let mut response = http_client
+                .get(&url, AsyncBody::empty(), true)
+                .await
+                .map_err(|err| anyhow!("error fetching extension themes: {}", err))?;
+            let mut body = Vec::new();
+            response
+                .body_mut()
+                .read_to_end(&mut body)
+                .await
+                .context("error reading extension themes")?;
+            if !response.status().is_success() {
+                bail!(
+                    "failed to fetch the themes of extension {extension_id} {version}: {}",
+                    response.status()
+                );
+            }
+
+            #[derive(Deserialize)]
+            struct ThemeFile {
+                path: String,
+                content: String,
+            }
+            #[derive(Deserialize)]
+            struct ThemesResponse {
+                data: Vec<ThemeFile>,
+            }
+            let response: ThemesResponse = serde_json::from_slice(&body)?;
+
+            let theme_paths = theme_paths
+                .iter()
+                .map(|path| path.trim_start_matches("./"))
+                .collect::<HashSet<_>>();
+            let mut theme_families = Vec::new();
+            for theme_file in response.data {
+                if !theme_paths.contains(theme_file.path.trim_start_matches("./")) {
+                    continue;
+                }
+                theme_families.push(
+                    ThemeRegistry::parse_user_theme(theme_file.content.as_bytes()).with_context(
+                        || format!("invalid theme {:?} in {extension_id}", theme_file.path),
+                    )?,
+                );
+            }
+
+            if theme_families.is_empty() {
+                bail!("extension {extension_id} does not provide any themes");
+            }
+
+            cx.update(|_| {
+                let mut theme_names = Vec::new();
+                for theme_family in theme_families {
+                    theme_names.extend(
+                        theme_family
+                            .themes
+                            .iter()
+                            .map(|theme| SharedString::from(theme.name.clone())),
+                    );
+                    theme_registry.insert_user_themes(theme_family.themes);
+                }
+                theme_names
+            })
+        })
     }
 
-    fn install_or_upgrade_extension(
+    pub fn unload_theme_preview(&mut self, theme_names: &[SharedString]) {
+        let themes_to_remove = theme_names
+            .iter()
+            .filter(|name| !self.extension_index.themes.contains_key(name.as_ref()))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.theme_registry.remove_user_themes(&themes_to_remove);
+    }
+
+    pub fn uninstall_extension(&mut self, extension_id: Arc<str>, cx: &mut ModelContext<Self>) {
+        let is_dev = self
+            .extension_index
+            .extensions
+            .get(&extension_id)
+            .map_or(false, |extension| extension.dev);
+        if is_dev {
+            self.uninstall_dev_extension(extension_id, false, cx);
+            return;
+        }
+        if ExtensionSettings::get_global(cx).requires_extension(&extension_id) {
+            log::error!("extension {extension_id} is managed by your organization");
+            return;
+        }
+
+        let dependents = self.dependents(&extension_id);
+        if !dependents.is_empty() {
+            log::warn!(
+                "uninstalling extension {extension_id}, which is required by {}",
+                dependents.join(", ")
+            );
+        }
+
+        // Start removing the extension first, so that unpinning it doesn't
+        // record it in the `synced_extensions` setting again.
+        let remove = self.remove_extension(extension_id.clone(), cx);
+        self.unpin_extension(&extension_id, cx);
+        self.set_extension_signer(&extension_id, None, cx);
+        self.set_extension_source(&extension_id, None, cx);
+        self.discard_pending_change(&extension_id, cx);
+        self.forget_synced_extension(&extension_id, cx);
+        self.forget_extension_activity(&extension_id, cx);
+        self.damaged_extensions.remove(&extension_id);
+        remove.detach_and_log_err(cx)
+    }
+
+    fn remove_extension(
         &mut self,
         extension_id: Arc<str>,
-        version: Arc<str>,
-        operation: ExtensionOperation,
         cx: &mut ModelContext<Self>,
-    ) {
-        log::info!("installing extension {extension_id} {version}");
-        let url = self
-            .http_client
-            .build_zed_api_url(&format!("/extensions/{extension_id}/{version}/download"));
-
+    ) -> Task<Result<()>> {
         let extensions_dir = self.extensions_dir();
-        let http_client = self.http_client.clone();
+        let fs = self.fs.clone();
+
+        if self
+            .extension_index
+            .extensions
+            .get(&extension_id)
+            .map_or(false, |extension| extension.dev)
+        {
+            return Task::ready(Err(anyhow!("extension {extension_id} is a dev extension")));
+        }
+        if ExtensionSettings::get_global(cx).requires_extension(&extension_id) {
+            return Task::ready(Err(anyhow!(
+                "extension {extension_id} is managed by your organization"
+            )));
+        }
 
         match self.outstanding_operations.entry(extension_id.clone()) {
-            hash_map::Entry::Occupied(_) => return,
-            hash_map::Entry::Vacant(e) => e.insert(operation),
+            hash_map::Entry::Occupied(_) => {
+                return Task::ready(Err(anyhow!(
+                    "extension {extension_id} is already being modified"
+                )))
+            }
+            hash_map::Entry::Vacant(e) => e.insert(ExtensionOperation::Remove),
         };
 
         cx.spawn(move |this, mut cx| async move {
@@ -438,31 +5591,49 @@ impl ExtensionStore {
                 let extension_id = extension_id.clone();
                 move || {
                     this.update(&mut cx, |this, cx| {
-                        this.outstanding_operations.remove(extension_id.as_ref());
+                        this.finish_operation(&extension_id);
                         cx.notify();
                     })
                     .ok();
                 }
             });
 
-            let mut response = http_client
-                .get(&url, Default::default(), true)
-                .await
-                .map_err(|err| anyhow!("error downloading extension: {}", err))?;
-            let decompressed_bytes = GzipDecoder::new(BufReader::new(response.body_mut()));
-            let archive = Archive::new(decompressed_bytes);
-            archive
-                .unpack(extensions_dir.join(extension_id.as_ref()))
-                .await?;
-            this.update(&mut cx, |this, cx| this.reload(Some(extension_id), cx))?
-                .await;
+            fs.remove_dir(
+                &extensions_dir.join(extension_id.as_ref()),
+                RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: true,
+                },
+            )
+            .await?;
+
+            this.update(&mut cx, |this, cx| {
+                this.acknowledge_upgrade(&extension_id, cx);
+                this.reload(None, cx)
+            })?
+            .await;
             anyhow::Ok(())
         })
-        .detach_and_log_err(cx);
     }
 
-    pub fn uninstall_extension(&mut self, extension_id: Arc<str>, cx: &mut ModelContext<Self>) {
-        let extensions_dir = self.extensions_dir();
+    /// Uninstalls a dev extension.
+    pub fn uninstall_dev_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        remove_build_artifacts: bool,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let Some(manifest) = self
+            .extension_index
+            .extensions
+            .get(&extension_id)
+            .filter(|extension| extension.dev)
+            .map(|extension| extension.manifest.clone())
+        else {
+            return;
+        };
+
+        let extension_path = self.extensions_dir().join(extension_id.as_ref());
         let fs = self.fs.clone();
 
         match self.outstanding_operations.entry(extension_id.clone()) {
@@ -477,22 +5648,27 @@ impl ExtensionStore {
                 let extension_id = extension_id.clone();
                 move || {
                     this.update(&mut cx, |this, cx| {
-                        this.outstanding_operations.remove(extension_id.as_ref());
+                        this.finish_operation(&extension_id);
                         cx.notify();
                     })
                     .ok();
                 }
             });
 
-            fs.remove_dir(
-                &extensions_dir.join(extension_id.as_ref()),
+            let source_path = fs.read_link(&extension_path).await?;
+            fs.remove_file(
+                &extension_path,
                 RemoveOptions {
-                    recursive: true,
+                    recursive: false,
                     ignore_if_not_exists: true,
                 },
             )
             .await?;
 
+            if remove_build_artifacts {
+                Self::remove_build_artifacts(fs.as_ref(), &source_path, &manifest).await?;
+            }
+
             this.update(&mut cx, |this, cx| this.reload(None, cx))?
                 .await;
             anyhow::Ok(())
@@ -500,6 +5676,37 @@ impl ExtensionStore {
         .detach_and_log_err(cx)
     }
 
+    async fn remove_build_artifacts(
+        fs: &dyn Fs,
+        source_path: &Path,
+        manifest: &ExtensionManifest,
+    ) -> Result<()> {
+        let options = RemoveOptions {
+            recursive: true,
+            ignore_if_not_exists: true,
+        };
+
+        fs.remove_dir(&source_path.join("target"), options).await?;
+        fs.remove_file(&source_path.join("extension.wasm"), options)
+            .await?;
+
+        // Grammars without a repository are checked into the extension's source,
+        // so only the ones that were cloned and compiled during the build are removed.
+        for (grammar_name, grammar) in &manifest.grammars {
+            if grammar.repository.is_empty() {
+                continue;
+            }
+
+            let mut grammar_path = source_path.join("grammars");
+            grammar_path.push(grammar_name.as_ref());
+            fs.remove_dir(&grammar_path, options).await?;
+            grammar_path.set_extension("wasm");
+            fs.remove_file(&grammar_path, options).await?;
+        }
+
+        Ok(())
+    }
+
     pub fn install_dev_extension(
         &mut self,
         extension_source_path: PathBuf,
@@ -513,17 +5720,25 @@ impl ExtensionStore {
             let extension_manifest =
                 Self::load_extension_manifest(fs.clone(), &extension_source_path).await?;
             let extension_id = extension_manifest.id.clone();
+            this.update(&mut cx, |_, cx| {
+                if !ExtensionSettings::get_global(cx).allows_extension(&extension_id) {
+                    bail!("extension {extension_id} is blocked by your organization");
+                }
+                anyhow::Ok(())
+            })??;
 
-            if !this.update(&mut cx, |this, cx| {
+            let Some(cancelled) = this.update(&mut cx, |this, cx| {
                 match this.outstanding_operations.entry(extension_id.clone()) {
-                    hash_map::Entry::Occupied(_) => return false,
+                    hash_map::Entry::Occupied(_) => return None,
                     hash_map::Entry::Vacant(e) => e.insert(ExtensionOperation::Remove),
                 };
+                this.start_watchdog(extension_id.clone(), cx);
                 cx.notify();
-                true
-            })? {
+                Some(this.start_cancellable_build(extension_id.clone()))
+            })?
+            else {
                 return Ok(());
-            }
+            };
 
             let _finish = util::defer({
                 let this = this.clone();
@@ -531,73 +5746,465 @@ impl ExtensionStore {
                 let extension_id = extension_id.clone();
                 move || {
                     this.update(&mut cx, |this, cx| {
-                        this.outstanding_operations.remove(extension_id.as_ref());
+                        this.finish_operation(&extension_id);
                         cx.notify();
                     })
                     .ok();
                 }
             });
 
+            let build_log = this.update(&mut cx, |this, cx| {
+                this.start_build_log(extension_id.clone(), cx)
+            })?;
+            let compile = cx.background_executor().spawn({
+                let extension_source_path = extension_source_path.clone();
+                async move {
+                    builder
+                        .compile_extension(
+                            &extension_source_path,
+                            CompileExtensionOptions {
+                                release: false,
+                                log: Some(build_log),
+                            },
+                        )
+                        .await
+                }
+            });
+            select_biased! {
+                result = compile.fuse() => result?,
+                _ = cancelled.fuse() => bail!("build of extension {extension_id} was cancelled"),
+            }
+
+            let output_path = &extensions_dir.join(extension_id.as_ref());
+            if let Some(metadata) = fs.metadata(&output_path).await? {
+                if metadata.is_symlink {
+                    fs.remove_file(
+                        &output_path,
+                        RemoveOptions {
+                            recursive: false,
+                            ignore_if_not_exists: true,
+                        },
+                    )
+                    .await?;
+                } else {
+                    bail!("extension {extension_id} is already installed");
+                }
+            }
+
+            fs.create_symlink(output_path, extension_source_path)
+                .await?;
+
+            this.update(&mut cx, |this, cx| this.reload(None, cx))?
+                .await;
+            Ok(())
+        })
+    }
+
+    /// Clones the repository at `repository_url`, builds it in the same way as
+    /// a dev extension, and installs the result as a regular extension.
+    pub fn install_from_git(
+        &mut self,
+        repository_url: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let url_hash = format!("{:x}", Sha256::digest(repository_url.as_bytes()));
+        let downloads_dir = self.downloads_dir.clone();
+        let checkout_dir = downloads_dir.join(format!("git-{}", &url_hash[..16]));
+        let builder = self.builder.clone();
+        let fs = self.fs.clone();
+        let remove_options = RemoveOptions {
+            recursive: true,
+            ignore_if_not_exists: true,
+        };
+        if ExtensionSettings::get_global(cx).require_signed_extensions {
+            return Task::ready(Err(anyhow!(
+                "cannot install an extension from {repository_url}: \
+                extensions built from git aren't signed, and signed extensions are required"
+            )));
+        }
+
+        cx.spawn(move |this, mut cx| async move {
+            fs.remove_dir(&checkout_dir, remove_options).await?;
+            let revision = cx
+                .background_executor()
+                .spawn({
+                    let builder = builder.clone();
+                    let checkout_dir = checkout_dir.clone();
+                    let repository_url = repository_url.clone();
+                    async move { builder.checkout_latest(&checkout_dir, &repository_url) }
+                })
+                .await?;
+            let _remove_checkout = util::defer({
+                let fs = fs.clone();
+                let checkout_dir = checkout_dir.clone();
+                let executor = cx.background_executor().clone();
+                move || {
+                    executor
+                        .spawn(async move {
+                            fs.remove_dir(&checkout_dir, remove_options).await.log_err();
+                        })
+                        .detach();
+                }
+            });
+
+            let extension_manifest =
+                Self::load_extension_manifest(fs.clone(), &checkout_dir).await?;
+            let extension_id = extension_manifest.id.clone();
+            let version = extension_manifest.version.clone();
+
+            let operation = this.update(&mut cx, |this, cx| {
+                if this.tracked_extensions.contains_key(&extension_id) {
+                    bail!("extension {extension_id} is tracked from its repository");
+                }
+                if !ExtensionSettings::get_global(cx).allows_extension(&extension_id) {
+                    bail!("extension {extension_id} is blocked by your organization");
+                }
+                anyhow::Ok(
+                    if this.extension_index.extensions.contains_key(&extension_id) {
+                        ExtensionOperation::Upgrade
+                    } else {
+                        ExtensionOperation::Install
+                    },
+                )
+            })??;
+
+            let build_log = this.update(&mut cx, |this, cx| {
+                this.start_build_log(extension_id.clone(), cx)
+            })?;
             cx.background_executor()
                 .spawn({
-                    let extension_source_path = extension_source_path.clone();
+                    let checkout_dir = checkout_dir.clone();
                     async move {
                         builder
                             .compile_extension(
-                                &extension_source_path,
-                                CompileExtensionOptions { release: false },
+                                &checkout_dir,
+                                CompileExtensionOptions {
+                                    release: true,
+                                    log: Some(build_log),
+                                },
                             )
                             .await
                     }
                 })
+                .await
+                .with_context(|| format!("failed to build extension {extension_id}"))?;
+            for build_dir in [".git", "target"] {
+                fs.remove_dir(&checkout_dir.join(build_dir), remove_options)
+                    .await?;
+            }
+
+            this.update(&mut cx, |this, cx| {
+                this.install_package_with_dependencies(
+                    extension_id,
+                    version,
+                    operation,
+                    ExtensionPackage::Unpacked {
+                        dir: checkout_dir.clone(),
+                        signer: None,
+                        source: Some(ExtensionSource::Git {
+                            repository_url,
+                            revision,
+                        }),
+                    },
+                    extension_manifest.dependencies.clone(),
+                    cx,
+                )
+            })?
+            .await
+        })
+    }
+
+    /// Installs an extension from a packaged archive on disk, such as one
+    /// downloaded from the registry.
+    pub fn install_from_archive(
+        &mut self,
+        archive_path: PathBuf,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+
+        cx.spawn(move |this, mut cx| async move {
+            let archive_name = archive_path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .ok_or_else(|| anyhow!("invalid extension archive path"))?;
+            let archive_name = archive_name
+                .strip_suffix(".tar.gz")
+                .or_else(|| archive_name.strip_suffix(".tgz"))
+                .or_else(|| archive_name.strip_suffix(".zip"))
+                .ok_or_else(|| anyhow!("{archive_name} is not a packaged extension"))?
+                .to_string();
+
+            let archive_bytes = cx
+                .background_executor()
+                .spawn({
+                    let archive_path = archive_path.clone();
+                    async move { load_bytes(fs.as_ref(), &archive_path).await }
+                })
+                .await
+                .with_context(|| format!("failed to read {archive_path:?}"))?;
+
+            this.update(&mut cx, |this, cx| {
+                this.install_archive_bytes(archive_name, archive_bytes, None, cx)
+            })?
+            .await
+            .with_context(|| format!("failed to install {archive_path:?}"))?;
+            Ok(())
+        })
+    }
+
+    fn install_archive_bytes(
+        &mut self,
+        archive_name: String,
+        archive_bytes: Vec<u8>,
+        source: Option<ExtensionSource>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<Arc<str>>> {
+        let unpack_dir = self.downloads_dir.join(&archive_name);
+        let fs = self.fs.clone();
+
+        cx.spawn(move |this, mut cx| async move {
+            let remove_options = RemoveOptions {
+                recursive: true,
+                ignore_if_not_exists: true,
+            };
+            fs.remove_dir(&unpack_dir, remove_options).await?;
+            let result = async {
+                let unpacked = if archive_bytes.starts_with(ZIP_SIGNATURE) {
+                    extract_zip(fs.as_ref(), &unpack_dir, &archive_bytes).await
+                } else {
+                    let body = GzipDecoder::new(BufReader::new(archive_bytes.as_slice()));
+                    futures::pin_mut!(body);
+                    fs.extract_tar_file(&unpack_dir, Archive::new(body)).await
+                };
+                unpacked.with_context(|| format!("failed to unpack {archive_name}"))?;
+
+                let extension_manifest =
+                    Self::load_extension_manifest(fs.clone(), &unpack_dir).await?;
+                let extension_id = extension_manifest.id.clone();
+                let version = extension_manifest.version.clone();
+                let signer = this
+                    .update(&mut cx, |this, cx| {
+                        this.verify_unpacked_archive(&extension_id, &version, &archive_bytes, cx)
+                    })?
+                    .await?;
+
+                this.update(&mut cx, |this, cx| {
+                    let operation = if this.extension_index.extensions.contains_key(&extension_id) {
+                        ExtensionOperation::Upgrade
+                    } else {
+                        ExtensionOperation::Install
+                    };
+                    this.install_package_with_dependencies(
+                        extension_id.clone(),
+                        version,
+                        operation,
+                        ExtensionPackage::Unpacked {
+                            dir: unpack_dir.clone(),
+                            signer,
+                            source,
+                        },
+                        extension_manifest.dependencies.clone(),
+                        cx,
+                    )
+                })?
                 .await?;
+                anyhow::Ok(extension_id)
+            }
+            .await;
+            // The unpacked files have been moved into place, unless the
+            // install failed.
+            fs.remove_dir(&unpack_dir, remove_options).await.log_err();
+            result
+        })
+    }
+
+    fn verify_unpacked_archive(
+        &self,
+        extension_id: &Arc<str>,
+        version: &Arc<str>,
+        archive_bytes: &[u8],
+        cx: &ModelContext<Self>,
+    ) -> Task<Result<Option<ExtensionSigner>>> {
+        let settings = ExtensionSettings::get_global(cx);
+        if !settings.require_signed_extensions {
+            return Task::ready(Ok(None));
+        }
+        if let Err(error) =
+            extension_signing::ensure_signing_keys_trusted(&settings.trusted_signing_keys)
+        {
+            return Task::ready(Err(error));
+        }
+
+        let trusted_signing_keys = settings.trusted_signing_keys.clone();
+        let archive_bytes = archive_bytes.to_vec();
+        let remote_extension = self.fetch_remote_extension_version(extension_id, version, cx);
+        let extension_id = extension_id.clone();
+        let version = version.clone();
+        cx.background_executor().spawn(async move {
+            let Some(remote_extension) = remote_extension.await? else {
+                bail!(
+                    "cannot install extension {extension_id} {version}: \
+                    it isn't in the registry, so its signature can't be verified"
+                );
+            };
+            let Some(expected_sha256) = remote_extension.archive_sha256.as_deref() else {
+                bail!(
+                    "cannot install extension {extension_id} {version}: \
+                    the registry has no checksum for it"
+                );
+            };
+            verify_extension_archive(
+                &extension_id,
+                &version,
+                &archive_bytes,
+                Some(expected_sha256),
+                remote_extension.signature.as_ref(),
+                true,
+                &trusted_signing_keys,
+            )
+        })
+    }
+
+    /// Installs the packaged extension attached to the latest release of a
+    /// GitHub repository, given as `owner/name` or as the repository's URL.
+    pub fn install_from_github_release(
+        &mut self,
+        repository: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let Some(repository) = github_repository_name(&repository) else {
+            return Task::ready(Err(anyhow!("{repository} isn't a GitHub repository")));
+        };
+        if ExtensionSettings::get_global(cx).require_signed_extensions {
+            return Task::ready(Err(anyhow!(
+                "extensions from GitHub releases aren't signed, and signed extensions are required"
+            )));
+        }
+        let http_client = self.registry_http_client();
 
-            let output_path = &extensions_dir.join(extension_id.as_ref());
-            if let Some(metadata) = fs.metadata(&output_path).await? {
-                if metadata.is_symlink {
-                    fs.remove_file(
-                        &output_path,
-                        RemoveOptions {
-                            recursive: false,
-                            ignore_if_not_exists: true,
-                        },
+        cx.spawn(move |this, mut cx| async move {
+            let release = latest_github_release(&repository, true, false, http_client.clone())
+                .await
+                .with_context(|| format!("failed to find a release of {repository}"))?;
+            let asset = release
+                .assets
+                .iter()
+                .find(|asset| asset.name.ends_with(".tar.gz") || asset.name.ends_with(".tgz"))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "release {} of {repository} has no packaged extension",
+                        release.tag_name
                     )
-                    .await?;
-                } else {
-                    bail!("extension {extension_id} is already installed");
+                })?;
+            let archive_bytes = download_bytes(http_client.as_ref(), &asset.browser_download_url)
+                .await
+                .with_context(|| format!("failed to download {}", asset.name))?;
+
+            let checksum_name = format!("{}.sha256", asset.name);
+            if let Some(checksum_asset) = release
+                .assets
+                .iter()
+                .find(|asset| asset.name == checksum_name)
+            {
+                let checksum =
+                    download_bytes(http_client.as_ref(), &checksum_asset.browser_download_url)
+                        .await
+                        .with_context(|| format!("failed to download {checksum_name}"))?;
+                let expected_sha256 = String::from_utf8_lossy(&checksum)
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_lowercase();
+                let actual_sha256 = format!("{:x}", Sha256::digest(&archive_bytes));
+                if expected_sha256 != actual_sha256 {
+                    bail!(
+                        "checksum mismatch for {}: expected {expected_sha256}, got {actual_sha256}",
+                        asset.name
+                    );
                 }
             }
 
-            fs.create_symlink(output_path, extension_source_path)
-                .await?;
-
-            this.update(&mut cx, |this, cx| this.reload(None, cx))?
-                .await;
+            let archive_name = asset
+                .name
+                .trim_end_matches(".tar.gz")
+                .trim_end_matches(".tgz")
+                .to_string();
+            let source = ExtensionSource::GithubRelease {
+                repository,
+                tag: release.tag_name,
+            };
+            this.update(&mut cx, |this, cx| {
+                this.install_archive_bytes(archive_name, archive_bytes, Some(source), cx)
+            })?
+            .await?;
             Ok(())
         })
     }
 
-    pub fn rebuild_dev_extension(&mut self, extension_id: Arc<str>, cx: &mut ModelContext<Self>) {
+    pub fn rebuild_dev_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        self.rebuild_dev_extension_with_log(extension_id, None, cx)
+    }
+
+    fn rebuild_dev_extension_with_log(
+        &mut self,
+        extension_id: Arc<str>,
+        mirror_log: Option<BuildLog>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
         let path = self.installed_dir.join(extension_id.as_ref());
         let builder = self.builder.clone();
 
+        if !ExtensionSettings::get_global(cx).allows_extension(&extension_id) {
+            return Task::ready(Err(anyhow!(
+                "extension {extension_id} is blocked by your organization"
+            )));
+        }
         match self.outstanding_operations.entry(extension_id.clone()) {
-            hash_map::Entry::Occupied(_) => return,
+            hash_map::Entry::Occupied(_) => {
+                return Task::ready(Err(anyhow!(
+                    "extension {extension_id} is already being modified"
+                )))
+            }
             hash_map::Entry::Vacant(e) => e.insert(ExtensionOperation::Upgrade),
         };
 
-        cx.notify();
+        let mut build_log = self.start_build_log(extension_id.clone(), cx);
+        if let Some(mirror_log) = mirror_log {
+            build_log = build_log.mirrored_to(mirror_log);
+        }
+        let cancelled = self.start_cancellable_build(extension_id.clone());
+        self.start_watchdog(extension_id.clone(), cx);
         let compile = cx.background_executor().spawn(async move {
             builder
-                .compile_extension(&path, CompileExtensionOptions { release: true })
+                .compile_extension(
+                    &path,
+                    CompileExtensionOptions {
+                        release: true,
+                        log: Some(build_log),
+                    },
+                )
                 .await
         });
 
         cx.spawn(|this, mut cx| async move {
-            let result = compile.await;
+            let result = select_biased! {
+                result = compile.fuse() => result,
+                _ = cancelled.fuse() => Err(anyhow!("build cancelled")),
+            };
 
             this.update(&mut cx, |this, cx| {
-                this.outstanding_operations.remove(&extension_id);
+                this.finish_operation(&extension_id);
+                match &result {
+                    Ok(()) => this.dev_extension_build_errors.remove(&extension_id),
+                    Err(error) => this
+                        .dev_extension_build_errors
+                        .insert(extension_id.clone(), format!("{error:#}")),
+                };
                 cx.notify();
             })?;
 
@@ -606,9 +6213,70 @@ impl ExtensionStore {
                     .await;
             }
 
+            this.update(&mut cx, |this, cx| {
+                this.check_dev_extensions_compatibility(cx)
+            })?
+            .await
+            .log_err();
+
             result
         })
-        .detach_and_log_err(cx)
+    }
+
+    /// Rebuilds every dev extension, one at a time, returning the result of
+    /// each build.
+    pub fn rebuild_dev_extensions(
+        &mut self,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Vec<(Arc<str>, Result<()>)>> {
+        let extension_ids = self
+            .dev_extensions()
+            .map(|manifest| manifest.id.clone())
+            .collect::<Vec<_>>();
+
+        self.is_rebuilding_dev_extensions = true;
+        let text = Arc::new(Mutex::new(String::new()));
+        self.dev_extensions_rebuild_log = Some(text.clone());
+        let rebuild_log = Self::notify_on_build_output(text, cx);
+
+        cx.spawn(|this, mut cx| async move {
+            let mut results = Vec::with_capacity(extension_ids.len());
+            for extension_id in extension_ids {
+                rebuild_log.push_line(&format!("Rebuilding {extension_id}"));
+                let rebuild = this.update(&mut cx, |this, cx| {
+                    this.rebuild_dev_extension_with_log(
+                        extension_id.clone(),
+                        Some(rebuild_log.clone()),
+                        cx,
+                    )
+                });
+                let result = match rebuild {
+                    Ok(rebuild) => rebuild.await,
+                    Err(error) => Err(error),
+                };
+                match &result {
+                    Ok(()) => rebuild_log.push_line(&format!("Rebuilt {extension_id}\n")),
+                    Err(error) => rebuild_log
+                        .push_line(&format!("Failed to rebuild {extension_id}: {error:#}\n")),
+                }
+                results.push((extension_id, result));
+            }
+
+            let failure_count = results.iter().filter(|(_, result)| result.is_err()).count();
+            rebuild_log.push_line(&format!(
+                "Rebuilt {} dev extensions, {failure_count} failed.",
+                results.len() - failure_count
+            ));
+            drop(rebuild_log);
+
+            this.update(&mut cx, |this, cx| {
+                this.is_rebuilding_dev_extensions = false;
+                cx.notify();
+            })
+            .ok();
+
+            results
+        })
     }
 
     /// Updates the set of installed extensions.
@@ -619,11 +6287,22 @@ impl ExtensionStore {
     /// added to the manifest, or whose files have changed on disk.
     fn extensions_updated(
         &mut self,
-        new_index: ExtensionIndex,
+        mut new_index: ExtensionIndex,
         cx: &mut ModelContext<Self>,
     ) -> Task<()> {
         let old_index = &self.extension_index;
 
+        // Damaged extensions stay listed even once their manifest can't be
+        // read, so that they can still be reinstalled or uninstalled.
+        for (extension_id, entry) in &old_index.extensions {
+            if self.damaged_extensions.contains_key(extension_id) {
+                new_index
+                    .extensions
+                    .entry(extension_id.clone())
+                    .or_insert_with(|| entry.clone());
+            }
+        }
+
         // Determine which extensions need to be loaded and unloaded, based
         // on the changes to the manifest and the extensions that we know have been
         // modified.
@@ -663,6 +6342,48 @@ impl ExtensionStore {
             self.modified_extensions.clear();
         }
 
+        // Disabled extensions stay in the index, but their resources are
+        // unloaded until they are enabled again.
+        extensions_to_unload
+            .retain(|extension_id| !self.unloaded_extensions.contains(extension_id));
+        extensions_to_load.retain(|extension_id| {
+            !self.disabled_extensions.contains_key(extension_id)
+                && !self.damaged_extensions.contains_key(extension_id)
+        });
+        for extension_id in self.disabled_extensions.keys() {
+            if old_index.extensions.contains_key(extension_id)
+                && !self.unloaded_extensions.contains(extension_id)
+                && !extensions_to_unload.contains(extension_id)
+            {
+                extensions_to_unload.push(extension_id.clone());
+            }
+        }
+        for extension_id in &self.unloaded_extensions {
+            if !self.disabled_extensions.contains_key(extension_id)
+                && !self.damaged_extensions.contains_key(extension_id)
+                && new_index.extensions.contains_key(extension_id)
+                && !extensions_to_load.contains(extension_id)
+            {
+                extensions_to_load.push(extension_id.clone());
+            }
+        }
+        extensions_to_load.retain(|extension_id| !self.is_suspended(extension_id));
+        // Extensions disabled in settings stay disabled if they are uninstalled,
+        // so that they are still disabled when installed again.
+        self.disabled_extensions.retain(|extension_id, disabled| {
+            disabled.until == DisabledUntil::Reenabled
+                || new_index.extensions.contains_key(extension_id)
+        });
+        self.unloaded_extensions = new_index
+            .extensions
+            .keys()
+            .filter(|extension_id| {
+                self.disabled_extensions.contains_key(*extension_id)
+                    || self.is_suspended(extension_id)
+            })
+            .cloned()
+            .collect();
+
         if extensions_to_load.is_empty() && extensions_to_unload.is_empty() {
             return Task::ready(());
         }
@@ -679,6 +6400,21 @@ impl ExtensionStore {
             extensions_to_unload.len() - reload_count
         );
 
+        for extension_id in &extensions_to_unload {
+            if !extensions_to_load.contains(extension_id) {
+                continue;
+            }
+            let Some(extension) = old_index.extensions.get(extension_id) else {
+                continue;
+            };
+            if !extension.manifest.grammars.is_empty()
+                || !extension.manifest.language_servers.is_empty()
+            {
+                self.extensions_requiring_restart
+                    .insert(extension_id.clone());
+            }
+        }
+
         let themes_to_remove = old_index
             .themes
             .iter()
@@ -715,6 +6451,8 @@ impl ExtensionStore {
 
         self.wasm_extensions
             .retain(|(extension, _)| !extensions_to_unload.contains(&extension.id));
+        self.extension_load_times
+            .retain(|extension_id, _| !extensions_to_unload.contains(extension_id));
         self.theme_registry.remove_user_themes(&themes_to_remove);
         self.language_registry
             .remove_languages(&languages_to_remove, &grammars_to_remove);
@@ -741,7 +6479,7 @@ impl ExtensionStore {
             themes_to_add.extend(extension.manifest.themes.iter().map(|theme_path| {
                 let mut path = self.installed_dir.clone();
                 path.extend([Path::new(extension_id.as_ref()), theme_path.as_path()]);
-                path
+                (extension_id.clone(), path)
             }));
         }
 
@@ -754,12 +6492,15 @@ impl ExtensionStore {
                 Path::new(language.extension.as_ref()),
                 language.path.as_path(),
             ]);
+            let extension_id = language.extension.clone();
+            let activation_tx = self.activation_tx.clone();
             self.language_registry.register_language(
                 language_name.clone(),
                 language.grammar.clone(),
                 language.matcher.clone(),
                 None,
                 move || {
+                    activation_tx.unbounded_send(extension_id.clone()).ok();
                     let config = std::fs::read_to_string(language_path.join("config.toml"))?;
                     let config: LanguageConfig = ::toml::from_str(&config)?;
                     let queries = load_plugin_queries(&language_path);
@@ -778,23 +6519,33 @@ impl ExtensionStore {
             .collect::<Vec<_>>();
 
         self.extension_index = new_index;
+        self.track_installed_extensions(cx);
         cx.notify();
         cx.emit(Event::ExtensionsUpdated);
 
         cx.spawn(|this, mut cx| async move {
-            cx.background_executor()
+            let mut load_times = cx
+                .background_executor()
                 .spawn({
                     let fs = fs.clone();
                     async move {
-                        for theme_path in &themes_to_add {
+                        let mut load_times = HashMap::<Arc<str>, Duration>::default();
+                        for (extension_id, theme_path) in &themes_to_add {
+                            let started_at = Instant::now();
                             theme_registry
                                 .load_user_theme(&theme_path, fs.clone())
                                 .await
                                 .log_err();
+                            *load_times.entry(extension_id.clone()).or_default() +=
+                                started_at.elapsed();
                         }
+                        load_times
                     }
                 })
                 .await;
+            for extension in &extension_entries {
+                load_times.entry(extension.manifest.id.clone()).or_default();
+            }
 
             let mut wasm_extensions = Vec::new();
             for extension in extension_entries {
@@ -802,6 +6553,7 @@ impl ExtensionStore {
                     continue;
                 };
 
+                let started_at = Instant::now();
                 let mut path = root_dir.clone();
                 path.extend([extension.manifest.id.as_ref(), "extension.wasm"]);
                 let Some(mut wasm_file) = fs
@@ -836,11 +6588,14 @@ impl ExtensionStore {
                     continue;
                 };
 
+                *load_times.entry(extension.manifest.id.clone()).or_default() +=
+                    started_at.elapsed();
                 wasm_extensions.push((extension.manifest.clone(), wasm_extension));
             }
 
             this.update(&mut cx, |this, cx| {
                 this.reload_complete_senders.clear();
+                this.extension_load_times.extend(load_times);
 
                 for (manifest, wasm_extension) in &wasm_extensions {
                     for (language_server_name, language_server_config) in &manifest.language_servers
@@ -850,6 +6605,9 @@ impl ExtensionStore {
                             Arc::new(ExtensionLspAdapter {
                                 extension: wasm_extension.clone(),
                                 host: this.wasm_host.clone(),
+                                activation_tx: this.activation_tx.clone(),
+                                crash_tx: this.crash_tx.clone(),
+                                started_at: Default::default(),
                                 config: wit::LanguageServerConfig {
                                     name: language_server_name.0.to_string(),
                                     language_name: language_server_config.language.to_string(),
@@ -859,7 +6617,8 @@ impl ExtensionStore {
                     }
                 }
                 this.wasm_extensions.extend(wasm_extensions);
-                ThemeSettings::reload_current_theme(cx)
+                ThemeSettings::reload_current_theme(cx);
+                this.record_active_theme(cx);
             })
             .ok();
         })
@@ -889,8 +6648,10 @@ impl ExtensionStore {
                 }
             }
 
+            // The index is replaced in a single step, so that an interrupted
+            // write can't leave a truncated index to be loaded on startup.
             if let Ok(index_json) = serde_json::to_string_pretty(&index) {
-                fs.save(&index_path, &index_json.as_str().into(), Default::default())
+                fs.atomic_write(index_path, index_json)
                     .await
                     .context("failed to save extension index")
                     .log_err();
@@ -1033,6 +6794,584 @@ impl ExtensionStore {
     }
 }
 
+async fn directory_size(fs: &dyn Fs, dir: PathBuf) -> Result<u64> {
+    if !fs.is_dir(&dir).await {
+        return Ok(0);
+    }
+
+    let mut size = 0;
+    let mut dirs = vec![dir];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = fs.read_dir(&dir).await?;
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+            let Some(metadata) = fs.metadata(&entry).await? else {
+                continue;
+            };
+            if metadata.is_symlink {
+                continue;
+            }
+            if metadata.is_dir {
+                dirs.push(entry);
+            } else {
+                size += metadata.len;
+            }
+        }
+    }
+    Ok(size)
+}
+
+async fn publish_extension_dir(
+    fs: &dyn Fs,
+    unpacked_dir: &Path,
+    extension_dir: &Path,
+    downloads_dir: &Path,
+) -> Result<()> {
+    let extension_dir_name = extension_dir
+        .file_name()
+        .and_then(OsStr::to_str)
+        .ok_or_else(|| anyhow!("invalid extension directory {extension_dir:?}"))?;
+    let replaced_dir = downloads_dir.join(format!("{extension_dir_name}{REPLACED_DIR_SUFFIX}"));
+    let remove_options = RemoveOptions {
+        recursive: true,
+        ignore_if_not_exists: true,
+    };
+
+    let checksums = extension_checksums(fs, unpacked_dir).await?;
+    fs.atomic_write(
+        unpacked_dir.join(CHECKSUMS_FILE_NAME),
+        serde_json::to_string_pretty(&checksums)?,
+    )
+    .await?;
+
+    let has_installed_version = fs.metadata(extension_dir).await?.is_some();
+    if has_installed_version {
+        fs.remove_dir(&replaced_dir, remove_options).await?;
+        fs.create_dir(downloads_dir).await?;
+        fs.rename(extension_dir, &replaced_dir, Default::default())
+            .await?;
+    }
+    if let Err(error) = fs
+        .rename(unpacked_dir, extension_dir, Default::default())
+        .await
+    {
+        if has_installed_version {
+            fs.rename(&replaced_dir, extension_dir, Default::default())
+                .await
+                .log_err();
+        }
+        return Err(error);
+    }
+    if has_installed_version {
+        fs.remove_dir(&replaced_dir, remove_options).await.log_err();
+    }
+    Ok(())
+}
+
+async fn extension_checksums(
+    fs: &dyn Fs,
+    extension_dir: &Path,
+) -> Result<BTreeMap<String, String>> {
+    let mut checksums = BTreeMap::default();
+    let mut dirs = vec![extension_dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = fs.read_dir(&dir).await?;
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+            let Some(metadata) = fs.metadata(&entry).await? else {
+                continue;
+            };
+            if metadata.is_symlink {
+                continue;
+            }
+            if metadata.is_dir {
+                dirs.push(entry);
+                continue;
+            }
+            let Ok(relative_path) = entry.strip_prefix(extension_dir) else {
+                continue;
+            };
+            if relative_path == Path::new(CHECKSUMS_FILE_NAME) {
+                continue;
+            }
+            checksums.insert(
+                relative_path.to_string_lossy().into_owned(),
+                file_sha256(fs, &entry).await?,
+            );
+        }
+    }
+    Ok(checksums)
+}
+
+async fn file_sha256(fs: &dyn Fs, path: &Path) -> Result<String> {
+    let bytes = load_bytes(fs, path).await?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+async fn load_bytes(fs: &dyn Fs, path: &Path) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    fs.open_sync(path).await?.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn verify_extension_archive(
+    extension_id: &str,
+    version: &str,
+    archive_bytes: &[u8],
+    expected_sha256: Option<&str>,
+    signature: Option<&ExtensionSignature>,
+    require_signed_extensions: bool,
+    trusted_signing_keys: &BTreeMap<String, String>,
+) -> Result<Option<ExtensionSigner>> {
+    let archive_digest = Sha256::digest(archive_bytes);
+    if let Some(expected_sha256) = expected_sha256 {
+        let actual_sha256 = format!("{:x}", archive_digest);
+        if !actual_sha256.eq_ignore_ascii_case(expected_sha256) {
+            bail!(
+                "checksum mismatch for extension {extension_id} {version}: \
+                expected {expected_sha256}, got {actual_sha256}"
+            );
+        }
+    } else {
+        log::warn!("the registry has no checksum for extension {extension_id} {version}");
+    }
+    match signature {
+        Some(signature) => match extension_signing::verify_archive_signature(
+            &archive_digest,
+            signature,
+            trusted_signing_keys,
+        ) {
+            Ok(signer) => Ok(Some(signer)),
+            Err(SignatureError::UntrustedKey(_)) if !require_signed_extensions => {
+                log::warn!(
+                    "extension {extension_id} {version} is signed with untrusted key {}",
+                    signature.key_id
+                );
+                Ok(None)
+            }
+            Err(error) => bail!("cannot install extension {extension_id} {version}: {error}"),
+        },
+        None if require_signed_extensions => {
+            bail!("cannot install extension {extension_id} {version}: it is not signed")
+        }
+        None => Ok(None),
+    }
+}
+
+async fn apply_extension_delta(
+    fs: Arc<dyn Fs>,
+    installed_dir: PathBuf,
+    target_dir: PathBuf,
+    delta_bytes: Vec<u8>,
+    version: Arc<str>,
+) -> Result<()> {
+    let mut entries = HashMap::default();
+    let archive = Archive::new(GzipDecoder::new(BufReader::new(delta_bytes.as_slice())));
+    let mut archive_entries = archive.entries()?;
+    while let Some(entry) = archive_entries.next().await {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content).await?;
+        entries.insert(path.trim_start_matches("./").to_string(), content);
+    }
+    let delta_manifest = entries
+        .remove(DELTA_MANIFEST_FILE)
+        .with_context(|| format!("delta has no {DELTA_MANIFEST_FILE}"))?;
+    let delta_manifest = serde_json::from_slice::<ExtensionDeltaManifest>(&delta_manifest)
+        .with_context(|| format!("invalid {DELTA_MANIFEST_FILE}"))?;
+
+    fs.create_dir(&target_dir).await?;
+    for file in delta_manifest.files {
+        let relative_path = Path::new(&file.path);
+        if !relative_path
+            .components()
+            .all(|component| matches!(component, path::Component::Normal(_)))
+        {
+            bail!("delta contains invalid path {:?}", file.path);
+        }
+        let content = match file.source {
+            ExtensionDeltaSource::Unchanged => {
+                load_bytes(fs.as_ref(), &installed_dir.join(relative_path)).await?
+            }
+            ExtensionDeltaSource::Replaced => entries
+                .remove(&format!("files/{}", file.path))
+                .with_context(|| format!("delta has no content for {:?}", file.path))?,
+            ExtensionDeltaSource::Patched => {
+                let patch = entries
+                    .remove(&format!("patches/{}", file.path))
+                    .with_context(|| format!("delta has no patch for {:?}", file.path))?;
+                let installed = load_bytes(fs.as_ref(), &installed_dir.join(relative_path)).await?;
+                let mut content = Vec::new();
+                bsdiff::patch(&installed, &mut patch.as_slice(), &mut content)
+                    .with_context(|| format!("failed to patch {:?}", file.path))?;
+                content
+            }
+        };
+        let actual_sha256 = format!("{:x}", Sha256::digest(&content));
+        if !actual_sha256.eq_ignore_ascii_case(&file.sha256) {
+            bail!(
+                "checksum mismatch for {:?}: expected {}, got {actual_sha256}",
+                file.path,
+                file.sha256
+            );
+        }
+
+        let path = target_dir.join(relative_path);
+        if let Some(parent) = path.parent() {
+            fs.create_dir(parent).await?;
+        }
+        let body = content.as_slice();
+        futures::pin_mut!(body);
+        fs.create_file_with(&path, body).await?;
+    }
+
+    let manifest = ExtensionStore::load_extension_manifest(fs, &target_dir).await?;
+    if manifest.version != version {
+        bail!(
+            "delta produced version {}, expected {version}",
+            manifest.version
+        );
+    }
+    Ok(())
+}
+
+async fn check_installed_extensions(
+    fs: Arc<dyn Fs>,
+    installed_dir: PathBuf,
+    index: &ExtensionIndex,
+) -> HashMap<Arc<str>, SharedString> {
+    let mut damaged_extensions = HashMap::default();
+    for (extension_id, entry) in &index.extensions {
+        if entry.dev {
+            continue;
+        }
+        let extension_dir = installed_dir.join(extension_id.as_ref());
+        if let Some(problem) = check_extension_integrity(&fs, &extension_dir, &entry.manifest).await
+        {
+            log::error!("extension {extension_id} is damaged: {problem}");
+            damaged_extensions.insert(extension_id.clone(), problem.into());
+        }
+    }
+    damaged_extensions
+}
+
+async fn check_extension_integrity(
+    fs: &Arc<dyn Fs>,
+    extension_dir: &Path,
+    manifest: &ExtensionManifest,
+) -> Option<String> {
+    if !fs.is_dir(extension_dir).await {
+        return None;
+    }
+    if ExtensionStore::load_extension_manifest(fs.clone(), extension_dir)
+        .await
+        .is_err()
+    {
+        return Some("its manifest is missing or invalid".to_string());
+    }
+
+    let mut required_paths = Vec::new();
+    if manifest.lib.kind.is_some() {
+        required_paths.push(PathBuf::from("extension.wasm"));
+    }
+    for grammar_name in manifest.grammars.keys() {
+        required_paths.push(Path::new("grammars").join(format!("{grammar_name}.wasm")));
+    }
+    for language_path in &manifest.languages {
+        required_paths.push(language_path.join("config.toml"));
+    }
+    required_paths.extend(manifest.themes.iter().cloned());
+    for path in required_paths {
+        if !fs.is_file(&extension_dir.join(&path)).await {
+            return Some(format!("{} is missing", path.display()));
+        }
+    }
+
+    let checksums = fs
+        .load(&extension_dir.join(CHECKSUMS_FILE_NAME))
+        .await
+        .ok()?;
+    let Ok(checksums) = serde_json::from_str::<BTreeMap<String, String>>(&checksums) else {
+        return Some(format!("{CHECKSUMS_FILE_NAME} is invalid"));
+    };
+    for (path, expected_sha256) in checksums {
+        let file_path = extension_dir.join(&path);
+        if !fs.is_file(&file_path).await {
+            return Some(format!("{path} is missing"));
+        }
+        match file_sha256(fs.as_ref(), &file_path).await {
+            Ok(actual_sha256) if actual_sha256 == expected_sha256 => {}
+            _ => return Some(format!("{path} has been modified")),
+        }
+    }
+    None
+}
+
+async fn load_registry_cache(fs: &Arc<dyn Fs>, path: &Path) -> Result<RegistryCache> {
+    let cached = fs.load(path).await.context("no cached extension listing")?;
+    Ok(serde_json::from_str(&cached)?)
+}
+
+#[derive(Clone, Debug)]
+struct RegistryBusyError {
+    status: u16,
+    retry_after: Option<Duration>,
+}
+
+impl RegistryBusyError {
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .retry_after
+            .unwrap_or_else(|| REGISTRY_RETRY_DELAY * 2u32.saturating_pow(attempt))
+            .min(REGISTRY_MAX_RETRY_DELAY);
+        let jitter = rand::thread_rng().gen_range(0..=delay.as_millis() as u64 / 2);
+        delay + Duration::from_millis(jitter)
+    }
+}
+
+impl std::fmt::Display for RegistryBusyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the extension registry is busy (status {})", self.status)
+    }
+}
+
+impl std::error::Error for RegistryBusyError {}
+
+async fn fetch_extension_updates(
+    http_client: &Arc<dyn HttpClient>,
+    url: &str,
+    body: String,
+    bandwidth_limit: Option<&BandwidthLimit>,
+    executor: &BackgroundExecutor,
+) -> Result<Option<Vec<ExtensionApiResponse>>> {
+    let mut response = http_client.post_json(url, body.into()).await?;
+
+    let status = response.status();
+    if status.as_u16() == 404 {
+        return Ok(None);
+    }
+    if status.as_u16() == 429 || status.is_server_error() {
+        return Err(RegistryBusyError {
+            status: status.as_u16(),
+            retry_after: None,
+        }
+        .into());
+    }
+
+    let mut body = Vec::new();
+    read_to_end_within(bandwidth_limit, response.body_mut(), &mut body, executor)
+        .await
+        .context("error reading extension updates")?;
+    if status.is_client_error() {
+        let text = String::from_utf8_lossy(body.as_slice());
+        bail!("status error {}, response: {text:?}", status.as_u16());
+    }
+
+    let response: ExtensionsApiResponse = serde_json::from_slice(&body)?;
+    Ok(Some(response.data))
+}
+
+async fn fetch_extension_version_metadata(
+    http_client: &Arc<dyn HttpClient>,
+    url: &str,
+) -> Result<Option<ExtensionApiResponse>> {
+    let mut response = http_client.get(url, AsyncBody::empty(), true).await?;
+
+    let status = response.status();
+    if status.as_u16() == 404 {
+        return Ok(None);
+    }
+
+    let mut body = Vec::new();
+    response
+        .body_mut()
+        .read_to_end(&mut body)
+        .await
+        .context("error reading extension metadata")?;
+    if !status.is_success() {
+        let text = String::from_utf8_lossy(body.as_slice());
+        bail!("status error {}, response: {text:?}", status.as_u16());
+    }
+
+    let response: ExtensionVersionApiResponse = serde_json::from_slice(&body)?;
+    Ok(Some(response.data))
+}
+
+async fn extract_zip(fs: &dyn Fs, destination: &Path, archive_bytes: &[u8]) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(io::Cursor::new(archive_bytes))?;
+    fs.create_dir(destination).await?;
+    for index in 0..archive.len() {
+        let (path, contents) = {
+            let mut file = archive.by_index(index)?;
+            let path = file
+                .enclosed_name()
+                .map(|path| destination.join(path))
+                .ok_or_else(|| anyhow!("invalid path {:?} in zip archive", file.name()))?;
+            if file.is_dir() {
+                (path, None)
+            } else {
+                let mut contents = Vec::new();
+                file.read_to_end(&mut contents)?;
+                (path, Some(contents))
+            }
+        };
+
+        let Some(contents) = contents else {
+            fs.create_dir(&path).await?;
+            continue;
+        };
+        if let Some(parent) = path.parent() {
+            fs.create_dir(parent).await?;
+        }
+        let body = contents.as_slice();
+        futures::pin_mut!(body);
+        fs.create_file_with(&path, body).await?;
+    }
+    Ok(())
+}
+
+enum RegistryListing {
+    Modified {
+        data: Vec<ExtensionApiResponse>,
+        etag: Option<String>,
+        total_count: Option<usize>,
+    },
+    NotModified,
+}
+
+async fn fetch_registry_extensions(
+    http_client: &Arc<dyn HttpClient>,
+    url: &str,
+    etag: Option<&str>,
+    bandwidth_limit: Option<&BandwidthLimit>,
+    executor: &BackgroundExecutor,
+) -> Result<RegistryListing> {
+    let mut request = http::Request::builder()
+        .redirect_policy(http::RedirectPolicy::Follow)
+        .method(http::Method::GET)
+        .uri(url);
+    if let Some(etag) = etag {
+        request = request.header("If-None-Match", etag);
+    }
+    let mut response = http_client.send(request.body(AsyncBody::empty())?).await?;
+
+    let status = response.status();
+    if status.as_u16() == 304 && etag.is_some() {
+        return Ok(RegistryListing::NotModified);
+    }
+    if status.as_u16() == 429 || status.is_server_error() {
+        let retry_after = response
+            .headers()
+            .get("retry-after")
+            .and_then(|value| value.to_str().ok()?.trim().parse().ok())
+            .map(Duration::from_secs);
+        return Err(RegistryBusyError {
+            status: status.as_u16(),
+            retry_after,
+        }
+        .into());
+    }
+
+    let mut body = Vec::new();
+    read_to_end_within(bandwidth_limit, response.body_mut(), &mut body, executor)
+        .await
+        .context("error reading extensions")?;
+
+    if response.status().is_client_error() {
+        let text = String::from_utf8_lossy(body.as_slice());
+        bail!(
+            "status error {}, response: {text:?}",
+            response.status().as_u16()
+        );
+    }
+
+    let etag = response
+        .headers()
+        .get("etag")
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_string);
+    let response: ExtensionsApiResponse = serde_json::from_slice(&body)?;
+    Ok(RegistryListing::Modified {
+        data: response.data,
+        etag,
+        total_count: response.total_count,
+    })
+}
+
+async fn download_bytes(http_client: &dyn HttpClient, url: &str) -> Result<Vec<u8>> {
+    let mut response = http_client.get(url, AsyncBody::empty(), true).await?;
+    if !response.status().is_success() {
+        bail!("failed to download {url}: {}", response.status());
+    }
+    let mut bytes = Vec::new();
+    response.body_mut().read_to_end(&mut bytes).await?;
+    Ok(bytes)
+}
+
+fn github_repository_name(repository: &str) -> Option<String> {
+    let repository = repository.trim();
+    let repository = repository.strip_prefix("https://").unwrap_or(repository);
+    let repository = repository.strip_prefix("github.com/").unwrap_or(repository);
+    let mut segments = repository.split('/');
+    // GitHub account names can't contain dots, so this also rejects the URLs
+    // of other hosts.
+    let owner = segments
+        .next()
+        .filter(|owner| !owner.is_empty() && !owner.contains(['.', ':']))?;
+    let name = segments.next()?;
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    (!name.is_empty()).then(|| format!("{owner}/{name}"))
+}
+
+struct PlannedInstall {
+    version: Arc<str>,
+    operation: ExtensionOperation,
+    dependencies: Vec<Arc<str>>,
+    package: ExtensionPackage,
+}
+
+#[derive(Clone)]
+enum ExtensionPackage {
+    Registry(Option<ExtensionApiResponse>),
+    Unpacked {
+        dir: PathBuf,
+        signer: Option<ExtensionSigner>,
+        source: Option<ExtensionSource>,
+    },
+}
+
+fn install_order(
+    extension_id: &Arc<str>,
+    plan: &HashMap<Arc<str>, PlannedInstall>,
+) -> Vec<Arc<str>> {
+    fn visit(
+        extension_id: &Arc<str>,
+        plan: &HashMap<Arc<str>, PlannedInstall>,
+        visited: &mut HashSet<Arc<str>>,
+        order: &mut Vec<Arc<str>>,
+    ) {
+        let Some(planned) = plan.get(extension_id) else {
+            return;
+        };
+        if !visited.insert(extension_id.clone()) {
+            return;
+        }
+        for dependency_id in &planned.dependencies {
+            visit(dependency_id, plan, visited, order);
+        }
+        order.push(extension_id.clone());
+    }
+
+    let mut order = Vec::new();
+    visit(extension_id, plan, &mut HashSet::default(), &mut order);
+    order
+}
+
+fn version_satisfies(requirement: &VersionReq, version: &str) -> bool {
+    semver::Version::parse(version).map_or(false, |version| requirement.matches(&version))
+}
+
 fn manifest_from_old_manifest(
     manifest_json: OldExtensionManifest,
     extension_id: &str,
@@ -1044,6 +7383,8 @@ fn manifest_from_old_manifest(
         description: manifest_json.description,
         repository: manifest_json.repository,
         authors: manifest_json.authors,
+        license: None,
+        funding: None,
         lib: Default::default(),
         themes: {
             let mut themes = manifest_json.themes.into_values().collect::<Vec<_>>();
@@ -1063,6 +7404,7 @@ fn manifest_from_old_manifest(
             .map(|grammar_name| (grammar_name, Default::default()))
             .collect(),
         language_servers: Default::default(),
+        dependencies: Default::default(),
     }
 }
 
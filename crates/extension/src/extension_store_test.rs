@@ -1,26 +1,42 @@
 use crate::{
-    ExtensionIndex, ExtensionIndexEntry, ExtensionIndexLanguageEntry, ExtensionIndexThemeEntry,
-    ExtensionManifest, ExtensionStore, GrammarManifestEntry, RELOAD_DEBOUNCE_DURATION,
+    wasm_host::WasmHost, DisabledUntil, Event, ExtensionActivity, ExtensionApiResponse,
+    ExtensionBisectStep, ExtensionCategory, ExtensionIndex, ExtensionIndexEntry,
+    ExtensionIndexLanguageEntry, ExtensionIndexThemeEntry, ExtensionList, ExtensionListEntry,
+    ExtensionLogLine, ExtensionLogSource, ExtensionManifest, ExtensionOperation,
+    ExtensionOperationEntry, ExtensionOperationState, ExtensionQuery, ExtensionRegistrySettings,
+    ExtensionReportReason, ExtensionSettings, ExtensionSigner, ExtensionSort, ExtensionSource,
+    ExtensionStatus, ExtensionStore, GrammarManifestEntry, LanguageProviders,
+    OrphanedDirectoryKind, SigningKeySource, SyncedExtension, UpgradedExtension,
+    METADATA_PREFETCH_DELAY, RELOAD_DEBOUNCE_DURATION, REQUIRED_EXTENSIONS_RETRY_DELAY,
+    UNUSED_EXTENSION_THRESHOLD,
 };
 use async_compression::futures::bufread::GzipEncoder;
-use collections::BTreeMap;
+use collections::{BTreeMap, HashMap};
 use fs::{FakeFs, Fs, RealFs};
 use futures::{io::BufReader, AsyncReadExt, StreamExt};
-use gpui::{Context, TestAppContext};
+use gpui::{Context, Model, SharedString, TestAppContext};
 use language::{LanguageMatcher, LanguageRegistry, LanguageServerBinaryStatus, LanguageServerName};
 use node_runtime::FakeNodeRuntime;
 use parking_lot::Mutex;
 use project::Project;
+use rsa::{PaddingScheme, PublicKeyEncoding, RSAPrivateKey, RSAPublicKey};
 use serde_json::json;
-use settings::SettingsStore;
+use settings::{Settings as _, SettingsStore};
+use sha2::{Digest, Sha256};
 use std::{
     ffi::OsString,
+    io::{Read as _, Write as _},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering::SeqCst},
+        Arc,
+    },
+    time::{Duration, Instant, SystemTime},
 };
 use theme::ThemeRegistry;
 use util::{
-    http::{FakeHttpClient, Response},
+    http::{FakeHttpClient, HttpClientWithUrl, Response},
+    paths,
     test::temp_tree,
 };
 
@@ -148,6 +164,8 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         description: None,
                         authors: Vec::new(),
                         repository: None,
+                        license: None,
+                        funding: None,
                         themes: Default::default(),
                         lib: Default::default(),
                         languages: vec!["languages/erb".into(), "languages/ruby".into()],
@@ -158,6 +176,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         .into_iter()
                         .collect(),
                         language_servers: BTreeMap::default(),
+                        dependencies: BTreeMap::default(),
                     }),
                     dev: false,
                 },
@@ -172,6 +191,8 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         description: None,
                         authors: vec![],
                         repository: None,
+                        license: None,
+                        funding: None,
                         themes: vec![
                             "themes/monokai-pro.json".into(),
                             "themes/monokai.json".into(),
@@ -180,6 +201,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         languages: Default::default(),
                         grammars: BTreeMap::default(),
                         language_servers: BTreeMap::default(),
+                        dependencies: BTreeMap::default(),
                     }),
                     dev: false,
                 },
@@ -287,6 +309,33 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 "One Dark",
             ]
         );
+
+        assert_eq!(
+            store.language_providers("ERB"),
+            LanguageProviders {
+                language: Some("zed-ruby".into()),
+                grammar: Some("zed-ruby".into()),
+                language_servers: Vec::new(),
+            }
+        );
+        assert_eq!(
+            store.language_providers("Plain Text"),
+            LanguageProviders::default()
+        );
+
+        // Extensions can be found by the names of what they provide.
+        assert_eq!(
+            store.extensions_with_matching_contributions("monokai pro"),
+            [Arc::<str>::from("zed-monokai")]
+        );
+        assert_eq!(
+            store.extensions_with_matching_contributions("erb"),
+            [Arc::<str>::from("zed-ruby")]
+        );
+        assert_eq!(
+            store.extensions_with_matching_contributions("gruvbox"),
+            Vec::<Arc<str>>::new()
+        );
     });
 
     fs.insert_tree(
@@ -327,11 +376,14 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 description: None,
                 authors: vec![],
                 repository: None,
+                license: None,
+                funding: None,
                 themes: vec!["themes/gruvbox.json".into()],
                 lib: Default::default(),
                 languages: Default::default(),
                 grammars: BTreeMap::default(),
                 language_servers: BTreeMap::default(),
+                dependencies: BTreeMap::default(),
             }),
             dev: false,
         },
@@ -366,6 +418,20 @@ async fn test_extension_store(cx: &mut TestAppContext) {
         );
     });
 
+    store.read_with(cx, |store, _| {
+        assert!(!store.has_extensions_requiring_restart());
+    });
+
+    // Reloading an extension that provides grammars can't replace the grammars
+    // that are already in use.
+    let _ = store.update(cx, |store, cx| store.reload(Some("zed-ruby".into()), cx));
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    store.read_with(cx, |store, _| {
+        assert!(store.extension_requires_restart("zed-ruby"));
+        assert!(!store.extension_requires_restart("zed-monokai"));
+        assert!(!store.extension_requires_restart("zed-gruvbox"));
+    });
+
     let prev_fs_metadata_call_count = fs.metadata_call_count();
     let prev_fs_read_dir_call_count = fs.read_dir_call_count();
 
@@ -387,6 +453,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
     cx.executor().run_until_parked();
     store.read_with(cx, |store, _| {
         assert_eq!(store.extension_index, expected_index);
+        assert!(!store.has_extensions_requiring_restart());
         assert_eq!(
             language_registry.language_names(),
             ["ERB", "Plain Text", "Ruby"]
@@ -604,6 +671,13 @@ async fn test_extension_store_with_gleam_extension(cx: &mut TestAppContext) {
         ]
     );
 
+    // The extension's wasm instance has allocated memory and handled calls.
+    extension_store.read_with(cx, |store, _| {
+        let usage = store.extension_resource_usage("gleam").unwrap();
+        assert!(usage.wasm_memory > 0);
+        assert!(usage.wasm_busy_time > Duration::ZERO);
+    });
+
     // Simulate a new version of the language server being released
     language_server_version.lock().version = "v2.0.0".into();
     language_server_version.lock().binary_contents = "the-new-binary-contents".into();
@@ -650,12 +724,6099 @@ async fn test_extension_store_with_gleam_extension(cx: &mut TestAppContext) {
     assert!(fs.metadata(&expected_server_path).await.unwrap().is_none());
 }
 
-fn init_test(cx: &mut TestAppContext) {
+#[gpui::test]
+async fn test_uninstall_dev_extension(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_200_response();
+
+    fs.insert_tree(
+        "/the-dev-extension",
+        json!({
+            "extension.toml": r#"
+                id = "zed-dev"
+                name = "Zed Dev"
+                version = "0.1.0"
+
+                [grammars.dev]
+                repository = "https://github.com/someone/tree-sitter-dev"
+                rev = "abc123"
+            "#,
+            "src": {
+                "lib.rs": "",
+            },
+            "target": {
+                "wasm32-wasi": {},
+            },
+            "extension.wasm": "",
+            "grammars": {
+                "dev": {
+                    "grammar.js": "",
+                },
+                "dev.wasm": "",
+            },
+        }),
+    )
+    .await;
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+    fs.insert_symlink(
+        "/the-extension-dir/installed/zed-dev",
+        PathBuf::from("/the-dev-extension"),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store
+                .dev_extensions()
+                .map(|extension| extension.id.as_ref())
+                .collect::<Vec<_>>(),
+            ["zed-dev"]
+        );
+    });
+
+    store.update(cx, |store, cx| {
+        store.uninstall_dev_extension("zed-dev".into(), true, cx)
+    });
+
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.dev_extensions().count(), 0);
+    });
+
+    // The symlink is removed, but the extension's source is left in place.
+    assert!(fs
+        .metadata(Path::new("/the-extension-dir/installed/zed-dev"))
+        .await
+        .unwrap()
+        .is_none());
+    assert!(
+        fs.is_file(Path::new("/the-dev-extension/extension.toml"))
+            .await
+    );
+    assert!(fs.is_file(Path::new("/the-dev-extension/src/lib.rs")).await);
+
+    // The build artifacts are removed.
+    assert!(!fs.is_dir(Path::new("/the-dev-extension/target")).await);
+    assert!(
+        !fs.is_file(Path::new("/the-dev-extension/extension.wasm"))
+            .await
+    );
+    assert!(
+        !fs.is_dir(Path::new("/the-dev-extension/grammars/dev"))
+            .await
+    );
+    assert!(
+        !fs.is_file(Path::new("/the-dev-extension/grammars/dev.wasm"))
+            .await
+    );
+}
+
+#[gpui::test]
+async fn test_extension_data(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_200_response();
+
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {},
+            "work": {
+                "zed-ruby": {
+                    "data": {
+                        "config.json": "{}",
+                        "cache": {
+                            "index": "0123456789",
+                        },
+                    },
+                    "solargraph-0.50.0": {
+                        "solargraph": "0123456789",
+                    },
+                },
+                "zed-gleam": {
+                    "data": {
+                        "gleam": "0123456789",
+                    },
+                },
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+
+    store
+        .update(cx, |store, cx| {
+            store.refresh_extension_data_sizes(vec!["zed-ruby".into(), "zed-unknown".into()], cx)
+        })
+        .await
+        .unwrap();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_data_size("zed-ruby"), Some(12));
+        assert_eq!(store.extension_data_size("zed-unknown"), Some(0));
+        assert_eq!(store.extension_data_size("zed-gleam"), None);
+    });
+
+    store
+        .update(cx, |store, cx| store.clear_extension_data("zed-ruby", cx))
+        .await
+        .unwrap();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_data_size("zed-ruby"), Some(0));
+    });
+
+    // The data directory is emptied, but kept in place, and the language
+    // server that the extension downloaded is kept.
+    assert!(
+        fs.is_dir(Path::new("/the-extension-dir/work/zed-ruby/data"))
+            .await
+    );
+    assert_eq!(
+        fs.read_dir(Path::new("/the-extension-dir/work/zed-ruby/data"))
+            .await
+            .unwrap()
+            .count()
+            .await,
+        0
+    );
+    assert!(
+        fs.is_file(Path::new(
+            "/the-extension-dir/work/zed-ruby/solargraph-0.50.0/solargraph"
+        ))
+        .await
+    );
+
+    // Other extensions' data is unaffected.
+    assert!(
+        fs.is_file(Path::new("/the-extension-dir/work/zed-gleam/data/gleam"))
+            .await
+    );
+
+    // Purging an extension's data removes its working directory altogether.
+    store
+        .update(cx, |store, cx| store.purge_extension_data("zed-ruby", cx))
+        .await
+        .unwrap();
+    assert!(
+        !fs.is_dir(Path::new("/the-extension-dir/work/zed-ruby"))
+            .await
+    );
+    assert!(
+        fs.is_file(Path::new("/the-extension-dir/work/zed-gleam/data/gleam"))
+            .await
+    );
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_data_size("zed-ruby"), None);
+    });
+}
+
+#[gpui::test]
+async fn test_disable_extension(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_200_response();
+
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-monokai": {
+                    "extension.json": r#"{
+                        "id": "zed-monokai",
+                        "name": "Zed Monokai",
+                        "version": "2.0.0",
+                        "themes": {
+                            "Monokai Dark": "themes/monokai.json"
+                        }
+                    }"#,
+                    "themes": {
+                        "monokai.json": r#"{
+                            "name": "Monokai",
+                            "author": "Someone",
+                            "themes": [
+                                {
+                                    "name": "Monokai Dark",
+                                    "appearance": "dark",
+                                    "style": {}
+                                }
+                            ]
+                        }"#,
+                    }
+                },
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(theme_registry.list_names(false), ["Monokai Dark"]);
+
+    // An extension disabled until restart stays unloaded until it is enabled.
+    store.update(cx, |store, cx| {
+        store.disable_extension("zed-monokai".into(), DisabledUntil::Restart, cx)
+    });
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(theme_registry.list_names(false), Vec::<SharedString>::new());
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store.extension_disabled_until("zed-monokai"),
+            Some(DisabledUntil::Restart)
+        );
+        assert!(matches!(
+            store.extension_status("zed-monokai"),
+            ExtensionStatus::Installed(_)
+        ));
+    });
+
+    store.update(cx, |store, cx| store.enable_extension("zed-monokai", cx));
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(theme_registry.list_names(false), ["Monokai Dark"]);
+
+    // An extension disabled for some time is enabled again once that time has passed.
+    let disabled_duration = Duration::from_secs(60 * 60);
+    store.update(cx, |store, cx| {
+        store.disable_extension(
+            "zed-monokai".into(),
+            DisabledUntil::Time(Instant::now() + disabled_duration),
+            cx,
+        )
+    });
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(theme_registry.list_names(false), Vec::<SharedString>::new());
+
+    cx.executor().advance_clock(disabled_duration);
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(theme_registry.list_names(false), ["Monokai Dark"]);
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_disabled_until("zed-monokai"), None);
+    });
+
+    // Extensions listed in the `disabled_extensions` setting are disabled
+    // until they are removed from it.
     cx.update(|cx| {
-        let store = SettingsStore::test(cx);
-        cx.set_global(store);
-        theme::init(theme::LoadThemes::JustBase, cx);
-        Project::init_settings(cx);
-        language::init(cx);
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.disabled_extensions = Some(vec!["zed-monokai".to_string()]);
+            });
+        });
     });
+    store.update(cx, |store, cx| store.sync_disabled_extensions(cx));
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(theme_registry.list_names(false), Vec::<SharedString>::new());
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store.extension_disabled_until("zed-monokai"),
+            Some(DisabledUntil::Reenabled)
+        );
+    });
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.disabled_extensions = Some(Vec::new());
+            });
+        });
+    });
+    store.update(cx, |store, cx| store.sync_disabled_extensions(cx));
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(theme_registry.list_names(false), ["Monokai Dark"]);
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_disabled_until("zed-monokai"), None);
+    });
+}
+
+#[gpui::test]
+async fn test_retry_failed_install(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let download_count = Arc::new(AtomicUsize::new(0));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let download_count = download_count.clone();
+        move |request| {
+            let download_count = download_count.clone();
+            async move {
+                if request.uri().path() == "/extensions/zed-ruby/0.0.2/download" {
+                    download_count.fetch_add(1, SeqCst);
+                    Ok(Response::new("not an archive".into()))
+                } else {
+                    Ok(Response::builder().status(404).body("not found".into())?)
+                }
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+
+    let failed_extension_ids = Arc::new(Mutex::new(Vec::new()));
+    cx.update(|cx| {
+        let failed_extension_ids = failed_extension_ids.clone();
+        cx.subscribe(&store, move |_, event, _| {
+            if let Event::ExtensionOperationFailed { extension_id, .. } = event {
+                failed_extension_ids.lock().push(extension_id.clone());
+            }
+        })
+        .detach();
+    });
+
+    store.update(cx, |store, cx| {
+        store.install_extension("zed-ruby".into(), "0.0.2".into(), cx);
+        assert_eq!(
+            store.operations(),
+            [ExtensionOperationEntry {
+                extension_id: "zed-ruby".into(),
+                operation: ExtensionOperation::Install,
+                state: ExtensionOperationState::Pending,
+            }]
+        );
+    });
+
+    cx.executor().run_until_parked();
+    assert_eq!(download_count.load(SeqCst), 1);
+    assert_eq!(
+        failed_extension_ids.lock().as_slice(),
+        [Arc::from("zed-ruby")]
+    );
+    store.read_with(cx, |store, _| {
+        let operations = store.operations();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].extension_id.as_ref(), "zed-ruby");
+        assert_eq!(operations[0].operation, ExtensionOperation::Install);
+        assert!(matches!(
+            operations[0].state,
+            ExtensionOperationState::Failed(_)
+        ));
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::NotInstalled
+        ));
+    });
+
+    // Retrying downloads the same version again.
+    store.update(cx, |store, cx| store.retry_operation("zed-ruby".into(), cx));
+    cx.executor().run_until_parked();
+    assert_eq!(download_count.load(SeqCst), 2);
+
+    store.update(cx, |store, cx| {
+        assert_eq!(store.operations().len(), 1);
+        store.dismiss_failed_operation("zed-ruby", cx);
+        assert_eq!(store.operations(), []);
+    });
+}
+
+#[gpui::test]
+async fn test_batch_operations(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        match request.uri().path() {
+            "/extensions/zed-ruby/0.0.2/download" => {
+                let manifest = "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.2\"\n";
+                let mut bytes = Vec::<u8>::new();
+                let mut archive = async_tar::Builder::new(&mut bytes);
+                let mut header = async_tar::Header::new_gnu();
+                header.set_size(manifest.len() as u64);
+                archive
+                    .append_data(&mut header, "extension.toml", manifest.as_bytes())
+                    .await
+                    .unwrap();
+                archive.into_inner().await.unwrap();
+                let mut gzipped_bytes = Vec::new();
+                let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+                encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+                Ok(Response::new(gzipped_bytes.into()))
+            }
+            "/extensions/zed-monokai/2.0.0/download" => Ok(Response::new("not an archive".into())),
+            _ => Ok(Response::builder().status(404).body("not found".into())?),
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+
+    // Each extension in the batch has its own outcome.
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![
+                ("zed-ruby".into(), "0.0.2".into()),
+                ("zed-monokai".into(), "2.0.0".into()),
+            ],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    let outcomes = batch.await;
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!(outcomes[0].0.as_ref(), "zed-ruby");
+    assert!(outcomes[0].1.is_ok());
+    assert_eq!(outcomes[1].0.as_ref(), "zed-monokai");
+    assert!(outcomes[1].1.is_err());
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(_)
+        ));
+        assert!(matches!(
+            store.extension_status("zed-monokai"),
+            ExtensionStatus::NotInstalled
+        ));
+    });
+
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Remove,
+            vec![("zed-ruby".into(), "0.0.2".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    let outcomes = batch.await;
+    assert!(outcomes[0].1.is_ok());
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::NotInstalled
+        ));
+    });
+}
+
+#[gpui::test]
+async fn test_recently_upgraded_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        let version = match request.uri().path() {
+            "/extensions/zed-ruby/0.0.1/download" => "0.0.1",
+            "/extensions/zed-ruby/0.0.2/download" => "0.0.2",
+            _ => return Ok(Response::builder().status(404).body("not found".into())?),
+        };
+        let manifest = format!("id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"{version}\"\n");
+        let mut bytes = Vec::<u8>::new();
+        let mut archive = async_tar::Builder::new(&mut bytes);
+        let mut header = async_tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        archive
+            .append_data(&mut header, "extension.toml", manifest.as_bytes())
+            .await
+            .unwrap();
+        archive.into_inner().await.unwrap();
+        let mut gzipped_bytes = Vec::new();
+        let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+        encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+        Ok(Response::new(gzipped_bytes.into()))
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let new_store = |cx: &mut TestAppContext| {
+        cx.new_model(|cx| {
+            ExtensionStore::new(
+                PathBuf::from("/the-extension-dir"),
+                None,
+                fs.clone(),
+                http_client.clone(),
+                node_runtime.clone(),
+                language_registry.clone(),
+                theme_registry.clone(),
+                cx,
+            )
+        })
+    };
+    let store = new_store(cx);
+
+    // Installing an extension doesn't report it as upgraded.
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![("zed-ruby".into(), "0.0.1".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert!(batch.await[0].1.is_ok());
+    store.read_with(cx, |store, _| {
+        assert!(!store.is_recently_upgraded("zed-ruby"));
+    });
+
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Upgrade,
+            vec![("zed-ruby".into(), "0.0.2".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert!(batch.await[0].1.is_ok());
+    cx.executor().run_until_parked();
+
+    let expected_upgrade = UpgradedExtension {
+        previous_version: Some("0.0.1".into()),
+        version: "0.0.2".into(),
+    };
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store
+                .recently_upgraded_extensions()
+                .map(|(id, upgrade)| (id.clone(), upgrade.clone()))
+                .collect::<Vec<_>>(),
+            vec![("zed-ruby".into(), expected_upgrade.clone())]
+        );
+    });
+
+    // Upgrades remain unacknowledged across restarts.
+    let store = new_store(cx);
+    store.read_with(cx, |store, _| {
+        assert!(store.is_recently_upgraded("zed-ruby"));
+    });
+
+    store.update(cx, |store, cx| store.acknowledge_upgrade("zed-ruby", cx));
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert!(!store.is_recently_upgraded("zed-ruby"));
+    });
+    let store = new_store(cx);
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.recently_upgraded_extensions().count(), 0);
+    });
+}
+
+#[gpui::test]
+async fn test_slow_operation_watchdog(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    // The registry never responds to the download request.
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|_| futures::future::pending());
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+    let timeout = cx.update(|cx| ExtensionSettings::get_global(cx).slow_operation_timeout());
+
+    store.update(cx, |store, cx| {
+        store.install_extension("zed-ruby".into(), "0.0.2".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| assert!(!store.is_operation_slow("zed-ruby")));
+
+    cx.executor().advance_clock(timeout);
+    store.read_with(cx, |store, _| assert!(store.is_operation_slow("zed-ruby")));
+
+    // Continuing to wait hides the warning until another timeout has elapsed.
+    store.update(cx, |store, cx| store.keep_waiting("zed-ruby".into(), cx));
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| assert!(!store.is_operation_slow("zed-ruby")));
+    cx.executor().advance_clock(timeout);
+    store.read_with(cx, |store, _| assert!(store.is_operation_slow("zed-ruby")));
+
+    // Cancelling stops waiting for the registry, without recording a failure.
+    store.update(cx, |store, _| store.cancel_operation("zed-ruby"));
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert!(!store.is_operation_slow("zed-ruby"));
+        assert_eq!(store.operations(), []);
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::NotInstalled
+        ));
+    });
+}
+
+#[gpui::test]
+async fn test_extension_state_is_shared_between_views(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        if request.uri().path() == "/extensions" {
+            Ok(Response::new(
+                json!({
+                    "data": [{
+                        "id": "zed-ruby",
+                        "name": "Ruby",
+                        "version": "0.0.2",
+                        "description": "Ruby support.",
+                        "authors": ["Zed"],
+                        "repository": "https://github.com/zed-industries/zed",
+                        "license": null,
+                        "download_count": 5
+                    }]
+                })
+                .to_string()
+                .into(),
+            ))
+        } else {
+            Ok(Response::builder().status(404).body("".into()).unwrap())
+        }
+    });
+
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {},
+            "work": {
+                "zed-ruby": {
+                    "data": {
+                        "config.json": "{}",
+                    },
+                },
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+
+    cx.executor().run_until_parked();
+
+    // Two views that display the store's state, as the extensions pages in
+    // two different windows would, reading it again whenever it changes.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct PageState {
+        ruby_version: Option<Arc<str>>,
+        ruby_data_size: Option<u64>,
+        is_rebuilding_dev_extensions: bool,
+    }
+
+    impl PageState {
+        fn read(store: &ExtensionStore) -> Self {
+            Self {
+                ruby_version: store
+                    .remote_extension("zed-ruby")
+                    .map(|extension| extension.version.clone()),
+                ruby_data_size: store.extension_data_size("zed-ruby"),
+                is_rebuilding_dev_extensions: store.is_rebuilding_dev_extensions(),
+            }
+        }
+    }
+
+    struct ExtensionsView {
+        state: PageState,
+    }
+
+    let views = [(); 2].map(|_| {
+        cx.new_model(|cx| {
+            cx.observe(&store, |view: &mut ExtensionsView, store, cx| {
+                view.state = PageState::read(store.read(cx));
+            })
+            .detach();
+            ExtensionsView {
+                state: PageState::read(store.read(cx)),
+            }
+        })
+    });
+
+    let page_states = |cx: &mut TestAppContext| {
+        views
+            .iter()
+            .map(|view| view.read_with(cx, |view, _| view.state.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        page_states(cx),
+        [PageState::default(), PageState::default()]
+    );
+
+    // Extensions fetched by one view are shown by the other.
+    store
+        .update(cx, |store, cx| store.fetch_extensions(None, cx))
+        .await
+        .unwrap();
+    let mut expected_state = PageState {
+        ruby_version: Some("0.0.2".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        page_states(cx),
+        [expected_state.clone(), expected_state.clone()]
+    );
+
+    // Data sizes computed for one view are shown by the other, and clearing
+    // an extension's data updates both.
+    store
+        .update(cx, |store, cx| {
+            store.refresh_extension_data_sizes(vec!["zed-ruby".into()], cx)
+        })
+        .await
+        .unwrap();
+    expected_state.ruby_data_size = Some(2);
+    assert_eq!(
+        page_states(cx),
+        [expected_state.clone(), expected_state.clone()]
+    );
+
+    store
+        .update(cx, |store, cx| store.clear_extension_data("zed-ruby", cx))
+        .await
+        .unwrap();
+    expected_state.ruby_data_size = Some(0);
+    assert_eq!(
+        page_states(cx),
+        [expected_state.clone(), expected_state.clone()]
+    );
+
+    // Both views show that dev extensions are being rebuilt, until the
+    // rebuild finishes.
+    let rebuild = store.update(cx, |store, cx| store.rebuild_dev_extensions(cx));
+    expected_state.is_rebuilding_dev_extensions = true;
+    assert_eq!(
+        page_states(cx),
+        [expected_state.clone(), expected_state.clone()]
+    );
+    rebuild.await;
+    cx.executor().run_until_parked();
+    expected_state.is_rebuilding_dev_extensions = false;
+    assert_eq!(page_states(cx), [expected_state.clone(), expected_state]);
+}
+
+#[gpui::test]
+async fn test_report_extension(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let reports = reports.clone();
+        move |mut request| {
+            let reports = reports.clone();
+            async move {
+                if request.uri().path() == "/extensions/zed-ruby/reports" {
+                    let mut body = String::new();
+                    request.body_mut().read_to_string(&mut body).await?;
+                    reports.lock().push(body);
+                    Ok(Response::new("".into()))
+                } else {
+                    Ok(Response::builder().status(404).body("not found".into())?)
+                }
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+
+    store
+        .update(cx, |store, cx| {
+            store.report_extension(
+                "zed-ruby",
+                ExtensionReportReason::Impersonation,
+                "Copies the official extension's name.".to_string(),
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        reports
+            .lock()
+            .iter()
+            .map(|report| serde_json::from_str::<serde_json::Value>(report).unwrap())
+            .collect::<Vec<_>>(),
+        [json!({
+            "reason": "impersonation",
+            "details": "Copies the official extension's name.",
+        })]
+    );
+
+    // Reports that the registry rejects are errors.
+    assert!(store
+        .update(cx, |store, cx| {
+            store.report_extension(
+                "zed-unknown",
+                ExtensionReportReason::Broken,
+                String::new(),
+                cx,
+            )
+        })
+        .await
+        .is_err());
+}
+
+#[gpui::test]
+async fn test_cached_extension_listing(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    // Always query the registry, so that it being unreachable is noticed.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.registry_cache_ttl_secs = Some(0);
+            });
+        });
+    });
+
+    let is_online = Arc::new(AtomicBool::new(true));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let is_online = is_online.clone();
+        move |request| {
+            let is_online = is_online.load(SeqCst);
+            async move {
+                if !is_online {
+                    return Ok(Response::builder()
+                        .status(503)
+                        .body("service unavailable".into())?);
+                }
+                if request.uri().path() == "/extensions" {
+                    Ok(Response::new(
+                        json!({
+                            "data": [
+                                {
+                                    "id": "zed-ruby",
+                                    "name": "Ruby",
+                                    "version": "0.0.2",
+                                    "description": "Ruby support.",
+                                    "authors": [],
+                                    "repository": "https://github.com/zed-industries/zed",
+                                    "download_count": 1,
+                                },
+                                {
+                                    "id": "zed-monokai",
+                                    "name": "Monokai",
+                                    "version": "2.0.0",
+                                    "description": null,
+                                    "authors": [],
+                                    "repository": "https://github.com/zed-industries/zed",
+                                    "download_count": 1,
+                                },
+                            ]
+                        })
+                        .to_string()
+                        .into(),
+                    ))
+                } else {
+                    Ok(Response::builder().status(404).body("not found".into())?)
+                }
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime.clone(),
+            language_registry.clone(),
+            theme_registry.clone(),
+            cx,
+        )
+    });
+
+    // Nothing is cached until the registry has been listed.
+    assert!(store
+        .update(cx, |store, cx| store.cached_extensions(None, cx))
+        .await
+        .is_err());
+
+    store
+        .update(cx, |store, cx| store.fetch_extensions(None, cx))
+        .await
+        .unwrap();
+
+    // Once the registry is unreachable, the cached listing is used instead.
+    is_online.store(false, SeqCst);
+    assert!(store
+        .update(cx, |store, cx| store.fetch_extensions(None, cx))
+        .await
+        .is_err());
+
+    let cached_ids = |extensions: Vec<ExtensionApiResponse>| {
+        extensions
+            .into_iter()
+            .map(|extension| extension.id)
+            .collect::<Vec<_>>()
+    };
+    let cached = store
+        .update(cx, |store, cx| store.cached_extensions(None, cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        cached_ids(cached),
+        [Arc::from("zed-ruby"), Arc::from("zed-monokai")]
+    );
+
+    let cached = store
+        .update(cx, |store, cx| store.cached_extensions(Some("MONO"), cx))
+        .await
+        .unwrap();
+    assert_eq!(cached_ids(cached), [Arc::from("zed-monokai")]);
+}
+
+#[gpui::test]
+async fn test_auto_update_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        let version = match request.uri().path() {
+            "/extensions" => {
+                return Ok(Response::new(
+                    json!({
+                        "data": [{
+                            "id": "zed-ruby",
+                            "name": "Ruby",
+                            "version": "0.0.2",
+                            "description": null,
+                            "authors": [],
+                            "repository": "https://github.com/zed-industries/zed",
+                            "download_count": 1,
+                        }]
+                    })
+                    .to_string()
+                    .into(),
+                ))
+            }
+            "/extensions/zed-ruby/0.0.1/download" => "0.0.1",
+            "/extensions/zed-ruby/0.0.2/download" => "0.0.2",
+            _ => return Ok(Response::builder().status(404).body("not found".into())?),
+        };
+        let manifest = format!("id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"{version}\"\n");
+        let mut bytes = Vec::<u8>::new();
+        let mut archive = async_tar::Builder::new(&mut bytes);
+        let mut header = async_tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        archive
+            .append_data(&mut header, "extension.toml", manifest.as_bytes())
+            .await
+            .unwrap();
+        archive.into_inner().await.unwrap();
+        let mut gzipped_bytes = Vec::new();
+        let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+        encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+        Ok(Response::new(gzipped_bytes.into()))
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let new_store = |cx: &mut TestAppContext| {
+        cx.new_model(|cx| {
+            ExtensionStore::new(
+                PathBuf::from("/the-extension-dir"),
+                None,
+                fs.clone(),
+                http_client.clone(),
+                node_runtime.clone(),
+                language_registry.clone(),
+                theme_registry.clone(),
+                cx,
+            )
+        })
+    };
+    let store = new_store(cx);
+
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![("zed-ruby".into(), "0.0.1".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert!(batch.await[0].1.is_ok());
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    cx.update(|cx| {
+        let events = events.clone();
+        cx.subscribe(&store, move |_, event, _| {
+            if let Event::ExtensionsAutoUpdated { upgraded, failed } = event {
+                events.lock().push((upgraded.clone(), failed.clone()));
+            }
+        })
+        .detach();
+    });
+
+    // Extensions whose override turns automatic updates off are left alone.
+    let set_auto_update_settings =
+        |enabled: bool, overrides: &[(&str, bool)], cx: &mut TestAppContext| {
+            let overrides = overrides
+                .iter()
+                .map(|(extension_id, enabled)| (extension_id.to_string(), *enabled))
+                .collect();
+            cx.update(|cx| {
+                cx.update_global::<SettingsStore, _>(|settings, cx| {
+                    settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                        settings.auto_update_extensions = Some(enabled);
+                        settings.auto_update_overrides = Some(overrides);
+                    });
+                });
+            });
+        };
+    set_auto_update_settings(true, &[("zed-ruby", false)], cx);
+    let update = store.update(cx, |store, cx| store.auto_update_extensions(cx));
+    cx.executor().run_until_parked();
+    update.await.unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+    });
+    assert!(events.lock().is_empty());
+
+    // An override can also turn automatic updates on for one extension.
+    set_auto_update_settings(false, &[("zed-ruby", true)], cx);
+    let update = store.update(cx, |store, cx| store.auto_update_extensions(cx));
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    update.await.unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.2"
+        ));
+        assert!(!store.is_auto_updating("zed-ruby"));
+    });
+    assert_eq!(
+        events.lock().drain(..).collect::<Vec<_>>(),
+        vec![(vec![("zed-ruby".into(), "0.0.2".into())], Vec::new())]
+    );
+
+    // Nothing is reported once every extension is up to date.
+    let update = store.update(cx, |store, cx| store.auto_update_extensions(cx));
+    cx.executor().run_until_parked();
+    update.await.unwrap();
+    assert!(events.lock().is_empty());
+}
+
+#[gpui::test]
+async fn test_pinned_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        let version = match request.uri().path() {
+            "/extensions" => {
+                return Ok(Response::new(
+                    json!({
+                        "data": [{
+                            "id": "zed-ruby",
+                            "name": "Ruby",
+                            "version": "0.0.2",
+                            "description": null,
+                            "authors": [],
+                            "repository": "https://github.com/zed-industries/zed",
+                            "download_count": 1,
+                        }]
+                    })
+                    .to_string()
+                    .into(),
+                ))
+            }
+            "/extensions/zed-ruby/0.0.1/download" => "0.0.1",
+            "/extensions/zed-ruby/0.0.2/download" => "0.0.2",
+            _ => return Ok(Response::builder().status(404).body("not found".into())?),
+        };
+        let manifest = format!("id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"{version}\"\n");
+        Ok(Response::new(
+            build_archive(&[("extension.toml", manifest.as_str())])
+                .await
+                .into(),
+        ))
+    });
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.auto_update_extensions = Some(true);
+            });
+        });
+    });
+
+    let store = new_test_store("/the-extension-dir", fs.clone(), http_client.clone(), cx);
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![("zed-ruby".into(), "0.0.1".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert!(batch.await[0].1.is_ok());
+
+    let pinned_version = |store: &Model<ExtensionStore>, cx: &mut TestAppContext| {
+        store.read_with(cx, |store, _| store.pinned_version("zed-ruby").cloned())
+    };
+    let installed_version = |store: &Model<ExtensionStore>, cx: &mut TestAppContext| {
+        store.read_with(cx, |store, _| match store.extension_status("zed-ruby") {
+            ExtensionStatus::Installed(version) => Some(version),
+            _ => None,
+        })
+    };
+
+    // Only installed extensions can be pinned, to their installed version.
+    store.update(cx, |store, cx| {
+        store.pin_extension("zed-python", cx);
+        store.pin_extension("zed-ruby", cx);
+    });
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.pinned_version("zed-python"), None);
+    });
+    assert_eq!(pinned_version(&store, cx), Some("0.0.1".into()));
+
+    // Pins are persisted, so they're kept across restarts.
+    let pinned_extensions = fs
+        .load(Path::new("/the-extension-dir/pinned_extensions.json"))
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<BTreeMap<String, String>>(&pinned_extensions).unwrap(),
+        [("zed-ruby".to_string(), "0.0.1".to_string())]
+            .into_iter()
+            .collect()
+    );
+    let restarted_store = new_test_store("/the-extension-dir", fs.clone(), http_client.clone(), cx);
+    assert_eq!(pinned_version(&restarted_store, cx), Some("0.0.1".into()));
+    drop(restarted_store);
+
+    // Pinned extensions are skipped by automatic updates.
+    let update = store.update(cx, |store, cx| store.auto_update_extensions(cx));
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    update.await.unwrap();
+    assert_eq!(installed_version(&store, cx), Some("0.0.1".into()));
+    assert_eq!(pinned_version(&store, cx), Some("0.0.1".into()));
+
+    // Once unpinned, the extension is upgraded again, and the removal of its
+    // pin is persisted too.
+    store.update(cx, |store, cx| store.unpin_extension("zed-ruby", cx));
+    cx.executor().run_until_parked();
+    assert_eq!(pinned_version(&store, cx), None);
+    let restarted_store = new_test_store("/the-extension-dir", fs.clone(), http_client.clone(), cx);
+    assert_eq!(pinned_version(&restarted_store, cx), None);
+    drop(restarted_store);
+
+    let update = store.update(cx, |store, cx| store.auto_update_extensions(cx));
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    update.await.unwrap();
+    assert_eq!(installed_version(&store, cx), Some("0.0.2".into()));
+
+    // Uninstalling an extension removes its pin.
+    store.update(cx, |store, cx| store.pin_extension("zed-ruby", cx));
+    assert_eq!(pinned_version(&store, cx), Some("0.0.2".into()));
+    store.update(cx, |store, cx| {
+        store.uninstall_extension("zed-ruby".into(), cx)
+    });
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    assert_eq!(pinned_version(&store, cx), None);
+    assert_eq!(installed_version(&store, cx), None);
+}
+
+#[gpui::test]
+async fn test_check_for_updates(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let requests = requests.clone();
+        move |request| {
+            let requests = requests.clone();
+            async move {
+                let request_host = request.uri().host().map(str::to_string);
+                let path = request.uri().path().to_string();
+                let mut body = String::new();
+                request.into_body().read_to_string(&mut body).await?;
+                requests.lock().push((path.clone(), body));
+                let extensions = match (request_host.as_deref(), path.as_str()) {
+                    (Some("internal.example.com"), "/extensions/updates") => {
+                        vec![("zed-monokai", "2.1.0")]
+                    }
+                    // The public registry doesn't support update checks, so
+                    // its listing is fetched instead.
+                    (Some("public.example.com"), "/extensions") => {
+                        vec![("zed-ruby", "0.0.3"), ("zed-python", "1.0.0")]
+                    }
+                    (Some("internal.example.com" | "public.example.com"), _) => {
+                        return Ok(Response::builder().status(404).body("not found".into())?)
+                    }
+                    (_, "/extensions/updates") => vec![("zed-ruby", "0.0.2")],
+                    _ => return Ok(Response::builder().status(404).body("not found".into())?),
+                };
+                Ok(Response::new(
+                    json!({
+                        "data": extensions
+                            .into_iter()
+                            .map(|(extension_id, version)| {
+                                json!({
+                                    "id": extension_id,
+                                    "name": extension_id,
+                                    "version": version,
+                                    "description": null,
+                                    "authors": [],
+                                    "repository": "https://github.com/zed-industries/zed",
+                                    "download_count": 1,
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .to_string()
+                    .into(),
+                ))
+            }
+        }
+    });
+
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-ruby": {
+                    "extension.toml": "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.1\"\n",
+                },
+                "zed-monokai": {
+                    "extension.toml": "id = \"zed-monokai\"\nname = \"Monokai\"\nversion = \"2.0.0\"\n",
+                },
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+
+    // Every installed extension is checked in a single request, without
+    // fetching the registry's listing.
+    store
+        .update(cx, |store, cx| store.check_for_updates(cx))
+        .await
+        .unwrap();
+    let sent_requests = requests.lock().drain(..).collect::<Vec<_>>();
+    assert_eq!(sent_requests.len(), 1);
+    assert_eq!(sent_requests[0].0, "/extensions/updates");
+    let body: serde_json::Value = serde_json::from_str(&sent_requests[0].1).unwrap();
+    let mut checked = body["extensions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|extension| {
+            (
+                extension["id"].as_str().unwrap().to_string(),
+                extension["version"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    checked.sort();
+    assert_eq!(
+        checked,
+        [
+            ("zed-monokai".to_string(), "2.0.0".to_string()),
+            ("zed-ruby".to_string(), "0.0.1".to_string()),
+        ]
+    );
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store
+                .remote_extension("zed-ruby")
+                .map(|extension| extension.version.as_ref()),
+            Some("0.0.2")
+        );
+        assert!(store.remote_extension("zed-monokai").is_none());
+    });
+
+    // A registry that doesn't support update checks falls back to its
+    // listing, without discarding the updates from the other registries.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.registries = Some(
+                    [
+                        ("Internal", "https://internal.example.com"),
+                        ("Public", "https://public.example.com"),
+                    ]
+                    .into_iter()
+                    .map(|(name, url)| ExtensionRegistrySettings {
+                        name: name.to_string(),
+                        url: url.to_string(),
+                        enabled: true,
+                    })
+                    .collect(),
+                );
+            });
+        });
+    });
+    store.update(cx, |store, cx| store.sync_registries(cx));
+    store
+        .update(cx, |store, cx| store.check_for_updates(cx))
+        .await
+        .unwrap();
+    let mut sent_paths = requests
+        .lock()
+        .iter()
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    sent_paths.sort();
+    assert_eq!(
+        sent_paths,
+        ["/extensions", "/extensions/updates", "/extensions/updates"]
+    );
+    store.read_with(cx, |store, _| {
+        let latest = |extension_id: &str| {
+            store.remote_extension(extension_id).map(|extension| {
+                format!(
+                    "{} {}",
+                    extension.version,
+                    extension.registry.as_deref().unwrap_or_default()
+                )
+            })
+        };
+        assert_eq!(latest("zed-monokai").as_deref(), Some("2.1.0 Internal"));
+        assert_eq!(latest("zed-ruby").as_deref(), Some("0.0.3 Public"));
+        assert_eq!(latest("zed-python"), None);
+    });
+}
+
+#[gpui::test]
+async fn test_prune_previous_versions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {},
+            "previous_versions": {
+                "zed-ruby": {
+                    "0.0.1": { "extension.toml": "" },
+                    "0.0.2": { "extension.toml": "" },
+                    "0.0.3": { "extension.toml": "" },
+                },
+                "zed-monokai": {
+                    "1.0.0": { "extension.toml": "" },
+                },
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let set_retained_previous_versions = |count: usize, cx: &mut TestAppContext| {
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                    settings.retained_previous_versions = Some(count);
+                });
+            });
+        });
+    };
+    let retained_versions = |extension_id: &str| {
+        let fs = fs.clone();
+        let extension_dir = Path::new("/the-extension-dir/previous_versions").join(extension_id);
+        async move {
+            match fs.read_dir(&extension_dir).await {
+                Ok(versions) => versions.count().await,
+                Err(_) => 0,
+            }
+        }
+    };
+
+    set_retained_previous_versions(2, cx);
+    store
+        .update(cx, |store, cx| store.prune_previous_versions(cx))
+        .await
+        .unwrap();
+    assert_eq!(retained_versions("zed-ruby").await, 2);
+    assert_eq!(retained_versions("zed-monokai").await, 1);
+
+    // When no previous versions are retained, nothing is left on disk.
+    set_retained_previous_versions(0, cx);
+    store
+        .update(cx, |store, cx| store.prune_previous_versions(cx))
+        .await
+        .unwrap();
+    assert_eq!(retained_versions("zed-ruby").await, 0);
+    assert!(
+        !fs.is_dir(Path::new(
+            "/the-extension-dir/previous_versions/zed-monokai"
+        ))
+        .await
+    );
+}
+
+#[gpui::test]
+async fn test_install_from_archive(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let manifest = "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.3\"\n";
+    let mut bytes = Vec::<u8>::new();
+    let mut archive = async_tar::Builder::new(&mut bytes);
+    let mut header = async_tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    archive
+        .append_data(&mut header, "extension.toml", manifest.as_bytes())
+        .await
+        .unwrap();
+    archive.into_inner().await.unwrap();
+    let mut gzipped_bytes = Vec::new();
+    let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+    encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+    fs.insert_file("/downloads/zed-ruby-0.0.3.tar.gz", gzipped_bytes)
+        .await;
+    fs.insert_file("/downloads/notes.txt", b"not an extension".to_vec())
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let install = store.update(cx, |store, cx| {
+        store.install_from_archive("/downloads/zed-ruby-0.0.3.tar.gz".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    install.await.unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.3"
+        ));
+    });
+    assert!(
+        fs.is_file(Path::new(
+            "/the-extension-dir/installed/zed-ruby/extension.toml"
+        ))
+        .await
+    );
+
+    // Files that aren't packaged extensions are rejected.
+    let install = store.update(cx, |store, cx| {
+        store.install_from_archive("/downloads/notes.txt".into(), cx)
+    });
+    assert!(install.await.is_err());
+
+    // Zip archives are unpacked too, including nested directories.
+    let mut zip_bytes = Vec::new();
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut zip_bytes));
+    zip.start_file("extension.toml", zip::write::FileOptions::default())
+        .unwrap();
+    zip.write_all(manifest.replace("0.0.3", "0.0.4").as_bytes())
+        .unwrap();
+    zip.start_file("docs/README.md", zip::write::FileOptions::default())
+        .unwrap();
+    zip.write_all(b"# Ruby").unwrap();
+    zip.finish().unwrap();
+    drop(zip);
+    fs.insert_file("/downloads/zed-ruby-0.0.4.zip", zip_bytes)
+        .await;
+
+    let install = store.update(cx, |store, cx| {
+        store.install_from_archive("/downloads/zed-ruby-0.0.4.zip".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    install.await.unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.4"
+        ));
+    });
+    assert_eq!(
+        fs.load(Path::new(
+            "/the-extension-dir/installed/zed-ruby/docs/README.md"
+        ))
+        .await
+        .unwrap(),
+        "# Ruby"
+    );
+}
+
+#[gpui::test]
+async fn test_install_from_archive_with_dependencies(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    // The archives on disk depend on `zed-base`, which is in the registry.
+    let base_metadata = json!({
+        "id": "zed-base",
+        "name": "zed-base",
+        "version": "0.1.0",
+        "description": null,
+        "authors": [],
+        "repository": "https://github.com/zed-industries/zed",
+        "download_count": 1,
+    });
+    let mut responses = HashMap::default();
+    responses.insert(
+        "/extensions".to_string(),
+        json!({ "data": [base_metadata.clone()] })
+            .to_string()
+            .into_bytes(),
+    );
+    responses.insert(
+        "/extensions/zed-base/0.1.0".to_string(),
+        json!({ "data": base_metadata }).to_string().into_bytes(),
+    );
+    responses.insert(
+        "/extensions/zed-base/0.1.0/download".to_string(),
+        build_archive(&[(
+            "extension.toml",
+            "id = \"zed-base\"\nname = \"zed-base\"\nversion = \"0.1.0\"\n",
+        )])
+        .await,
+    );
+    let http_client = FakeHttpClient::create(move |request| {
+        let response = responses.get(request.uri().path()).cloned();
+        async move {
+            match response {
+                Some(body) => Ok(Response::new(body.into())),
+                None => Ok(Response::builder().status(404).body("not found".into())?),
+            }
+        }
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+    for version in ["0.0.1", "0.0.2", "0.0.3"] {
+        let manifest = format!(
+            "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"{version}\"\n\n\
+            [dependencies]\nzed-base = \"^0.1\"\n"
+        );
+        fs.insert_file(
+            format!("/downloads/zed-ruby-{version}.tar.gz"),
+            build_archive(&[("extension.toml", manifest)]).await,
+        )
+        .await;
+    }
+    let store = new_test_store("/the-extension-dir", fs.clone(), http_client, cx);
+    let install_archive = |path: &'static str, cx: &mut TestAppContext| {
+        let install = store.update(cx, |store, cx| store.install_from_archive(path.into(), cx));
+        cx.executor().run_until_parked();
+        cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+        install
+    };
+
+    // The dependency is installed from the registry along with the archive.
+    install_archive("/downloads/zed-ruby-0.0.1.tar.gz", cx)
+        .await
+        .unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+        assert!(matches!(
+            store.extension_status("zed-base"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.1.0"
+        ));
+    });
+
+    // With staging turned on, an archive is staged like any other upgrade.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.stage_extension_changes = Some(true);
+            });
+        });
+    });
+    install_archive("/downloads/zed-ruby-0.0.2.tar.gz", cx)
+        .await
+        .unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+        assert_eq!(
+            store.pending_changes()["zed-ruby"].version.as_ref(),
+            "0.0.2"
+        );
+    });
+    assert!(
+        !fs.is_dir(Path::new("/the-extension-dir/downloads/zed-ruby-0.0.2"))
+            .await
+    );
+
+    // Archives that would take extensions over the disk quota are refused.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.disk_quota_mb = Some(0);
+            });
+        });
+    });
+    let error = install_archive("/downloads/zed-ruby-0.0.3.tar.gz", cx)
+        .await
+        .unwrap_err();
+    assert!(format!("{error:#}").contains("disk quota"), "{error:#}");
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store.pending_changes()["zed-ruby"].version.as_ref(),
+            "0.0.2"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_archive_checksum_mismatch(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let metadata = json!({
+        "id": "zed-ruby",
+        "name": "Ruby",
+        "version": "0.0.2",
+        "description": null,
+        "authors": [],
+        "repository": "https://github.com/zed-industries/zed",
+        "download_count": 1,
+        "archive_sha256": "0000000000000000000000000000000000000000000000000000000000000000",
+    });
+    let http_client = FakeHttpClient::create(move |request| {
+        let metadata = metadata.clone();
+        async move {
+            match request.uri().path() {
+                "/extensions" => Ok(Response::new(
+                    json!({ "data": [metadata] }).to_string().into(),
+                )),
+                "/extensions/zed-ruby/0.0.2" => Ok(Response::new(
+                    json!({ "data": metadata }).to_string().into(),
+                )),
+                "/extensions/zed-ruby/0.0.2/download" => Ok(Response::new(
+                    build_archive(&[(
+                        "extension.toml",
+                        "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.2\"\n",
+                    )])
+                    .await
+                    .into(),
+                )),
+                _ => Ok(Response::builder().status(404).body("not found".into())?),
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let new_store = |cx: &mut TestAppContext| {
+        new_test_store("/the-extension-dir", fs.clone(), http_client.clone(), cx)
+    };
+    let store = new_store(cx);
+
+    let fetch = store.update(cx, |store, cx| store.fetch_extensions(None, cx));
+    cx.executor().run_until_parked();
+    fetch.await.unwrap();
+
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![("zed-ruby".into(), "0.0.2".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    let error = batch.await.remove(0).1.unwrap_err();
+    assert!(error.to_string().contains("checksum mismatch"), "{error}");
+
+    // Nothing from the rejected archive was unpacked or installed.
+    assert!(
+        !fs.is_dir(Path::new("/the-extension-dir/installed/zed-ruby"))
+            .await
+    );
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::NotInstalled
+        ));
+    });
+
+    // When the listing hasn't been fetched, the version's metadata is fetched
+    // to verify the archive.
+    let store = new_store(cx);
+    cx.executor().run_until_parked();
+    let install = store.update(cx, |store, cx| {
+        store.install_or_upgrade_extension(
+            "zed-ruby".into(),
+            "0.0.2".into(),
+            ExtensionOperation::Install,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    let error = install.await.unwrap_err();
+    assert!(error.to_string().contains("checksum mismatch"), "{error}");
+    assert!(
+        !fs.is_dir(Path::new("/the-extension-dir/installed/zed-ruby"))
+            .await
+    );
+}
+
+#[gpui::test]
+async fn test_signed_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let mut archives = HashMap::default();
+    for extension_id in ["zed-ruby", "zed-monokai", "zed-gleam"] {
+        let manifest =
+            format!("id = \"{extension_id}\"\nname = \"{extension_id}\"\nversion = \"0.0.1\"\n");
+        archives.insert(
+            extension_id,
+            build_archive(&[("extension.toml", manifest.as_str())]).await,
+        );
+    }
+
+    let private_key = RSAPrivateKey::new(&mut rand::thread_rng(), 1024).unwrap();
+    let public_key = base64::encode(RSAPublicKey::from(&private_key).to_pkcs1().unwrap());
+    let sign = |archive: &[u8]| {
+        let signature = private_key
+            .sign(
+                PaddingScheme::new_pkcs1v15_sign(Some(rsa::Hash::SHA2_256)),
+                &Sha256::digest(archive),
+            )
+            .unwrap();
+        json!({ "key_id": "test-key", "signature": base64::encode(signature) })
+    };
+
+    // `zed-ruby` is signed correctly, `zed-monokai` is unsigned, and the
+    // signature of `zed-gleam` was made for a different archive.
+    let listing = json!({
+        "data": [
+            ("zed-ruby", Some(sign(&archives["zed-ruby"]))),
+            ("zed-monokai", None),
+            ("zed-gleam", Some(sign(&archives["zed-ruby"]))),
+        ]
+        .into_iter()
+        .map(|(extension_id, signature)| {
+            json!({
+                "id": extension_id,
+                "name": extension_id,
+                "version": "0.0.1",
+                "description": null,
+                "authors": [],
+                "repository": "https://github.com/zed-industries/zed",
+                "download_count": 1,
+                "archive_sha256": format!("{:x}", Sha256::digest(&archives[extension_id])),
+                "signature": signature,
+            })
+        })
+        .collect::<Vec<_>>()
+    })
+    .to_string();
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_file("/downloads/zed-ruby.tar.gz", archives["zed-ruby"].clone())
+        .await;
+    fs.insert_file(
+        "/downloads/zed-ruby-tampered.tar.gz",
+        build_archive(&[
+            (
+                "extension.toml",
+                "id = \"zed-ruby\"\nname = \"zed-ruby\"\nversion = \"0.0.1\"\n",
+            ),
+            ("extra.txt", "tampered"),
+        ])
+        .await,
+    )
+    .await;
+    let http_client = FakeHttpClient::create(move |request| {
+        let listing = listing.clone();
+        let archives = archives.clone();
+        async move {
+            let path = request.uri().path();
+            if path == "/extensions" {
+                return Ok(Response::new(listing.into()));
+            }
+            let archive = path
+                .strip_prefix("/extensions/")
+                .and_then(|path| path.strip_suffix("/0.0.1/download"))
+                .and_then(|extension_id| archives.get(extension_id));
+            match archive {
+                Some(archive) => Ok(Response::new(archive.clone().into())),
+                None => Ok(Response::builder().status(404).body("not found".into())?),
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let new_store = |cx: &mut TestAppContext| {
+        new_test_store("/the-extension-dir", fs.clone(), http_client.clone(), cx)
+    };
+    let store = new_store(cx);
+
+    let fetch = store.update(cx, |store, cx| store.fetch_extensions(None, cx));
+    cx.executor().run_until_parked();
+    fetch.await.unwrap();
+
+    let install = |extension_id: &'static str, cx: &mut TestAppContext| {
+        let batch = store.update(cx, |store, cx| {
+            store.run_batch_operation(
+                ExtensionOperation::Install,
+                vec![(extension_id.into(), "0.0.1".into())],
+                cx,
+            )
+        });
+        cx.executor().run_until_parked();
+        cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+        batch
+    };
+    let set_signature_settings = |require_signed_extensions: bool,
+                                  trusted_signing_keys: &[(&str, &str)],
+                                  cx: &mut TestAppContext| {
+        let trusted_signing_keys = trusted_signing_keys
+            .iter()
+            .map(|(key_id, public_key)| (key_id.to_string(), public_key.to_string()))
+            .collect();
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                    settings.require_signed_extensions = Some(require_signed_extensions);
+                    settings.trusted_signing_keys = Some(trusted_signing_keys);
+                });
+            });
+        });
+    };
+
+    // Extensions signed with an untrusted key are installed without a signer,
+    // unless signatures are required.
+    install("zed-ruby", cx).await.remove(0).1.unwrap();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_signer("zed-ruby"), None);
+    });
+    set_signature_settings(true, &[("other-key", &public_key)], cx);
+    let error = install("zed-ruby", cx).await.remove(0).1.unwrap_err();
+    assert!(
+        error.to_string().contains("untrusted key test-key"),
+        "{error}"
+    );
+
+    // Requiring signatures without trusting any key fails closed.
+    set_signature_settings(true, &[], cx);
+    let error = install("zed-ruby", cx).await.remove(0).1.unwrap_err();
+    assert!(
+        error.to_string().contains("no signing keys are trusted"),
+        "{error}"
+    );
+
+    // Once the key is trusted, the signer is recorded and persisted.
+    set_signature_settings(true, &[("test-key", &public_key)], cx);
+    install("zed-ruby", cx).await.remove(0).1.unwrap();
+    let expected_signer = ExtensionSigner {
+        key_id: "test-key".into(),
+        source: SigningKeySource::User,
+    };
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_signer("zed-ruby"), Some(&expected_signer));
+    });
+    new_store(cx).read_with(cx, |store, _| {
+        assert_eq!(store.extension_signer("zed-ruby"), Some(&expected_signer));
+    });
+
+    // Unsigned extensions are rejected when signatures are required.
+    let error = install("zed-monokai", cx).await.remove(0).1.unwrap_err();
+    assert!(error.to_string().contains("not signed"), "{error}");
+
+    // Archives installed from disk are checked against the registry, and
+    // extensions built from git are refused.
+    let install_archive = |path: &'static str, cx: &mut TestAppContext| {
+        let install = store.update(cx, |store, cx| store.install_from_archive(path.into(), cx));
+        cx.executor().run_until_parked();
+        cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+        install
+    };
+    store.update(cx, |store, cx| {
+        store.uninstall_extension("zed-ruby".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    let error = install_archive("/downloads/zed-ruby-tampered.tar.gz", cx)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("checksum mismatch"), "{error}");
+    install_archive("/downloads/zed-ruby.tar.gz", cx)
+        .await
+        .unwrap();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_signer("zed-ruby"), Some(&expected_signer));
+    });
+    let error = store
+        .update(cx, |store, cx| {
+            store.install_from_git("https://github.com/zed-industries/zed".into(), cx)
+        })
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("aren't signed"), "{error}");
+
+    // Signatures that don't match the archive are always rejected.
+    set_signature_settings(false, &[("test-key", &public_key)], cx);
+    let error = install("zed-gleam", cx).await.remove(0).1.unwrap_err();
+    assert!(error.to_string().contains("invalid signature"), "{error}");
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-monokai"),
+            ExtensionStatus::NotInstalled
+        ));
+        assert!(matches!(
+            store.extension_status("zed-gleam"),
+            ExtensionStatus::NotInstalled
+        ));
+    });
+}
+
+#[gpui::test]
+async fn test_install_extension_dependencies(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    // `zed-ruby` and `zed-haml` depend on `zed-base`, which depends on
+    // `zed-core`. `zed-erb` requires a version of `zed-base` that the
+    // registry doesn't have.
+    let extensions = [
+        ("zed-ruby", "0.0.1", json!({ "zed-base": "^0.1" })),
+        ("zed-haml", "0.0.1", json!({ "zed-base": "^0.1" })),
+        (
+            "zed-erb",
+            "0.0.1",
+            json!({ "zed-base": ">=1.0", "zed-core": "^1" }),
+        ),
+        ("zed-base", "0.1.2", json!({ "zed-core": "^1" })),
+        ("zed-core", "1.0.0", json!({})),
+    ];
+    let mut responses = HashMap::default();
+    let mut listing = Vec::new();
+    for (extension_id, version, dependencies) in extensions {
+        let metadata = json!({
+            "id": extension_id,
+            "name": extension_id,
+            "version": version,
+            "description": null,
+            "authors": [],
+            "repository": "https://github.com/zed-industries/zed",
+            "download_count": 1,
+            "dependencies": dependencies,
+        });
+        responses.insert(
+            format!("/extensions/{extension_id}/{version}"),
+            json!({ "data": metadata }).to_string().into_bytes(),
+        );
+        listing.push(metadata);
+
+        let mut manifest =
+            format!("id = \"{extension_id}\"\nname = \"{extension_id}\"\nversion = \"{version}\"\n\n[dependencies]\n");
+        for (dependency_id, requirement) in dependencies.as_object().unwrap() {
+            manifest.push_str(&format!("{dependency_id} = {requirement}\n"));
+        }
+        responses.insert(
+            format!("/extensions/{extension_id}/{version}/download"),
+            build_archive(&[("extension.toml", manifest.as_str())]).await,
+        );
+    }
+    responses.insert(
+        "/extensions".into(),
+        json!({ "data": listing }).to_string().into_bytes(),
+    );
+
+    let fs = FakeFs::new(cx.executor());
+    let requested_paths = Arc::new(Mutex::new(Vec::new()));
+    let http_client = FakeHttpClient::create({
+        let requested_paths = requested_paths.clone();
+        move |request| {
+            let path = request.uri().path().to_string();
+            requested_paths.lock().push(path.clone());
+            let response = responses.get(&path).cloned();
+            async move {
+                match response {
+                    Some(body) => Ok(Response::new(body.into())),
+                    None => Ok(Response::builder().status(404).body("not found".into())?),
+                }
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let store = new_test_store("/the-extension-dir", fs.clone(), http_client, cx);
+
+    // Installing extensions also installs the registry's latest version of
+    // each of their dependencies, fetching the registry's listing if needed.
+    // Extensions that depend on the same extension share its install, and
+    // dependencies are installed before the extensions that depend on them.
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![
+                ("zed-ruby".into(), "0.0.1".into()),
+                ("zed-haml".into(), "0.0.1".into()),
+            ],
+            cx,
+        )
+    });
+    for _ in 0..4 {
+        cx.executor().run_until_parked();
+        cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    }
+    for (extension_id, result) in batch.await {
+        result.unwrap_or_else(|error| panic!("failed to install {extension_id}: {error}"));
+    }
+    store.read_with(cx, |store, _| {
+        for (extension_id, version) in [
+            ("zed-ruby", "0.0.1"),
+            ("zed-haml", "0.0.1"),
+            ("zed-base", "0.1.2"),
+            ("zed-core", "1.0.0"),
+        ] {
+            assert!(matches!(
+                store.extension_status(extension_id),
+                ExtensionStatus::Installed(installed) if installed.as_ref() == version
+            ));
+        }
+        assert_eq!(
+            store.dependents("zed-base"),
+            [Arc::<str>::from("zed-haml"), Arc::<str>::from("zed-ruby")]
+        );
+        assert!(store.dependents("zed-ruby").is_empty());
+    });
+    let downloads = requested_paths
+        .lock()
+        .iter()
+        .filter_map(|path| {
+            path.strip_suffix("/download")?
+                .strip_prefix("/extensions/")?
+                .split('/')
+                .next()
+                .map(str::to_string)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(downloads.len(), 4, "{downloads:?}");
+    let position = |extension_id: &str| {
+        downloads
+            .iter()
+            .position(|download| download == extension_id)
+            .unwrap()
+    };
+    assert!(position("zed-core") < position("zed-base"));
+    assert!(position("zed-base") < position("zed-ruby"));
+    assert!(position("zed-base") < position("zed-haml"));
+
+    // Dependencies whose latest version doesn't satisfy a requirement fail
+    // the install before anything is downloaded.
+    requested_paths.lock().clear();
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![("zed-erb".into(), "0.0.1".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    let error = batch.await.remove(0).1.unwrap_err();
+    assert!(
+        error.to_string().contains("requires zed-base >=1.0"),
+        "{error}"
+    );
+    assert!(!requested_paths
+        .lock()
+        .iter()
+        .any(|path| path.ends_with("/download")));
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-erb"),
+            ExtensionStatus::NotInstalled
+        ));
+    });
+
+    // Only the dependencies that aren't installed at a satisfying version
+    // would be installed along with an extension.
+    store.read_with(cx, |store, _| {
+        let extension = store.remote_extension("zed-erb").unwrap();
+        let dependencies = store.dependencies_to_install(extension);
+        assert_eq!(
+            dependencies
+                .iter()
+                .map(|dependency| dependency.id.as_ref())
+                .collect::<Vec<_>>(),
+            ["zed-base"]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_preview_install(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        if request.uri().path() != "/extensions/zed-ruby/0.0.2/download" {
+            return Ok(Response::builder().status(404).body("not found".into())?);
+        }
+        let files = [
+            (
+                "extension.toml",
+                "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.2\"\n\
+                languages = [\"languages/ruby\"]\nthemes = [\"themes/ruby.json\"]\n\n\
+                [grammars.ruby]\nrepository = \"https://github.com/tree-sitter/tree-sitter-ruby\"\n\
+                rev = \"main\"\n\n\
+                [language_servers.solargraph]\nlanguage = \"Ruby\"\n",
+            ),
+            (
+                "languages/ruby/config.toml",
+                "name = \"Ruby\"\ngrammar = \"ruby\"\n",
+            ),
+            ("languages/ruby/highlights.scm", "(comment) @comment\n"),
+            (
+                "themes/ruby.json",
+                r#"{ "name": "Ruby", "author": "", "themes": [{ "name": "Ruby Dark" }] }"#,
+            ),
+        ];
+        Ok(Response::new(build_archive(&files).await.into()))
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let store = new_test_store("/the-extension-dir", fs.clone(), http_client, cx);
+
+    let preview = store.update(cx, |store, cx| {
+        store.preview_install("zed-ruby".into(), "0.0.2".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    let preview = preview.await.unwrap();
+
+    assert_eq!(
+        preview
+            .files
+            .iter()
+            .map(|(path, _)| path.to_string_lossy().into_owned())
+            .collect::<Vec<_>>(),
+        [
+            "extension.toml",
+            "languages/ruby/config.toml",
+            "languages/ruby/highlights.scm",
+            "themes/ruby.json",
+        ]
+    );
+    assert_eq!(preview.languages, [Arc::<str>::from("Ruby")]);
+    assert_eq!(preview.themes, [Arc::<str>::from("Ruby Dark")]);
+    assert_eq!(preview.grammars, [Arc::<str>::from("ruby")]);
+    assert_eq!(
+        preview.language_servers,
+        [LanguageServerName("solargraph".into())]
+    );
+    assert!(preview.total_size() > 0);
+    assert!(preview.download_size > 0);
+
+    // Previewing doesn't install anything.
+    assert!(
+        !fs.is_dir(Path::new("/the-extension-dir/installed/zed-ruby"))
+            .await
+    );
+}
+
+#[gpui::test]
+async fn test_theme_preview(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let requested_paths = Arc::new(Mutex::new(Vec::new()));
+    let http_client = FakeHttpClient::create({
+        let requested_paths = requested_paths.clone();
+        move |request| {
+            let path = request.uri().path().to_string();
+            requested_paths.lock().push(path.clone());
+            async move {
+                if path != "/extensions/zed-monokai/0.0.1/themes" {
+                    return Ok(Response::builder().status(404).body("not found".into())?);
+                }
+                let theme_file = |name: &str| {
+                    json!({
+                        "name": name,
+                        "author": "",
+                        "themes": [{ "name": format!("{name} Dark"), "appearance": "dark", "style": {} }],
+                    })
+                    .to_string()
+                };
+                Ok(Response::new(
+                    json!({
+                        "data": [
+                            { "path": "themes/monokai.json", "content": theme_file("Monokai") },
+                            { "path": "themes/monokai-pro.json", "content": theme_file("Monokai Pro") },
+                        ]
+                    })
+                    .to_string()
+                    .into(),
+                ))
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let store = new_test_store("/the-extension-dir", fs.clone(), http_client, cx);
+
+    // Only the requested themes are previewed, and the extension's archive
+    // isn't downloaded.
+    let preview = store.update(cx, |store, cx| {
+        store.load_theme_preview(
+            "zed-monokai".into(),
+            "0.0.1".into(),
+            vec!["./themes/monokai.json".into()],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    let theme_names = preview.await.unwrap();
+    assert_eq!(theme_names, [SharedString::from("Monokai Dark")]);
+    assert_eq!(
+        *requested_paths.lock(),
+        ["/extensions/zed-monokai/0.0.1/themes"]
+    );
+    store.read_with(cx, |store, _| {
+        assert!(store.theme_registry.get("Monokai Dark").is_ok());
+        assert!(store.theme_registry.get("Monokai Pro Dark").is_err());
+    });
+
+    store.update(cx, |store, _| store.unload_theme_preview(&theme_names));
+    store.read_with(cx, |store, _| {
+        assert!(store.theme_registry.get("Monokai Dark").is_err());
+    });
+
+    // Extensions without themes can't be previewed.
+    let preview = store.update(cx, |store, cx| {
+        store.load_theme_preview(
+            "zed-ruby".into(),
+            "0.0.1".into(),
+            vec!["themes/ruby.json".into()],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    assert!(preview.await.is_err());
+}
+
+#[gpui::test]
+async fn test_import_extension_list(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        let (extension_id, version) = match request.uri().path() {
+            "/extensions" => {
+                return Ok(Response::new(
+                    json!({
+                        "data": [("zed-ruby", "0.0.2"), ("zed-monokai", "2.0.0")]
+                            .into_iter()
+                            .map(|(extension_id, version)| {
+                                json!({
+                                    "id": extension_id,
+                                    "name": extension_id,
+                                    "version": version,
+                                    "description": null,
+                                    "authors": [],
+                                    "repository": "https://github.com/zed-industries/zed",
+                                    "download_count": 1,
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .to_string()
+                    .into(),
+                ))
+            }
+            "/extensions/zed-ruby/0.0.1/download" => ("zed-ruby", "0.0.1"),
+            "/extensions/zed-monokai/2.0.0/download" => ("zed-monokai", "2.0.0"),
+            _ => return Ok(Response::builder().status(404).body("not found".into())?),
+        };
+        let manifest = format!(
+            "id = \"{extension_id}\"\nname = \"{extension_id}\"\nversion = \"{version}\"\n"
+        );
+        let mut bytes = Vec::<u8>::new();
+        let mut archive = async_tar::Builder::new(&mut bytes);
+        let mut header = async_tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        archive
+            .append_data(&mut header, "extension.toml", manifest.as_bytes())
+            .await
+            .unwrap();
+        archive.into_inner().await.unwrap();
+        let mut gzipped_bytes = Vec::new();
+        let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+        encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+        Ok(Response::new(gzipped_bytes.into()))
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    // Pinned extensions are installed at the listed version, and the others
+    // at the registry's latest version.
+    let list = ExtensionList {
+        extensions: vec![
+            ExtensionListEntry {
+                id: "zed-ruby".into(),
+                version: "0.0.1".into(),
+                pinned: true,
+            },
+            ExtensionListEntry {
+                id: "zed-monokai".into(),
+                version: "1.0.0".into(),
+                pinned: false,
+            },
+            ExtensionListEntry {
+                id: "zed-unlisted".into(),
+                version: "1.0.0".into(),
+                pinned: false,
+            },
+        ],
+    };
+    let import = store.update(cx, |store, cx| store.import_extension_list(list, cx));
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    let outcomes = import.await;
+    assert_eq!(
+        outcomes
+            .iter()
+            .map(|(extension_id, result)| (extension_id.as_ref(), result.is_ok()))
+            .collect::<Vec<_>>(),
+        [
+            ("zed-ruby", true),
+            ("zed-monokai", true),
+            ("zed-unlisted", false)
+        ]
+    );
+
+    assert_eq!(
+        store.read_with(cx, |store, _| store.export_extension_list()),
+        ExtensionList {
+            extensions: vec![
+                ExtensionListEntry {
+                    id: "zed-monokai".into(),
+                    version: "2.0.0".into(),
+                    pinned: false,
+                },
+                ExtensionListEntry {
+                    id: "zed-ruby".into(),
+                    version: "0.0.1".into(),
+                    pinned: true,
+                },
+            ],
+        }
+    );
+}
+
+#[gpui::test]
+async fn test_synced_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        let (extension_id, version) = match request.uri().path() {
+            "/extensions" => {
+                return Ok(Response::new(
+                    json!({
+                        "data": [("zed-ruby", "0.0.2"), ("zed-monokai", "2.0.0")]
+                            .into_iter()
+                            .map(|(extension_id, version)| {
+                                json!({
+                                    "id": extension_id,
+                                    "name": extension_id,
+                                    "version": version,
+                                    "description": null,
+                                    "authors": [],
+                                    "repository": "https://github.com/zed-industries/zed",
+                                    "download_count": 1,
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .to_string()
+                    .into(),
+                ))
+            }
+            "/extensions/zed-ruby/0.0.1/download" => ("zed-ruby", "0.0.1"),
+            "/extensions/zed-monokai/2.0.0/download" => ("zed-monokai", "2.0.0"),
+            _ => return Ok(Response::builder().status(404).body("not found".into())?),
+        };
+        let manifest = format!(
+            "id = \"{extension_id}\"\nname = \"{extension_id}\"\nversion = \"{version}\"\n"
+        );
+        let mut bytes = Vec::<u8>::new();
+        let mut archive = async_tar::Builder::new(&mut bytes);
+        let mut header = async_tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        archive
+            .append_data(&mut header, "extension.toml", manifest.as_bytes())
+            .await
+            .unwrap();
+        archive.into_inner().await.unwrap();
+        let mut gzipped_bytes = Vec::new();
+        let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+        encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+        Ok(Response::new(gzipped_bytes.into()))
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+    fs.insert_tree(
+        paths::CONFIG_DIR.as_path(),
+        json!({ "settings.json": "{}" }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let offers = Arc::new(Mutex::new(Vec::new()));
+    cx.update({
+        let offers = offers.clone();
+        |cx| {
+            cx.subscribe(&store, move |_, event, _| {
+                if let Event::SyncedExtensionsAvailable { extensions } = event {
+                    offers.lock().push(extensions.clone());
+                }
+            })
+            .detach();
+        }
+    });
+
+    // Another machine recorded its extensions in the settings file, and
+    // excluded one of them from syncing.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.sync_extensions = Some(true);
+                settings.synced_extensions = Some(
+                    [
+                        ("zed-ruby", "0.0.1", true),
+                        ("zed-monokai", "1.0.0", false),
+                        ("zed-private", "1.0.0", false),
+                    ]
+                    .into_iter()
+                    .map(|(extension_id, version, pinned)| {
+                        (
+                            extension_id.to_string(),
+                            SyncedExtension {
+                                version: version.to_string(),
+                                pinned,
+                            },
+                        )
+                    })
+                    .collect(),
+                );
+                settings.unsynced_extensions = Some(vec!["zed-private".to_string()]);
+            });
+        });
+    });
+
+    // The missing extensions are offered once.
+    store.update(cx, |store, cx| store.sync_synced_extensions(cx));
+    store.update(cx, |store, cx| store.sync_synced_extensions(cx));
+    let expected_offer = ExtensionList {
+        extensions: vec![
+            ExtensionListEntry {
+                id: "zed-monokai".into(),
+                version: "1.0.0".into(),
+                pinned: false,
+            },
+            ExtensionListEntry {
+                id: "zed-ruby".into(),
+                version: "0.0.1".into(),
+                pinned: true,
+            },
+        ],
+    };
+    assert_eq!(*offers.lock(), [expected_offer.clone()]);
+    assert_eq!(
+        store.read_with(cx, |store, _| store.synced_extensions_offer().cloned()),
+        Some(expected_offer)
+    );
+
+    // Accepting the offer installs them like an imported extension list.
+    let install = store.update(cx, |store, cx| store.install_synced_extensions(cx));
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    let outcomes = install.await;
+    assert!(outcomes.iter().all(|(_, result)| result.is_ok()));
+    store.read_with(cx, |store, _| {
+        assert!(store.synced_extensions_offer().is_none());
+        assert_eq!(
+            store
+                .installed_extension("zed-monokai")
+                .unwrap()
+                .version
+                .as_ref(),
+            "2.0.0"
+        );
+        assert_eq!(
+            store
+                .pinned_version("zed-ruby")
+                .map(|version| version.as_ref()),
+            Some("0.0.1")
+        );
+    });
+
+    // Pinning an extension records the pin in the settings file.
+    store.update(cx, |store, cx| store.pin_extension("zed-monokai", cx));
+    cx.executor().run_until_parked();
+    let settings_content = fs.load(paths::SETTINGS.as_path()).await.unwrap();
+    let settings_content: serde_json::Value = serde_json::from_str(&settings_content).unwrap();
+    assert_eq!(
+        settings_content["extensions"]["synced_extensions"]["zed-monokai"],
+        json!({ "version": "2.0.0", "pinned": true })
+    );
+}
+
+#[gpui::test]
+async fn test_custom_registry_url(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let requested_urls = Arc::new(Mutex::new(Vec::new()));
+    let http_client = FakeHttpClient::create({
+        let requested_urls = requested_urls.clone();
+        move |request| {
+            requested_urls.lock().push(request.uri().to_string());
+            async move { Ok(Response::new(json!({ "data": [] }).to_string().into())) }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.registry_url = Some("https://extensions.example.com/api/".to_string());
+            });
+        });
+    });
+    store.update(cx, |store, cx| store.sync_registries(cx));
+    assert_eq!(
+        store.read_with(cx, |store, _| store.registry_url()),
+        "https://extensions.example.com/api"
+    );
+
+    store
+        .update(cx, |store, cx| store.fetch_extensions(None, cx))
+        .await
+        .unwrap();
+    store
+        .update(cx, |store, cx| store.fetch_extensions(Some("ruby"), cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        *requested_urls.lock(),
+        [
+            "https://extensions.example.com/api/extensions",
+            "https://extensions.example.com/api/extensions?filter=ruby",
+        ]
+    );
+}
+
+#[gpui::test]
+async fn test_multiple_registries(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        let extensions = match request.uri().host() {
+            Some("internal.example.com") => vec![("zed-ruby", "1.0.0")],
+            Some("public.example.com") => vec![("zed-ruby", "2.0.0"), ("zed-monokai", "1.0.0")],
+            _ => return Ok(Response::builder().status(404).body("not found".into())?),
+        };
+        Ok(Response::new(
+            json!({
+                "data": extensions
+                    .into_iter()
+                    .map(|(extension_id, version)| {
+                        json!({
+                            "id": extension_id,
+                            "name": extension_id,
+                            "version": version,
+                            "description": null,
+                            "authors": [],
+                            "repository": "https://github.com/zed-industries/zed",
+                            "download_count": 1,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .to_string()
+            .into(),
+        ))
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let set_registries = |internal_enabled: bool, cx: &mut TestAppContext| {
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                    settings.registries = Some(
+                        [
+                            ("Internal", "https://internal.example.com", internal_enabled),
+                            ("Broken", "https://broken.example.com", true),
+                            ("Public", "https://public.example.com/", true),
+                        ]
+                        .into_iter()
+                        .map(|(name, url, enabled)| ExtensionRegistrySettings {
+                            name: name.to_string(),
+                            url: url.to_string(),
+                            enabled,
+                        })
+                        .collect(),
+                    );
+                });
+            });
+        });
+        store.update(cx, |store, cx| store.sync_registries(cx));
+    };
+    let registries_of_extensions = |extensions: Vec<ExtensionApiResponse>| {
+        extensions
+            .into_iter()
+            .map(|extension| {
+                format!(
+                    "{} {} {}",
+                    extension.id,
+                    extension.version,
+                    extension.registry.unwrap()
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Extensions are merged from the registries that can be reached, and the
+    // first registry that lists an extension takes precedence.
+    set_registries(true, cx);
+    let extensions = store
+        .update(cx, |store, cx| store.fetch_extensions(None, cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        registries_of_extensions(extensions),
+        ["zed-ruby 1.0.0 Internal", "zed-monokai 1.0.0 Public",]
+    );
+
+    // Disabling a registry forgets the extensions fetched from it.
+    set_registries(false, cx);
+    assert!(store.read_with(cx, |store, _| store.remote_extension("zed-ruby").is_none()));
+    let extensions = store
+        .update(cx, |store, cx| store.fetch_extensions(None, cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        registries_of_extensions(extensions),
+        ["zed-ruby 2.0.0 Public", "zed-monokai 1.0.0 Public",]
+    );
+}
+
+#[gpui::test]
+async fn test_download_mirrors(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let requested_urls = Arc::new(Mutex::new(Vec::new()));
+    let http_client = FakeHttpClient::create({
+        let requested_urls = requested_urls.clone();
+        move |request| {
+            let url = request.uri().to_string();
+            requested_urls.lock().push(url.clone());
+            async move {
+                match url.as_str() {
+                    "http://test.example/extensions" => {
+                        return Ok(Response::new(
+                            json!({
+                                "data": [{
+                                    "id": "zed-ruby",
+                                    "name": "Ruby",
+                                    "version": "0.0.1",
+                                    "description": null,
+                                    "authors": [],
+                                    "repository": "https://github.com/zed-industries/zed",
+                                    "download_count": 1,
+                                    "mirrors": [
+                                        "https://mirror-1.example.com/zed-ruby.tar.gz",
+                                        "https://mirror-2.example.com/zed-ruby.tar.gz",
+                                    ],
+                                }]
+                            })
+                            .to_string()
+                            .into(),
+                        ))
+                    }
+                    "https://mirror-2.example.com/zed-ruby.tar.gz" => {}
+                    "https://mirror-1.example.com/zed-ruby.tar.gz" => {
+                        return Ok(Response::builder().status(404).body("not found".into())?)
+                    }
+                    _ => return Ok(Response::builder().status(503).body("unavailable".into())?),
+                }
+
+                let manifest = "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.1\"\n";
+                let mut bytes = Vec::<u8>::new();
+                let mut archive = async_tar::Builder::new(&mut bytes);
+                let mut header = async_tar::Header::new_gnu();
+                header.set_size(manifest.len() as u64);
+                archive
+                    .append_data(&mut header, "extension.toml", manifest.as_bytes())
+                    .await
+                    .unwrap();
+                archive.into_inner().await.unwrap();
+                let mut gzipped_bytes = Vec::new();
+                let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+                encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+                Ok(Response::new(gzipped_bytes.into()))
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    store
+        .update(cx, |store, cx| store.fetch_extensions(None, cx))
+        .await
+        .unwrap();
+    requested_urls.lock().clear();
+
+    // When the registry's download fails, the mirrors are tried in order.
+    store.update(cx, |store, cx| {
+        store.install_extension("zed-ruby".into(), "0.0.1".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+
+    assert_eq!(
+        *requested_urls.lock(),
+        [
+            "http://test.example/extensions/zed-ruby/0.0.1/download",
+            "https://mirror-1.example.com/zed-ruby.tar.gz",
+            "https://mirror-2.example.com/zed-ruby.tar.gz",
+        ]
+    );
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(_)
+        ));
+    });
+}
+
+#[gpui::test]
+async fn test_registry_cache_ttl(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let request_count = Arc::new(AtomicUsize::new(0));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let request_count = request_count.clone();
+        move |request| {
+            request_count.fetch_add(1, SeqCst);
+            async move {
+                if request.uri().path() != "/extensions" {
+                    return Ok(Response::builder().status(404).body("not found".into())?);
+                }
+                Ok(Response::new(
+                    json!({
+                        "data": [{
+                            "id": "zed-ruby",
+                            "name": "Ruby",
+                            "version": "0.0.2",
+                            "description": null,
+                            "authors": [],
+                            "repository": "https://github.com/zed-industries/zed",
+                            "download_count": 1,
+                        }]
+                    })
+                    .to_string()
+                    .into(),
+                ))
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let new_store = |cx: &mut TestAppContext| {
+        cx.new_model(|cx| {
+            ExtensionStore::new(
+                PathBuf::from("/the-extension-dir"),
+                None,
+                fs.clone(),
+                http_client.clone(),
+                node_runtime.clone(),
+                language_registry.clone(),
+                theme_registry.clone(),
+                cx,
+            )
+        })
+    };
+    let store = new_store(cx);
+    let fetch = |store: &Model<ExtensionStore>, cx: &mut TestAppContext| {
+        store.update(cx, |store, cx| store.fetch_extensions(None, cx))
+    };
+
+    // The first listing is fetched from the registry.
+    fetch(&store, cx).await.unwrap();
+    assert_eq!(request_count.load(SeqCst), 1);
+
+    // While the cached listing is fresh, it is used instead, including by
+    // stores that are created later.
+    fetch(&store, cx).await.unwrap();
+    let store = new_store(cx);
+    let extensions = fetch(&store, cx).await.unwrap();
+    assert_eq!(request_count.load(SeqCst), 1);
+    assert_eq!(extensions[0].id.as_ref(), "zed-ruby");
+    assert!(store.read_with(cx, |store, _| store.remote_extension("zed-ruby").is_some()));
+
+    // Searches still query the registry.
+    store
+        .update(cx, |store, cx| store.fetch_extensions(Some("ruby"), cx))
+        .await
+        .unwrap();
+    assert_eq!(request_count.load(SeqCst), 2);
+
+    // A cached listing that has expired is refreshed.
+    fs.atomic_write(
+        PathBuf::from("/the-extension-dir/registry_cache.json"),
+        json!({ "fetched_at": { "secs_since_epoch": 0, "nanos_since_epoch": 0 }, "data": [] })
+            .to_string(),
+    )
+    .await
+    .unwrap();
+    let extensions = fetch(&store, cx).await.unwrap();
+    assert_eq!(request_count.load(SeqCst), 3);
+    assert_eq!(extensions.len(), 1);
+
+    // A TTL of zero disables the cache.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.registry_cache_ttl_secs = Some(0);
+            });
+        });
+    });
+    fetch(&store, cx).await.unwrap();
+    assert_eq!(request_count.load(SeqCst), 4);
+}
+
+#[gpui::test]
+async fn test_delta_upgrades(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let manifest = |id: &str, version: &str| {
+        format!("id = \"{id}\"\nname = \"{id}\"\nversion = \"{version}\"\n").into_bytes()
+    };
+    let old_grammar = (0..4096u32).map(|ix| (ix % 251) as u8).collect::<Vec<_>>();
+    let mut new_grammar = old_grammar.clone();
+    new_grammar[2048] = 0xff;
+    let mut grammar_patch = Vec::new();
+    bsdiff::diff(&old_grammar, &new_grammar, &mut grammar_patch).unwrap();
+    let sha256 = |content: &[u8]| format!("{:x}", Sha256::digest(content));
+
+    // Each extension's delta patches its grammar, replaces its manifest, adds
+    // `new.txt`, and drops `old.txt`. The delta of `zed-lua` expects a
+    // different `kept.txt` than the installed one, so it can't be applied.
+    let mut responses = HashMap::default();
+    for id in ["zed-ruby", "zed-lua"] {
+        responses.insert(
+            format!("/extensions/{id}/0.0.1/download"),
+            build_archive(&[
+                ("extension.toml", manifest(id, "0.0.1")),
+                ("kept.txt", b"kept".to_vec()),
+                ("old.txt", b"old".to_vec()),
+                ("grammars/ruby.wasm", old_grammar.clone()),
+            ])
+            .await,
+        );
+        responses.insert(
+            format!("/extensions/{id}/0.0.2/download"),
+            build_archive(&[
+                ("extension.toml", manifest(id, "0.0.2")),
+                ("kept.txt", b"kept".to_vec()),
+                ("new.txt", b"new".to_vec()),
+                ("grammars/ruby.wasm", new_grammar.clone()),
+            ])
+            .await,
+        );
+        let kept_sha256 = if id == "zed-ruby" {
+            sha256(b"kept")
+        } else {
+            sha256(b"changed")
+        };
+        let delta_manifest = json!({
+            "files": [
+                {
+                    "path": "extension.toml",
+                    "sha256": sha256(&manifest(id, "0.0.2")),
+                    "source": "replaced",
+                },
+                { "path": "kept.txt", "sha256": kept_sha256, "source": "unchanged" },
+                { "path": "new.txt", "sha256": sha256(b"new"), "source": "replaced" },
+                {
+                    "path": "grammars/ruby.wasm",
+                    "sha256": sha256(&new_grammar),
+                    "source": "patched",
+                },
+            ]
+        });
+        responses.insert(
+            format!("/extensions/{id}/0.0.2/delta"),
+            build_archive(&[
+                ("delta.json", delta_manifest.to_string().into_bytes()),
+                ("files/extension.toml", manifest(id, "0.0.2")),
+                ("files/new.txt", b"new".to_vec()),
+                ("patches/grammars/ruby.wasm", grammar_patch.clone()),
+            ])
+            .await,
+        );
+    }
+
+    let requested_urls = Arc::new(Mutex::new(Vec::new()));
+    let http_client = FakeHttpClient::create({
+        let requested_urls = requested_urls.clone();
+        let responses = Arc::new(responses);
+        move |request| {
+            requested_urls.lock().push(request.uri().to_string());
+            let body = responses.get(request.uri().path()).cloned();
+            async move {
+                match body {
+                    Some(body) => Ok(Response::new(body.into())),
+                    None => Ok(Response::builder().status(404).body("not found".into())?),
+                }
+            }
+        }
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let run_batch = |operation, version: &str, cx: &mut TestAppContext| {
+        let batch = store.update(cx, |store, cx| {
+            store.run_batch_operation(
+                operation,
+                vec![
+                    ("zed-ruby".into(), version.into()),
+                    ("zed-lua".into(), version.into()),
+                ],
+                cx,
+            )
+        });
+        cx.executor().run_until_parked();
+        cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+        batch
+    };
+    for (_, result) in run_batch(ExtensionOperation::Install, "0.0.1", cx).await {
+        result.unwrap();
+    }
+    requested_urls.lock().clear();
+    for (_, result) in run_batch(ExtensionOperation::Upgrade, "0.0.2", cx).await {
+        result.unwrap();
+    }
+    cx.executor().run_until_parked();
+
+    // The delta is applied to the installed version, and when it can't be,
+    // the whole archive is downloaded instead.
+    let mut requested_urls = requested_urls
+        .lock()
+        .iter()
+        .filter(|url| url.contains("/delta") || url.ends_with("/download"))
+        .cloned()
+        .collect::<Vec<_>>();
+    requested_urls.sort();
+    assert_eq!(
+        requested_urls,
+        [
+            "http://test.example/extensions/zed-lua/0.0.2/delta?from=0.0.1",
+            "http://test.example/extensions/zed-lua/0.0.2/download",
+            "http://test.example/extensions/zed-ruby/0.0.2/delta?from=0.0.1",
+        ]
+    );
+    for id in ["zed-ruby", "zed-lua"] {
+        let installed_dir = Path::new("/the-extension-dir/installed").join(id);
+        assert_eq!(
+            fs.load(&installed_dir.join("kept.txt")).await.unwrap(),
+            "kept"
+        );
+        assert_eq!(
+            fs.load(&installed_dir.join("new.txt")).await.unwrap(),
+            "new"
+        );
+        assert!(!fs.is_file(&installed_dir.join("old.txt")).await);
+        assert!(!fs.is_file(&installed_dir.join("delta.json")).await);
+        let mut grammar = Vec::new();
+        fs.open_sync(&installed_dir.join("grammars/ruby.wasm"))
+            .await
+            .unwrap()
+            .read_to_end(&mut grammar)
+            .unwrap();
+        assert_eq!(grammar, new_grammar);
+    }
+    store.read_with(cx, |store, _| {
+        for id in ["zed-ruby", "zed-lua"] {
+            assert_eq!(
+                store.installed_extension(id).unwrap().version.as_ref(),
+                "0.0.2"
+            );
+        }
+    });
+}
+
+#[gpui::test]
+async fn test_registry_conditional_requests(cx: &mut TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.registry_cache_ttl_secs = Some(0);
+            });
+        });
+    });
+
+    // The registry serves its listing with an ETag, and only responds with
+    // the listing again when it's asked about a different one.
+    let listing_count = Arc::new(AtomicUsize::new(0));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let listing_count = listing_count.clone();
+        move |request| {
+            let listing_count = listing_count.clone();
+            async move {
+                if request.uri().path() != "/extensions" {
+                    return Ok(Response::builder().status(404).body("not found".into())?);
+                }
+                if request
+                    .headers()
+                    .get("if-none-match")
+                    .map(|etag| etag.as_bytes())
+                    == Some(b"\"v1\"")
+                {
+                    return Ok(Response::builder().status(304).body("".into())?);
+                }
+                listing_count.fetch_add(1, SeqCst);
+                Ok(Response::builder().header("ETag", "\"v1\"").body(
+                    json!({
+                        "data": [{
+                            "id": "zed-ruby",
+                            "name": "Ruby",
+                            "version": "0.0.2",
+                            "description": null,
+                            "authors": [],
+                            "repository": "https://github.com/zed-industries/zed",
+                            "download_count": 1,
+                        }]
+                    })
+                    .to_string()
+                    .into(),
+                )?)
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    let fetch = |store: &Model<ExtensionStore>, cx: &mut TestAppContext| {
+        store.update(cx, |store, cx| store.fetch_extensions(None, cx))
+    };
+
+    let extensions = fetch(&store, cx).await.unwrap();
+    assert_eq!(extensions[0].id.as_ref(), "zed-ruby");
+    assert_eq!(listing_count.load(SeqCst), 1);
+
+    // Refreshes reuse the cached listing when it hasn't changed.
+    let extensions = fetch(&store, cx).await.unwrap();
+    assert_eq!(extensions[0].id.as_ref(), "zed-ruby");
+    assert_eq!(extensions[0].registry.as_deref(), Some("Zed"));
+    assert_eq!(listing_count.load(SeqCst), 1);
+    let extensions = fetch(&store, cx).await.unwrap();
+    assert_eq!(extensions.len(), 1);
+    assert_eq!(listing_count.load(SeqCst), 1);
+}
+
+#[gpui::test]
+async fn test_query_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let queries = queries.clone();
+        move |request| {
+            queries
+                .lock()
+                .push(request.uri().query().unwrap_or_default().to_string());
+            async move {
+                Ok(Response::new(
+                    json!({
+                        "data": [{
+                            "id": "zed-monokai",
+                            "name": "Monokai",
+                            "version": "2.0.0",
+                            "description": null,
+                            "authors": [],
+                            "repository": "https://github.com/zed-industries/zed",
+                            "download_count": 1,
+                        }],
+                        "total_count": 42,
+                    })
+                    .to_string()
+                    .into(),
+                ))
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let page = store
+        .update(cx, |store, cx| {
+            store.query_extensions(
+                ExtensionQuery {
+                    search: Some("mono".into()),
+                    category: Some(ExtensionCategory::Themes),
+                    sort: Some(ExtensionSort::Downloads),
+                    page: 2,
+                    page_size: Some(10),
+                    ids: Vec::new(),
+                },
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        *queries.lock(),
+        ["filter=mono&category=themes&sort=downloads&page=2&page_size=10"]
+    );
+    assert_eq!(page.total_count, 42);
+    assert_eq!(page.extensions[0].id.as_ref(), "zed-monokai");
+
+    // Registries that don't paginate report as many extensions as they list.
+    let page = store
+        .update(cx, |store, cx| {
+            store.query_extensions(
+                ExtensionQuery {
+                    sort: Some(ExtensionSort::Name),
+                    ..Default::default()
+                },
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(queries.lock()[1], "sort=name");
+    assert_eq!(page.total_count, 42);
+}
+
+#[gpui::test]
+async fn test_extension_collections(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let lint_archive = build_archive(&[(
+        "extension.toml",
+        "id = \"acme-lint\"\nname = \"Acme Lint\"\nversion = \"0.0.1\"\n",
+    )])
+    .await;
+    let format_archive = build_archive(&[(
+        "extension.toml",
+        "id = \"acme-format\"\nname = \"Acme Format\"\nversion = \"0.2.0\"\n",
+    )])
+    .await;
+    let listing_queries = Arc::new(Mutex::new(Vec::new()));
+    let http_client = FakeHttpClient::create({
+        let listing_queries = listing_queries.clone();
+        move |request| {
+            let lint_archive = lint_archive.clone();
+            let format_archive = format_archive.clone();
+            let listing_queries = listing_queries.clone();
+            async move {
+                let body = match request.uri().path() {
+                    "/extensions/collections" => json!({
+                        "data": [{
+                            "id": "backend",
+                            "name": "Backend team baseline",
+                            "description": null,
+                            "extension_ids": ["acme-lint", "acme-format"],
+                        }]
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    "/extensions" => {
+                        let query = request.uri().query().unwrap_or_default().to_string();
+                        listing_queries.lock().push(query.clone());
+                        // The collection's extensions aren't in the first page
+                        // of the full listing.
+                        let data = if query == "ids=acme-lint,acme-format" {
+                            json!([
+                                {
+                                    "id": "acme-lint",
+                                    "name": "Acme Lint",
+                                    "version": "0.0.1",
+                                    "description": null,
+                                    "authors": [],
+                                    "repository": "https://github.com/zed-industries/zed",
+                                    "download_count": 1,
+                                },
+                                {
+                                    "id": "acme-format",
+                                    "name": "Acme Format",
+                                    "version": "0.2.0",
+                                    "description": null,
+                                    "authors": [],
+                                    "repository": "https://github.com/zed-industries/zed",
+                                    "download_count": 1,
+                                },
+                            ])
+                        } else {
+                            json!([])
+                        };
+                        json!({ "data": data }).to_string().into_bytes()
+                    }
+                    "/extensions/acme-lint/0.0.1/download" => lint_archive,
+                    "/extensions/acme-format/0.2.0/download" => format_archive,
+                    _ => return Ok(Response::builder().status(404).body("not found".into())?),
+                };
+                Ok(Response::new(body.into()))
+            }
+        }
+    });
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+    let store = new_test_store("/the-extension-dir", fs.clone(), http_client, cx);
+    cx.executor().run_until_parked();
+
+    store
+        .update(cx, |store, cx| store.fetch_extension_collections(cx))
+        .await
+        .unwrap();
+    let collection = store.read_with(cx, |store, _| {
+        let collections = store.extension_collections();
+        assert_eq!(collections.len(), 1);
+        assert_eq!(collections[0].name, "Backend team baseline");
+        collections[0].clone()
+    });
+
+    // The collection's extensions are fetched by their ids, and installed.
+    listing_queries.lock().clear();
+    store
+        .update(cx, |store, cx| {
+            store.install_extension_collection(&collection, cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    assert_eq!(listing_queries.lock()[0], "ids=acme-lint,acme-format");
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("acme-lint"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+        assert!(matches!(
+            store.extension_status("acme-format"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.2.0"
+        ));
+    });
+}
+
+#[gpui::test]
+async fn test_registry_backoff(cx: &mut TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.registry_cache_ttl_secs = Some(0);
+            });
+        });
+    });
+
+    // The registry is busy for two requests, then lists the extensions, and
+    // then fails for every request after that.
+    let request_count = Arc::new(AtomicUsize::new(0));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let request_count = request_count.clone();
+        move |_| {
+            let request_index = request_count.fetch_add(1, SeqCst);
+            async move {
+                match request_index {
+                    0 | 1 => Ok(Response::builder()
+                        .status(429)
+                        .header("Retry-After", "2")
+                        .body("slow down".into())?),
+                    2 => Ok(Response::new(
+                        json!({
+                            "data": [{
+                                "id": "zed-ruby",
+                                "name": "Ruby",
+                                "version": "0.0.2",
+                                "description": null,
+                                "authors": [],
+                                "repository": "https://github.com/zed-industries/zed",
+                                "download_count": 1,
+                            }]
+                        })
+                        .to_string()
+                        .into(),
+                    )),
+                    _ => Ok(Response::builder().status(503).body("unavailable".into())?),
+                }
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    // Full listings wait for as long as the registry asks, plus some jitter,
+    // before retrying.
+    let fetch = store.update(cx, |store, cx| store.fetch_extensions(None, cx));
+    cx.executor().run_until_parked();
+    assert_eq!(request_count.load(SeqCst), 1);
+    assert!(store.read_with(cx, |store, _| store.is_registry_busy()));
+    cx.executor().advance_clock(Duration::from_secs(1));
+    cx.executor().run_until_parked();
+    assert_eq!(request_count.load(SeqCst), 1);
+    cx.executor().advance_clock(Duration::from_secs(2));
+    cx.executor().run_until_parked();
+    assert_eq!(request_count.load(SeqCst), 2);
+    cx.executor().advance_clock(Duration::from_secs(3));
+    cx.executor().run_until_parked();
+    let extensions = fetch.await.unwrap();
+    assert_eq!(request_count.load(SeqCst), 3);
+    assert_eq!(extensions[0].id.as_ref(), "zed-ruby");
+    assert!(!store.read_with(cx, |store, _| store.is_registry_busy()));
+
+    // Searches aren't retried, and aren't sent while the registry is being
+    // backed off from.
+    let search = |store: &Model<ExtensionStore>, cx: &mut TestAppContext| {
+        store.update(cx, |store, cx| store.fetch_extensions(Some("ruby"), cx))
+    };
+    assert!(search(&store, cx).await.is_err());
+    assert_eq!(request_count.load(SeqCst), 4);
+    assert!(store.read_with(cx, |store, _| store.is_registry_busy()));
+    assert!(search(&store, cx).await.is_err());
+    assert_eq!(request_count.load(SeqCst), 4);
+}
+
+#[gpui::test]
+async fn test_download_queue(cx: &mut TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.max_concurrent_downloads = Some(1);
+            });
+        });
+    });
+
+    // The registry never responds to the download requests.
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|_| futures::future::pending());
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    let states = |cx: &mut TestAppContext| {
+        store.read_with(cx, |store, _| {
+            store
+                .operations()
+                .into_iter()
+                .map(|entry| (entry.extension_id.to_string(), entry.state))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Downloads beyond the limit wait in the order they were started.
+    store.update(cx, |store, cx| {
+        for extension_id in ["zed-c", "zed-a", "zed-b"] {
+            store.install_extension(extension_id.into(), "0.0.1".into(), cx);
+        }
+    });
+    cx.executor().run_until_parked();
+    assert_eq!(
+        states(cx),
+        [
+            ("zed-a".to_string(), ExtensionOperationState::Queued(0)),
+            ("zed-b".to_string(), ExtensionOperationState::Queued(1)),
+            ("zed-c".to_string(), ExtensionOperationState::Pending),
+        ]
+    );
+
+    // Cancelling a queued download moves the ones behind it up.
+    store.update(cx, |store, _| store.cancel_download("zed-a"));
+    cx.executor().run_until_parked();
+    assert_eq!(
+        states(cx),
+        [
+            ("zed-b".to_string(), ExtensionOperationState::Queued(0)),
+            ("zed-c".to_string(), ExtensionOperationState::Pending),
+        ]
+    );
+
+    // Once the active download ends, the next one starts.
+    store.update(cx, |store, _| store.cancel_download("zed-c"));
+    cx.executor().run_until_parked();
+    assert_eq!(
+        states(cx),
+        [("zed-b".to_string(), ExtensionOperationState::Pending)]
+    );
+}
+
+#[gpui::test]
+async fn test_prefetch_metadata(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let requested_paths = Arc::new(Mutex::new(Vec::new()));
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create({
+        let requested_paths = requested_paths.clone();
+        move |request| {
+            let path = request.uri().path().to_string();
+            requested_paths.lock().push(path.clone());
+            async move {
+                let body = match path.as_str() {
+                    "/extensions" => json!({
+                        "data": [{
+                            "id": "zed-ruby",
+                            "name": "Ruby",
+                            "version": "0.0.2",
+                            "description": "Ruby support.",
+                            "authors": [],
+                            "repository": "https://github.com/zed-industries/zed",
+                            "download_count": 1,
+                        }]
+                    }),
+                    "/extensions/collections" => json!({
+                        "data": [{
+                            "id": "languages",
+                            "name": "Languages",
+                            "description": null,
+                            "extension_ids": ["zed-ruby"],
+                        }]
+                    }),
+                    _ => return Ok(Response::builder().status(404).body("not found".into())?),
+                };
+                Ok(Response::new(body.to_string().into()))
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    // Nothing is fetched until the delay has passed.
+    store.update(cx, |store, cx| store.prefetch_metadata(cx));
+    cx.executor().run_until_parked();
+    assert!(requested_paths.lock().is_empty());
+
+    cx.executor().advance_clock(METADATA_PREFETCH_DELAY);
+    cx.executor().run_until_parked();
+    let mut requested_paths = requested_paths.lock().clone();
+    requested_paths.sort();
+    assert_eq!(requested_paths, ["/extensions", "/extensions/collections"]);
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store.remote_extension("zed-ruby").unwrap().version.as_ref(),
+            "0.0.2"
+        );
+        assert_eq!(store.extension_collections()[0].id.as_ref(), "languages");
+    });
+}
+
+#[gpui::test]
+async fn test_orphaned_directories(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_200_response();
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-ruby": {
+                    "extension.toml": "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.2\"\n",
+                },
+                "zed-half-copied": {
+                    "languages": {},
+                },
+            },
+            "downloads": {
+                "zed-lua": {
+                    "extension.toml": "",
+                },
+            },
+            "previous_versions": {
+                "zed-ruby": { "0.0.1": {} },
+                "zed-uninstalled": { "0.0.1": { "extension.toml": "12345" } },
+            },
+            "tracked": {
+                "zed-untracked": {},
+            },
+            "work": {
+                "zed-uninstalled": { "data.txt": "kept" },
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+
+    let orphans = store
+        .update(cx, |store, cx| store.find_orphaned_directories(cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        orphans
+            .iter()
+            .map(|orphan| (orphan.path.as_path(), orphan.kind, orphan.size))
+            .collect::<Vec<_>>(),
+        [
+            (
+                Path::new("/the-extension-dir/downloads/zed-lua"),
+                OrphanedDirectoryKind::InterruptedDownload,
+                0,
+            ),
+            (
+                Path::new("/the-extension-dir/installed/zed-half-copied"),
+                OrphanedDirectoryKind::BrokenInstall,
+                0,
+            ),
+            (
+                Path::new("/the-extension-dir/previous_versions/zed-uninstalled"),
+                OrphanedDirectoryKind::PreviousVersions,
+                5,
+            ),
+            (
+                Path::new("/the-extension-dir/tracked/zed-untracked"),
+                OrphanedDirectoryKind::TrackedCheckout,
+                0,
+            ),
+        ]
+    );
+
+    let removed = store
+        .update(cx, |store, cx| store.remove_orphaned_directories(cx))
+        .await
+        .unwrap();
+    assert_eq!(removed, orphans);
+    for orphan in &orphans {
+        assert!(!fs.is_dir(&orphan.path).await);
+    }
+    assert!(
+        fs.is_dir(Path::new("/the-extension-dir/installed/zed-ruby"))
+            .await
+    );
+    assert!(
+        fs.is_dir(Path::new("/the-extension-dir/previous_versions/zed-ruby"))
+            .await
+    );
+    assert!(
+        fs.is_dir(Path::new("/the-extension-dir/work/zed-uninstalled"))
+            .await
+    );
+}
+
+#[gpui::test]
+async fn test_disk_usage(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_200_response();
+    let manifest = "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.2\"\n";
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-ruby": {
+                    "extension.toml": manifest,
+                    "grammars": { "ruby.wasm": "0123456789" },
+                },
+            },
+            "work": {
+                "zed-ruby": {
+                    "ruby-lsp": { "bin": "01234" },
+                    "data": { "cache": "012" },
+                },
+                "zed-uninstalled": { "data.txt": "0123456789" },
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+
+    store
+        .update(cx, |store, cx| store.refresh_disk_usage(cx))
+        .await
+        .unwrap();
+
+    // The usage includes the language servers that the extension downloaded
+    // and its data, but not the data of extensions that aren't installed.
+    let expected_usage = manifest.len() as u64 + 10 + 5 + 3;
+    store.read_with(cx, |store, cx| {
+        assert_eq!(store.extension_disk_usage("zed-ruby"), Some(expected_usage));
+        assert_eq!(store.extension_disk_usage("zed-uninstalled"), None);
+        assert_eq!(store.total_disk_usage(), expected_usage);
+        assert!(!store.would_exceed_disk_quota(u64::MAX / 2, cx));
+    });
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.disk_quota_mb = Some(1);
+            });
+        });
+    });
+    store.read_with(cx, |store, cx| {
+        assert_eq!(store.disk_quota(cx), Some(1024 * 1024));
+        assert!(!store.would_exceed_disk_quota(1024 * 1024 - expected_usage, cx));
+        assert!(store.would_exceed_disk_quota(1024 * 1024 - expected_usage + 1, cx));
+    });
+}
+
+#[gpui::test]
+async fn test_git_extension_sources(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        match request.uri().path() {
+            "/extensions" => Ok(Response::new(
+                json!({
+                    "data": [{
+                        "id": "zed-ruby",
+                        "name": "Ruby",
+                        "version": "0.0.2",
+                        "description": null,
+                        "authors": [],
+                        "repository": "https://github.com/zed-industries/zed",
+                        "download_count": 1,
+                    }]
+                })
+                .to_string()
+                .into(),
+            )),
+            _ => Ok(Response::builder().status(404).body("not found".into())?),
+        }
+    });
+
+    let source = ExtensionSource::Git {
+        repository_url: "https://github.com/someone/zed-ruby".into(),
+        revision: "0123456789abcdef".into(),
+    };
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-ruby": {
+                    "extension.toml": "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.1\"\n",
+                },
+            },
+            "git_extensions.json": json!({ "zed-ruby": source }).to_string(),
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let new_store = |cx: &mut TestAppContext| {
+        cx.new_model(|cx| {
+            ExtensionStore::new(
+                PathBuf::from("/the-extension-dir"),
+                None,
+                fs.clone(),
+                http_client.clone(),
+                node_runtime.clone(),
+                language_registry.clone(),
+                theme_registry.clone(),
+                cx,
+            )
+        })
+    };
+    let store = new_store(cx);
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_source("zed-ruby"), Some(&source));
+    });
+
+    // Extensions installed from git aren't replaced by the registry's releases.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.auto_update_extensions = Some(true);
+            });
+        });
+    });
+    let update = store.update(cx, |store, cx| store.auto_update_extensions(cx));
+    cx.executor().run_until_parked();
+    update.await.unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+    });
+
+    // Uninstalling the extension forgets its repository.
+    store.update(cx, |store, cx| {
+        store.uninstall_extension("zed-ruby".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_source("zed-ruby"), None);
+    });
+    let store = new_store(cx);
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_source("zed-ruby"), None);
+    });
+}
+
+#[gpui::test]
+async fn test_install_from_github_release(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let manifest = "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.3\"\n";
+    let mut bytes = Vec::<u8>::new();
+    let mut archive = async_tar::Builder::new(&mut bytes);
+    let mut header = async_tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    archive
+        .append_data(&mut header, "extension.toml", manifest.as_bytes())
+        .await
+        .unwrap();
+    archive.into_inner().await.unwrap();
+    let mut gzipped_bytes = Vec::new();
+    let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+    encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+
+    // The release of `someone/bad-ruby` publishes the wrong checksum.
+    let mut files = HashMap::default();
+    for (repository, sha256) in [
+        (
+            "someone/zed-ruby",
+            format!("{:x}", Sha256::digest(&gzipped_bytes)),
+        ),
+        ("someone/bad-ruby", "0".repeat(64)),
+    ] {
+        let download_path = format!("/{repository}/releases/download/v0.0.3");
+        files.insert(
+            format!("/repos/{repository}/releases"),
+            json!([{
+                "tag_name": "v0.0.3",
+                "prerelease": false,
+                "tarball_url": "",
+                "zipball_url": "",
+                "assets": [
+                    {
+                        "name": "zed-ruby.tar.gz",
+                        "browser_download_url": format!("https://github.com{download_path}/zed-ruby.tar.gz"),
+                    },
+                    {
+                        "name": "zed-ruby.tar.gz.sha256",
+                        "browser_download_url": format!("https://github.com{download_path}/zed-ruby.tar.gz.sha256"),
+                    },
+                ],
+            }])
+            .to_string()
+            .into_bytes(),
+        );
+        files.insert(
+            format!("{download_path}/zed-ruby.tar.gz"),
+            gzipped_bytes.clone(),
+        );
+        files.insert(
+            format!("{download_path}/zed-ruby.tar.gz.sha256"),
+            format!("{sha256}  zed-ruby.tar.gz\n").into_bytes(),
+        );
+    }
+    let http_client = FakeHttpClient::create({
+        let files = Arc::new(files);
+        move |request| {
+            let body = files.get(request.uri().path()).cloned();
+            async move {
+                match body {
+                    Some(body) => Ok(Response::new(body.into())),
+                    None => Ok(Response::builder().status(404).body("not found".into())?),
+                }
+            }
+        }
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let install = store.update(cx, |store, cx| {
+        store.install_from_github_release("https://github.com/someone/zed-ruby".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    install.await.unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.3"
+        ));
+        assert_eq!(
+            store.extension_source("zed-ruby"),
+            Some(&ExtensionSource::GithubRelease {
+                repository: "someone/zed-ruby".into(),
+                tag: "v0.0.3".into(),
+            })
+        );
+    });
+
+    let install = store.update(cx, |store, cx| {
+        store.install_from_github_release("someone/bad-ruby".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    let error = install.await.unwrap_err().to_string();
+    assert!(error.contains("checksum mismatch"), "{error}");
+
+    let install = store.update(cx, |store, cx| {
+        store.install_from_github_release("https://gitlab.com/someone/zed-ruby".into(), cx)
+    });
+    assert!(install.await.is_err());
+}
+
+#[gpui::test]
+async fn test_staged_extension_changes(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        let version = match request.uri().path() {
+            "/extensions/zed-ruby/0.0.1/download" => "0.0.1",
+            "/extensions/zed-ruby/0.0.2/download" => "0.0.2",
+            _ => return Ok(Response::builder().status(404).body("not found".into())?),
+        };
+        let manifest = format!("id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"{version}\"\n");
+        let mut bytes = Vec::<u8>::new();
+        let mut archive = async_tar::Builder::new(&mut bytes);
+        let mut header = async_tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        archive
+            .append_data(&mut header, "extension.toml", manifest.as_bytes())
+            .await
+            .unwrap();
+        archive.into_inner().await.unwrap();
+        let mut gzipped_bytes = Vec::new();
+        let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+        encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+        Ok(Response::new(gzipped_bytes.into()))
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![("zed-ruby".into(), "0.0.1".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert!(batch.await[0].1.is_ok());
+
+    // With staging turned on, an upgrade is unpacked but not applied.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.stage_extension_changes = Some(true);
+            });
+        });
+    });
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Upgrade,
+            vec![("zed-ruby".into(), "0.0.2".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert!(batch.await[0].1.is_ok());
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+        let pending_change = &store.pending_changes()["zed-ruby"];
+        assert_eq!(pending_change.version.as_ref(), "0.0.2");
+        assert_eq!(pending_change.previous_version.as_deref(), Some("0.0.1"));
+    });
+    assert!(
+        fs.is_file(Path::new(
+            "/the-extension-dir/pending/zed-ruby/extension.toml"
+        ))
+        .await
+    );
+
+    let apply = store.update(cx, |store, cx| store.apply_pending_changes(cx));
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    apply.await.unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.2"
+        ));
+        assert!(store.pending_changes().is_empty());
+    });
+    assert!(
+        !fs.is_dir(Path::new("/the-extension-dir/pending/zed-ruby"))
+            .await
+    );
+}
+
+#[gpui::test]
+async fn test_recover_interrupted_installs(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    // The upgrade of zed-ruby was interrupted before the new version was moved
+    // into place, and the upgrade of zed-lua after it.
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-lua": {
+                    "extension.toml": "id = \"zed-lua\"\nname = \"Lua\"\nversion = \"0.0.2\"\n",
+                },
+            },
+            "downloads": {
+                "zed-ruby.replaced": {
+                    "extension.toml": "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.1\"\n",
+                },
+                "zed-lua.replaced": {
+                    "extension.toml": "id = \"zed-lua\"\nname = \"Lua\"\nversion = \"0.0.1\"\n",
+                },
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            FakeHttpClient::with_404_response(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+        assert!(matches!(
+            store.extension_status("zed-lua"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.2"
+        ));
+    });
+    for replaced_dir in ["zed-ruby.replaced", "zed-lua.replaced"] {
+        assert!(
+            !fs.is_dir(&Path::new("/the-extension-dir/downloads").join(replaced_dir))
+                .await
+        );
+    }
+}
+
+#[gpui::test]
+async fn test_detect_damaged_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let lua_manifest = "id = \"zed-lua\"\nname = \"Lua\"\nversion = \"0.0.1\"\n";
+    let gleam_manifest = "id = \"zed-gleam\"\nname = \"Gleam\"\nversion = \"0.0.1\"\n";
+    let checksums = |files: &[(&str, &str)]| {
+        serde_json::to_string(
+            &files
+                .iter()
+                .map(|(path, content)| (*path, format!("{:x}", Sha256::digest(content))))
+                .collect::<BTreeMap<_, _>>(),
+        )
+        .unwrap()
+    };
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/the-extension-dir/installed",
+        json!({
+            "zed-ruby": {
+                "extension.toml": "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.1\"\n",
+                "languages": {
+                    "ruby": {
+                        "config.toml": "name = \"Ruby\"\npath_suffixes = [\"rb\"]\n",
+                    },
+                },
+            },
+            "zed-lua": {
+                "extension.toml": lua_manifest,
+                ".checksums.json": checksums(&[("extension.toml", lua_manifest)]),
+            },
+            "zed-gleam": {
+                "extension.toml": gleam_manifest,
+                "README.md": "# Gleam",
+                ".checksums.json": checksums(&[
+                    ("extension.toml", gleam_manifest),
+                    ("README.md", "# Gleam"),
+                ]),
+            },
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let new_store = |cx: &mut TestAppContext| {
+        cx.new_model(|cx| {
+            ExtensionStore::new(
+                PathBuf::from("/the-extension-dir"),
+                None,
+                fs.clone(),
+                FakeHttpClient::with_404_response(),
+                node_runtime.clone(),
+                language_registry.clone(),
+                theme_registry.clone(),
+                cx,
+            )
+        })
+    };
+
+    // Intact extensions aren't damaged, and starting up records their index.
+    let store = new_store(cx);
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        for extension_id in ["zed-ruby", "zed-lua", "zed-gleam"] {
+            assert_eq!(store.damaged_extension(extension_id), None);
+        }
+    });
+    drop(store);
+    cx.executor().run_until_parked();
+
+    fs.remove_file(
+        Path::new("/the-extension-dir/installed/zed-ruby/languages/ruby/config.toml"),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    fs.insert_file(
+        "/the-extension-dir/installed/zed-lua/extension.toml",
+        lua_manifest.replace("Lua", "Moon").into_bytes(),
+    )
+    .await;
+    fs.remove_file(
+        Path::new("/the-extension-dir/installed/zed-gleam/README.md"),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+
+    let store = new_store(cx);
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store
+                .damaged_extension("zed-ruby")
+                .map(|problem| problem.as_ref()),
+            Some("languages/ruby/config.toml is missing")
+        );
+        assert_eq!(
+            store
+                .damaged_extension("zed-lua")
+                .map(|problem| problem.as_ref()),
+            Some("extension.toml has been modified")
+        );
+        assert_eq!(
+            store
+                .damaged_extension("zed-gleam")
+                .map(|problem| problem.as_ref()),
+            Some("README.md is missing")
+        );
+    });
+
+    // Damaged extensions stay listed once the index is rebuilt, even when
+    // they can no longer be indexed, but their resources aren't loaded.
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(_)
+        ));
+    });
+    assert!(!language_registry
+        .language_names()
+        .contains(&"Ruby".to_string()));
+}
+
+#[gpui::test]
+async fn test_reinstall_extension(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let manifest = "id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"0.0.1\"\n";
+    let fs = FakeFs::new(cx.executor());
+    let download_count = Arc::new(AtomicUsize::new(0));
+    let http_client = FakeHttpClient::create({
+        let download_count = download_count.clone();
+        move |request| {
+            let download_count = download_count.clone();
+            async move {
+                if request.uri().path() != "/extensions/zed-ruby/0.0.1/download" {
+                    return Ok(Response::builder().status(404).body("not found".into())?);
+                }
+                download_count.fetch_add(1, SeqCst);
+                let mut bytes = Vec::<u8>::new();
+                let mut archive = async_tar::Builder::new(&mut bytes);
+                let mut header = async_tar::Header::new_gnu();
+                header.set_size(manifest.len() as u64);
+                archive
+                    .append_data(&mut header, "extension.toml", manifest.as_bytes())
+                    .await
+                    .unwrap();
+                archive.into_inner().await.unwrap();
+                let mut gzipped_bytes = Vec::new();
+                let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+                encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+                Ok(Response::new(gzipped_bytes.into()))
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![("zed-ruby".into(), "0.0.1".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert!(batch.await[0].1.is_ok());
+    store.update(cx, |store, cx| store.pin_extension("zed-ruby", cx));
+
+    let manifest_path = Path::new("/the-extension-dir/installed/zed-ruby/extension.toml");
+    fs.insert_file(manifest_path, b"corrupted".to_vec()).await;
+
+    let reinstall = store.update(cx, |store, cx| store.reinstall_all_extensions(cx));
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    let results = reinstall.await;
+    assert_eq!(results.len(), 1);
+    assert!(results[0].1.is_ok());
+
+    // The installed version is downloaded again in place, without being
+    // retained as a previous version, and stays pinned.
+    assert_eq!(download_count.load(SeqCst), 2);
+    assert_eq!(fs.load(manifest_path).await.unwrap(), manifest);
+    assert!(
+        !fs.is_dir(Path::new("/the-extension-dir/previous_versions/zed-ruby"))
+            .await
+    );
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+        assert_eq!(
+            store.pinned_version("zed-ruby").map(|v| v.as_ref()),
+            Some("0.0.1")
+        );
+    });
+
+    let reinstall = store.update(cx, |store, cx| {
+        store.reinstall_extension("zed-lua".into(), cx)
+    });
+    assert!(reinstall.await.is_err());
+}
+
+#[gpui::test]
+async fn test_extension_blocklist(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let publish_blocklist = Arc::new(AtomicBool::new(true));
+    let http_client = FakeHttpClient::create({
+        let publish_blocklist = publish_blocklist.clone();
+        move |request| {
+            let publish_blocklist = publish_blocklist.load(SeqCst);
+            async move {
+                let version = match request.uri().path() {
+                    "/extensions/blocklist" if publish_blocklist => {
+                        return Ok(Response::new(
+                            json!({
+                                "data": [{
+                                    "extension_id": "zed-ruby",
+                                    "version": "0.0.2",
+                                    "reason": "This release uploads your files.",
+                                    "safe_version": "0.0.1",
+                                }]
+                            })
+                            .to_string()
+                            .into(),
+                        ))
+                    }
+                    "/extensions/zed-ruby/0.0.1/download" => "0.0.1",
+                    "/extensions/zed-ruby/0.0.2/download" => "0.0.2",
+                    _ => return Ok(Response::builder().status(404).body("not found".into())?),
+                };
+                let manifest =
+                    format!("id = \"zed-ruby\"\nname = \"Ruby\"\nversion = \"{version}\"\n");
+                let mut bytes = Vec::<u8>::new();
+                let mut archive = async_tar::Builder::new(&mut bytes);
+                let mut header = async_tar::Header::new_gnu();
+                header.set_size(manifest.len() as u64);
+                archive
+                    .append_data(&mut header, "extension.toml", manifest.as_bytes())
+                    .await
+                    .unwrap();
+                archive.into_inner().await.unwrap();
+                let mut gzipped_bytes = Vec::new();
+                let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+                encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+                Ok(Response::new(gzipped_bytes.into()))
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![("zed-ruby".into(), "0.0.2".into())],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert!(batch.await[0].1.is_ok());
+    store.update(cx, |store, cx| store.pin_extension("zed-ruby", cx));
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    cx.update(|cx| {
+        let events = events.clone();
+        cx.subscribe(&store, move |_, event, _| {
+            if let Event::InstalledVersionsBlocked { extension_ids } = event {
+                events.lock().push(extension_ids.clone());
+            }
+        })
+        .detach();
+    });
+
+    // Once the registry blocks the installed version, it is flagged, and it
+    // can't be installed again.
+    store
+        .update(cx, |store, cx| store.fetch_blocklist(cx))
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    assert_eq!(*events.lock(), vec![vec![Arc::<str>::from("zed-ruby")]]);
+    store.read_with(cx, |store, _| {
+        let blocked = store.installed_version_block("zed-ruby").unwrap();
+        assert_eq!(blocked.reason, "This release uploads your files.");
+    });
+    assert!(
+        fs.is_file(Path::new("/the-extension-dir/blocklist.json"))
+            .await
+    );
+    let reinstall = store.update(cx, |store, cx| {
+        store.reinstall_extension("zed-ruby".into(), cx)
+    });
+    assert!(reinstall.await.is_err());
+
+    // Downgrading installs the recommended version, keeping the extension
+    // pinned, and doesn't retain the blocked version.
+    let downgrade = store.update(cx, |store, cx| {
+        store.downgrade_blocked_extension("zed-ruby".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    downgrade.await.unwrap();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+        assert!(store.installed_version_block("zed-ruby").is_none());
+        assert_eq!(
+            store.pinned_version("zed-ruby").map(|v| v.as_ref()),
+            Some("0.0.1")
+        );
+    });
+    assert!(
+        !fs.is_dir(Path::new(
+            "/the-extension-dir/previous_versions/zed-ruby/0.0.2"
+        ))
+        .await
+    );
+
+    // A registry that stops serving its blocklist is reported as an error,
+    // rather than unblocking everything.
+    publish_blocklist.store(false, SeqCst);
+    let fetch = store.update(cx, |store, cx| store.fetch_blocklist(cx));
+    assert!(fetch.await.is_err());
+    store.read_with(cx, |store, _| {
+        assert!(store.blocked_version("zed-ruby", "0.0.2").is_some());
+    });
+}
+
+#[gpui::test]
+async fn test_extension_install_policy(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::create(|request| async move {
+        let (id, name) = match request.uri().path() {
+            "/extensions/zed-ruby/0.0.1/download" => ("zed-ruby", "Ruby"),
+            "/extensions/acme-lint/0.0.1/download" => ("acme-lint", "Acme Lint"),
+            _ => return Ok(Response::builder().status(404).body("not found".into())?),
+        };
+        let manifest = format!("id = \"{id}\"\nname = \"{name}\"\nversion = \"0.0.1\"\n");
+        let mut bytes = Vec::<u8>::new();
+        let mut archive = async_tar::Builder::new(&mut bytes);
+        let mut header = async_tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        archive
+            .append_data(&mut header, "extension.toml", manifest.as_bytes())
+            .await
+            .unwrap();
+        archive.into_inner().await.unwrap();
+        let mut gzipped_bytes = Vec::new();
+        let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+        encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+        Ok(Response::new(gzipped_bytes.into()))
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    // The managed settings only allow the organization's own extensions, and
+    // the user's settings can't widen that.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings
+                .set_managed_settings(
+                    r#"{ "extensions": { "allowed_extensions": ["acme-*"] } }"#,
+                    cx,
+                )
+                .unwrap();
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.allowed_extensions = Some(vec!["*".into()]);
+            });
+        });
+    });
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Install,
+            vec![
+                ("zed-ruby".into(), "0.0.1".into()),
+                ("acme-lint".into(), "0.0.1".into()),
+            ],
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    let results = batch.await;
+    assert_eq!(
+        results[0].1.as_ref().unwrap_err().to_string(),
+        "extension zed-ruby is blocked by your organization"
+    );
+    assert!(results[1].1.is_ok());
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("zed-ruby"),
+            ExtensionStatus::NotInstalled
+        ));
+        assert!(matches!(
+            store.extension_status("acme-lint"),
+            ExtensionStatus::Installed(_)
+        ));
+    });
+
+    // Blocked patterns take precedence over allowed ones.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings
+                .set_managed_settings(
+                    r#"{
+                        "extensions": {
+                            "allowed_extensions": ["acme-*", "zed-ruby"],
+                            "blocked_extensions": ["*-lint"]
+                        }
+                    }"#,
+                    cx,
+                )
+                .unwrap();
+        });
+        let settings = ExtensionSettings::get_global(cx);
+        assert!(settings.allows_extension("zed-ruby"));
+        assert!(settings.allows_extension("acme-format"));
+        assert!(!settings.allows_extension("acme-lint"));
+        assert!(!settings.allows_extension("zed-ruby-extra"));
+    });
+
+    // Dev extensions can't be installed or rebuilt outside the policy either.
+    fs.insert_tree(
+        "/the-dev-extension",
+        json!({
+            "extension.toml": "id = \"zed-lint\"\nname = \"Zed Lint\"\nversion = \"0.1.0\"\n",
+        }),
+    )
+    .await;
+    let error = store
+        .update(cx, |store, cx| {
+            store.install_dev_extension(PathBuf::from("/the-dev-extension"), cx)
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "extension zed-lint is blocked by your organization"
+    );
+    assert!(fs
+        .metadata(Path::new("/the-extension-dir/installed/zed-lint"))
+        .await
+        .unwrap()
+        .is_none());
+    let error = store
+        .update(cx, |store, cx| {
+            store.rebuild_dev_extension("acme-lint".into(), cx)
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "extension acme-lint is blocked by your organization"
+    );
+}
+
+#[gpui::test]
+async fn test_rebuild_dev_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+    for (id, name) in [("acme-lint", "Acme Lint"), ("zed-lint", "Zed Lint")] {
+        fs.insert_tree(
+            format!("/dev-extensions/{id}"),
+            json!({
+                "extension.toml": format!("id = \"{id}\"\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+            }),
+        )
+        .await;
+    }
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+    for id in ["acme-lint", "zed-lint"] {
+        fs.insert_symlink(
+            format!("/the-extension-dir/installed/{id}"),
+            PathBuf::from(format!("/dev-extensions/{id}")),
+        )
+        .await;
+    }
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings
+                .set_managed_settings(
+                    r#"{ "extensions": { "allowed_extensions": ["acme-*"] } }"#,
+                    cx,
+                )
+                .unwrap();
+        });
+    });
+
+    let store = new_test_store("/the-extension-dir", fs.clone(), http_client, cx);
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.dev_extensions().count(), 2);
+        assert_eq!(store.dev_extensions_rebuild_log(), None);
+    });
+
+    // The result of each build is collected in a single log, followed by a
+    // summary of the whole rebuild.
+    let results = store
+        .update(cx, |store, cx| store.rebuild_dev_extensions(cx))
+        .await;
+    cx.executor().run_until_parked();
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|(_, result)| result.is_err()));
+    let log = store.read_with(cx, |store, _| store.dev_extensions_rebuild_log().unwrap());
+    assert!(log.contains("Rebuilding acme-lint\n"), "{log}");
+    assert!(log.contains("Failed to rebuild acme-lint: "), "{log}");
+    assert!(
+        log.contains(
+            "Rebuilding zed-lint\nFailed to rebuild zed-lint: \
+             extension zed-lint is blocked by your organization\n"
+        ),
+        "{log}"
+    );
+    assert!(
+        log.ends_with("Rebuilt 0 dev extensions, 2 failed.\n"),
+        "{log}"
+    );
+}
+
+#[gpui::test]
+async fn test_required_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let archive = build_archive(&[(
+        "extension.toml",
+        "id = \"acme-lint\"\nname = \"Acme Lint\"\nversion = \"0.0.1\"\n",
+    )])
+    .await;
+    let fail_downloads = Arc::new(AtomicBool::new(true));
+    let download_count = Arc::new(AtomicUsize::new(0));
+    let http_client = FakeHttpClient::create({
+        let fail_downloads = fail_downloads.clone();
+        let download_count = download_count.clone();
+        move |request| {
+            let response = match request.uri().path() {
+                "/extensions" => Some(
+                    json!({
+                        "data": [{
+                            "id": "acme-lint",
+                            "name": "Acme Lint",
+                            "version": "0.0.1",
+                            "description": null,
+                            "authors": [],
+                            "repository": "https://github.com/zed-industries/zed",
+                            "download_count": 1,
+                        }]
+                    })
+                    .to_string()
+                    .into_bytes(),
+                ),
+                "/extensions/acme-lint/0.0.1/download" => {
+                    download_count.fetch_add(1, SeqCst);
+                    (!fail_downloads.load(SeqCst)).then(|| archive.clone())
+                }
+                _ => None,
+            };
+            async move {
+                match response {
+                    Some(body) => Ok(Response::new(body.into())),
+                    None => Ok(Response::builder().status(503).body("unavailable".into())?),
+                }
+            }
+        }
+    });
+
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings
+                .set_managed_settings(
+                    r#"{ "extensions": { "required_extensions": ["acme-lint"] } }"#,
+                    cx,
+                )
+                .unwrap();
+        });
+    });
+
+    let store = new_test_store("/the-extension-dir", fs.clone(), http_client, cx);
+
+    // Required extensions that aren't installed are installed automatically.
+    store.update(cx, |store, cx| store.sync_required_extensions(cx));
+    cx.executor().run_until_parked();
+    assert_eq!(download_count.load(SeqCst), 1);
+
+    // Other changes to the settings don't install them again.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.auto_update_extensions = Some(false);
+            });
+        });
+    });
+    store.update(cx, |store, cx| store.sync_required_extensions(cx));
+    cx.executor().run_until_parked();
+    assert_eq!(download_count.load(SeqCst), 1);
+
+    // Installs that failed are retried after a delay.
+    fail_downloads.store(false, SeqCst);
+    cx.executor().advance_clock(REQUIRED_EXTENSIONS_RETRY_DELAY);
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    assert_eq!(download_count.load(SeqCst), 2);
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("acme-lint"),
+            ExtensionStatus::Installed(version) if version.as_ref() == "0.0.1"
+        ));
+    });
+
+    // They can't be uninstalled.
+    let batch = store.update(cx, |store, cx| {
+        store.run_batch_operation(
+            ExtensionOperation::Remove,
+            vec![("acme-lint".into(), "0.0.1".into())],
+            cx,
+        )
+    });
+    assert_eq!(
+        batch.await[0].1.as_ref().unwrap_err().to_string(),
+        "extension acme-lint is managed by your organization"
+    );
+    store.update(cx, |store, cx| {
+        store.uninstall_extension("acme-lint".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert!(matches!(
+            store.extension_status("acme-lint"),
+            ExtensionStatus::Installed(_)
+        ));
+    });
+}
+
+#[gpui::test]
+async fn test_extension_activity(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-ruby": {
+                    "extension.json": r#"{
+                        "id": "zed-ruby",
+                        "name": "Zed Ruby",
+                        "version": "1.0.0",
+                        "languages": {
+                            "ruby": "languages/ruby"
+                        }
+                    }"#,
+                    "languages": {
+                        "ruby": {
+                            "config.toml": r#"
+                                name = "Ruby"
+                                path_suffixes = ["rb"]
+                            "#,
+                        }
+                    },
+                }
+            }
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry.clone(),
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_last_used("zed-ruby"), None);
+    });
+
+    // Loading one of the extension's languages records that it was used. The
+    // language's config isn't on the real filesystem, so the load itself fails.
+    language_registry.language_for_name("Ruby").await.ok();
+    cx.executor().run_until_parked();
+    let last_used = store.read_with(cx, |store, _| store.extension_last_used("zed-ruby"));
+    assert!(last_used.is_some());
+    let activity = fs
+        .load(Path::new("/the-extension-dir/activity.json"))
+        .await
+        .unwrap();
+    assert!(activity.contains("zed-ruby"));
+
+    // Further uses within the same hour aren't recorded again.
+    store.update(cx, |store, cx| {
+        store.record_extension_activation("zed-ruby".into(), cx)
+    });
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_last_used("zed-ruby"), last_used);
+    });
+
+    // Extensions that aren't installed aren't recorded.
+    store.update(cx, |store, cx| {
+        store.record_extension_activation("zed-gleam".into(), cx)
+    });
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_last_used("zed-gleam"), None);
+    });
+
+    // Uninstalling an extension forgets when it was used.
+    store.update(cx, |store, cx| {
+        store.uninstall_extension("zed-ruby".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_last_used("zed-ruby"), None);
+    });
+    let activity = fs
+        .load(Path::new("/the-extension-dir/activity.json"))
+        .await
+        .unwrap();
+    assert!(!activity.contains("zed-ruby"));
+}
+
+#[gpui::test]
+async fn test_extension_load_times(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-monokai": {
+                    "extension.json": r#"{
+                        "id": "zed-monokai",
+                        "name": "Zed Monokai",
+                        "version": "2.0.0",
+                        "themes": ["themes/monokai.json"]
+                    }"#,
+                    "themes": {
+                        "monokai.json": r#"{
+                            "name": "Monokai",
+                            "author": "Someone",
+                            "themes": []
+                        }"#,
+                    },
+                },
+                "zed-ruby": {
+                    "extension.json": r#"{
+                        "id": "zed-ruby",
+                        "name": "Zed Ruby",
+                        "version": "1.0.0"
+                    }"#,
+                },
+            }
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+
+    // Every loaded extension has a load time, even if loading it did nothing.
+    store.read_with(cx, |store, _| {
+        assert!(store.extension_load_time("zed-monokai").is_some());
+        assert_eq!(store.extension_load_time("zed-ruby"), Some(Duration::ZERO));
+        assert_eq!(store.extension_load_time("zed-gleam"), None);
+    });
+
+    // Unloaded extensions no longer have one.
+    store.update(cx, |store, cx| {
+        store.uninstall_extension("zed-monokai".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.extension_load_time("zed-monokai"), None);
+    });
+}
+
+#[gpui::test]
+async fn test_disable_crashing_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "installed": {
+                "zed-ruby": {
+                    "extension.json": r#"{
+                        "id": "zed-ruby",
+                        "name": "Zed Ruby",
+                        "version": "1.0.0"
+                    }"#,
+                },
+            }
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    cx.update(|cx| {
+        let events = events.clone();
+        cx.subscribe(&store, move |_, event, _| {
+            if let Event::ExtensionDisabledAfterCrashes {
+                extension_id,
+                error,
+            } = event
+            {
+                events.lock().push((extension_id.clone(), error.clone()));
+            }
+        })
+        .detach();
+    });
+
+    // Extensions are left alone until they crash as many times as the
+    // `crash_limit` setting allows.
+    let crash = |description: &str, cx: &mut TestAppContext| {
+        store.update(cx, |store, cx| {
+            store.record_extension_crash("zed-ruby".into(), description.into(), cx)
+        });
+    };
+    crash("wasm trap: unreachable", cx);
+    crash("wasm trap: unreachable", cx);
+    store.read_with(cx, |store, _| {
+        assert!(store.extension_disabled_until("zed-ruby").is_none());
+        assert!(store.extension_crash_error("zed-ruby").is_none());
+    });
+
+    crash("wasm trap: out of bounds", cx);
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store.extension_disabled_until("zed-ruby"),
+            Some(DisabledUntil::Reenabled)
+        );
+        assert_eq!(
+            store
+                .extension_crash_error("zed-ruby")
+                .map(|error| error.as_ref()),
+            Some("wasm trap: out of bounds")
+        );
+    });
+    assert_eq!(
+        events.lock().as_slice(),
+        [(
+            Arc::<str>::from("zed-ruby"),
+            SharedString::from("wasm trap: out of bounds")
+        )]
+    );
+
+    // Re-enabling the extension clears its crashes.
+    store.update(cx, |store, cx| store.enable_extension("zed-ruby", cx));
+    store.read_with(cx, |store, _| {
+        assert!(store.extension_disabled_until("zed-ruby").is_none());
+        assert!(store.extension_crash_error("zed-ruby").is_none());
+    });
+
+    // A crash limit of zero never disables extensions.
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.crash_limit = Some(0);
+            });
+        });
+    });
+    for _ in 0..5 {
+        crash("wasm trap: unreachable", cx);
+    }
+    store.read_with(cx, |store, _| {
+        assert!(store.extension_disabled_until("zed-ruby").is_none());
+    });
+}
+
+#[gpui::test]
+async fn test_safe_mode(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "safe_mode": "",
+            "installed": {
+                "zed-ruby": {
+                    "extension.json": r#"{
+                        "id": "zed-ruby",
+                        "name": "Zed Ruby",
+                        "version": "1.0.0"
+                    }"#,
+                },
+            }
+        }),
+    )
+    .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+
+    // In safe mode, the installed extensions are listed but not loaded.
+    store.read_with(cx, |store, _| {
+        assert!(store.is_in_safe_mode());
+        assert!(store.installed_extension("zed-ruby").is_some());
+        assert_eq!(store.loaded_extensions().count(), 0);
+    });
+
+    // Leaving safe mode takes effect on the next launch.
+    store
+        .update(cx, |store, cx| store.set_safe_mode_on_restart(false, cx))
+        .await
+        .unwrap();
+    assert!(!fs.is_file(Path::new("/the-extension-dir/safe_mode")).await);
+    store.read_with(cx, |store, _| assert!(store.is_in_safe_mode()));
+
+    store
+        .update(cx, |store, cx| store.set_safe_mode_on_restart(true, cx))
+        .await
+        .unwrap();
+    assert!(fs.is_file(Path::new("/the-extension-dir/safe_mode")).await);
+}
+
+#[gpui::test]
+async fn test_extension_bisect(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+
+    let mut installed = serde_json::Map::new();
+    for extension_id in ["zed-elixir", "zed-gleam", "zed-ruby"] {
+        installed.insert(
+            extension_id.into(),
+            json!({
+                "extension.json": format!(
+                    r#"{{"id": "{extension_id}", "name": "{extension_id}", "version": "1.0.0"}}"#
+                ),
+            }),
+        );
+    }
+    fs.insert_tree("/the-extension-dir", json!({ "installed": installed }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let new_store = |cx: &mut TestAppContext| {
+        let store = cx.new_model(|cx| {
+            ExtensionStore::new(
+                PathBuf::from("/the-extension-dir"),
+                None,
+                fs.clone(),
+                http_client.clone(),
+                FakeNodeRuntime::new(),
+                language_registry.clone(),
+                theme_registry.clone(),
+                cx,
+            )
+        });
+        cx.executor().run_until_parked();
+        cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+        cx.executor().run_until_parked();
+        store
+    };
+    let loaded_extensions = |store: &Model<ExtensionStore>, cx: &mut TestAppContext| {
+        store.read_with(cx, |store, _| {
+            store
+                .loaded_extensions()
+                .map(|manifest| manifest.id.to_string())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let store = new_store(cx);
+    assert!(store.read_with(cx, |store, _| store.extension_bisect().is_none()));
+    let bisect = store
+        .update(cx, |store, cx| store.start_bisect(cx))
+        .await
+        .unwrap();
+    assert_eq!(bisect.candidate_count(), 3);
+
+    // The bisect takes effect the next time Zed is launched.
+    assert_eq!(
+        loaded_extensions(&store, cx),
+        ["zed-elixir", "zed-gleam", "zed-ruby"]
+    );
+    drop(store);
+    let store = new_store(cx);
+    assert!(loaded_extensions(&store, cx).is_empty());
+
+    // The problem went away with all of the extensions disabled.
+    let step = store
+        .update(cx, |store, cx| store.continue_bisect(false, cx))
+        .await
+        .unwrap();
+    assert!(matches!(step, ExtensionBisectStep::Continue(_)));
+    drop(store);
+    let store = new_store(cx);
+    assert_eq!(loaded_extensions(&store, cx), ["zed-elixir"]);
+
+    // The problem went away with zed-gleam and zed-ruby disabled.
+    let step = store
+        .update(cx, |store, cx| store.continue_bisect(false, cx))
+        .await
+        .unwrap();
+    assert!(matches!(step, ExtensionBisectStep::Continue(_)));
+    drop(store);
+    let store = new_store(cx);
+    assert_eq!(loaded_extensions(&store, cx), ["zed-elixir", "zed-gleam"]);
+
+    // The problem was still present with only zed-gleam loaded.
+    let step = store
+        .update(cx, |store, cx| store.continue_bisect(true, cx))
+        .await
+        .unwrap();
+    assert_eq!(step, ExtensionBisectStep::Found("zed-gleam".into()));
+
+    // Once the bisect is finished, all of the extensions are loaded again.
+    drop(store);
+    let store = new_store(cx);
+    assert!(store.read_with(cx, |store, _| store.extension_bisect().is_none()));
+    assert_eq!(
+        loaded_extensions(&store, cx),
+        ["zed-elixir", "zed-gleam", "zed-ruby"]
+    );
+}
+
+#[gpui::test]
+async fn test_extension_logs(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    cx.update(|cx| {
+        let events = events.clone();
+        cx.subscribe(&store, move |_, event, _| {
+            if let Event::ExtensionLogged { extension_id } = event {
+                events.lock().push(extension_id.clone());
+            }
+        })
+        .detach();
+    });
+
+    let log_tx = store.read_with(cx, |store, _| store.log_tx.clone());
+    for (source, text) in [
+        (ExtensionLogSource::Wasm, "downloading ruby-lsp"),
+        (
+            ExtensionLogSource::LanguageServer("ruby-lsp".into()),
+            "Starting Ruby LSP",
+        ),
+    ] {
+        log_tx
+            .unbounded_send((
+                "zed-ruby".into(),
+                ExtensionLogLine {
+                    source,
+                    text: text.into(),
+                },
+            ))
+            .unwrap();
+    }
+    cx.executor().run_until_parked();
+
+    store.read_with(cx, |store, _| {
+        let log = store.extension_log("zed-ruby").unwrap();
+        assert_eq!(
+            log.lines().map(|line| line.to_string()).collect::<Vec<_>>(),
+            [
+                "[wasm] downloading ruby-lsp",
+                "[ruby-lsp] Starting Ruby LSP"
+            ]
+        );
+        assert!(store.extension_log("zed-gleam").is_none());
+    });
+    assert_eq!(
+        events.lock().as_slice(),
+        [Arc::<str>::from("zed-ruby"), Arc::<str>::from("zed-ruby")]
+    );
+}
+
+#[gpui::test]
+async fn test_unused_extensions(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    let http_client = FakeHttpClient::with_404_response();
+
+    let now = SystemTime::now();
+    let long_ago = now - UNUSED_EXTENSION_THRESHOLD - Duration::from_secs(60 * 60);
+    let recently = now - Duration::from_secs(24 * 60 * 60);
+    let activity = BTreeMap::from_iter([
+        (
+            "stale-theme",
+            ExtensionActivity {
+                tracked_since: long_ago,
+                last_used: Some(long_ago),
+            },
+        ),
+        (
+            "never-used",
+            ExtensionActivity {
+                tracked_since: long_ago,
+                last_used: None,
+            },
+        ),
+        (
+            "in-use",
+            ExtensionActivity {
+                tracked_since: long_ago,
+                last_used: Some(recently),
+            },
+        ),
+        (
+            "required",
+            ExtensionActivity {
+                tracked_since: long_ago,
+                last_used: None,
+            },
+        ),
+    ]);
+    let manifest = |id: &str| {
+        json!({
+            "extension.json": format!(
+                r#"{{ "id": "{id}", "name": "{id}", "version": "1.0.0" }}"#
+            ),
+        })
+    };
+    fs.insert_tree(
+        "/the-extension-dir",
+        json!({
+            "activity.json": serde_json::to_string(&activity).unwrap(),
+            "installed": {
+                "stale-theme": manifest("stale-theme"),
+                "never-used": manifest("never-used"),
+                "in-use": manifest("in-use"),
+                "required": manifest("required"),
+                "just-installed": manifest("just-installed"),
+            }
+        }),
+    )
+    .await;
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<ExtensionSettings>(cx, |settings| {
+                settings.required_extensions = Some(vec!["required".into()]);
+            });
+        });
+    });
+
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    let node_runtime = FakeNodeRuntime::new();
+    let store = cx.new_model(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            fs.clone(),
+            http_client.clone(),
+            node_runtime,
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    cx.executor().run_until_parked();
+
+    // Extensions count as unused from when they were last used, or from when
+    // they were first seen if they were never used. Newly seen extensions and
+    // required extensions are never suggested for removal.
+    store.read_with(cx, |store, cx| {
+        assert_eq!(
+            store.unused_extensions(cx),
+            ["never-used".into(), "stale-theme".into()] as [Arc<str>; 2]
+        );
+    });
+
+    // Using an extension makes it no longer unused.
+    store.update(cx, |store, cx| {
+        store.record_extension_activation("stale-theme".into(), cx)
+    });
+    store.read_with(cx, |store, cx| {
+        assert_eq!(
+            store.unused_extensions(cx),
+            ["never-used".into()] as [Arc<str>; 1]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_wasm_lifecycle_hooks(cx: &mut TestAppContext) {
+    init_test(cx);
+    cx.executor().allow_parking();
+
+    let work_dir = temp_tree(json!({ "work": {} }));
+    let work_dir = work_dir.path().canonicalize().unwrap().join("work");
+    let (log_tx, _log_rx) = futures::channel::mpsc::unbounded();
+    let wasm_host = WasmHost::new(
+        Arc::new(RealFs),
+        FakeHttpClient::with_404_response(),
+        FakeNodeRuntime::new(),
+        Arc::new(LanguageRegistry::test(cx.executor())),
+        work_dir,
+        log_tx,
+    );
+    let manifest = |id: &str| {
+        Arc::new(ExtensionManifest {
+            id: id.into(),
+            name: id.into(),
+            version: "0.0.1".into(),
+            description: None,
+            repository: None,
+            authors: Vec::new(),
+            license: None,
+            funding: None,
+            lib: Default::default(),
+            themes: Vec::new(),
+            languages: Vec::new(),
+            grammars: BTreeMap::default(),
+            language_servers: BTreeMap::default(),
+            dependencies: BTreeMap::default(),
+        })
+    };
+
+    // Extensions that export the hooks have them called on install and upgrade.
+    let with_hooks = wasm_host
+        .load_extension(
+            build_wasm_component(true),
+            manifest("with-hooks"),
+            cx.executor(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        with_hooks
+            .run_lifecycle_hook(None)
+            .await
+            .unwrap_err()
+            .to_string(),
+        "on-installed was called"
+    );
+    assert_eq!(
+        with_hooks
+            .run_lifecycle_hook(Some("0.0.1".into()))
+            .await
+            .unwrap_err()
+            .to_string(),
+        "on-upgraded was called"
+    );
+
+    // Extensions built without the hooks still load, and the hooks are skipped.
+    let without_hooks = wasm_host
+        .load_extension(
+            build_wasm_component(false),
+            manifest("without-hooks"),
+            cx.executor(),
+        )
+        .await
+        .unwrap();
+    without_hooks.run_lifecycle_hook(None).await.unwrap();
+    without_hooks
+        .run_lifecycle_hook(Some("0.0.1".into()))
+        .await
+        .unwrap();
+}
+
+fn init_test(cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        let store = SettingsStore::test(cx);
+        cx.set_global(store);
+        theme::init(theme::LoadThemes::JustBase, cx);
+        Project::init_settings(cx);
+        ExtensionSettings::register(cx);
+        language::init(cx);
+    });
+}
+
+/// Creates an extension store for the extensions in the given directory.
+fn new_test_store(
+    extensions_dir: impl AsRef<Path>,
+    fs: Arc<dyn Fs>,
+    http_client: Arc<HttpClientWithUrl>,
+    cx: &mut TestAppContext,
+) -> Model<ExtensionStore> {
+    let language_registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let theme_registry = Arc::new(ThemeRegistry::new(Box::new(())));
+    cx.new_model(|cx| {
+        ExtensionStore::new(
+            extensions_dir.as_ref().to_path_buf(),
+            None,
+            fs,
+            http_client,
+            FakeNodeRuntime::new(),
+            language_registry,
+            theme_registry,
+            cx,
+        )
+    })
+}
+
+/// Builds a gzipped tarball containing the given files.
+async fn build_archive(files: &[(&str, impl AsRef<[u8]>)]) -> Vec<u8> {
+    let mut bytes = Vec::<u8>::new();
+    let mut archive = async_tar::Builder::new(&mut bytes);
+    for (path, content) in files {
+        let content = content.as_ref();
+        let mut header = async_tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        archive
+            .append_data(&mut header, path, content)
+            .await
+            .unwrap();
+    }
+    archive.into_inner().await.unwrap();
+    let mut gzipped_bytes = Vec::new();
+    let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+    encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+    gzipped_bytes
+}
+
+/// Builds a minimal extension component for the extension API's WIT worlds.
+///
+/// Its `on-installed` and `on-upgraded` hooks, when included, fail with a
+/// message saying that they were called.
+fn build_wasm_component(with_lifecycle_hooks: bool) -> Vec<u8> {
+    use wasm_encoder::{
+        CodeSection, ConstExpr, CustomSection, DataSection, ExportKind, ExportSection, Function,
+        FunctionSection, Instruction, MemorySection, MemoryType, Module, TypeSection, ValType,
+    };
+
+    const ON_INSTALLED_RESULT: i32 = 16;
+    const ON_UPGRADED_RESULT: i32 = 32;
+    const ON_INSTALLED_MESSAGE: &str = "on-installed was called";
+    const ON_UPGRADED_MESSAGE: &str = "on-upgraded was called";
+    const ON_INSTALLED_MESSAGE_OFFSET: i32 = 64;
+    const ON_UPGRADED_MESSAGE_OFFSET: i32 = 128;
+    const HEAP_OFFSET: i32 = 1024;
+
+    let mut types = TypeSection::new();
+    // `init-extension`
+    types.function([], []);
+    // `language-server-command`
+    types.function([ValType::I32; 5], [ValType::I32]);
+    // `cabi_realloc`
+    types.function([ValType::I32; 4], [ValType::I32]);
+    // `on-installed`
+    types.function([], [ValType::I32]);
+    // `on-upgraded`
+    types.function([ValType::I32; 2], [ValType::I32]);
+
+    let mut exports = ExportSection::new();
+    exports.export("memory", ExportKind::Memory, 0);
+    exports.export("init-extension", ExportKind::Func, 0);
+    exports.export("language-server-command", ExportKind::Func, 1);
+    exports.export("cabi_realloc", ExportKind::Func, 2);
+
+    let mut functions = FunctionSection::new();
+    let mut code = CodeSection::new();
+    let mut function = |instructions: &[Instruction]| {
+        let mut function = Function::new([]);
+        for instruction in instructions {
+            function.instruction(instruction);
+        }
+        function.instruction(&Instruction::End);
+        code.function(&function);
+    };
+    functions.function(0);
+    function(&[]);
+    functions.function(1);
+    function(&[Instruction::Unreachable]);
+    functions.function(2);
+    function(&[Instruction::I32Const(HEAP_OFFSET)]);
+
+    let mut data = DataSection::new();
+    if with_lifecycle_hooks {
+        exports.export("on-installed", ExportKind::Func, 3);
+        exports.export("on-upgraded", ExportKind::Func, 4);
+        functions.function(3);
+        function(&[Instruction::I32Const(ON_INSTALLED_RESULT)]);
+        functions.function(4);
+        function(&[Instruction::I32Const(ON_UPGRADED_RESULT)]);
+
+        // A `result<_, string>` is laid out as a discriminant followed by the
+        // error's pointer and length.
+        for (result_offset, message_offset, message) in [
+            (
+                ON_INSTALLED_RESULT,
+                ON_INSTALLED_MESSAGE_OFFSET,
+                ON_INSTALLED_MESSAGE,
+            ),
+            (
+                ON_UPGRADED_RESULT,
+                ON_UPGRADED_MESSAGE_OFFSET,
+                ON_UPGRADED_MESSAGE,
+            ),
+        ] {
+            let mut result = vec![1, 0, 0, 0];
+            result.extend_from_slice(&message_offset.to_le_bytes());
+            result.extend_from_slice(&(message.len() as i32).to_le_bytes());
+            data.active(0, &ConstExpr::i32_const(result_offset), result);
+            data.active(
+                0,
+                &ConstExpr::i32_const(message_offset),
+                message.bytes().collect::<Vec<_>>(),
+            );
+        }
+    }
+
+    let mut memories = MemorySection::new();
+    memories.memory(MemoryType {
+        minimum: 1,
+        maximum: None,
+        memory64: false,
+        shared: false,
+    });
+
+    let mut module = Module::new();
+    module.section(&types);
+    module.section(&functions);
+    module.section(&memories);
+    module.section(&exports);
+    module.section(&code);
+    module.section(&data);
+    module.section(&CustomSection {
+        name: "zed:api-version".into(),
+        data: [0, 0, 0, 1, 0, 0].as_slice().into(),
+    });
+    let mut module = module.finish();
+
+    let mut resolve = wit_parser::Resolve::default();
+    let wit_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../extension_api/wit");
+    let (package, _) = resolve.push_dir(&wit_dir).unwrap();
+    let world = resolve
+        .select_world(
+            package,
+            Some(if with_lifecycle_hooks {
+                "extension-with-lifecycle-hooks"
+            } else {
+                "extension"
+            }),
+        )
+        .unwrap();
+    wit_component::embed_component_metadata(
+        &mut module,
+        &resolve,
+        world,
+        wit_component::StringEncoding::UTF8,
+    )
+    .unwrap();
+    wit_component::ComponentEncoder::default()
+        .module(&module)
+        .unwrap()
+        .validate(true)
+        .encode()
+        .unwrap()
 }
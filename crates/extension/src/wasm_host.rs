@@ -1,8 +1,9 @@
-use crate::ExtensionManifest;
+use crate::{ExtensionLogLine, ExtensionLogSource, ExtensionManifest};
 use anyhow::{anyhow, bail, Context as _, Result};
 use async_compression::futures::bufread::GzipDecoder;
 use async_tar::Archive;
 use async_trait::async_trait;
+use bytes::Bytes;
 use fs::{normalize_path, Fs};
 use futures::{
     channel::{
@@ -19,11 +20,15 @@ use node_runtime::NodeRuntime;
 use std::{
     env,
     path::{Path, PathBuf},
-    sync::{Arc, OnceLock},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering::SeqCst},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant},
 };
 use util::{http::HttpClient, SemanticVersion};
 use wasmtime::{
-    component::{Component, Linker, Resource, ResourceTable},
+    component::{Component, Linker, Resource, ResourceTable, TypedFunc},
     Engine, Store,
 };
 use wasmtime_wasi::preview2::{self as wasi, WasiCtx};
@@ -32,6 +37,7 @@ pub mod wit {
     wasmtime::component::bindgen!({
         async: true,
         path: "../extension_api/wit",
+        world: "extension",
         with: {
              "worktree": super::ExtensionWorktree,
         },
@@ -40,6 +46,10 @@ pub mod wit {
 
 pub type ExtensionWorktree = Arc<dyn LspAdapterDelegate>;
 
+/// The name of the directory, within an extension's working directory, that
+/// holds the data the extension stores.
+const EXTENSION_DATA_DIR_NAME: &str = "data";
+
 pub(crate) struct WasmHost {
     engine: Engine,
     linker: Arc<wasmtime::component::Linker<WasmState>>,
@@ -47,6 +57,7 @@ pub(crate) struct WasmHost {
     node_runtime: Arc<dyn NodeRuntime>,
     language_registry: Arc<LanguageRegistry>,
     fs: Arc<dyn Fs>,
+    log_tx: UnboundedSender<(Arc<str>, ExtensionLogLine)>,
     pub(crate) work_dir: PathBuf,
 }
 
@@ -56,6 +67,124 @@ pub struct WasmExtension {
     pub(crate) manifest: Arc<ExtensionManifest>,
     #[allow(unused)]
     zed_api_version: SemanticVersion,
+    lifecycle_hooks: LifecycleHooks,
+    usage: Arc<WasmUsage>,
+}
+
+/// The resources that an extension's wasm instance has used.
+#[derive(Default)]
+struct WasmUsage {
+    memory_bytes: AtomicUsize,
+    busy_nanos: AtomicU64,
+}
+
+/// Tracks the growth of an extension's linear memories, without limiting it.
+struct MemoryTracker {
+    usage: Arc<WasmUsage>,
+}
+
+impl wasmtime::ResourceLimiter for MemoryTracker {
+    fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool> {
+        self.usage
+            .memory_bytes
+            .fetch_add(desired.saturating_sub(current), SeqCst);
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: u32,
+        _desired: u32,
+        _maximum: Option<u32>,
+    ) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+/// Forwards the output that an extension prints to its log, a line at a time.
+#[derive(Clone)]
+struct ExtensionLogOutput {
+    extension_id: Arc<str>,
+    log_tx: UnboundedSender<(Arc<str>, ExtensionLogLine)>,
+}
+
+impl wasi::StdoutStream for ExtensionLogOutput {
+    fn stream(&self) -> Box<dyn wasi::HostOutputStream> {
+        Box::new(ExtensionLogWriter {
+            output: self.clone(),
+            partial_line: Vec::new(),
+        })
+    }
+
+    fn isatty(&self) -> bool {
+        false
+    }
+}
+
+struct ExtensionLogWriter {
+    output: ExtensionLogOutput,
+    partial_line: Vec<u8>,
+}
+
+impl ExtensionLogWriter {
+    fn send_line(&self, line: &[u8]) {
+        let text = String::from_utf8_lossy(line).trim_end().to_string();
+        log::info!("extension {}: {text}", self.output.extension_id);
+        self.output
+            .log_tx
+            .unbounded_send((
+                self.output.extension_id.clone(),
+                ExtensionLogLine {
+                    source: ExtensionLogSource::Wasm,
+                    text,
+                },
+            ))
+            .ok();
+    }
+}
+
+#[async_trait]
+impl wasi::Subscribe for ExtensionLogWriter {
+    async fn ready(&mut self) {}
+}
+
+impl wasi::HostOutputStream for ExtensionLogWriter {
+    fn write(&mut self, bytes: Bytes) -> wasi::StreamResult<()> {
+        self.partial_line.extend_from_slice(&bytes);
+        while let Some(newline_ix) = self.partial_line.iter().position(|byte| *byte == b'\n') {
+            let line = self.partial_line.drain(..=newline_ix).collect::<Vec<_>>();
+            self.send_line(&line);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> wasi::StreamResult<()> {
+        Ok(())
+    }
+
+    fn check_write(&mut self) -> wasi::StreamResult<usize> {
+        Ok(usize::MAX)
+    }
+}
+
+impl Drop for ExtensionLogWriter {
+    fn drop(&mut self) {
+        if !self.partial_line.is_empty() {
+            self.send_line(&self.partial_line);
+        }
+    }
+}
+
+/// The optional `on-installed` and `on-upgraded` exports of an extension.
+#[derive(Clone, Copy)]
+struct LifecycleHooks {
+    on_installed: Option<TypedFunc<(), (Result<(), String>,)>>,
+    on_upgraded: Option<TypedFunc<(String,), (Result<(), String>,)>>,
 }
 
 pub(crate) struct WasmState {
@@ -63,6 +192,7 @@ pub(crate) struct WasmState {
     table: ResourceTable,
     ctx: wasi::WasiCtx,
     host: Arc<WasmHost>,
+    memory_tracker: MemoryTracker,
 }
 
 type ExtensionCall = Box<
@@ -79,6 +209,7 @@ impl WasmHost {
         node_runtime: Arc<dyn NodeRuntime>,
         language_registry: Arc<LanguageRegistry>,
         work_dir: PathBuf,
+        log_tx: UnboundedSender<(Arc<str>, ExtensionLogLine)>,
     ) -> Arc<Self> {
         let engine = WASM_ENGINE
             .get_or_init(|| {
@@ -95,6 +226,7 @@ impl WasmHost {
             engine,
             linker: Arc::new(linker),
             fs,
+            log_tx,
             work_dir,
             http_client,
             node_runtime,
@@ -113,26 +245,9 @@ impl WasmHost {
             let component = Component::from_binary(&this.engine, &wasm_bytes)
                 .context("failed to compile wasm component")?;
 
-            let mut zed_api_version = None;
-            for part in wasmparser::Parser::new(0).parse_all(&wasm_bytes) {
-                if let wasmparser::Payload::CustomSection(s) = part? {
-                    if s.name() == "zed:api-version" {
-                        zed_api_version = parse_extension_version(s.data());
-                        if zed_api_version.is_none() {
-                            bail!(
-                                "extension {} has invalid zed:api-version section: {:?}",
-                                manifest.id,
-                                s.data()
-                            );
-                        }
-                    }
-                }
-            }
-
-            let Some(zed_api_version) = zed_api_version else {
-                bail!("extension {} has no zed:api-version section", manifest.id);
-            };
+            let zed_api_version = parse_wasm_extension_version(&manifest.id, &wasm_bytes)?;
 
+            let usage = Arc::new(WasmUsage::default());
             let mut store = wasmtime::Store::new(
                 &this.engine,
                 WasmState {
@@ -140,8 +255,12 @@ impl WasmHost {
                     manifest: manifest.clone(),
                     table: ResourceTable::new(),
                     host: this.clone(),
+                    memory_tracker: MemoryTracker {
+                        usage: usage.clone(),
+                    },
                 },
             );
+            store.limiter(|state| &mut state.memory_tracker);
 
             let (mut extension, instance) =
                 wit::Extension::instantiate_async(&mut store, &component, &this.linker)
@@ -152,12 +271,30 @@ impl WasmHost {
                 .await
                 .context("failed to initialize wasm extension")?;
 
+            // These exports aren't part of the `extension` world, so that
+            // extensions built before they existed can still be instantiated.
+            let lifecycle_hooks = LifecycleHooks {
+                on_installed: instance
+                    .get_typed_func::<(), (Result<(), String>,)>(&mut store, "on-installed")
+                    .ok(),
+                on_upgraded: instance
+                    .get_typed_func::<(String,), (Result<(), String>,)>(&mut store, "on-upgraded")
+                    .ok(),
+            };
+
             let (tx, mut rx) = mpsc::unbounded::<ExtensionCall>();
             executor
-                .spawn(async move {
-                    let _instance = instance;
-                    while let Some(call) = rx.next().await {
-                        (call)(&mut extension, &mut store).await;
+                .spawn({
+                    let usage = usage.clone();
+                    async move {
+                        let _instance = instance;
+                        while let Some(call) = rx.next().await {
+                            let started_at = Instant::now();
+                            (call)(&mut extension, &mut store).await;
+                            usage
+                                .busy_nanos
+                                .fetch_add(started_at.elapsed().as_nanos() as u64, SeqCst);
+                        }
                     }
                 })
                 .detach();
@@ -166,6 +303,8 @@ impl WasmHost {
                 manifest,
                 tx,
                 zed_api_version,
+                lifecycle_hooks,
+                usage,
             })
         }
     }
@@ -189,8 +328,15 @@ impl WasmHost {
         let perms = wasi::FilePerms::all();
         let dir_perms = wasi::DirPerms::all();
 
+        let log_output = ExtensionLogOutput {
+            extension_id: manifest.id.clone(),
+            log_tx: self.log_tx.clone(),
+        };
+
         Ok(wasi::WasiCtxBuilder::new()
-            .inherit_stdio()
+            .inherit_stdin()
+            .stdout(log_output.clone())
+            .stderr(log_output)
             .preopened_dir(current_dir_preopen, dir_perms, perms, ".")
             .preopened_dir(work_dir_preopen, dir_perms, perms, &extension_work_dir)
             .env("PWD", &extension_work_dir)
@@ -198,6 +344,15 @@ impl WasmHost {
             .build())
     }
 
+    /// Returns the directory in which the given extension stores its data.
+    ///
+    /// It's kept within the extension's working directory, so that the
+    /// extension can access it, but apart from the language servers that the
+    /// extension downloads there.
+    pub fn extension_data_dir(&self, id: &str) -> PathBuf {
+        self.work_dir.join(id).join(EXTENSION_DATA_DIR_NAME)
+    }
+
     pub fn path_from_extension(&self, id: &Arc<str>, path: &Path) -> PathBuf {
         let extension_work_dir = self.work_dir.join(id.as_ref());
         normalize_path(&extension_work_dir.join(path))
@@ -214,6 +369,31 @@ impl WasmHost {
     }
 }
 
+/// Reads the version of the extension API that a WASM extension was compiled against.
+pub(crate) fn parse_wasm_extension_version(
+    extension_id: &str,
+    wasm_bytes: &[u8],
+) -> Result<SemanticVersion> {
+    let mut zed_api_version = None;
+    for part in wasmparser::Parser::new(0).parse_all(wasm_bytes) {
+        if let wasmparser::Payload::CustomSection(s) = part? {
+            if s.name() == "zed:api-version" {
+                zed_api_version = parse_extension_version(s.data());
+                if zed_api_version.is_none() {
+                    bail!(
+                        "extension {} has invalid zed:api-version section: {:?}",
+                        extension_id,
+                        s.data()
+                    );
+                }
+            }
+        }
+    }
+
+    zed_api_version
+        .ok_or_else(|| anyhow!("extension {extension_id} has no zed:api-version section"))
+}
+
 fn parse_extension_version(data: &[u8]) -> Option<SemanticVersion> {
     if data.len() == 6 {
         Some(SemanticVersion {
@@ -227,6 +407,17 @@ fn parse_extension_version(data: &[u8]) -> Option<SemanticVersion> {
 }
 
 impl WasmExtension {
+    /// Returns how much memory the extension's wasm instance has allocated.
+    pub fn memory_usage(&self) -> usize {
+        self.usage.memory_bytes.load(SeqCst)
+    }
+
+    /// Returns how long the extension's wasm instance has spent handling
+    /// calls, including the time spent waiting on the host.
+    pub fn busy_time(&self) -> Duration {
+        Duration::from_nanos(self.usage.busy_nanos.load(SeqCst))
+    }
+
     pub async fn call<T, Fn>(&self, f: Fn) -> T
     where
         T: 'static + Send,
@@ -247,6 +438,46 @@ impl WasmExtension {
             .expect("wasm extension channel should not be closed yet");
         return_rx.await.expect("wasm extension channel")
     }
+
+    /// Runs the extension's `on-installed` hook, or its `on-upgraded` hook when
+    /// a previous version is given. Does nothing if the extension doesn't
+    /// export the hook.
+    pub async fn run_lifecycle_hook(&self, previous_version: Option<Arc<str>>) -> Result<()> {
+        let hooks = self.lifecycle_hooks;
+        let result = match previous_version {
+            Some(previous_version) => {
+                let Some(on_upgraded) = hooks.on_upgraded else {
+                    return Ok(());
+                };
+                self.call(move |_, store| {
+                    async move {
+                        let (result,) = on_upgraded
+                            .call_async(&mut *store, (previous_version.to_string(),))
+                            .await?;
+                        on_upgraded.post_return_async(&mut *store).await?;
+                        anyhow::Ok(result)
+                    }
+                    .boxed()
+                })
+                .await?
+            }
+            None => {
+                let Some(on_installed) = hooks.on_installed else {
+                    return Ok(());
+                };
+                self.call(move |_, store| {
+                    async move {
+                        let (result,) = on_installed.call_async(&mut *store, ()).await?;
+                        on_installed.post_return_async(&mut *store).await?;
+                        anyhow::Ok(result)
+                    }
+                    .boxed()
+                })
+                .await?
+            }
+        };
+        result.map_err(|error| anyhow!(error))
+    }
 }
 
 #[async_trait]
@@ -342,6 +573,12 @@ impl wit::ExtensionImports for WasmState {
             .map_err(|err| err.to_string()))
     }
 
+    async fn extension_data_dir(&mut self) -> Result<String> {
+        let data_dir = self.host.extension_data_dir(&self.manifest.id);
+        self.host.fs.create_dir(&data_dir).await?;
+        Ok(data_dir.to_string_lossy().into_owned())
+    }
+
     async fn current_platform(&mut self) -> Result<(wit::Os, wit::Architecture)> {
         Ok((
             match env::consts::OS {
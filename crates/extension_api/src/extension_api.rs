@@ -13,6 +13,16 @@ pub trait Extension: Send + Sync {
         config: wit::LanguageServerConfig,
         worktree: &wit::Worktree,
     ) -> Result<Command>;
+
+    /// Called once after the extension is installed.
+    fn on_installed(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called once after the extension is upgraded from the given version.
+    fn on_upgraded(&mut self, _previous_version: String) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[macro_export]
@@ -46,6 +56,7 @@ pub static ZED_API_VERSION: [u8; 6] = *include_bytes!(concat!(env!("OUT_DIR"), "
 
 mod wit {
     wit_bindgen::generate!({
+        world: "extension-with-lifecycle-hooks",
         exports: { world: super::Component },
         skip: ["init-extension"]
     });
@@ -60,4 +71,12 @@ impl wit::Guest for Component {
     ) -> Result<wit::Command> {
         extension().language_server_command(config, worktree)
     }
+
+    fn on_installed() -> Result<()> {
+        extension().on_installed()
+    }
+
+    fn on_upgraded(previous_version: String) -> Result<()> {
+        extension().on_upgraded(previous_version)
+    }
 }
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use extension::{parse_ansi, AnsiColor, ExtensionStore};
+use gpui::{
+    AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, FontWeight, HighlightStyle,
+    Hsla, StyledText, Subscription, View, WindowContext,
+};
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::prelude::*;
+use workspace::{
+    item::{Item, ItemEvent},
+    WorkspaceId,
+};
+
+/// The builds whose output is shown in a [`BuildLogView`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildLogSource {
+    /// The most recent build of the given dev extension.
+    DevExtension(Arc<str>),
+    /// The most recent rebuild of all dev extensions.
+    AllDevExtensions,
+}
+
+/// Shows the output of the most recent build of a dev extension, which is
+/// updated as the build progresses.
+pub struct BuildLogView {
+    source: BuildLogSource,
+    title: SharedString,
+    focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl BuildLogView {
+    pub fn new(
+        source: BuildLogSource,
+        title: impl Into<SharedString>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let store = ExtensionStore::global(cx);
+        Self {
+            source,
+            title: title.into(),
+            focus_handle: cx.focus_handle(),
+            _subscription: cx.observe(&store, |_, _, cx| cx.notify()),
+        }
+    }
+
+    pub fn source(&self) -> &BuildLogSource {
+        &self.source
+    }
+}
+
+impl Render for BuildLogView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let store = ExtensionStore::global(cx).read(cx);
+        let log = match &self.source {
+            BuildLogSource::DevExtension(extension_id) => {
+                store.dev_extension_build_log(extension_id)
+            }
+            BuildLogSource::AllDevExtensions => store.dev_extensions_rebuild_log(),
+        }
+        .unwrap_or_default();
+        let (text, styles) = parse_ansi(&log);
+
+        let settings = ThemeSettings::get_global(cx);
+        let font_family = settings.buffer_font.family.clone();
+        let font_size = settings.buffer_font_size(cx);
+        let mut text_style = cx.text_style();
+        text_style.font_family = font_family.clone();
+        text_style.font_size = font_size.into();
+        text_style.color = cx.theme().colors().editor_foreground;
+
+        let highlights = styles
+            .into_iter()
+            .map(|(range, style)| {
+                let highlight = HighlightStyle {
+                    color: style.foreground.map(|color| ansi_color(color, cx)),
+                    font_weight: style.bold.then_some(FontWeight::BOLD),
+                    ..Default::default()
+                };
+                (range, highlight)
+            })
+            .collect::<Vec<_>>();
+
+        div()
+            .id("build-log")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .font(font_family)
+            .text_size(font_size)
+            .child(if text.is_empty() {
+                Label::new("There is no build output yet.")
+                    .color(Color::Muted)
+                    .into_any_element()
+            } else {
+                StyledText::new(text)
+                    .with_highlights(&text_style, highlights)
+                    .into_any_element()
+            })
+    }
+}
+
+fn ansi_color(color: AnsiColor, cx: &WindowContext) -> Hsla {
+    let colors = cx.theme().colors();
+    match (color.index, color.bright) {
+        (0, false) => colors.terminal_ansi_black,
+        (1, false) => colors.terminal_ansi_red,
+        (2, false) => colors.terminal_ansi_green,
+        (3, false) => colors.terminal_ansi_yellow,
+        (4, false) => colors.terminal_ansi_blue,
+        (5, false) => colors.terminal_ansi_magenta,
+        (6, false) => colors.terminal_ansi_cyan,
+        (0, true) => colors.terminal_ansi_bright_black,
+        (1, true) => colors.terminal_ansi_bright_red,
+        (2, true) => colors.terminal_ansi_bright_green,
+        (3, true) => colors.terminal_ansi_bright_yellow,
+        (4, true) => colors.terminal_ansi_bright_blue,
+        (5, true) => colors.terminal_ansi_bright_magenta,
+        (6, true) => colors.terminal_ansi_bright_cyan,
+        (_, false) => colors.terminal_ansi_white,
+        (_, true) => colors.terminal_ansi_bright_white,
+    }
+}
+
+impl EventEmitter<ItemEvent> for BuildLogView {}
+
+impl FocusableView for BuildLogView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for BuildLogView {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, _: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        Label::new(format!("Build Log: {}", self.title))
+            .color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<View<Self>> {
+        let source = self.source.clone();
+        let title = self.title.clone();
+        Some(cx.new_view(|cx| Self::new(source, title, cx)))
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(workspace::item::ItemEvent)) {
+        f(*event)
+    }
+}
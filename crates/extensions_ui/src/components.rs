@@ -1,3 +1,5 @@
+mod extension_badge;
 mod extension_card;
 
+pub use extension_badge::*;
 pub use extension_card::*;
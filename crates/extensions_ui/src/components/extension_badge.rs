@@ -0,0 +1,40 @@
+use gpui::prelude::*;
+use ui::prelude::*;
+
+/// A small label used to call out something about an extension, such as a
+/// language it provides.
+#[derive(IntoElement)]
+pub struct ExtensionBadge {
+    label: SharedString,
+    color: Color,
+}
+
+impl ExtensionBadge {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            color: Color::Muted,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl RenderOnce for ExtensionBadge {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        div()
+            .px_1()
+            .rounded_md()
+            .bg(cx.theme().colors().element_background)
+            .border_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new(self.label)
+                    .size(LabelSize::XSmall)
+                    .color(self.color),
+            )
+    }
+}
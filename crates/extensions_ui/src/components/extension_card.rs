@@ -5,14 +5,31 @@ use ui::prelude::*;
 #[derive(IntoElement)]
 pub struct ExtensionCard {
     children: SmallVec<[AnyElement; 2]>,
+    details: Option<AnyElement>,
+    compact: bool,
 }
 
 impl ExtensionCard {
     pub fn new() -> Self {
         Self {
             children: SmallVec::new(),
+            details: None,
+            compact: false,
         }
     }
+
+    /// Shows the card as a single line, for dense lists.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets additional content to show below the card's summary, which makes
+    /// the card grow past its usual height.
+    pub fn details(mut self, details: impl IntoElement) -> Self {
+        self.details = Some(details.into_any_element());
+        self
+    }
 }
 
 impl ParentElement for ExtensionCard {
@@ -23,18 +40,27 @@ impl ParentElement for ExtensionCard {
 
 impl RenderOnce for ExtensionCard {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let has_details = self.details.is_some();
+        let compact = self.compact;
         div().w_full().child(
             v_flex()
                 .w_full()
-                .h(rems(7.))
-                .p_3()
-                .mt_4()
+                .map(|this| {
+                    if compact {
+                        this.h(rems(2.5)).px_3().py_1().mt_1().justify_center()
+                    } else if has_details {
+                        this.min_h(rems(7.)).p_3().mt_4()
+                    } else {
+                        this.h(rems(7.)).p_3().mt_4()
+                    }
+                })
                 .gap_2()
                 .bg(cx.theme().colors().elevated_surface_background)
                 .border_1()
                 .border_color(cx.theme().colors().border)
                 .rounded_md()
-                .children(self.children),
+                .children(self.children)
+                .children(self.details),
         )
     }
 }
@@ -0,0 +1,160 @@
+use extension::{extension_api_reference, ExtensionApiItemKind, ExtensionApiReference};
+use gpui::{AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, View, WindowContext};
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::prelude::*;
+use workspace::{
+    item::{Item, ItemEvent},
+    WorkspaceId,
+};
+
+/// Lists the capabilities of the extension API that this version of Zed
+/// provides, so that extension authors can check what they are able to use.
+pub struct ExtensionApiReferenceView {
+    reference: ExtensionApiReference,
+    focus_handle: FocusHandle,
+}
+
+impl ExtensionApiReferenceView {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            reference: extension_api_reference(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn render_section(
+        &self,
+        kind: ExtensionApiItemKind,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        let items = self
+            .reference
+            .items
+            .iter()
+            .filter(|item| item.kind == kind)
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            return None;
+        }
+
+        let buffer_font = ThemeSettings::get_global(cx).buffer_font.family.clone();
+        let title = match kind {
+            ExtensionApiItemKind::Import => "Functions Provided by Zed",
+            ExtensionApiItemKind::Export => "Functions Implemented by Extensions",
+            ExtensionApiItemKind::Resource => "Resources",
+            ExtensionApiItemKind::Type => "Types",
+        };
+
+        Some(
+            v_flex()
+                .gap_2()
+                .child(Headline::new(title).size(HeadlineSize::Small))
+                .children(items.into_iter().map(|item| {
+                    v_flex()
+                        .gap_1()
+                        .p_2()
+                        .rounded_md()
+                        .bg(cx.theme().colors().elevated_surface_background)
+                        .child(Label::new(item.name.clone()))
+                        .when(!item.docs.is_empty(), |this| {
+                            this.child(
+                                Label::new(item.docs.clone())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        })
+                        .child(v_flex().font(buffer_font.clone()).text_xs().children(
+                            item.definition.lines().map(|line| {
+                                div()
+                                    .when(line.starts_with(' '), |this| this.pl_4())
+                                    .child(line.trim_start().to_string())
+                            }),
+                        ))
+                })),
+        )
+    }
+}
+
+impl Render for ExtensionApiReferenceView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let reference = &self.reference;
+        let version_range = if reference.min_version == reference.max_version {
+            format!("API version {}", reference.min_version)
+        } else {
+            format!(
+                "API versions {} through {}.{}.x",
+                reference.min_version, reference.max_version.major, reference.max_version.minor
+            )
+        };
+
+        v_flex()
+            .id("extension-api-reference")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_4()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(Headline::new("Extension API Reference").size(HeadlineSize::Large))
+                    .child(
+                        Label::new(format!(
+                            "This version of Zed supports extensions built with {version_range}, \
+                             using the \"{}\" world of the \"{}\" WIT package.",
+                            reference.world, reference.package
+                        ))
+                        .color(Color::Muted),
+                    ),
+            )
+            .children(
+                ExtensionApiItemKind::ALL
+                    .into_iter()
+                    .filter_map(|kind| self.render_section(kind, cx)),
+            )
+    }
+}
+
+impl EventEmitter<ItemEvent> for ExtensionApiReferenceView {}
+
+impl FocusableView for ExtensionApiReferenceView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for ExtensionApiReferenceView {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, _: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        Label::new("Extension API Reference")
+            .color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<View<Self>> {
+        Some(cx.new_view(Self::new))
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(workspace::item::ItemEvent)) {
+        f(*event)
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use editor::{actions::MoveToEnd, Editor};
+use extension::ExtensionStore;
+use gpui::{
+    AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, Subscription, View,
+    WindowContext,
+};
+use ui::prelude::*;
+use workspace::{
+    item::{Item, ItemEvent},
+    WorkspaceId,
+};
+
+/// Shows the recent output of an extension's wasm module and language
+/// servers in a read-only editor, which follows new output while the cursor
+/// is at the end.
+pub struct ExtensionLogView {
+    extension_id: Arc<str>,
+    extension_name: SharedString,
+    editor: View<Editor>,
+    /// The number of the extension's log lines that have been added to the
+    /// editor, including the ones that have since been dropped from the log.
+    seen_line_count: usize,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ExtensionLogView {
+    pub fn new(
+        extension_id: Arc<str>,
+        extension_name: impl Into<SharedString>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let store = ExtensionStore::global(cx);
+        let editor = cx.new_view(|cx| {
+            let mut editor = Editor::multi_line(cx);
+            editor.set_placeholder_text("This extension hasn't logged any output yet.", cx);
+            editor.set_read_only(true);
+            editor.set_show_copilot_suggestions(false);
+            editor
+        });
+
+        let focus_handle = cx.focus_handle();
+        let subscriptions = vec![
+            cx.subscribe(&store, |this, _, event, cx| {
+                if let extension::Event::ExtensionLogged { extension_id } = event {
+                    if *extension_id == this.extension_id {
+                        this.append_new_lines(cx);
+                    }
+                }
+            }),
+            cx.on_focus(&focus_handle, |this, cx| cx.focus_view(&this.editor)),
+        ];
+
+        let mut this = Self {
+            extension_id,
+            extension_name: extension_name.into(),
+            editor,
+            seen_line_count: 0,
+            focus_handle,
+            _subscriptions: subscriptions,
+        };
+        this.append_new_lines(cx);
+        this
+    }
+
+    pub fn extension_id(&self) -> &Arc<str> {
+        &self.extension_id
+    }
+
+    fn append_new_lines(&mut self, cx: &mut ViewContext<Self>) {
+        let store = ExtensionStore::global(cx);
+        let Some(log) = store.read(cx).extension_log(&self.extension_id) else {
+            return;
+        };
+        let mut new_text = String::new();
+        for line in log.lines_after(self.seen_line_count) {
+            new_text.push_str(&line.to_string());
+            new_text.push('\n');
+        }
+        self.seen_line_count = log.total_line_count();
+        if new_text.is_empty() {
+            return;
+        }
+
+        self.editor.update(cx, |editor, cx| {
+            let end = editor.buffer().read(cx).len(cx);
+            let is_following = editor.selections.newest::<usize>(cx).head() == end;
+            editor.set_read_only(false);
+            editor.edit([(end..end, new_text)], cx);
+            editor.set_read_only(true);
+            if is_following {
+                editor.move_to_end(&MoveToEnd, cx);
+            }
+        });
+    }
+}
+
+impl Render for ExtensionLogView {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.editor.clone())
+    }
+}
+
+impl EventEmitter<ItemEvent> for ExtensionLogView {}
+
+impl FocusableView for ExtensionLogView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for ExtensionLogView {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, _: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        Label::new(format!("Logs: {}", self.extension_name))
+            .color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<View<Self>> {
+        let extension_id = self.extension_id.clone();
+        let extension_name = self.extension_name.clone();
+        Some(cx.new_view(|cx| Self::new(extension_id, extension_name, cx)))
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(workspace::item::ItemEvent)) {
+        f(*event)
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::Result;
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+
+#[derive(Deserialize, Debug)]
+pub struct ExtensionsPageSettings {
+    pub list_density: ListDensity,
+    pub group_by_category: bool,
+    pub card_metadata: CardMetadata,
+    pub starred_extensions: Vec<String>,
+    pub hidden_extensions: Vec<String>,
+    pub search_contributions: bool,
+    pub telemetry: bool,
+}
+
+/// How much room each extension takes up in the extensions list.
+#[derive(Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ListDensity {
+    /// Show each extension on a card with its description and metadata.
+    #[default]
+    Comfortable,
+    /// Show each extension on a single line, with its name, version, status,
+    /// and one action.
+    Compact,
+}
+
+/// Which metadata is shown on extension cards.
+#[derive(Clone, Copy, Deserialize, Debug)]
+pub struct CardMetadata {
+    pub downloads: bool,
+    pub data_size: bool,
+    pub license: bool,
+    pub languages: bool,
+}
+
+/// Configuration of the extensions page.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct ExtensionsPageSettingsContent {
+    /// How much room each extension takes up in the extensions list.
+    ///
+    /// Default: comfortable
+    pub list_density: Option<ListDensity>,
+    /// Whether registry extensions are listed under headers for the kind of
+    /// extension they are, such as themes or languages.
+    ///
+    /// Default: false
+    pub group_by_category: Option<bool>,
+    /// Which metadata is shown on extension cards.
+    pub card_metadata: Option<CardMetadataContent>,
+    /// The ids of the extensions that are listed before all others.
+    ///
+    /// Default: []
+    pub starred_extensions: Option<Vec<String>>,
+    /// The ids of the extensions that are left out of the "All" and
+    /// "Not Installed" listings unless hidden extensions are shown.
+    ///
+    /// Default: []
+    pub hidden_extensions: Option<Vec<String>>,
+    /// Whether searches also match the languages, themes, grammars, and
+    /// language servers that installed extensions provide.
+    ///
+    /// Default: false
+    pub search_contributions: Option<bool>,
+    /// Whether to send telemetry events when extensions are installed,
+    /// upgraded, or uninstalled from the extensions page.
+    ///
+    /// Default: true
+    pub telemetry: Option<bool>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct CardMetadataContent {
+    /// Whether to show how many times an extension has been downloaded.
+    ///
+    /// Default: true
+    pub downloads: Option<bool>,
+    /// Whether to show how much data an installed extension has stored.
+    ///
+    /// Default: true
+    pub data_size: Option<bool>,
+    /// Whether to show an extension's license.
+    ///
+    /// Default: true
+    pub license: Option<bool>,
+    /// Whether to show the languages and grammars that an extension provides.
+    ///
+    /// Default: true
+    pub languages: Option<bool>,
+}
+
+/// A piece of metadata that can be shown on or hidden from extension cards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CardMetadataField {
+    Downloads,
+    DataSize,
+    License,
+    Languages,
+}
+
+impl CardMetadataField {
+    pub const ALL: [Self; 4] = [
+        Self::Downloads,
+        Self::DataSize,
+        Self::License,
+        Self::Languages,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Downloads => "Downloads",
+            Self::DataSize => "Data Size",
+            Self::License => "License",
+            Self::Languages => "Provided Languages",
+        }
+    }
+
+    pub fn is_shown(&self, metadata: &CardMetadata) -> bool {
+        match self {
+            Self::Downloads => metadata.downloads,
+            Self::DataSize => metadata.data_size,
+            Self::License => metadata.license,
+            Self::Languages => metadata.languages,
+        }
+    }
+
+    pub fn set_shown(&self, metadata: &mut CardMetadataContent, shown: bool) {
+        let field = match self {
+            Self::Downloads => &mut metadata.downloads,
+            Self::DataSize => &mut metadata.data_size,
+            Self::License => &mut metadata.license,
+            Self::Languages => &mut metadata.languages,
+        };
+        *field = Some(shown);
+    }
+}
+
+impl Settings for ExtensionsPageSettings {
+    const KEY: Option<&'static str> = Some("extensions_page");
+
+    type FileContent = ExtensionsPageSettingsContent;
+
+    fn load(
+        default_value: &Self::FileContent,
+        user_values: &[&Self::FileContent],
+        _cx: &mut AppContext,
+    ) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::load_via_json_merge(default_value, user_values)
+    }
+}
@@ -1,36 +1,202 @@
+mod build_log_view;
 mod components;
+mod extension_api_reference_view;
+mod extension_log_view;
+mod extensions_page_settings;
+mod host_extensions;
+mod install_confirmation_modal;
+mod install_from_repository_modal;
+mod install_preview_modal;
+mod install_queue_view;
+mod language_providers_view;
+mod persistence;
+mod report_extension_modal;
+mod uninstall_confirmation_modal;
+mod workspace_recommendations;
 
-use crate::components::ExtensionCard;
-use client::telemetry::Telemetry;
-use editor::{Editor, EditorElement, EditorStyle};
-use extension::{ExtensionApiResponse, ExtensionManifest, ExtensionStatus, ExtensionStore};
+use crate::build_log_view::{BuildLogSource, BuildLogView};
+use crate::components::{ExtensionBadge, ExtensionCard};
+use crate::extension_api_reference_view::ExtensionApiReferenceView;
+use crate::extension_log_view::ExtensionLogView;
+use crate::extensions_page_settings::{CardMetadataField, ExtensionsPageSettings, ListDensity};
+use crate::host_extensions::HostExtensionsView;
+use crate::install_confirmation_modal::InstallConfirmationModal;
+use crate::install_from_repository_modal::{InstallFromRepositoryModal, RepositoryInstallKind};
+use crate::install_preview_modal::InstallPreviewModal;
+use crate::install_queue_view::InstallQueueView;
+use crate::persistence::EXTENSIONS_PAGE_DB;
+use crate::report_extension_modal::ReportExtensionModal;
+use crate::uninstall_confirmation_modal::UninstallConfirmationModal;
+use anyhow::Result;
+use client::{telemetry::Telemetry, ClientSettings};
+use collections::HashSet;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{scroll::Autoscroll, Editor, EditorElement, EditorStyle};
+use extension::{
+    BlockedExtensionVersion, DisabledUntil, DownloadProgress, ExtensionApiResponse,
+    ExtensionBisectStep, ExtensionCollection, ExtensionList, ExtensionManifest, ExtensionOperation,
+    ExtensionResourceUsage, ExtensionSettings, ExtensionSigner, ExtensionStatus, ExtensionStore,
+    SigningKeySource, EXTENSION_API_MIGRATION_NOTES_URL, UNUSED_EXTENSION_THRESHOLD,
+};
 use fuzzy::{match_strings, StringMatchCandidate};
 use gpui::{
-    actions, canvas, uniform_list, AnyElement, AppContext, EventEmitter, FocusableView, FontStyle,
-    FontWeight, InteractiveElement, KeyContext, ParentElement, Render, Styled, Task, TextStyle,
-    UniformListScrollHandle, View, ViewContext, VisualContext, WhiteSpace, WindowContext,
+    actions, canvas, uniform_list, AnyElement, AppContext, EventEmitter, ExternalPaths,
+    FocusHandle, FocusableView, FontStyle, FontWeight, InteractiveElement, KeyContext, Model,
+    ParentElement, PromptLevel, Render, Styled, Task, TextStyle, UniformListScrollHandle, View,
+    ViewContext, VisualContext, WeakView, WhiteSpace, WindowContext,
 };
-use settings::Settings;
+use project::Project;
+use settings::{Settings, SettingsStore};
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::mem;
 use std::ops::DerefMut;
-use std::time::Duration;
-use std::{ops::Range, sync::Arc};
-use theme::ThemeSettings;
-use ui::{prelude::*, ToggleButton, Tooltip};
-use util::ResultExt as _;
+use std::time::{Duration, Instant};
+use std::{ops::Range, path::Path, sync::Arc};
+use theme::{Theme, ThemeRegistry, ThemeSettings};
+use time::{OffsetDateTime, UtcOffset};
+use ui::{
+    popover_menu, prelude::*, Checkbox, ContextMenu, Indicator, ListHeader, ListSubHeader,
+    Selection, ToggleButton, Tooltip,
+};
+use util::{paths, ResultExt as _};
 use workspace::{
     item::{Item, ItemEvent},
-    Workspace, WorkspaceId,
+    pane::{GoBack, GoForward},
+    ItemId, Pane, Toast, Workspace, WorkspaceId,
 };
 
-actions!(zed, [Extensions, InstallDevExtension]);
+actions!(
+    zed,
+    [
+        Extensions,
+        InstallDevExtension,
+        InstallExtensionFromFile,
+        InstallExtensionFromGit,
+        InstallExtensionFromGithubRelease,
+        HostExtensions,
+        CleanUpExtensions,
+        RestartWithExtensionsDisabled,
+        RestartWithExtensionsEnabled
+    ]
+);
+
+actions!(
+    extensions,
+    [RepairAll, StartBisect, ContinueBisect, StopBisect]
+);
+
+const DEV_EXTENSIONS_COLLAPSED_KEY: &str = "ExtensionsPageDevExtensionsCollapsed";
+
+const HOST_EXTENSIONS_TOAST_ID: usize = 0x4e8a1d07;
+
+const AUTO_UPDATE_TOAST_ID: usize = 0x7b2f4c90;
+
+const SYNCED_EXTENSIONS_TOAST_ID: usize = 0x2c96e15a;
+
+const CLEAN_UP_TOAST_ID: usize = 0x61d3b8f2;
+
+const INSTALL_FROM_FILE_TOAST_ID: usize = 0x3fa07c5e;
+
+const SAFE_MODE_TOAST_ID: usize = 0x5a4e7d13;
+
+const BISECT_TOAST_ID: usize = 0x1b7c93e4;
+
+const REPAIR_TOAST_ID: usize = 0x5d17a9e4;
+
+const BLOCKED_VERSIONS_TOAST_ID: usize = 0x1e6bc053;
+
+/// The number of language badges shown on an extension card before the rest are
+/// summarized.
+const MAX_LANGUAGE_BADGES: usize = 3;
+
+/// How long an extension stays disabled when disabled from its card's menu,
+/// unless it is disabled until restart.
+const TRIAL_DISABLE_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// How often the resources used by an extension are measured while its
+/// details are shown.
+const RESOURCE_USAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
 
 pub fn init(cx: &mut AppContext) {
-    cx.observe_new_views(move |workspace: &mut Workspace, _cx| {
+    ExtensionsPageSettings::register(cx);
+    host_extensions::init(cx);
+    language_providers_view::init(cx);
+    workspace_recommendations::init(cx);
+    workspace::register_deserializable_item::<ExtensionsPage>(cx);
+
+    cx.observe_new_views(move |workspace: &mut Workspace, cx| {
+        let store = ExtensionStore::global(cx);
+        cx.subscribe(&store, |workspace, _, event, cx| {
+            show_extension_operation_toast(workspace, event, cx)
+        })
+        .detach();
+        let project = workspace.project().clone();
+        store.update(cx, |store, cx| {
+            store.capture_language_server_logs(&project, cx)
+        });
+        // The extensions from the `synced_extensions` setting may have been
+        // offered before this workspace was opened.
+        if let Some(offer) = store.read(cx).synced_extensions_offer().cloned() {
+            let toast = synced_extensions_toast(&offer, &|extension_id| extension_id.to_string());
+            workspace.show_toast(toast, cx);
+        }
+        if store.read(cx).is_in_safe_mode() {
+            workspace.show_toast(safe_mode_toast(), cx);
+        }
+        if let Some(bisect) = store.read(cx).extension_bisect() {
+            let message = format!(
+                "Bisecting extensions: {} of {} are disabled. Check whether the problem is still present, then continue.",
+                bisect.disabled_extensions().len(),
+                bisect.candidate_count()
+            );
+            workspace.show_toast(
+                Toast::new(BISECT_TOAST_ID, message).on_click("Continue Bisect", |cx| {
+                    cx.dispatch_action(Box::new(ContinueBisect))
+                }),
+                cx,
+            );
+        }
+
         workspace
             .register_action(move |workspace, _: &Extensions, cx| {
                 let extensions_page = ExtensionsPage::new(workspace, cx);
                 workspace.add_item_to_active_pane(Box::new(extensions_page), cx)
             })
+            .register_action(move |workspace, _: &HostExtensions, cx| {
+                open_host_extensions(workspace, cx)
+            })
+            .register_action(move |_, _: &CleanUpExtensions, cx| clean_up_extensions(cx))
+            .register_action(move |_, _: &RepairAll, cx| repair_all_extensions(cx))
+            .register_action(move |_, _: &RestartWithExtensionsDisabled, cx| {
+                restart_in_safe_mode(true, cx)
+            })
+            .register_action(move |_, _: &RestartWithExtensionsEnabled, cx| {
+                restart_in_safe_mode(false, cx)
+            })
+            .register_action(move |workspace, _: &StartBisect, cx| {
+                start_extension_bisect(workspace, cx)
+            })
+            .register_action(move |workspace, _: &ContinueBisect, cx| {
+                continue_extension_bisect(workspace, cx)
+            })
+            .register_action(move |_, _: &StopBisect, cx| stop_extension_bisect(cx))
+            .register_action(move |_, _: &InstallExtensionFromFile, cx| {
+                install_extension_from_file(cx)
+            })
+            .register_action(move |workspace, _: &InstallExtensionFromGit, cx| {
+                workspace.toggle_modal(cx, |cx| {
+                    InstallFromRepositoryModal::new(RepositoryInstallKind::Git, cx)
+                })
+            })
+            .register_action(
+                move |workspace, _: &InstallExtensionFromGithubRelease, cx| {
+                    workspace.toggle_modal(cx, |cx| {
+                        InstallFromRepositoryModal::new(RepositoryInstallKind::GithubRelease, cx)
+                    })
+                },
+            )
             .register_action(move |_, _: &InstallDevExtension, cx| {
                 let store = ExtensionStore::global(cx);
                 let prompt = cx.prompt_for_paths(gpui::PathPromptOptions {
@@ -57,96 +223,1257 @@ pub fn init(cx: &mut AppContext) {
     .detach();
 }
 
+/// Opens the list of the extensions that the host of the workspace's shared
+/// project has loaded.
+fn open_host_extensions(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    if !workspace.project().read(cx).is_remote() {
+        workspace.show_toast(
+            Toast::new(
+                HOST_EXTENSIONS_TOAST_ID,
+                "Host extensions can only be viewed in a project shared by someone else.",
+            ),
+            cx,
+        );
+        return;
+    }
+
+    if let Some(view) = workspace.items_of_type::<HostExtensionsView>(cx).next() {
+        workspace.activate_item(&view, cx);
+    } else {
+        let view = cx.new_view(|cx| HostExtensionsView::new(workspace, cx));
+        workspace.add_item_to_active_pane(Box::new(view), cx);
+    }
+}
+
+/// Restarts Zed, either with all extensions disabled or back to normal. The
+/// safe mode marker is only changed once the restart has been confirmed, so
+/// cancelling it leaves the next launch unaffected.
+fn restart_in_safe_mode(safe_mode: bool, cx: &mut ViewContext<Workspace>) {
+    let store = ExtensionStore::global(cx);
+    let confirm_restart = workspace::confirm_restart(cx);
+    cx.spawn(|_, mut cx| async move {
+        if !confirm_restart.await? {
+            return Ok(());
+        }
+        store
+            .update(&mut cx, |store, cx| {
+                store.set_safe_mode_on_restart(safe_mode, cx)
+            })?
+            .await?;
+        cx.update(|cx| cx.restart())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Starts looking for the extension that causes a problem, by restarting with
+/// half of the loaded extensions disabled.
+fn start_extension_bisect(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    let store = ExtensionStore::global(cx);
+    if store.read(cx).extension_bisect().is_some() {
+        continue_extension_bisect(workspace, cx);
+        return;
+    }
+
+    let answer = cx.prompt(
+        PromptLevel::Info,
+        "Find the extension that is causing a problem?",
+        Some("Zed will restart with half of the extensions disabled and ask whether the problem is still present. This repeats until the problem is narrowed down to a single extension."),
+        &["Start Bisect", "Cancel"],
+    );
+    cx.spawn(|this, mut cx| async move {
+        if answer.await? != 0 {
+            return Ok(());
+        }
+        let start = store.update(&mut cx, |store, cx| store.start_bisect(cx))?;
+        match start.await {
+            Ok(_) => cx.update(|cx| cx.dispatch_action(Box::new(workspace::Restart))),
+            Err(error) => this.update(&mut cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(
+                        BISECT_TOAST_ID,
+                        format!("Couldn't start the extension bisect: {error}"),
+                    ),
+                    cx,
+                )
+            }),
+        }
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Asks whether the problem is still present with the current set of
+/// extensions, and restarts with the next set, or reports the culprit.
+fn continue_extension_bisect(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    let store = ExtensionStore::global(cx);
+    let Some(bisect) = store.read(cx).extension_bisect() else {
+        workspace.show_toast(
+            Toast::new(BISECT_TOAST_ID, "No extension bisect is in progress."),
+            cx,
+        );
+        return;
+    };
+
+    let remaining_rounds = bisect.remaining_rounds();
+    let detail = format!(
+        "{} of the {} remaining extensions are disabled. {}",
+        bisect.disabled_extensions().len(),
+        bisect.candidate_count(),
+        if remaining_rounds == 1 {
+            "This is the last step.".to_string()
+        } else {
+            format!("About {remaining_rounds} steps are left.")
+        }
+    );
+    let answer = cx.prompt(
+        PromptLevel::Info,
+        "Is the problem still present?",
+        Some(&detail),
+        &[
+            "Problem Is Present",
+            "Problem Is Gone",
+            "Stop Bisect",
+            "Cancel",
+        ],
+    );
+    cx.spawn(|_, mut cx| async move {
+        let problem_present = match answer.await? {
+            0 => true,
+            1 => false,
+            2 => {
+                store
+                    .update(&mut cx, |store, cx| store.stop_bisect(cx))?
+                    .await?;
+                return cx.update(|cx| cx.dispatch_action(Box::new(workspace::Restart)));
+            }
+            _ => return Ok(()),
+        };
+
+        let step = store
+            .update(&mut cx, |store, cx| {
+                store.continue_bisect(problem_present, cx)
+            })?
+            .await?;
+        match step {
+            ExtensionBisectStep::Continue(_) => {}
+            ExtensionBisectStep::Found(extension_id) => {
+                let extension_name = store.update(&mut cx, |store, _| {
+                    store
+                        .installed_extension(&extension_id)
+                        .map_or_else(|| extension_id.to_string(), |manifest| manifest.name.clone())
+                })?;
+                let answer = cx.update(|cx| {
+                    cx.prompt(
+                        PromptLevel::Info,
+                        &format!("{extension_name} is causing the problem."),
+                        Some("All of the other extensions will be loaded again after restarting."),
+                        &["Disable It and Restart", "Restart"],
+                    )
+                })?;
+                if answer.await? == 0 {
+                    store.update(&mut cx, |store, cx| {
+                        store.disable_extension(extension_id, DisabledUntil::Reenabled, cx)
+                    })?;
+                }
+            }
+            ExtensionBisectStep::NotFound => {
+                cx.update(|cx| {
+                    cx.prompt(
+                        PromptLevel::Info,
+                        "The problem isn't caused by an extension.",
+                        Some("The problem was still present with all of the extensions disabled. They will be loaded again after restarting."),
+                        &["Restart"],
+                    )
+                })?
+                .await?;
+            }
+        }
+        cx.update(|cx| cx.dispatch_action(Box::new(workspace::Restart)))
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Abandons the extension bisect, restarting with all of the extensions loaded.
+fn stop_extension_bisect(cx: &mut ViewContext<Workspace>) {
+    let stop = ExtensionStore::global(cx).update(cx, |store, cx| store.stop_bisect(cx));
+    cx.spawn(|_, mut cx| async move {
+        stop.await?;
+        cx.update(|cx| cx.dispatch_action(Box::new(workspace::Restart)))
+    })
+    .detach_and_log_err(cx);
+}
+
+/// The notification shown in every workspace while extensions are disabled,
+/// so that it's clear why they aren't working.
+fn safe_mode_toast() -> Toast {
+    Toast::new(
+        SAFE_MODE_TOAST_ID,
+        "Zed was started with all extensions disabled.",
+    )
+    .on_click("Enable Extensions and Restart", |cx| {
+        cx.dispatch_action(Box::new(RestartWithExtensionsEnabled))
+    })
+}
+
+/// Removes the directories left behind in the extensions directory, and
+/// reports how much space that freed.
+fn clean_up_extensions(cx: &mut ViewContext<Workspace>) {
+    let clean_up =
+        ExtensionStore::global(cx).update(cx, |store, cx| store.remove_orphaned_directories(cx));
+    cx.spawn(|workspace, mut cx| async move {
+        let message = match clean_up.await {
+            Ok(removed) if removed.is_empty() => {
+                "There were no leftover extension files to clean up.".to_string()
+            }
+            Ok(removed) => format!(
+                "Removed {} leftover extension directories, freeing {}.",
+                removed.len(),
+                format_data_size(removed.iter().map(|orphan| orphan.size).sum())
+            ),
+            Err(error) => format!("Failed to clean up extension files: {error}"),
+        };
+        workspace.update(&mut cx, |workspace, cx| {
+            workspace.show_toast(Toast::new(CLEAN_UP_TOAST_ID, message), cx)
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Reinstalls every extension installed from the registry, to repair any whose
+/// files were corrupted, and reports the extensions that couldn't be
+/// reinstalled.
+fn repair_all_extensions(cx: &mut ViewContext<Workspace>) {
+    let reinstall =
+        ExtensionStore::global(cx).update(cx, |store, cx| store.reinstall_all_extensions(cx));
+    cx.spawn(|workspace, mut cx| async move {
+        let results = reinstall.await;
+        let failed = results
+            .iter()
+            .filter(|(_, result)| result.is_err())
+            .map(|(extension_id, _)| extension_id.to_string())
+            .collect::<Vec<_>>();
+        let message = if results.is_empty() {
+            "There are no extensions from the registry to repair.".to_string()
+        } else if failed.is_empty() {
+            format!("Reinstalled {} extensions.", results.len())
+        } else {
+            format!("Failed to reinstall {}.", failed.join(", "))
+        };
+        workspace.update(&mut cx, |workspace, cx| {
+            workspace.show_toast(Toast::new(REPAIR_TOAST_ID, message), cx)
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Prompts for a packaged extension archive and installs it, for machines
+/// without access to the registry. Success is reported by the usual install
+/// toast, so only failures are reported here.
+fn install_extension_from_file(cx: &mut ViewContext<Workspace>) {
+    let prompt = cx.prompt_for_paths(gpui::PathPromptOptions {
+        files: true,
+        directories: false,
+        multiple: false,
+    });
+    cx.spawn(|workspace, mut cx| async move {
+        let Some(archive_path) = prompt.await??.and_then(|mut paths| paths.pop()) else {
+            return Ok(());
+        };
+        let install = cx.update(|cx| {
+            ExtensionStore::global(cx).update(cx, |store, cx| {
+                store.install_from_archive(archive_path.clone(), cx)
+            })
+        })?;
+        if let Err(error) = install.await {
+            let message = format!(
+                "Failed to install {}: {error}",
+                archive_path
+                    .file_name()
+                    .unwrap_or(archive_path.as_os_str())
+                    .to_string_lossy()
+            );
+            workspace.update(&mut cx, |workspace, cx| {
+                workspace.show_toast(Toast::new(INSTALL_FROM_FILE_TOAST_ID, message), cx)
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Notifies the user when an install or upgrade finishes, since it may complete
+/// after they have moved on from the extensions page.
+fn show_extension_operation_toast(
+    workspace: &mut Workspace,
+    event: &extension::Event,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let store = ExtensionStore::global(cx).read(cx);
+    let extension_name = |extension_id: &str| {
+        store
+            .installed_extension(extension_id)
+            .map(|manifest| manifest.name.clone())
+            .or_else(|| {
+                store
+                    .remote_extension(extension_id)
+                    .map(|extension| extension.name.clone())
+            })
+            .unwrap_or_else(|| extension_id.to_string())
+    };
+
+    let toast = match event {
+        extension::Event::ExtensionInstalled { extension_id, .. }
+        | extension::Event::ExtensionOperationFailed { extension_id, .. }
+            if store.is_auto_updating(extension_id) =>
+        {
+            return;
+        }
+        extension::Event::ExtensionInstalled {
+            extension_id,
+            version,
+            operation,
+        } => {
+            let name = extension_name(extension_id);
+            let message = match operation {
+                ExtensionOperation::Upgrade => format!("Upgraded {name} to v{version}."),
+                _ => format!("Installed {name} v{version}."),
+            };
+            Toast::new(extension_toast_id(extension_id), message)
+        }
+        extension::Event::ExtensionOperationFailed {
+            extension_id,
+            operation,
+            error,
+        } => {
+            let name = extension_name(extension_id);
+            let message = match operation {
+                ExtensionOperation::Upgrade => format!("Failed to upgrade {name}: {error}"),
+                _ => format!("Failed to install {name}: {error}"),
+            };
+            let extension_id = extension_id.clone();
+            Toast::new(extension_toast_id(&extension_id), message).on_click("Retry", move |cx| {
+                ExtensionStore::global(cx).update(cx, |store, cx| {
+                    store.retry_operation(extension_id.clone(), cx)
+                })
+            })
+        }
+        extension::Event::ExtensionsAutoUpdated { upgraded, failed } => {
+            let mut message = String::new();
+            if !upgraded.is_empty() {
+                let upgraded = upgraded
+                    .iter()
+                    .map(|(extension_id, version)| {
+                        format!("{} v{version}", extension_name(extension_id))
+                    })
+                    .collect::<Vec<_>>();
+                message.push_str(&format!("Updated {}.", upgraded.join(", ")));
+            }
+            if !failed.is_empty() {
+                let failed = failed
+                    .iter()
+                    .map(|extension_id| extension_name(extension_id))
+                    .collect::<Vec<_>>();
+                if !message.is_empty() {
+                    message.push(' ');
+                }
+                message.push_str(&format!("Failed to update {}.", failed.join(", ")));
+            }
+            Toast::new(AUTO_UPDATE_TOAST_ID, message)
+        }
+        extension::Event::SyncedExtensionsAvailable { extensions } => {
+            synced_extensions_toast(extensions, &extension_name)
+        }
+        extension::Event::ExtensionDisabledAfterCrashes {
+            extension_id,
+            error,
+        } => {
+            let message = format!(
+                "{} was disabled after crashing repeatedly: {error}",
+                extension_name(extension_id)
+            );
+            let extension_id = extension_id.clone();
+            Toast::new(extension_toast_id(&extension_id), message).on_click(
+                "Re-enable",
+                move |cx| {
+                    ExtensionStore::global(cx)
+                        .update(cx, |store, cx| store.enable_extension(&extension_id, cx))
+                },
+            )
+        }
+        extension::Event::InstalledVersionsBlocked { extension_ids } => {
+            let names = extension_ids
+                .iter()
+                .map(|extension_id| extension_name(extension_id))
+                .collect::<Vec<_>>();
+            let message = format!(
+                "The extension registry blocked the installed version of {}.",
+                names.join(", ")
+            );
+            let toast = Toast::new(BLOCKED_VERSIONS_TOAST_ID, message);
+            match extension_ids.as_slice() {
+                [extension_id] => {
+                    let extension_id = extension_id.clone();
+                    let can_downgrade = store
+                        .installed_version_block(&extension_id)
+                        .map_or(false, |blocked| blocked.safe_version.is_some());
+                    if can_downgrade {
+                        toast.on_click("Downgrade", move |cx| {
+                            ExtensionStore::global(cx).update(cx, |store, cx| {
+                                store
+                                    .downgrade_blocked_extension(extension_id.clone(), cx)
+                                    .detach_and_log_err(cx)
+                            })
+                        })
+                    } else if ExtensionSettings::get_global(cx).requires_extension(&extension_id) {
+                        toast
+                    } else {
+                        toast.on_click("Uninstall", move |cx| {
+                            ExtensionStore::global(cx).update(cx, |store, cx| {
+                                store.uninstall_extension(extension_id.clone(), cx)
+                            })
+                        })
+                    }
+                }
+                _ => toast,
+            }
+        }
+        _ => return,
+    };
+    workspace.show_toast(toast, cx);
+}
+
+/// Offers to install the extensions that are installed on the user's other
+/// machines, but not on this one.
+fn synced_extensions_toast(
+    extensions: &ExtensionList,
+    extension_name: &dyn Fn(&str) -> String,
+) -> Toast {
+    let names = extensions
+        .extensions
+        .iter()
+        .map(|entry| extension_name(&entry.id))
+        .collect::<Vec<_>>();
+    let message = format!(
+        "Extensions installed on your other machines aren't installed here: {}.",
+        names.join(", ")
+    );
+    Toast::new(SYNCED_EXTENSIONS_TOAST_ID, message).on_click("Install", |cx| {
+        ExtensionStore::global(cx)
+            .update(cx, |store, cx| store.install_synced_extensions(cx))
+            .detach()
+    })
+}
+
+/// Returns an ID for the toasts about the given extension, so that each new
+/// toast replaces the previous one for the same extension.
+fn extension_toast_id(extension_id: &str) -> usize {
+    let mut hasher = DefaultHasher::new();
+    extension_id.hash(&mut hasher);
+    hasher.finish() as usize
+}
+
+/// Opens the extensions page and scrolls to the extension with the given ID.
+pub fn open_extension(
+    workspace: &mut Workspace,
+    extension_id: &str,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let extensions_page =
+        if let Some(extensions_page) = workspace.item_of_type::<ExtensionsPage>(cx) {
+            workspace.activate_item(&extensions_page, cx);
+            extensions_page
+        } else {
+            let extensions_page = ExtensionsPage::new(workspace, cx);
+            workspace.add_item_to_active_pane(Box::new(extensions_page.clone()), cx);
+            extensions_page
+        };
+    extensions_page.update(cx, |page, cx| {
+        page.reveal_extension(extension_id.into(), cx)
+    });
+}
+
+/// Returns the settings that apply to what an installed extension provides,
+/// as the key of each setting's section along with the key within it.
+fn extension_settings_keys(extension_id: &str, cx: &AppContext) -> Vec<(&'static str, Arc<str>)> {
+    let store = ExtensionStore::global(cx).read(cx);
+    let Some(manifest) = store.installed_extension(extension_id) else {
+        return Vec::new();
+    };
+    manifest
+        .language_servers
+        .keys()
+        .map(|name| ("lsp", name.0.clone()))
+        .chain(
+            store
+                .extension_language_names(extension_id)
+                .into_iter()
+                .map(|name| ("languages", name)),
+        )
+        .collect()
+}
+
+/// Opens the user's settings file, placing the cursor on the settings for
+/// what the given extension provides, if any have been configured.
+fn open_extension_settings(
+    workspace: &mut Workspace,
+    extension_id: &str,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let keys = extension_settings_keys(extension_id, cx);
+    let open_task = workspace::create_and_open_local_file(&paths::SETTINGS, cx, || {
+        settings::initial_user_settings_content().as_ref().into()
+    });
+    cx.spawn(|_, mut cx| async move {
+        let item = open_task.await?;
+        cx.update(|cx| {
+            let Some(editor) = item.act_as::<Editor>(cx) else {
+                return;
+            };
+            editor.update(cx, |editor, cx| {
+                let text = editor.buffer().read(cx).snapshot(cx).text();
+                let offset = keys.iter().find_map(|(section, key)| {
+                    let section_offset = text.find(&format!("\"{section}\""))?;
+                    let key_offset = text[section_offset..].find(&format!("\"{key}\""))?;
+                    Some(section_offset + key_offset)
+                });
+                if let Some(offset) = offset {
+                    editor.change_selections(Some(Autoscroll::center()), cx, |selections| {
+                        selections.select_ranges([offset..offset])
+                    });
+                }
+            });
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Returns the URL of the extension's listing on the web.
+pub fn extension_web_url(extension_id: &str, cx: &AppContext) -> String {
+    let server_url = &ClientSettings::get_global(cx).server_url;
+    format!("{server_url}/extensions/{extension_id}")
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 enum ExtensionFilter {
     All,
     Installed,
     NotInstalled,
+    /// The extensions recommended by the workspace's `.zed/extensions.json`.
+    Recommended,
 }
 
 impl ExtensionFilter {
+    /// Returns the name under which the filter is persisted.
+    fn key(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Installed => "installed",
+            Self::NotInstalled => "not_installed",
+            Self::Recommended => "recommended",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "all" => Some(Self::All),
+            "installed" => Some(Self::Installed),
+            "not_installed" => Some(Self::NotInstalled),
+            "recommended" => Some(Self::Recommended),
+            _ => None,
+        }
+    }
+
     pub fn include_dev_extensions(&self) -> bool {
         match self {
             Self::All | Self::Installed => true,
-            Self::NotInstalled => false,
+            Self::NotInstalled | Self::Recommended => false,
+        }
+    }
+}
+
+/// The kind of extension under which a registry extension is listed when the
+/// list is grouped by category.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ExtensionCategory {
+    Languages,
+    Themes,
+    LanguageServers,
+    Grammars,
+    Other,
+}
+
+impl ExtensionCategory {
+    const ALL: [Self; 5] = [
+        Self::Languages,
+        Self::Themes,
+        Self::LanguageServers,
+        Self::Grammars,
+        Self::Other,
+    ];
+
+    /// Returns the category of the first kind of contribution the extension
+    /// provides, so that each extension is listed only once.
+    fn of(extension: &ExtensionApiResponse) -> Self {
+        if !extension.languages.is_empty() {
+            Self::Languages
+        } else if !extension.themes.is_empty() {
+            Self::Themes
+        } else if !extension.language_servers.is_empty() {
+            Self::LanguageServers
+        } else if !extension.grammars.is_empty() {
+            Self::Grammars
+        } else {
+            Self::Other
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Languages => "Languages",
+            Self::Themes => "Themes",
+            Self::LanguageServers => "Language Servers",
+            Self::Grammars => "Grammars",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// A row in the list of registry extensions.
+#[derive(Debug, Clone, Copy)]
+enum ListEntry {
+    /// The header of a category, when the list is grouped by category.
+    CategoryHeader(ExtensionCategory),
+    /// An index into `remote_extension_ids`.
+    Extension(usize),
+}
+
+struct ThemePreview {
+    extension_id: Arc<str>,
+    original_theme: Arc<Theme>,
+    /// The names of the previewed themes, or `None` while they are being downloaded.
+    theme_names: Option<Vec<SharedString>>,
+    active_theme_ix: usize,
+}
+
+/// What the extensions page is showing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PageLocation {
+    List,
+    /// The details of an extension in the registry.
+    Detail(Arc<str>),
+}
+
+/// The outcome of an operation that was performed on several extensions at once.
+struct BatchOutcome {
+    operation: ExtensionOperation,
+    /// Each extension in the batch, along with the error that its operation
+    /// failed with, if any.
+    outcomes: Vec<(Arc<str>, Option<SharedString>)>,
+}
+
+/// The kind of contributions an extension makes, as shown on its card.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ExtensionKind {
+    Theme,
+    Language,
+    LanguageServer,
+    Multiple,
+}
+
+impl ExtensionKind {
+    fn from_contributions(
+        has_themes: bool,
+        has_languages: bool,
+        has_language_servers: bool,
+    ) -> Option<Self> {
+        let mut kinds = [
+            (has_themes, Self::Theme),
+            (has_languages, Self::Language),
+            (has_language_servers, Self::LanguageServer),
+        ]
+        .into_iter()
+        .filter_map(|(contributed, kind)| contributed.then_some(kind));
+
+        let kind = kinds.next()?;
+        if kinds.next().is_some() {
+            Some(Self::Multiple)
+        } else {
+            Some(kind)
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Theme => "Theme",
+            Self::Language => "Language",
+            Self::LanguageServer => "Language Server",
+            Self::Multiple => "Multiple",
+        }
+    }
+}
+
+/// The state of an extension, as conveyed on its card by both an icon and a
+/// color, so that it doesn't depend on the labels of the card's buttons.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ExtensionCardStatus {
+    Installed,
+    UpdateAvailable,
+    InProgress,
+    Disabled,
+    Damaged,
+    Blocked,
+    Failed,
+}
+
+impl ExtensionCardStatus {
+    const ALL: [Self; 7] = [
+        Self::Installed,
+        Self::UpdateAvailable,
+        Self::InProgress,
+        Self::Disabled,
+        Self::Damaged,
+        Self::Blocked,
+        Self::Failed,
+    ];
+
+    fn from_extension_status(
+        status: &ExtensionStatus,
+        latest_version: Option<&str>,
+        is_disabled: bool,
+        is_damaged: bool,
+        is_blocked: bool,
+    ) -> Option<Self> {
+        match status {
+            ExtensionStatus::NotInstalled => None,
+            ExtensionStatus::Installing
+            | ExtensionStatus::Upgrading
+            | ExtensionStatus::Removing => Some(Self::InProgress),
+            ExtensionStatus::Installed(_) if is_blocked => Some(Self::Blocked),
+            ExtensionStatus::Installed(_) if is_damaged => Some(Self::Damaged),
+            ExtensionStatus::Installed(_) if is_disabled => Some(Self::Disabled),
+            ExtensionStatus::Installed(installed_version) => {
+                if latest_version.map_or(false, |version| version != installed_version.as_ref()) {
+                    Some(Self::UpdateAvailable)
+                } else {
+                    Some(Self::Installed)
+                }
+            }
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Installed => "Installed",
+            Self::UpdateAvailable => "Update Available",
+            Self::InProgress => "In Progress",
+            Self::Disabled => "Disabled",
+            Self::Damaged => "Damaged",
+            Self::Blocked => "Blocked",
+            Self::Failed => "Failed",
+        }
+    }
+
+    fn icon(&self) -> IconName {
+        match self {
+            Self::Installed => IconName::Check,
+            Self::UpdateAvailable => IconName::ArrowUp,
+            Self::InProgress => IconName::ArrowCircle,
+            Self::Disabled => IconName::Dash,
+            Self::Damaged => IconName::ExclamationTriangle,
+            Self::Blocked => IconName::Close,
+            Self::Failed => IconName::XCircle,
+        }
+    }
+
+    /// The colors come from the theme's status colors, so that high-contrast
+    /// themes can make them stand out further.
+    fn color(&self) -> Color {
+        match self {
+            Self::Installed => Color::Success,
+            Self::UpdateAvailable => Color::Info,
+            Self::InProgress => Color::Muted,
+            Self::Disabled => Color::Warning,
+            Self::Damaged => Color::Error,
+            Self::Blocked => Color::Error,
+            Self::Failed => Color::Error,
         }
     }
 }
 
 pub struct ExtensionsPage {
+    workspace: WeakView<Workspace>,
+    workspace_id: WorkspaceId,
+    /// Whether the workspace's project is shared by someone else.
+    is_guest: bool,
     list: UniformListScrollHandle,
     telemetry: Arc<Telemetry>,
+    local_timezone: UtcOffset,
     is_fetching_extensions: bool,
+    /// Why the most recent attempt to fetch extensions from the registry failed.
+    fetch_error: Option<SharedString>,
+    /// Whether the registry couldn't be reached, and the extensions shown are
+    /// from the most recent listing that was fetched.
+    is_showing_cached_extensions: bool,
     filter: ExtensionFilter,
-    remote_extension_entries: Vec<ExtensionApiResponse>,
+    /// The registry collection that the remote extensions are limited to.
+    selected_collection: Option<Arc<str>>,
+    /// The registry extensions matching the current search, whose metadata is
+    /// kept in the [`ExtensionStore`].
+    remote_extension_ids: Vec<Arc<str>>,
     dev_extension_entries: Vec<Arc<ExtensionManifest>>,
+    /// The dev extensions whose build errors are expanded on their cards.
+    expanded_build_errors: HashSet<Arc<str>>,
+    /// The registry extensions selected for a batch operation.
+    selected_extension_ids: HashSet<Arc<str>>,
+    /// The list and detail views are swapped within the page, so the list's
+    /// search, scroll position, and selection are kept while a detail view is
+    /// shown.
+    location: PageLocation,
+    back_stack: Vec<PageLocation>,
+    forward_stack: Vec<PageLocation>,
+    detail_focus_handle: FocusHandle,
+    batch_outcome: Option<BatchOutcome>,
     filtered_remote_extension_indices: Vec<usize>,
+    /// The rows of the list, built from `filtered_remote_extension_indices`.
+    list_entries: Vec<ListEntry>,
+    /// The number of installed extensions that match the search and collection,
+    /// as of the last call to `filter_extension_entries`.
+    installed_extension_count: usize,
+    /// How many extensions are listed out of how many match the search, as of
+    /// the last call to `filter_extension_entries`.
+    results_summary: SharedString,
     query_editor: View<Editor>,
     query_contains_error: bool,
-    _subscriptions: [gpui::Subscription; 2],
+    theme_preview: Option<ThemePreview>,
+    /// An extension to scroll to once it appears in the list.
+    extension_to_reveal: Option<Arc<str>>,
+    dev_extensions_collapsed: bool,
+    /// Whether hidden extensions are listed anyway.
+    show_hidden_extensions: bool,
+    /// Whether the installed extensions are sorted by how long they took to
+    /// load, slowest first.
+    sort_by_startup_impact: bool,
+    /// The extensions recommended by the workspace's `.zed/extensions.json`.
+    workspace_recommendations: Vec<Arc<str>>,
+    _subscriptions: [gpui::Subscription; 4],
     extension_fetch_task: Option<Task<()>>,
+    _resource_usage_task: Task<()>,
 }
 
 impl ExtensionsPage {
     pub fn new(workspace: &Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let is_guest = workspace.project().read(cx).is_remote();
+        let recommendations = workspace_recommendations::load_recommendations(
+            workspace.project(),
+            workspace.app_state().fs.clone(),
+            cx,
+        );
         cx.new_view(|cx: &mut ViewContext<Self>| {
-            let store = ExtensionStore::global(cx);
-            let subscriptions = [
-                cx.observe(&store, |_, _, cx| cx.notify()),
-                cx.subscribe(&store, |this, _, event, cx| match event {
-                    extension::Event::ExtensionsUpdated => this.fetch_extensions_debounced(cx),
-                    _ => {}
-                }),
-            ];
-
-            let query_editor = cx.new_view(|cx| {
-                let mut input = Editor::single_line(cx);
-                input.set_placeholder_text("Search extensions...", cx);
-                input
-            });
-            cx.subscribe(&query_editor, Self::on_query_change).detach();
-
-            let mut this = Self {
-                list: UniformListScrollHandle::new(),
-                telemetry: workspace.client().telemetry().clone(),
-                is_fetching_extensions: false,
-                filter: ExtensionFilter::All,
-                dev_extension_entries: Vec::new(),
-                filtered_remote_extension_indices: Vec::new(),
-                remote_extension_entries: Vec::new(),
-                query_contains_error: false,
-                extension_fetch_task: None,
-                _subscriptions: subscriptions,
-                query_editor,
-            };
+            let mut this = Self::build(
+                workspace.weak_handle(),
+                workspace.database_id(),
+                is_guest,
+                workspace.client().telemetry().clone(),
+                None,
+                cx,
+            );
             this.fetch_extensions(None, cx);
+            ExtensionStore::global(cx).update(cx, |store, cx| {
+                // The collections may already have been prefetched.
+                if store.extension_collections().is_empty() {
+                    store.fetch_extension_collections(cx).detach_and_log_err(cx);
+                }
+            });
+            cx.spawn(|this, mut cx| async move {
+                let recommendations = recommendations.await;
+                this.update(&mut cx, |this, cx| {
+                    this.workspace_recommendations = recommendations;
+                    this.filter_extension_entries(cx);
+                })
+            })
+            .detach_and_log_err(cx);
             this
         })
     }
 
+    fn build(
+        workspace: WeakView<Workspace>,
+        workspace_id: WorkspaceId,
+        is_guest: bool,
+        telemetry: Arc<Telemetry>,
+        search_query: Option<String>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let store = ExtensionStore::global(cx);
+        let subscriptions = [
+            cx.observe(&store, |_, _, cx| cx.notify()),
+            cx.subscribe(&store, |this, _, event, cx| match event {
+                extension::Event::ExtensionsUpdated => this.fetch_extensions_debounced(cx),
+                extension::Event::RegistryChanged => {
+                    // The store no longer describes the previous registry's
+                    // extensions, so they're no longer listed.
+                    this.remote_extension_ids.clear();
+                    this.filter_extension_entries(cx);
+                    this.fetch_extensions(this.search_query(cx), cx);
+                    ExtensionStore::global(cx)
+                        .update(cx, |store, cx| store.fetch_extension_collections(cx))
+                        .detach_and_log_err(cx);
+                }
+                _ => {}
+            }),
+            cx.on_release(|this, _, cx| this.end_theme_preview(cx)),
+            cx.observe_global::<SettingsStore>(|this, cx| this.filter_extension_entries(cx)),
+        ];
+
+        let query_editor = cx.new_view(|cx| {
+            let mut input = Editor::single_line(cx);
+            input.set_placeholder_text("Search extensions...", cx);
+            if let Some(search_query) = search_query {
+                input.set_text(search_query, cx);
+            }
+            input
+        });
+        cx.subscribe(&query_editor, Self::on_query_change).detach();
+
+        let resource_usage_task = cx.spawn(|this, mut cx| async move {
+            loop {
+                cx.background_executor()
+                    .timer(RESOURCE_USAGE_REFRESH_INTERVAL)
+                    .await;
+                let Ok(refresh) = this.update(&mut cx, |this, cx| {
+                    matches!(this.location, PageLocation::Detail(_)).then(|| {
+                        ExtensionStore::global(cx)
+                            .update(cx, |store, cx| store.refresh_resource_usage(cx))
+                    })
+                }) else {
+                    break;
+                };
+                if let Some(refresh) = refresh {
+                    refresh.await;
+                }
+            }
+        });
+
+        Self {
+            workspace,
+            workspace_id,
+            is_guest,
+            list: UniformListScrollHandle::new(),
+            telemetry,
+            local_timezone: cx.local_timezone(),
+            is_fetching_extensions: false,
+            fetch_error: None,
+            is_showing_cached_extensions: false,
+            filter: ExtensionFilter::All,
+            selected_collection: None,
+            dev_extension_entries: Vec::new(),
+            expanded_build_errors: HashSet::default(),
+            selected_extension_ids: HashSet::default(),
+            location: PageLocation::List,
+            back_stack: Vec::new(),
+            forward_stack: Vec::new(),
+            detail_focus_handle: cx.focus_handle(),
+            batch_outcome: None,
+            filtered_remote_extension_indices: Vec::new(),
+            list_entries: Vec::new(),
+            installed_extension_count: 0,
+            results_summary: SharedString::default(),
+            remote_extension_ids: Vec::new(),
+            query_contains_error: false,
+            theme_preview: None,
+            extension_to_reveal: None,
+            dev_extensions_collapsed: KEY_VALUE_STORE
+                .read_kvp(DEV_EXTENSIONS_COLLAPSED_KEY)
+                .log_err()
+                .flatten()
+                .map_or(false, |collapsed| collapsed == "true"),
+            show_hidden_extensions: false,
+            sort_by_startup_impact: false,
+            workspace_recommendations: Vec::new(),
+            extension_fetch_task: None,
+            _resource_usage_task: resource_usage_task,
+            _subscriptions: subscriptions,
+            query_editor,
+        }
+    }
+
     fn filter_extension_entries(&mut self, cx: &mut ViewContext<Self>) {
         let extension_store = ExtensionStore::global(cx).read(cx);
+        let collection = self.selected_collection.as_ref().and_then(|collection_id| {
+            extension_store
+                .extension_collections()
+                .iter()
+                .find(|collection| &collection.id == collection_id)
+        });
 
+        let hidden_extensions = &ExtensionsPageSettings::get_global(cx).hidden_extensions;
+        // Only extensions that the store describes are listed, so that every
+        // row of the list can be rendered.
+        let is_listed = |extension_id: &Arc<str>| {
+            extension_store.remote_extension(extension_id).is_some()
+                && collection.map_or(true, |collection| {
+                    collection.extension_ids.contains(extension_id)
+                })
+        };
+        self.installed_extension_count = self.dev_extension_entries.len()
+            + self
+                .remote_extension_ids
+                .iter()
+                .filter(|extension_id| {
+                    is_listed(extension_id)
+                        && matches!(
+                            extension_store.extension_status(extension_id),
+                            ExtensionStatus::Installed(_)
+                        )
+                })
+                .count();
         self.filtered_remote_extension_indices.clear();
         self.filtered_remote_extension_indices.extend(
-            self.remote_extension_entries
+            self.remote_extension_ids
                 .iter()
                 .enumerate()
-                .filter(|(_, extension)| match self.filter {
+                .filter(|(_, extension_id)| is_listed(extension_id))
+                .filter(|(_, extension_id)| {
+                    self.show_hidden_extensions
+                        || self.filter == ExtensionFilter::Installed
+                        || !hidden_extensions
+                            .iter()
+                            .any(|hidden_id| hidden_id.as_str() == extension_id.as_ref())
+                })
+                .filter(|(_, extension_id)| match self.filter {
                     ExtensionFilter::All => true,
                     ExtensionFilter::Installed => {
-                        let status = extension_store.extension_status(&extension.id);
+                        let status = extension_store.extension_status(extension_id);
                         matches!(status, ExtensionStatus::Installed(_))
                     }
                     ExtensionFilter::NotInstalled => {
-                        let status = extension_store.extension_status(&extension.id);
+                        let status = extension_store.extension_status(extension_id);
 
                         matches!(status, ExtensionStatus::NotInstalled)
                     }
+                    ExtensionFilter::Recommended => {
+                        self.workspace_recommendations.contains(*extension_id)
+                    }
                 })
                 .map(|(ix, _)| ix),
         );
-        cx.notify();
+
+        // Starred extensions are listed first, in the registry's order.
+        let starred_extensions = &ExtensionsPageSettings::get_global(cx).starred_extensions;
+        self.filtered_remote_extension_indices.sort_by_key(|ix| {
+            let extension_id = &self.remote_extension_ids[*ix];
+            !starred_extensions
+                .iter()
+                .any(|starred_id| starred_id.as_str() == extension_id.as_ref())
+        });
+        if self.filter == ExtensionFilter::Installed && self.sort_by_startup_impact {
+            self.filtered_remote_extension_indices.sort_by_key(|ix| {
+                let extension_id = &self.remote_extension_ids[*ix];
+                Reverse(extension_store.extension_load_time(extension_id))
+            });
+        }
+
+        self.list_entries.clear();
+        if ExtensionsPageSettings::get_global(cx).group_by_category {
+            for category in ExtensionCategory::ALL {
+                let mut indices = self
+                    .filtered_remote_extension_indices
+                    .iter()
+                    .copied()
+                    .filter(|ix| {
+                        extension_store
+                            .remote_extension(&self.remote_extension_ids[*ix])
+                            .map_or(ExtensionCategory::Other, ExtensionCategory::of)
+                            == category
+                    })
+                    .peekable();
+                if indices.peek().is_some() {
+                    self.list_entries.push(ListEntry::CategoryHeader(category));
+                    self.list_entries.extend(indices.map(ListEntry::Extension));
+                }
+            }
+        } else {
+            self.list_entries.extend(
+                self.filtered_remote_extension_indices
+                    .iter()
+                    .copied()
+                    .map(ListEntry::Extension),
+            );
+        }
+
+        let dev_extension_count = self.dev_extension_entries.len();
+        let shown_dev_extension_count = if self.filter.include_dev_extensions() {
+            dev_extension_count
+        } else {
+            0
+        };
+        let shown_count = self.filtered_remote_extension_indices.len() + shown_dev_extension_count;
+        let total_count = self.remote_extension_ids.len() + dev_extension_count;
+        let mut results_summary = format!(
+            "Showing {shown_count} of {total_count} {}",
+            if total_count == 1 {
+                "extension"
+            } else {
+                "extensions"
+            }
+        );
+        if let Some(search) = self.search_query(cx) {
+            results_summary.push_str(&format!(" for '{}'", search.trim()));
+        }
+        if shown_dev_extension_count > 0 {
+            results_summary.push_str(&format!(", {shown_dev_extension_count} in development"));
+        }
+        self.results_summary = results_summary.into();
+
+        self.save_page_state(cx);
+        cx.notify();
+    }
+
+    /// Persists the filter, search query, and collection, so that they can be
+    /// restored when the workspace is reopened.
+    fn save_page_state(&self, cx: &mut ViewContext<Self>) {
+        let item_id = cx.entity_id().as_u64();
+        let workspace_id = self.workspace_id;
+        let filter = self.filter.key().to_string();
+        let search_query = self.search_query(cx);
+        let collection = self
+            .selected_collection
+            .as_ref()
+            .map(|collection_id| collection_id.to_string());
+        cx.background_executor()
+            .spawn(async move {
+                EXTENSIONS_PAGE_DB
+                    .save_page_state(item_id, workspace_id, filter, search_query, collection)
+                    .await
+                    .log_err();
+            })
+            .detach();
+    }
+
+    fn restore_page_state(
+        &mut self,
+        filter: &str,
+        search_query: Option<String>,
+        collection: Option<String>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(filter) = ExtensionFilter::from_key(filter) {
+            self.filter = filter;
+        }
+        self.selected_collection = collection.map(Arc::from);
+        if let Some(search_query) = search_query {
+            // Editing the query refetches the extensions.
+            self.query_editor
+                .update(cx, |editor, cx| editor.set_text(search_query, cx));
+        }
+        self.filter_extension_entries(cx);
+    }
+
+    fn is_starred(extension_id: &str, cx: &AppContext) -> bool {
+        ExtensionsPageSettings::get_global(cx)
+            .starred_extensions
+            .iter()
+            .any(|starred_id| starred_id == extension_id)
+    }
+
+    fn toggle_starred(&mut self, extension_id: Arc<str>, cx: &mut ViewContext<Self>) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let fs = workspace.app_state().fs.clone();
+                settings::update_settings_file::<ExtensionsPageSettings>(fs, cx, move |settings| {
+                    let starred = settings.starred_extensions.get_or_insert_with(Vec::new);
+                    if let Some(ix) = starred
+                        .iter()
+                        .position(|starred_id| starred_id.as_str() == extension_id.as_ref())
+                    {
+                        starred.remove(ix);
+                    } else {
+                        starred.push(extension_id.to_string());
+                    }
+                });
+            })
+            .log_err();
+    }
+
+    fn is_hidden(extension_id: &str, cx: &AppContext) -> bool {
+        ExtensionsPageSettings::get_global(cx)
+            .hidden_extensions
+            .iter()
+            .any(|hidden_id| hidden_id == extension_id)
+    }
+
+    fn toggle_hidden(&mut self, extension_id: Arc<str>, cx: &mut ViewContext<Self>) {
+        self.selected_extension_ids.remove(&extension_id);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let fs = workspace.app_state().fs.clone();
+                settings::update_settings_file::<ExtensionsPageSettings>(fs, cx, move |settings| {
+                    let hidden = settings.hidden_extensions.get_or_insert_with(Vec::new);
+                    if let Some(ix) = hidden
+                        .iter()
+                        .position(|hidden_id| hidden_id.as_str() == extension_id.as_ref())
+                    {
+                        hidden.remove(ix);
+                    } else {
+                        hidden.push(extension_id.to_string());
+                    }
+                });
+            })
+            .log_err();
+    }
+
+    /// Overrides whether the extension is upgraded automatically, removing the
+    /// override when it matches the `auto_update_extensions` setting.
+    fn set_auto_update(
+        &mut self,
+        extension_id: Arc<str>,
+        enabled: bool,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let auto_update_extensions = ExtensionSettings::get_global(cx).auto_update_extensions;
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let fs = workspace.app_state().fs.clone();
+                settings::update_settings_file::<ExtensionSettings>(fs, cx, move |settings| {
+                    let overrides = settings
+                        .auto_update_overrides
+                        .get_or_insert_with(Default::default);
+                    if enabled == auto_update_extensions {
+                        overrides.remove(extension_id.as_ref());
+                    } else {
+                        overrides.insert(extension_id.to_string(), enabled);
+                    }
+                });
+            })
+            .log_err();
+    }
+
+    /// Starts building the extension from the latest commit on the default
+    /// branch of the given repository, or stops doing so if no repository is
+    /// given.
+    fn set_tracked_repository(
+        &mut self,
+        extension_id: Arc<str>,
+        repository_url: Option<String>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let fs = workspace.app_state().fs.clone();
+                settings::update_settings_file::<ExtensionSettings>(fs, cx, move |settings| {
+                    let tracked = settings
+                        .tracked_extensions
+                        .get_or_insert_with(Default::default);
+                    match repository_url {
+                        Some(repository_url) => {
+                            tracked.insert(extension_id.to_string(), repository_url);
+                        }
+                        None => {
+                            tracked.remove(extension_id.as_ref());
+                        }
+                    }
+                });
+            })
+            .log_err();
     }
 
     fn fetch_extensions(&mut self, search: Option<String>, cx: &mut ViewContext<Self>) {
@@ -163,8 +1490,17 @@ impl ExtensionsPage {
             store.fetch_extensions(search.as_deref(), cx)
         });
 
+        let contribution_matches = match search.as_deref() {
+            Some(search) if ExtensionsPageSettings::get_global(cx).search_contributions => {
+                extension_store
+                    .read(cx)
+                    .extensions_with_matching_contributions(search)
+            }
+            _ => Vec::new(),
+        };
+
         cx.spawn(move |this, mut cx| async move {
-            let dev_extensions = if let Some(search) = search {
+            let dev_extensions = if let Some(search) = search.clone() {
                 let match_candidates = dev_extensions
                     .iter()
                     .enumerate()
@@ -184,62 +1520,468 @@ impl ExtensionsPage {
                     cx.background_executor().clone(),
                 )
                 .await;
-                matches
+                let mut matching_dev_extensions = matches
                     .into_iter()
                     .map(|mat| dev_extensions[mat.candidate_id].clone())
-                    .collect()
+                    .collect::<Vec<_>>();
+                for manifest in &dev_extensions {
+                    if contribution_matches.contains(&manifest.id)
+                        && !matching_dev_extensions
+                            .iter()
+                            .any(|matching| matching.id == manifest.id)
+                    {
+                        matching_dev_extensions.push(manifest.clone());
+                    }
+                }
+                matching_dev_extensions
             } else {
                 dev_extensions
             };
 
             let fetch_result = remote_extensions.await;
+            let cached_extensions = match &fetch_result {
+                Ok(_) => None,
+                Err(_) => extension_store
+                    .update(&mut cx, |store, cx| {
+                        store.cached_extensions(search.as_deref(), cx)
+                    })?
+                    .await
+                    .log_err(),
+            };
             this.update(&mut cx, |this, cx| {
                 cx.notify();
                 this.dev_extension_entries = dev_extensions;
                 this.is_fetching_extensions = false;
-                this.remote_extension_entries = fetch_result?;
+                this.check_dev_extensions_compatibility(cx);
+                this.is_showing_cached_extensions = cached_extensions.is_some();
+                let remote_extensions = match (fetch_result, cached_extensions) {
+                    (Ok(remote_extensions), _) => {
+                        this.fetch_error = None;
+                        remote_extensions
+                    }
+                    (Err(error), Some(cached_extensions)) => {
+                        this.fetch_error = Some(error.to_string().into());
+                        cached_extensions
+                    }
+                    (Err(error), None) => {
+                        this.fetch_error = Some(error.to_string().into());
+                        this.filter_extension_entries(cx);
+                        return Err(error);
+                    }
+                };
+                this.remote_extension_ids = remote_extensions
+                    .into_iter()
+                    .map(|extension| extension.id)
+                    .collect();
+                // Installed extensions whose languages, themes, or language
+                // servers match the search are listed after the registry's
+                // matches, as long as the registry has described them.
+                let store = ExtensionStore::global(cx).read(cx);
+                for extension_id in contribution_matches {
+                    if !this.remote_extension_ids.contains(&extension_id)
+                        && store.remote_extension(&extension_id).is_some()
+                    {
+                        this.remote_extension_ids.push(extension_id);
+                    }
+                }
                 this.filter_extension_entries(cx);
+                this.refresh_extension_data_sizes(cx);
+                this.scroll_to_extension_to_reveal(cx);
                 anyhow::Ok(())
             })?
         })
         .detach_and_log_err(cx);
     }
 
+    fn reveal_extension(&mut self, extension_id: Arc<str>, cx: &mut ViewContext<Self>) {
+        self.extension_to_reveal = Some(extension_id);
+        if self.search_query(cx).is_some() {
+            // Clearing the query refetches the extensions, after which the
+            // extension is revealed.
+            self.query_editor
+                .update(cx, |editor, cx| editor.set_text("", cx));
+        }
+        if self.filter != ExtensionFilter::All {
+            self.filter = ExtensionFilter::All;
+            self.filter_extension_entries(cx);
+        }
+        self.scroll_to_extension_to_reveal(cx);
+    }
+
+    fn scroll_to_extension_to_reveal(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(extension_id) = self.extension_to_reveal.as_ref() else {
+            return;
+        };
+
+        if self.filter.include_dev_extensions()
+            && self
+                .dev_extension_entries
+                .iter()
+                .any(|extension| &extension.id == extension_id)
+        {
+            self.extension_to_reveal = None;
+            self.set_dev_extensions_collapsed(false, cx);
+            return;
+        }
+
+        let Some(ix) = self.list_entries.iter().position(|entry| match entry {
+            ListEntry::Extension(ix) => &self.remote_extension_ids[*ix] == extension_id,
+            ListEntry::CategoryHeader(_) => false,
+        }) else {
+            return;
+        };
+
+        self.list.scroll_to_item(ix);
+        self.extension_to_reveal = None;
+    }
+
+    fn set_dev_extensions_collapsed(&mut self, collapsed: bool, cx: &mut ViewContext<Self>) {
+        if self.dev_extensions_collapsed == collapsed {
+            return;
+        }
+        self.dev_extensions_collapsed = collapsed;
+        cx.notify();
+
+        cx.background_executor()
+            .spawn(async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        DEV_EXTENSIONS_COLLAPSED_KEY.to_string(),
+                        collapsed.to_string(),
+                    )
+                    .await
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn check_dev_extensions_compatibility(&mut self, cx: &mut ViewContext<Self>) {
+        ExtensionStore::global(cx)
+            .update(cx, |store, cx| store.check_dev_extensions_compatibility(cx))
+            .detach_and_log_err(cx);
+    }
+
+    fn render_collections(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let collections = extension_store.extension_collections().to_vec();
+        let selected_collection = collections
+            .iter()
+            .find(|collection| Some(&collection.id) == self.selected_collection.as_ref())
+            .cloned();
+        let not_installed_count = selected_collection.as_ref().map_or(0, |collection| {
+            collection
+                .extension_ids
+                .iter()
+                .filter(|extension_id| {
+                    matches!(
+                        extension_store.extension_status(extension_id),
+                        ExtensionStatus::NotInstalled
+                    )
+                })
+                .count()
+        });
+
+        v_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .flex_wrap()
+                    .child(
+                        Label::new("Collections")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .children(collections.into_iter().map(|collection| {
+                        let is_selected = self.selected_collection.as_ref() == Some(&collection.id);
+                        Button::new(
+                            SharedString::from(format!("collection-{}", collection.id)),
+                            collection.name,
+                        )
+                        .style(ButtonStyle::Filled)
+                        .selected(is_selected)
+                        .on_click(cx.listener(move |this, _, cx| {
+                            this.select_collection(collection.id.clone(), cx)
+                        }))
+                    })),
+            )
+            .children(selected_collection.map(|collection| {
+                h_flex()
+                    .gap_2()
+                    .justify_between()
+                    .child(
+                        Label::new(collection.description.clone().unwrap_or_default())
+                            .size(LabelSize::Small),
+                    )
+                    .child(
+                        Button::new(
+                            "install-collection",
+                            format!("Install All ({not_installed_count})"),
+                        )
+                        .style(ButtonStyle::Filled)
+                        .disabled(not_installed_count == 0)
+                        .on_click(
+                            cx.listener(move |this, _, cx| {
+                                this.install_collection(&collection, cx)
+                            }),
+                        )
+                        .tooltip(|cx| {
+                            Tooltip::text("Install every extension in this collection", cx)
+                        }),
+                    )
+            }))
+    }
+
+    fn select_collection(&mut self, collection_id: Arc<str>, cx: &mut ViewContext<Self>) {
+        if self.selected_collection.as_ref() == Some(&collection_id) {
+            self.selected_collection = None;
+        } else {
+            self.selected_collection = Some(collection_id);
+        }
+        self.filter_extension_entries(cx);
+    }
+
+    fn install_collection(&mut self, collection: &ExtensionCollection, cx: &mut ViewContext<Self>) {
+        report_extension_event(
+            &self.telemetry,
+            "extensions: install extension collection".to_string(),
+            cx,
+        );
+        ExtensionStore::global(cx)
+            .update(cx, |store, cx| {
+                store.install_extension_collection(collection, cx)
+            })
+            .detach_and_log_err(cx);
+    }
+
     fn render_extensions(
         &mut self,
         range: Range<usize>,
         cx: &mut ViewContext<Self>,
-    ) -> Vec<ExtensionCard> {
-        let dev_extension_entries_len = if self.filter.include_dev_extensions() {
-            self.dev_extension_entries.len()
-        } else {
-            0
-        };
-        range
-            .map(|ix| {
-                if ix < dev_extension_entries_len {
-                    let extension = &self.dev_extension_entries[ix];
-                    self.render_dev_extension(extension, cx)
-                } else {
-                    let extension_ix =
-                        self.filtered_remote_extension_indices[ix - dev_extension_entries_len];
-                    let extension = &self.remote_extension_entries[extension_ix];
-                    self.render_remote_extension(extension, cx)
+    ) -> Vec<AnyElement> {
+        // The list's item count is the number of `list_entries`, so exactly one
+        // element is rendered for each of them.
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let entries = self.list_entries[range]
+            .iter()
+            .map(|entry| match entry {
+                ListEntry::CategoryHeader(category) => Err(Some(*category)),
+                ListEntry::Extension(extension_ix) => {
+                    let extension_id = &self.remote_extension_ids[*extension_ix];
+                    extension_store
+                        .remote_extension(extension_id)
+                        .cloned()
+                        .ok_or(None)
                 }
             })
+            .collect::<Vec<_>>();
+        let is_compact =
+            ExtensionsPageSettings::get_global(cx).list_density == ListDensity::Compact;
+        entries
+            .iter()
+            .map(|entry| match entry {
+                Ok(extension) if is_compact => self
+                    .render_compact_remote_extension(extension, cx)
+                    .into_any_element(),
+                Ok(extension) => self
+                    .render_remote_extension(extension, cx)
+                    .into_any_element(),
+                Err(Some(category)) => render_category_header(*category).into_any_element(),
+                // Rows are only built for extensions that the store describes,
+                // and are rebuilt when it stops describing them.
+                Err(None) => div().into_any_element(),
+            })
             .collect()
     }
 
+    fn render_dev_extensions_section(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let is_open = !self.dev_extensions_collapsed;
+        let is_rebuilding_dev_extensions = ExtensionStore::global(cx)
+            .read(cx)
+            .is_rebuilding_dev_extensions();
+        v_flex()
+            .pt_4()
+            .child(
+                ListHeader::new("Development")
+                    .toggle(is_open)
+                    .on_toggle(cx.listener(move |this, _, cx| {
+                        this.set_dev_extensions_collapsed(is_open, cx)
+                    }))
+                    .end_slot(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Button::new("rebuild-all-dev-extensions", "Rebuild All")
+                                    .style(ButtonStyle::Subtle)
+                                    .label_size(LabelSize::Small)
+                                    .disabled(is_rebuilding_dev_extensions)
+                                    .on_click(
+                                        cx.listener(|this, _, cx| this.rebuild_dev_extensions(cx)),
+                                    )
+                                    .tooltip(|cx| Tooltip::text("Rebuild all dev extensions", cx)),
+                            )
+                            .child(
+                                IconButton::new("extension-api-reference", IconName::FileDoc)
+                                    .icon_size(IconSize::Small)
+                                    .on_click(cx.listener(|this, _, cx| {
+                                        this.open_extension_api_reference(cx)
+                                    }))
+                                    .tooltip(|cx| Tooltip::text("Extension API Reference", cx)),
+                            )
+                            .child(
+                                Label::new(self.dev_extension_entries.len().to_string())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    ),
+            )
+            .when(is_open, |this| {
+                this.child(
+                    v_flex()
+                        .id("dev-extensions")
+                        .max_h(rems(24.))
+                        .overflow_y_scroll()
+                        .children(
+                            self.dev_extension_entries
+                                .iter()
+                                .map(|extension| self.render_dev_extension(extension, cx))
+                                .collect::<Vec<_>>(),
+                        ),
+                )
+            })
+    }
+
+    /// Renders the extensions that were upgraded since the user last
+    /// acknowledged their upgrades.
+    fn render_recently_upgraded_section(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let upgrades = extension_store
+            .recently_upgraded_extensions()
+            .map(|(extension_id, upgrade)| {
+                let name = extension_store
+                    .installed_extension(extension_id)
+                    .map_or_else(
+                        || extension_id.to_string(),
+                        |manifest| manifest.name.clone(),
+                    );
+                (extension_id.clone(), name, upgrade.clone())
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .pt_4()
+            .child(
+                ListHeader::new("Recently Upgraded").end_slot(
+                    Button::new("acknowledge-all-upgrades", "Dismiss All")
+                        .style(ButtonStyle::Subtle)
+                        .label_size(LabelSize::Small)
+                        .on_click(|_, cx| {
+                            ExtensionStore::global(cx)
+                                .update(cx, |store, cx| store.acknowledge_all_upgrades(cx))
+                        }),
+                ),
+            )
+            .children(
+                upgrades
+                    .into_iter()
+                    .map(|(extension_id, name, upgrade)| {
+                        let versions = match upgrade.previous_version {
+                            Some(previous_version) => {
+                                format!("v{previous_version} → v{}", upgrade.version)
+                            }
+                            None => format!("v{}", upgrade.version),
+                        };
+                        h_flex()
+                            .py_1()
+                            .gap_2()
+                            .justify_between()
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .child(Indicator::dot().color(Color::Accent))
+                                    .child(
+                                        div()
+                                            .id(SharedString::from(format!(
+                                                "upgraded-name-{extension_id}"
+                                            )))
+                                            .cursor_pointer()
+                                            .child(Label::new(name))
+                                            .on_click(cx.listener({
+                                                let extension_id = extension_id.clone();
+                                                move |this, _, cx| {
+                                                    this.open_extension_detail(
+                                                        extension_id.clone(),
+                                                        cx,
+                                                    )
+                                                }
+                                            })),
+                                    )
+                                    .child(
+                                        Label::new(versions)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    ),
+                            )
+                            .child(
+                                IconButton::new(
+                                    SharedString::from(format!("acknowledge-{extension_id}")),
+                                    IconName::Close,
+                                )
+                                .icon_size(IconSize::Small)
+                                .on_click(move |_, cx| {
+                                    ExtensionStore::global(cx).update(cx, |store, cx| {
+                                        store.acknowledge_upgrade(&extension_id, cx)
+                                    })
+                                })
+                                .tooltip(|cx| Tooltip::text("Dismiss", cx)),
+                            )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+    }
+
     fn render_dev_extension(
         &self,
         extension: &ExtensionManifest,
         cx: &mut ViewContext<Self>,
     ) -> ExtensionCard {
-        let status = ExtensionStore::global(cx)
-            .read(cx)
-            .extension_status(&extension.id);
+        let card_metadata = ExtensionsPageSettings::get_global(cx).card_metadata;
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let status = extension_store.extension_status(&extension.id);
+        let build_error = extension_store
+            .dev_extension_build_error(&extension.id)
+            .map(|error| SharedString::from(error.to_string()));
+        let card_status = if build_error.is_some() {
+            Some(ExtensionCardStatus::Failed)
+        } else {
+            ExtensionCardStatus::from_extension_status(
+                &status,
+                None,
+                extension_store
+                    .extension_disabled_until(&extension.id)
+                    .is_some(),
+                false,
+                false,
+            )
+        };
+        let is_build_error_expanded =
+            build_error.is_some() && self.expanded_build_errors.contains(&extension.id);
+        let requires_restart = extension_store.extension_requires_restart(&extension.id);
+        let is_operation_slow = extension_store.is_operation_slow(&extension.id);
+        let is_tracked = extension_store.is_tracked(&extension.id);
+        let provides_active_theme = extension_store.provides_active_theme(&extension.id, cx);
 
         let repository_url = extension.repository.clone();
+        let compatibility_issues = extension_store
+            .dev_extension_compatibility(&extension.id)
+            .filter(|report| !report.is_compatible())
+            .map(|report| {
+                report
+                    .issues
+                    .iter()
+                    .map(|issue| issue.message())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            });
 
         ExtensionCard::new()
             .child(
@@ -253,7 +1995,74 @@ impl ExtensionsPage {
                             .child(
                                 Headline::new(format!("v{}", extension.version))
                                     .size(HeadlineSize::XSmall),
-                            ),
+                            )
+                            .children(
+                                ExtensionKind::from_contributions(
+                                    !extension.themes.is_empty(),
+                                    !extension.languages.is_empty()
+                                        || !extension.grammars.is_empty(),
+                                    !extension.language_servers.is_empty(),
+                                )
+                                .map(render_kind_badge),
+                            )
+                            .when(provides_active_theme, |this| {
+                                this.child(render_active_theme_badge())
+                            })
+                            .when(is_tracked, |this| {
+                                this.child(ExtensionBadge::new("Tracking Main Branch"))
+                            })
+                            .children(card_status.map(render_status_indicator))
+                            .children(requires_restart.then(render_restart_required_badge))
+                            .when(card_metadata.languages, |this| {
+                                this.children(self.render_language_badges(
+                                    &extension.id,
+                                    extension.languages.iter().map(|path| path.as_path()),
+                                    extension.grammars.keys().map(|name| name.as_ref()),
+                                    cx,
+                                ))
+                            })
+                            .children(build_error.is_some().then(|| {
+                                IconButton::new(
+                                    SharedString::from(format!("build-error-{}", extension.id)),
+                                    IconName::XCircle,
+                                )
+                                .icon_color(Color::Error)
+                                .icon_size(IconSize::Small)
+                                .selected(is_build_error_expanded)
+                                .on_click(cx.listener({
+                                    let extension_id = extension.id.clone();
+                                    move |this, _, cx| {
+                                        this.toggle_build_error(extension_id.clone(), cx)
+                                    }
+                                }))
+                                .tooltip(move |cx| {
+                                    Tooltip::text(
+                                        if is_build_error_expanded {
+                                            "Hide build error"
+                                        } else {
+                                            "Show build error"
+                                        },
+                                        cx,
+                                    )
+                                })
+                            }))
+                            .children(compatibility_issues.map(|issues| {
+                                IconButton::new(
+                                    SharedString::from(format!("compatibility-{}", extension.id)),
+                                    IconName::ExclamationTriangle,
+                                )
+                                .icon_color(Color::Warning)
+                                .icon_size(IconSize::Small)
+                                .on_click(|_, cx| cx.open_url(EXTENSION_API_MIGRATION_NOTES_URL))
+                                .tooltip(move |cx| {
+                                    Tooltip::with_meta(
+                                        "Incompatible with this version of Zed",
+                                        None,
+                                        issues.clone(),
+                                        cx,
+                                    )
+                                })
+                            })),
                     )
                     .child(
                         h_flex()
@@ -264,30 +2073,51 @@ impl ExtensionsPage {
                                     SharedString::from(format!("rebuild-{}", extension.id)),
                                     "Rebuild",
                                 )
-                                .on_click({
+                                .on_click(cx.listener({
                                     let extension_id = extension.id.clone();
-                                    move |_, cx| {
-                                        ExtensionStore::global(cx).update(cx, |store, cx| {
-                                            store.rebuild_dev_extension(extension_id.clone(), cx)
-                                        });
+                                    move |this, _, cx| {
+                                        this.rebuild_dev_extension(extension_id.clone(), cx)
                                     }
+                                }))
+                                .tooltip({
+                                    let tooltip = format!("Rebuild {}", extension.name);
+                                    move |cx| Tooltip::text(tooltip.clone(), cx)
                                 })
                                 .color(Color::Accent)
                                 .disabled(matches!(status, ExtensionStatus::Upgrading)),
                             )
                             .child(
-                                Button::new(SharedString::from(extension.id.clone()), "Uninstall")
-                                    .on_click({
-                                        let extension_id = extension.id.clone();
-                                        move |_, cx| {
-                                            ExtensionStore::global(cx).update(cx, |store, cx| {
-                                                store.uninstall_extension(extension_id.clone(), cx)
-                                            });
-                                        }
-                                    })
-                                    .color(Color::Accent)
-                                    .disabled(matches!(status, ExtensionStatus::Removing)),
-                            ),
+                                Button::new(
+                                    SharedString::from(format!("uninstall-{}", extension.id)),
+                                    "Uninstall",
+                                )
+                                .on_click(cx.listener({
+                                    let extension_id = extension.id.clone();
+                                    let extension_name = extension.name.clone();
+                                    move |this, _, cx| {
+                                        this.uninstall_dev_extension(
+                                            extension_id.clone(),
+                                            &extension_name,
+                                            cx,
+                                        )
+                                    }
+                                }))
+                                .tooltip(|cx| {
+                                    Tooltip::text(
+                                        "Stop loading this extension. Its source is kept.",
+                                        cx,
+                                    )
+                                })
+                                .color(Color::Accent)
+                                .disabled(matches!(status, ExtensionStatus::Removing)),
+                            )
+                            .children(self.render_settings_button(&extension.id, cx))
+                            .child(self.render_extension_menu(
+                                extension.id.clone(),
+                                extension.name.clone(),
+                                false,
+                                cx,
+                            )),
                     ),
             )
             .child(
@@ -305,7 +2135,24 @@ impl ExtensionsPage {
                         ))
                         .size(LabelSize::Small),
                     )
-                    .child(Label::new("<>").size(LabelSize::Small)),
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .children(self.render_data_size_label(&extension.id, cx))
+                            .children(self.render_last_used_label(&extension.id, cx))
+                            .children(self.render_startup_impact_label(&extension.id, cx))
+                            .children(
+                                extension
+                                    .license
+                                    .as_ref()
+                                    .filter(|_| card_metadata.license)
+                                    .map(|license| {
+                                        Label::new(format!("License: {license}"))
+                                            .size(LabelSize::Small)
+                                    }),
+                            )
+                            .child(Label::new("<>").size(LabelSize::Small)),
+                    ),
             )
             .child(
                 h_flex()
@@ -315,37 +2162,438 @@ impl ExtensionsPage {
                             .size(LabelSize::Small)
                             .color(Color::Default)
                     }))
-                    .children(repository_url.map(|repository_url| {
-                        IconButton::new(
-                            SharedString::from(format!("repository-{}", extension.id)),
-                            IconName::Github,
-                        )
-                        .icon_color(Color::Accent)
-                        .icon_size(IconSize::Small)
-                        .style(ButtonStyle::Filled)
-                        .on_click(cx.listener({
-                            let repository_url = repository_url.clone();
-                            move |_, _, cx| {
-                                cx.open_url(&repository_url);
-                            }
-                        }))
-                        .tooltip(move |cx| Tooltip::text(repository_url.clone(), cx))
-                    })),
-            )
-    }
-
-    fn render_remote_extension(
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .children(render_funding_button(
+                                &extension.id,
+                                extension.funding.clone(),
+                            ))
+                            .children(repository_url.map(|repository_url| {
+                                IconButton::new(
+                                    SharedString::from(format!("repository-{}", extension.id)),
+                                    IconName::Github,
+                                )
+                                .icon_color(Color::Accent)
+                                .icon_size(IconSize::Small)
+                                .style(ButtonStyle::Filled)
+                                .on_click(cx.listener({
+                                    let repository_url = repository_url.clone();
+                                    move |_, _, cx| {
+                                        cx.open_url(&repository_url);
+                                    }
+                                }))
+                                .tooltip(move |cx| Tooltip::text(repository_url.clone(), cx))
+                            })),
+                    ),
+            )
+            .when(is_operation_slow, |card| {
+                card.child(self.render_slow_operation_warning(
+                    &extension.id,
+                    &extension.name,
+                    true,
+                    cx,
+                ))
+            })
+            .when_some(
+                build_error.filter(|_| is_build_error_expanded),
+                |card, build_error| {
+                    card.details(
+                        v_flex()
+                            .id(SharedString::from(format!(
+                                "build-error-text-{}",
+                                extension.id
+                            )))
+                            .max_h(rems(12.))
+                            .overflow_y_scroll()
+                            .p_2()
+                            .rounded_md()
+                            .bg(cx.theme().colors().editor_background)
+                            .child(
+                                Label::new(build_error)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Error),
+                            ),
+                    )
+                },
+            )
+    }
+
+    fn toggle_build_error(&mut self, extension_id: Arc<str>, cx: &mut ViewContext<Self>) {
+        if !self.expanded_build_errors.remove(&extension_id) {
+            self.expanded_build_errors.insert(extension_id);
+        }
+        cx.notify();
+    }
+
+    /// Lists the languages and grammars that an extension provides.
+    ///
+    /// Installed extensions are labeled with the names of their languages. Otherwise,
+    /// the names of the language directories are used.
+    fn render_language_badges<'a>(
+        &self,
+        extension_id: &str,
+        language_paths: impl Iterator<Item = &'a Path>,
+        grammar_names: impl Iterator<Item = &'a str>,
+        cx: &AppContext,
+    ) -> Vec<ExtensionBadge> {
+        let mut names = ExtensionStore::global(cx)
+            .read(cx)
+            .extension_language_names(extension_id)
+            .into_iter()
+            .map(SharedString::from)
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            names.extend(language_paths.filter_map(|path| {
+                Some(SharedString::from(
+                    path.file_name()?.to_string_lossy().to_string(),
+                ))
+            }));
+        }
+
+        // Grammars are usually named after the language that uses them.
+        for grammar_name in grammar_names {
+            if !names
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(grammar_name))
+            {
+                names.push(SharedString::from(grammar_name.to_string()));
+            }
+        }
+
+        let hidden_count = names.len().saturating_sub(MAX_LANGUAGE_BADGES);
+        let mut badges = names
+            .into_iter()
+            .take(MAX_LANGUAGE_BADGES)
+            .map(ExtensionBadge::new)
+            .collect::<Vec<_>>();
+        if hidden_count > 0 {
+            badges.push(ExtensionBadge::new(format!("+{hidden_count}")));
+        }
+        badges
+    }
+
+    fn uninstall_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        extension_name: &str,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let data_size = self.extension_data_size(&extension_id, cx);
+        let extension_name = SharedString::from(extension_name.to_string());
+        let telemetry = self.telemetry.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(cx, |cx| {
+                    UninstallConfirmationModal::new(
+                        extension_id,
+                        extension_name,
+                        data_size,
+                        telemetry,
+                        cx,
+                    )
+                })
+            })
+            .ok();
+    }
+
+    fn preview_install(
+        &mut self,
+        extension_id: Arc<str>,
+        extension_name: SharedString,
+        version: Arc<str>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(cx, |cx| {
+                    InstallPreviewModal::new(extension_id, extension_name, version, cx)
+                })
+            })
+            .ok();
+    }
+
+    fn report_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        extension_name: SharedString,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let workspace = self.workspace.clone();
+        self.workspace
+            .update(cx, |workspace_view, cx| {
+                workspace_view.toggle_modal(cx, |cx| {
+                    ReportExtensionModal::new(workspace, extension_id, extension_name, cx)
+                })
+            })
+            .ok();
+    }
+
+    fn uninstall_dev_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        extension_name: &str,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let answer = cx.prompt(
+            PromptLevel::Info,
+            &format!("Uninstall the dev extension \"{extension_name}\"?"),
+            Some("Zed will stop loading this extension. Its source directory will not be deleted."),
+            &[
+                "Uninstall",
+                "Uninstall and Remove Build Artifacts",
+                "Cancel",
+            ],
+        );
+        cx.spawn(|_, mut cx| async move {
+            let remove_build_artifacts = match answer.await? {
+                0 => false,
+                1 => true,
+                _ => return Ok(()),
+            };
+            cx.update(|cx| {
+                ExtensionStore::global(cx).update(cx, |store, cx| {
+                    store.uninstall_dev_extension(extension_id, remove_build_artifacts, cx)
+                })
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn rebuild_dev_extension(&mut self, extension_id: Arc<str>, cx: &mut ViewContext<Self>) {
+        ExtensionStore::global(cx)
+            .update(cx, |store, cx| {
+                store.rebuild_dev_extension(extension_id, cx)
+            })
+            .detach_and_log_err(cx);
+    }
+
+    /// Installs the extensions dropped onto the page. Folders are installed
+    /// as dev extensions, and files as packaged extension archives.
+    fn install_dropped_paths(&mut self, paths: &ExternalPaths, cx: &mut ViewContext<Self>) {
+        let Some(fs) = self
+            .workspace
+            .update(cx, |workspace, _| workspace.app_state().fs.clone())
+            .ok()
+        else {
+            return;
+        };
+        let store = ExtensionStore::global(cx);
+        let paths = paths.paths().to_vec();
+        cx.spawn(|this, mut cx| async move {
+            let mut failures = Vec::new();
+            for path in paths {
+                let is_dir = fs.is_dir(&path).await;
+                let install = store.update(&mut cx, |store, cx| {
+                    if is_dir {
+                        store.install_dev_extension(path.clone(), cx)
+                    } else {
+                        store.install_from_archive(path.clone(), cx)
+                    }
+                })?;
+                if let Err(error) = install.await {
+                    let error = error.to_string();
+                    let message = error.lines().next().unwrap_or_default();
+                    failures.push(format!("{}: {message}", path.display()));
+                }
+            }
+            if failures.is_empty() {
+                return anyhow::Ok(());
+            }
+
+            this.update(&mut cx, |_, cx| {
+                drop(cx.prompt(
+                    PromptLevel::Warning,
+                    "Some of the dropped extensions couldn't be installed.",
+                    Some(&failures.join("\n")),
+                    &["Ok"],
+                ));
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Rebuilds every dev extension, streaming the output and result of each
+    /// build into a single build log.
+    fn rebuild_dev_extensions(&mut self, cx: &mut ViewContext<Self>) {
+        ExtensionStore::global(cx)
+            .update(cx, |store, cx| store.rebuild_dev_extensions(cx))
+            .detach();
+        self.open_build_log(
+            BuildLogSource::AllDevExtensions,
+            "Dev Extensions".to_string(),
+            cx,
+        );
+    }
+
+    /// Renders an extension from the registry on a single line, with its name,
+    /// version, status, and its most relevant action.
+    fn render_published_at_label(&self, extension: &ExtensionApiResponse) -> Option<Label> {
+        let published_at = extension.published_at?;
+        let relative = time_format::format_localized_timestamp(
+            published_at,
+            OffsetDateTime::now_utc(),
+            self.local_timezone,
+            time_format::TimestampFormat::Relative,
+        );
+        Some(Label::new(format!("Updated {relative}")).size(LabelSize::Small))
+    }
+
+    /// Renders how long the given extension took to load, on the cards of the
+    /// installed extensions.
+    fn render_startup_impact_label(&self, extension_id: &str, cx: &AppContext) -> Option<Label> {
+        if self.filter != ExtensionFilter::Installed {
+            return None;
+        }
+        let load_time = ExtensionStore::global(cx)
+            .read(cx)
+            .extension_load_time(extension_id)?;
+        Some(
+            Label::new(format!("Startup impact: {}", format_load_time(load_time)))
+                .size(LabelSize::Small),
+        )
+    }
+
+    /// Renders when the given extension was last used, on the cards of the
+    /// installed extensions.
+    fn render_last_used_label(&self, extension_id: &str, cx: &AppContext) -> Option<Label> {
+        if self.filter != ExtensionFilter::Installed {
+            return None;
+        }
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        if !matches!(
+            extension_store.extension_status(extension_id),
+            ExtensionStatus::Installed(_)
+        ) {
+            return None;
+        }
+        let label = match extension_store.extension_last_used(extension_id) {
+            Some(last_used) => {
+                let relative = time_format::format_localized_timestamp(
+                    OffsetDateTime::from(last_used),
+                    OffsetDateTime::now_utc(),
+                    self.local_timezone,
+                    time_format::TimestampFormat::Relative,
+                );
+                format!("Last used {relative}")
+            }
+            None => "Not used yet".to_string(),
+        };
+        Some(Label::new(label).size(LabelSize::Small))
+    }
+
+    /// Renders an extension from the registry on a single line, with its name,
+    /// version, status, and its most relevant action.
+    fn render_compact_remote_extension(
         &self,
         extension: &ExtensionApiResponse,
         cx: &mut ViewContext<Self>,
     ) -> ExtensionCard {
-        let status = ExtensionStore::global(cx)
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let status = extension_store.extension_status(&extension.id);
+        let is_disabled = extension_store
+            .extension_disabled_until(&extension.id)
+            .is_some();
+        let is_damaged = extension_store.damaged_extension(&extension.id).is_some();
+        let is_blocked = extension_store
+            .installed_version_block(&extension.id)
+            .is_some();
+        let pinned_version = extension_store.pinned_version(&extension.id).cloned();
+        let (install_or_uninstall_button, upgrade_button) =
+            self.buttons_for_entry(extension, &status, cx);
+        let is_allowed = ExtensionSettings::get_global(cx).allows_extension(&extension.id);
+        let crash_error = ExtensionStore::global(cx)
             .read(cx)
-            .extension_status(&extension.id);
+            .extension_crash_error(&extension.id)
+            .cloned();
+
+        ExtensionCard::new().compact(true).child(
+            h_flex()
+                .justify_between()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .child(
+                            div()
+                                .id(SharedString::from(format!("name-{}", extension.id)))
+                                .cursor_pointer()
+                                .child(Label::new(extension.name.clone()))
+                                .on_click(cx.listener({
+                                    let extension_id = extension.id.clone();
+                                    move |this, _, cx| {
+                                        this.open_extension_detail(extension_id.clone(), cx)
+                                    }
+                                }))
+                                .tooltip(|cx| Tooltip::text("View Details", cx)),
+                        )
+                        .child(
+                            Label::new(format!("v{}", extension.version))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .children(
+                            ExtensionCardStatus::from_extension_status(
+                                &status,
+                                pinned_version
+                                    .is_none()
+                                    .then_some(extension.version.as_ref()),
+                                is_disabled,
+                                is_damaged,
+                                is_blocked,
+                            )
+                            .map(|card_status| {
+                                render_status_chip(card_status, &status, &extension.version, cx)
+                            }),
+                        )
+                        .children(pinned_version.map(|version| {
+                            self.render_pin_indicator(extension.id.clone(), &version, cx)
+                        }))
+                        .when(!is_allowed, |this| {
+                            this.child(ExtensionBadge::new("Blocked by your organization"))
+                        })
+                        .children(
+                            crash_error
+                                .map(|error| render_crashed_indicator(extension.id.clone(), error)),
+                        ),
+                )
+                .child(upgrade_button.unwrap_or(install_or_uninstall_button)),
+        )
+    }
 
+    fn render_remote_extension(
+        &self,
+        extension: &ExtensionApiResponse,
+        cx: &mut ViewContext<Self>,
+    ) -> ExtensionCard {
+        let card_metadata = ExtensionsPageSettings::get_global(cx).card_metadata;
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let status = extension_store.extension_status(&extension.id);
+        let is_disabled = extension_store
+            .extension_disabled_until(&extension.id)
+            .is_some();
+        let is_damaged = extension_store.damaged_extension(&extension.id).is_some();
+        let is_blocked = extension_store
+            .installed_version_block(&extension.id)
+            .is_some();
+        let pinned_version = extension_store.pinned_version(&extension.id).cloned();
+        let download_progress = extension_store.download_progress(&extension.id);
+        let requires_restart = extension_store.extension_requires_restart(&extension.id);
+        let is_operation_slow = extension_store.is_operation_slow(&extension.id);
+        let provides_active_theme = extension_store.provides_active_theme(&extension.id, cx);
         let (install_or_uninstall_button, upgrade_button) =
             self.buttons_for_entry(extension, &status, cx);
         let repository_url = extension.repository.clone();
+        let is_selected = self.selected_extension_ids.contains(&extension.id);
+        let is_starred = Self::is_starred(&extension.id, cx);
+        let is_hidden = Self::is_hidden(&extension.id, cx);
+        let is_recently_upgraded = extension_store.is_recently_upgraded(&extension.id);
+        let has_multiple_registries = extension_store.registries().len() > 1;
+        let is_allowed = ExtensionSettings::get_global(cx).allows_extension(&extension.id);
+        let crash_error = ExtensionStore::global(cx)
+            .read(cx)
+            .extension_crash_error(&extension.id)
+            .cloned();
 
         ExtensionCard::new()
             .child(
@@ -355,68 +2603,2193 @@ impl ExtensionsPage {
                         h_flex()
                             .gap_2()
                             .items_end()
-                            .child(Headline::new(extension.name.clone()).size(HeadlineSize::Medium))
+                            .child(
+                                Checkbox::new(
+                                    SharedString::from(format!("select-{}", extension.id)),
+                                    if is_selected {
+                                        Selection::Selected
+                                    } else {
+                                        Selection::Unselected
+                                    },
+                                )
+                                .on_click(cx.listener({
+                                    let extension_id = extension.id.clone();
+                                    move |this, _, cx| {
+                                        this.toggle_extension_selected(extension_id.clone(), cx)
+                                    }
+                                })),
+                            )
+                            .child(
+                                div()
+                                    .id(SharedString::from(format!("name-{}", extension.id)))
+                                    .cursor_pointer()
+                                    .child(
+                                        Headline::new(extension.name.clone())
+                                            .size(HeadlineSize::Medium),
+                                    )
+                                    .on_click(cx.listener({
+                                        let extension_id = extension.id.clone();
+                                        move |this, _, cx| {
+                                            this.open_extension_detail(extension_id.clone(), cx)
+                                        }
+                                    }))
+                                    .tooltip(|cx| Tooltip::text("View Details", cx)),
+                            )
                             .child(
                                 Headline::new(format!("v{}", extension.version))
                                     .size(HeadlineSize::XSmall),
-                            ),
+                            )
+                            .when(is_recently_upgraded, |this| {
+                                this.child(
+                                    div()
+                                        .id(SharedString::from(format!(
+                                            "upgraded-{}",
+                                            extension.id
+                                        )))
+                                        .child(Indicator::dot().color(Color::Accent))
+                                        .tooltip(|cx| Tooltip::text("Updated", cx)),
+                                )
+                            })
+                            .children(
+                                ExtensionKind::from_contributions(
+                                    !extension.themes.is_empty(),
+                                    !extension.languages.is_empty()
+                                        || !extension.grammars.is_empty(),
+                                    !extension.language_servers.is_empty(),
+                                )
+                                .map(render_kind_badge),
+                            )
+                            .when(provides_active_theme, |this| {
+                                this.child(render_active_theme_badge())
+                            })
+                            .when(is_hidden, |this| this.child(ExtensionBadge::new("Hidden")))
+                            .when_some(
+                                extension
+                                    .registry
+                                    .clone()
+                                    .filter(|_| has_multiple_registries),
+                                |this, registry| this.child(ExtensionBadge::new(registry)),
+                            )
+                            .children(
+                                ExtensionCardStatus::from_extension_status(
+                                    &status,
+                                    pinned_version
+                                        .is_none()
+                                        .then_some(extension.version.as_ref()),
+                                    is_disabled,
+                                    is_damaged,
+                                    is_blocked,
+                                )
+                                .map(|card_status| {
+                                    render_status_chip(card_status, &status, &extension.version, cx)
+                                }),
+                            )
+                            .children(pinned_version.map(|version| {
+                                self.render_pin_indicator(extension.id.clone(), &version, cx)
+                            }))
+                            .when(!is_allowed, |this| {
+                                this.child(ExtensionBadge::new("Blocked by your organization"))
+                            })
+                            .children(
+                                crash_error.map(|error| {
+                                    render_crashed_indicator(extension.id.clone(), error)
+                                }),
+                            )
+                            .children(requires_restart.then(render_restart_required_badge))
+                            .when(card_metadata.languages, |this| {
+                                this.children(self.render_language_badges(
+                                    &extension.id,
+                                    extension.languages.iter().map(Path::new),
+                                    extension.grammars.iter().map(String::as_str),
+                                    cx,
+                                ))
+                            }),
                     )
                     .child(
                         h_flex()
                             .gap_2()
                             .justify_between()
-                            .children(upgrade_button)
-                            .child(install_or_uninstall_button),
+                            .children(self.render_theme_preview_controls(extension, &status, cx))
+                            .map(|this| {
+                                if let Some(progress) = download_progress {
+                                    this.child(self.render_download_progress(
+                                        &extension.id,
+                                        progress,
+                                        cx,
+                                    ))
+                                } else {
+                                    this.children(upgrade_button)
+                                        .child(install_or_uninstall_button)
+                                }
+                            })
+                            .child(
+                                IconButton::new(
+                                    SharedString::from(format!("star-{}", extension.id)),
+                                    if is_starred {
+                                        IconName::StarFilled
+                                    } else {
+                                        IconName::Star
+                                    },
+                                )
+                                .icon_size(IconSize::Small)
+                                .icon_color(if is_starred {
+                                    Color::Accent
+                                } else {
+                                    Color::Muted
+                                })
+                                .on_click(cx.listener({
+                                    let extension_id = extension.id.clone();
+                                    move |this, _, cx| this.toggle_starred(extension_id.clone(), cx)
+                                }))
+                                .tooltip(move |cx| {
+                                    Tooltip::text(
+                                        if is_starred {
+                                            "Unstar"
+                                        } else {
+                                            "Star to list first"
+                                        },
+                                        cx,
+                                    )
+                                }),
+                            )
+                            .children(self.render_settings_button(&extension.id, cx))
+                            .child(self.render_extension_menu(
+                                extension.id.clone(),
+                                extension.name.clone(),
+                                true,
+                                cx,
+                            )),
                     ),
             )
             .child(
                 h_flex()
                     .justify_between()
                     .child(
-                        Label::new(format!(
-                            "{}: {}",
-                            if extension.authors.len() > 1 {
-                                "Authors"
-                            } else {
-                                "Author"
-                            },
-                            extension.authors.join(", ")
-                        ))
-                        .size(LabelSize::Small),
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Label::new(format!(
+                                    "{}: {}",
+                                    if extension.authors.len() > 1 {
+                                        "Authors"
+                                    } else {
+                                        "Author"
+                                    },
+                                    extension.authors.join(", ")
+                                ))
+                                .size(LabelSize::Small),
+                            )
+                            .children(render_verified_publisher_badge(extension)),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .children(self.render_data_size_label(&extension.id, cx))
+                            .children(self.render_last_used_label(&extension.id, cx))
+                            .children(self.render_startup_impact_label(&extension.id, cx))
+                            .children(
+                                extension
+                                    .license
+                                    .as_ref()
+                                    .filter(|_| card_metadata.license)
+                                    .map(|license| {
+                                        Label::new(format!("License: {license}"))
+                                            .size(LabelSize::Small)
+                                    }),
+                            )
+                            .when(card_metadata.downloads, |this| {
+                                this.child(
+                                    Label::new(format!("Downloads: {}", extension.download_count))
+                                        .size(LabelSize::Small),
+                                )
+                            })
+                            .children(self.render_published_at_label(extension)),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_between()
+                    .children(extension.description.as_ref().map(|description| {
+                        h_flex().overflow_x_hidden().child(
+                            Label::new(description.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Default),
+                        )
+                    }))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .children(render_funding_button(
+                                &extension.id,
+                                extension.funding.clone(),
+                            ))
+                            .child(
+                                IconButton::new(
+                                    SharedString::from(format!("repository-{}", extension.id)),
+                                    IconName::Github,
+                                )
+                                .icon_color(Color::Accent)
+                                .icon_size(IconSize::Small)
+                                .style(ButtonStyle::Filled)
+                                .on_click(cx.listener({
+                                    let repository_url = repository_url.clone();
+                                    move |_, _, cx| {
+                                        cx.open_url(&repository_url);
+                                    }
+                                }))
+                                .tooltip(move |cx| Tooltip::text(repository_url.clone(), cx)),
+                            ),
+                    ),
+            )
+            .when(is_operation_slow, |card| {
+                card.child(self.render_slow_operation_warning(
+                    &extension.id,
+                    &extension.name,
+                    false,
+                    cx,
+                ))
+            })
+    }
+
+    /// Warns that an extension's install, upgrade, or build is taking longer
+    /// than expected, offering to keep waiting, cancel it, or view its logs.
+    fn render_slow_operation_warning(
+        &self,
+        extension_id: &Arc<str>,
+        extension_name: &str,
+        is_dev: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .justify_between()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::ExclamationTriangle)
+                            .size(IconSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .child(
+                        Label::new(if is_dev {
+                            "This build is taking longer than expected."
+                        } else {
+                            "This download is taking longer than expected."
+                        })
+                        .size(LabelSize::Small)
+                        .color(Color::Warning),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new(
+                            SharedString::from(format!("keep-waiting-{extension_id}")),
+                            "Keep Waiting",
+                        )
+                        .label_size(LabelSize::Small)
+                        .on_click({
+                            let extension_id = extension_id.clone();
+                            move |_, cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.keep_waiting(extension_id.clone(), cx)
+                                });
+                            }
+                        }),
+                    )
+                    .child(
+                        Button::new(
+                            SharedString::from(format!("cancel-slow-operation-{extension_id}")),
+                            "Cancel",
+                        )
+                        .label_size(LabelSize::Small)
+                        .on_click({
+                            let extension_id = extension_id.clone();
+                            move |_, cx| {
+                                ExtensionStore::global(cx)
+                                    .update(cx, |store, _| store.cancel_operation(&extension_id));
+                            }
+                        }),
+                    )
+                    .child(
+                        Button::new(
+                            SharedString::from(format!("view-slow-operation-log-{extension_id}")),
+                            "View Log",
+                        )
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener({
+                            let extension_id = extension_id.clone();
+                            let extension_name = extension_name.to_string();
+                            move |this, _, cx| {
+                                if is_dev {
+                                    this.view_build_log(extension_id.clone(), &extension_name, cx);
+                                } else {
+                                    cx.dispatch_action(Box::new(workspace::OpenLog));
+                                }
+                            }
+                        })),
+                    ),
+            )
+    }
+
+    fn render_fetch_error_banner(
+        &self,
+        error: SharedString,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .justify_between()
+            .px_4()
+            .py_2()
+            .border_b()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().surface_background)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .overflow_x_hidden()
+                    .map(|this| {
+                        if self.is_showing_cached_extensions {
+                            this.child(
+                                Icon::new(IconName::ExclamationTriangle)
+                                    .size(IconSize::Small)
+                                    .color(Color::Warning),
+                            )
+                            .child(Label::new("Offline — showing cached results."))
+                        } else {
+                            this.child(
+                                Icon::new(IconName::XCircle)
+                                    .size(IconSize::Small)
+                                    .color(Color::Error),
+                            )
+                            .child(Label::new("Failed to load extensions from the registry:"))
+                        }
+                    })
+                    .child(Label::new(error).color(Color::Muted)),
+            )
+            .child(
+                Button::new("retry-fetch-extensions", "Retry")
+                    .style(ButtonStyle::Filled)
+                    .on_click(cx.listener(|this, _, cx| {
+                        let search = this.search_query(cx);
+                        this.fetch_extensions(search, cx);
+                    })),
+            )
+    }
+
+    fn render_restart_banner(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .justify_between()
+            .px_4()
+            .py_2()
+            .border_b()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().surface_background)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::ExclamationTriangle)
+                            .size(IconSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .child(Label::new(
+                        "Some extension changes will take full effect after Zed restarts.",
+                    )),
+            )
+            .child(
+                Button::new("restart-for-extensions", "Restart")
+                    .style(ButtonStyle::Filled)
+                    .on_click(|_, cx| cx.dispatch_action(Box::new(workspace::Restart))),
+            )
+    }
+
+    fn render_safe_mode_banner(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .justify_between()
+            .px_4()
+            .py_2()
+            .border_b()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().surface_background)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::ExclamationTriangle)
+                            .size(IconSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .child(Label::new(
+                        "All extensions are disabled until Zed is restarted normally.",
+                    )),
+            )
+            .child(
+                Button::new("exit-safe-mode", "Enable Extensions and Restart")
+                    .style(ButtonStyle::Filled)
+                    .on_click(|_, cx| cx.dispatch_action(Box::new(RestartWithExtensionsEnabled))),
+            )
+    }
+
+    fn render_bisect_banner(
+        &self,
+        message: String,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .justify_between()
+            .px_4()
+            .py_2()
+            .border_b()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().surface_background)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::ExclamationTriangle)
+                            .size(IconSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .child(Label::new(message)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("stop-bisect", "Stop Bisect")
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(StopBisect))),
+                    )
+                    .child(
+                        Button::new("continue-bisect", "Continue Bisect")
+                            .style(ButtonStyle::Filled)
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(ContinueBisect))),
+                    ),
+            )
+    }
+
+    fn render_pending_changes_banner(
+        &self,
+        pending_change_count: usize,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let message = if pending_change_count == 1 {
+            "1 extension change is staged until the extensions are reloaded.".to_string()
+        } else {
+            format!("{pending_change_count} extension changes are staged until the extensions are reloaded.")
+        };
+        let button_label = if pending_change_count == 1 {
+            "Reload to Apply 1 Change".to_string()
+        } else {
+            format!("Reload to Apply {pending_change_count} Changes")
+        };
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .justify_between()
+            .px_4()
+            .py_2()
+            .border_b()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().surface_background)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::ArrowCircle)
+                            .size(IconSize::Small)
+                            .color(Color::Accent),
+                    )
+                    .child(Label::new(message)),
+            )
+            .child(
+                Button::new("apply-pending-extension-changes", button_label)
+                    .style(ButtonStyle::Filled)
+                    .on_click(|_, cx| {
+                        ExtensionStore::global(cx)
+                            .update(cx, |store, cx| store.apply_pending_changes(cx))
+                            .detach_and_log_err(cx)
+                    }),
+            )
+    }
+
+    fn open_extension_detail(&mut self, extension_id: Arc<str>, cx: &mut ViewContext<Self>) {
+        let location = PageLocation::Detail(extension_id);
+        if self.location == location {
+            return;
+        }
+        let previous_location = mem::replace(&mut self.location, location);
+        self.back_stack.push(previous_location);
+        self.forward_stack.clear();
+        self.focus_location(cx);
+    }
+
+    fn go_back(&mut self, _: &GoBack, cx: &mut ViewContext<Self>) {
+        let Some(location) = self.back_stack.pop() else {
+            cx.propagate();
+            return;
+        };
+        let previous_location = mem::replace(&mut self.location, location);
+        self.forward_stack.push(previous_location);
+        self.focus_location(cx);
+    }
+
+    fn go_forward(&mut self, _: &GoForward, cx: &mut ViewContext<Self>) {
+        let Some(location) = self.forward_stack.pop() else {
+            cx.propagate();
+            return;
+        };
+        let previous_location = mem::replace(&mut self.location, location);
+        self.back_stack.push(previous_location);
+        self.focus_location(cx);
+    }
+
+    fn focus_location(&mut self, cx: &mut ViewContext<Self>) {
+        let focus_handle = self.focus_handle(cx);
+        cx.focus(&focus_handle);
+        cx.notify();
+    }
+
+    fn render_extension_detail(
+        &self,
+        extension_id: Arc<str>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let navigation_buttons = h_flex()
+            .gap_1()
+            .child(
+                IconButton::new("extension-detail-back", IconName::ArrowLeft)
+                    .disabled(self.back_stack.is_empty())
+                    .on_click(cx.listener(|this, _, cx| this.go_back(&GoBack, cx)))
+                    .tooltip(|cx| Tooltip::for_action("Go Back", &GoBack, cx)),
+            )
+            .child(
+                IconButton::new("extension-detail-forward", IconName::ArrowRight)
+                    .disabled(self.forward_stack.is_empty())
+                    .on_click(cx.listener(|this, _, cx| this.go_forward(&GoForward, cx)))
+                    .tooltip(|cx| Tooltip::for_action("Go Forward", &GoForward, cx)),
+            );
+
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let extension = extension_store.remote_extension(&extension_id).cloned();
+        let status = extension_store.extension_status(&extension_id);
+        let is_disabled = extension_store
+            .extension_disabled_until(&extension_id)
+            .is_some();
+        let damaged_problem = extension_store.damaged_extension(&extension_id).cloned();
+        let blocked = extension_store
+            .installed_version_block(&extension_id)
+            .cloned();
+        let pinned_version = extension_store.pinned_version(&extension_id).cloned();
+        let signer = matches!(status, ExtensionStatus::Installed(_))
+            .then(|| extension_store.extension_signer(&extension_id).cloned());
+        let resource_usage = extension_store.extension_resource_usage(&extension_id);
+
+        v_flex()
+            .id("extension-detail")
+            .track_focus(&self.detail_focus_handle)
+            .size_full()
+            .p_4()
+            .gap_4()
+            .overflow_y_scroll()
+            .child(navigation_buttons)
+            .map(|this| {
+                let Some(extension) = extension else {
+                    return this.child(
+                        Label::new("This extension is no longer listed in the registry.")
+                            .color(Color::Muted),
+                    );
+                };
+                let (install_or_uninstall_button, upgrade_button) =
+                    self.buttons_for_entry(&extension, &status, cx);
+
+                this.child(
+                    h_flex()
+                        .gap_4()
+                        .justify_between()
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(
+                                    h_flex()
+                                        .gap_2()
+                                        .items_end()
+                                        .child(
+                                            Headline::new(extension.name.clone())
+                                                .size(HeadlineSize::XLarge),
+                                        )
+                                        .child(
+                                            Headline::new(format!("v{}", extension.version))
+                                                .size(HeadlineSize::Small),
+                                        )
+                                        .children(
+                                            ExtensionCardStatus::from_extension_status(
+                                                &status,
+                                                pinned_version
+                                                    .is_none()
+                                                    .then_some(extension.version.as_ref()),
+                                                is_disabled,
+                                                damaged_problem.is_some(),
+                                                blocked.is_some(),
+                                            )
+                                            .map(
+                                                |card_status| {
+                                                    render_status_chip(
+                                                        card_status,
+                                                        &status,
+                                                        &extension.version,
+                                                        cx,
+                                                    )
+                                                },
+                                            ),
+                                        )
+                                        .children(pinned_version.clone().map(|version| {
+                                            self.render_pin_indicator(
+                                                extension.id.clone(),
+                                                &version,
+                                                cx,
+                                            )
+                                        })),
+                                )
+                                .child(
+                                    Label::new(format!("By {}", extension.authors.join(", ")))
+                                        .color(Color::Muted),
+                                )
+                                .children(damaged_problem.map(|problem| {
+                                    Label::new(format!(
+                                        "This extension is damaged, as {problem}. \
+                                        Reinstall it to repair it."
+                                    ))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Error)
+                                }))
+                                .children(
+                                    blocked.map(|blocked| self.render_blocked_version(blocked, cx)),
+                                ),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .children(upgrade_button)
+                                .child(install_or_uninstall_button),
+                        ),
+                )
+                .children(
+                    extension
+                        .description
+                        .clone()
+                        .map(|description| Label::new(description)),
+                )
+                .child(
+                    h_flex()
+                        .gap_4()
+                        .children(extension.license.clone().map(|license| {
+                            Label::new(format!("License: {license}")).size(LabelSize::Small)
+                        }))
+                        .child(
+                            Label::new(format!("Downloads: {}", extension.download_count))
+                                .size(LabelSize::Small),
+                        )
+                        .children(signer.map(|signer| render_signer_label(signer.as_ref())))
+                        .child(
+                            Button::new("extension-detail-repository", "Repository")
+                                .icon(IconName::Github)
+                                .icon_position(IconPosition::Start)
+                                .icon_size(IconSize::Small)
+                                .label_size(LabelSize::Small)
+                                .on_click({
+                                    let repository_url = extension.repository.clone();
+                                    move |_, cx| cx.open_url(&repository_url)
+                                }),
+                        )
+                        .children(extension.funding.clone().map(|funding_url| {
+                            Button::new("extension-detail-funding", "Sponsor")
+                                .icon(IconName::Heart)
+                                .icon_position(IconPosition::Start)
+                                .icon_size(IconSize::Small)
+                                .label_size(LabelSize::Small)
+                                .on_click(move |_, cx| cx.open_url(&funding_url))
+                        })),
+                )
+                .children(resource_usage.map(render_resource_usage))
+                .children(render_detail_section("Themes", &extension.themes))
+                .children(render_detail_section("Languages", &extension.languages))
+                .children(render_detail_section("Grammars", &extension.grammars))
+                .children(render_detail_section(
+                    "Language Servers",
+                    &extension.language_servers,
+                ))
+            })
+    }
+
+    fn toggle_extension_selected(&mut self, extension_id: Arc<str>, cx: &mut ViewContext<Self>) {
+        if !self.selected_extension_ids.remove(&extension_id) {
+            self.selected_extension_ids.insert(extension_id);
+        }
+        cx.notify();
+    }
+
+    fn render_unused_extensions_section(
+        &self,
+        unused_extensions: Vec<Arc<str>>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let unused_extensions = unused_extensions
+            .into_iter()
+            .map(|extension_id| {
+                let name = extension_store
+                    .installed_extension(&extension_id)
+                    .map_or_else(
+                        || extension_id.to_string(),
+                        |manifest| manifest.name.clone(),
+                    );
+                let last_used = match extension_store.extension_last_used(&extension_id) {
+                    Some(last_used) => {
+                        let relative = time_format::format_localized_timestamp(
+                            OffsetDateTime::from(last_used),
+                            OffsetDateTime::now_utc(),
+                            self.local_timezone,
+                            time_format::TimestampFormat::Relative,
+                        );
+                        format!("Last used {relative}")
+                    }
+                    None => "Never used".to_string(),
+                };
+                (extension_id, name, last_used)
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .pt_4()
+            .child(
+                ListHeader::new(format!(
+                    "Unused for {}+ Days",
+                    UNUSED_EXTENSION_THRESHOLD.as_secs() / (24 * 60 * 60)
+                ))
+                .end_slot(
+                    Button::new("remove-unused-extensions", "Remove Unused")
+                        .style(ButtonStyle::Subtle)
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(|this, _, cx| this.remove_unused_extensions(cx))),
+                ),
+            )
+            .children(
+                unused_extensions
+                    .into_iter()
+                    .map(|(extension_id, name, last_used)| {
+                        h_flex()
+                            .py_1()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .id(SharedString::from(format!("unused-name-{extension_id}")))
+                                    .cursor_pointer()
+                                    .child(Label::new(name))
+                                    .on_click(cx.listener(move |this, _, cx| {
+                                        this.open_extension_detail(extension_id.clone(), cx)
+                                    })),
+                            )
+                            .child(
+                                Label::new(last_used)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+    }
+
+    /// Performs the given operation on each selected extension that it
+    /// applies to, then reports the outcome for each of them.
+    fn run_batch_operation(&mut self, operation: ExtensionOperation, cx: &mut ViewContext<Self>) {
+        let store = ExtensionStore::global(cx);
+        let extensions = {
+            let store = store.read(cx);
+            let mut extensions = self
+                .selected_extension_ids
+                .iter()
+                .filter_map(|extension_id| {
+                    let extension = store.remote_extension(extension_id)?;
+                    let applies = match store.extension_status(extension_id) {
+                        ExtensionStatus::NotInstalled => operation == ExtensionOperation::Install,
+                        ExtensionStatus::Installed(version) => match operation {
+                            ExtensionOperation::Install => false,
+                            ExtensionOperation::Upgrade => {
+                                version != extension.version
+                                    && store.pinned_version(extension_id).is_none()
+                            }
+                            ExtensionOperation::Remove => true,
+                        },
+                        _ => false,
+                    };
+                    applies.then(|| (extension_id.clone(), extension.version.clone()))
+                })
+                .collect::<Vec<_>>();
+            extensions.sort();
+            extensions
+        };
+        self.selected_extension_ids.clear();
+        self.start_batch_operation(operation, extensions, cx);
+    }
+
+    /// Removes the installed extensions that haven't been used for at least
+    /// [`UNUSED_EXTENSION_THRESHOLD`].
+    fn remove_unused_extensions(&mut self, cx: &mut ViewContext<Self>) {
+        let extensions = {
+            let store = ExtensionStore::global(cx).read(cx);
+            store
+                .unused_extensions(cx)
+                .into_iter()
+                .filter_map(|extension_id| {
+                    let version = store.installed_extension(&extension_id)?.version.clone();
+                    Some((extension_id, version))
+                })
+                .collect::<Vec<_>>()
+        };
+        self.start_batch_operation(ExtensionOperation::Remove, extensions, cx);
+    }
+
+    fn start_batch_operation(
+        &mut self,
+        operation: ExtensionOperation,
+        extensions: Vec<(Arc<str>, Arc<str>)>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if extensions.is_empty() {
+            return;
+        }
+
+        report_extension_event(
+            &self.telemetry,
+            format!(
+                "extensions: batch {operation:?} {} extensions",
+                extensions.len()
+            ),
+            cx,
+        );
+        self.batch_outcome = None;
+        cx.notify();
+
+        let batch = ExtensionStore::global(cx).update(cx, |store, cx| {
+            store.run_batch_operation(operation, extensions, cx)
+        });
+        cx.spawn(|this, mut cx| async move {
+            let outcomes = batch.await;
+            this.update(&mut cx, |this, cx| {
+                this.batch_outcome = Some(BatchOutcome {
+                    operation,
+                    outcomes: outcomes
+                        .into_iter()
+                        .map(|(extension_id, result)| {
+                            (
+                                extension_id,
+                                result.err().map(|error| error.to_string().into()),
+                            )
+                        })
+                        .collect(),
+                });
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Writes the installed extensions to a file that can be imported on
+    /// another machine.
+    fn export_extension_list(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(fs) = self
+            .workspace
+            .update(cx, |workspace, _| workspace.app_state().fs.clone())
+            .ok()
+        else {
+            return;
+        };
+        let list = ExtensionStore::global(cx).read(cx).export_extension_list();
+        let path = cx.prompt_for_new_path(&paths::HOME);
+        cx.spawn(|_, _| async move {
+            let Some(path) = path.await? else {
+                return Ok(());
+            };
+            let content = serde_json::to_string_pretty(&list)?;
+            fs.atomic_write(path, content).await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Installs the extensions listed in a previously exported file, then
+    /// reports the outcome for each of them.
+    fn import_extension_list(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(fs) = self
+            .workspace
+            .update(cx, |workspace, _| workspace.app_state().fs.clone())
+            .ok()
+        else {
+            return;
+        };
+        let prompt = cx.prompt_for_paths(gpui::PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        });
+        cx.spawn(|this, mut cx| async move {
+            let Some(path) = prompt.await?.and_then(|mut paths| paths.pop()) else {
+                return Ok(());
+            };
+            let list = match fs
+                .load(&path)
+                .await
+                .and_then(|content| Ok(serde_json::from_str::<ExtensionList>(&content)?))
+            {
+                Ok(list) => list,
+                Err(error) => {
+                    this.update(&mut cx, |_, cx| {
+                        drop(cx.prompt(
+                            PromptLevel::Warning,
+                            "Couldn't read the extension list.",
+                            Some(&error.to_string()),
+                            &["Ok"],
+                        ));
+                    })?;
+                    return Ok(());
+                }
+            };
+
+            let import = this.update(&mut cx, |this, cx| {
+                report_extension_event(
+                    &this.telemetry,
+                    format!("extensions: import {} extensions", list.extensions.len()),
+                    cx,
+                );
+                this.batch_outcome = None;
+                cx.notify();
+                ExtensionStore::global(cx)
+                    .update(cx, |store, cx| store.import_extension_list(list, cx))
+            })?;
+            let outcomes = import.await;
+            this.update(&mut cx, |this, cx| {
+                this.batch_outcome = Some(BatchOutcome {
+                    operation: ExtensionOperation::Install,
+                    outcomes: outcomes
+                        .into_iter()
+                        .map(|(extension_id, result)| {
+                            (
+                                extension_id,
+                                result.err().map(|error| error.to_string().into()),
+                            )
+                        })
+                        .collect(),
+                });
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_batch_bar(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let selected_count = self.selected_extension_ids.len();
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let extension_name = |extension_id: &Arc<str>| {
+            extension_store.remote_extension(extension_id).map_or_else(
+                || extension_id.to_string(),
+                |extension| extension.name.clone(),
+            )
+        };
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .border_b()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().surface_background)
+            .when(selected_count > 0, |this| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .justify_between()
+                        .child(Label::new(format!(
+                            "{selected_count} {} selected",
+                            if selected_count == 1 {
+                                "extension"
+                            } else {
+                                "extensions"
+                            }
+                        )))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Button::new("batch-install", "Install")
+                                        .style(ButtonStyle::Filled)
+                                        .on_click(cx.listener(|this, _, cx| {
+                                            this.run_batch_operation(
+                                                ExtensionOperation::Install,
+                                                cx,
+                                            )
+                                        })),
+                                )
+                                .child(
+                                    Button::new("batch-upgrade", "Upgrade")
+                                        .style(ButtonStyle::Filled)
+                                        .on_click(cx.listener(|this, _, cx| {
+                                            this.run_batch_operation(
+                                                ExtensionOperation::Upgrade,
+                                                cx,
+                                            )
+                                        })),
+                                )
+                                .child(
+                                    Button::new("batch-uninstall", "Uninstall")
+                                        .style(ButtonStyle::Filled)
+                                        .on_click(cx.listener(|this, _, cx| {
+                                            this.run_batch_operation(ExtensionOperation::Remove, cx)
+                                        })),
+                                )
+                                .child(Button::new("clear-selection", "Clear Selection").on_click(
+                                    cx.listener(|this, _, cx| {
+                                        this.selected_extension_ids.clear();
+                                        cx.notify();
+                                    }),
+                                )),
+                        ),
+                )
+            })
+            .when_some(self.batch_outcome.as_ref(), |this, batch_outcome| {
+                let failed_count = batch_outcome
+                    .outcomes
+                    .iter()
+                    .filter(|(_, error)| error.is_some())
+                    .count();
+                let succeeded_count = batch_outcome.outcomes.len() - failed_count;
+                let operation = match batch_outcome.operation {
+                    ExtensionOperation::Install => "Installed",
+                    ExtensionOperation::Upgrade => "Upgraded",
+                    ExtensionOperation::Remove => "Uninstalled",
+                };
+
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .justify_between()
+                        .child(Label::new(format!(
+                            "{operation} {succeeded_count} of {} extensions.",
+                            batch_outcome.outcomes.len()
+                        )))
+                        .child(
+                            IconButton::new("dismiss-batch-outcome", IconName::Close)
+                                .icon_size(IconSize::Small)
+                                .on_click(cx.listener(|this, _, cx| {
+                                    this.batch_outcome = None;
+                                    cx.notify();
+                                }))
+                                .tooltip(|cx| Tooltip::text("Dismiss", cx)),
+                        ),
+                )
+                .children(batch_outcome.outcomes.iter().map(|(extension_id, error)| {
+                    h_flex()
+                        .gap_2()
+                        .child(
+                            Icon::new(if error.is_some() {
+                                IconName::XCircle
+                            } else {
+                                IconName::Check
+                            })
+                            .size(IconSize::Small)
+                            .color(if error.is_some() {
+                                Color::Error
+                            } else {
+                                Color::Success
+                            }),
+                        )
+                        .child(Label::new(extension_name(extension_id)).size(LabelSize::Small))
+                        .children(error.clone().map(|error| {
+                            Label::new(error).size(LabelSize::Small).color(Color::Muted)
+                        }))
+                }))
+            })
+    }
+
+    fn render_install_queue(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let has_operations = !ExtensionStore::global(cx).read(cx).operations().is_empty();
+        popover_menu("install-queue")
+            .trigger(
+                IconButton::new("install-queue-trigger", IconName::ArrowDown)
+                    .icon_color(if has_operations {
+                        Color::Accent
+                    } else {
+                        Color::Default
+                    })
+                    .tooltip(|cx| Tooltip::text("Install Queue", cx)),
+            )
+            .menu(|cx| Some(cx.new_view(InstallQueueView::new)))
+    }
+
+    /// Renders the page's overflow menu, which includes a legend for the status
+    /// indicators shown on extension cards.
+    fn render_page_menu(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let workspace = self.workspace.clone();
+        let this = cx.view().clone();
+        popover_menu("extensions-page-menu")
+            .trigger(IconButton::new(
+                "extensions-page-menu-trigger",
+                IconName::Ellipsis,
+            ))
+            .menu(move |cx| {
+                let workspace = workspace.clone();
+                let this = this.clone();
+                Some(ContextMenu::build(cx, move |menu, cx| {
+                    let card_metadata = ExtensionsPageSettings::get_global(cx).card_metadata;
+                    let menu = ExtensionCardStatus::ALL.into_iter().fold(
+                        menu.header("Status Legend"),
+                        |menu, status| {
+                            menu.custom_entry(
+                                move |_| render_status_indicator(status).into_any_element(),
+                                |_| {},
+                            )
+                        },
+                    );
+                    let search_contributions =
+                        ExtensionsPageSettings::get_global(cx).search_contributions;
+                    let menu = menu.separator().header("Search Options").custom_entry(
+                        move |_| {
+                            h_flex()
+                                .gap_2()
+                                .child(Checkbox::new(
+                                    "search-contributions",
+                                    if search_contributions {
+                                        Selection::Selected
+                                    } else {
+                                        Selection::Unselected
+                                    },
+                                ))
+                                .child(Label::new("Match Installed Languages and Themes"))
+                                .into_any_element()
+                        },
+                        {
+                            let workspace = workspace.clone();
+                            move |cx| {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        let fs = workspace.app_state().fs.clone();
+                                        settings::update_settings_file::<ExtensionsPageSettings>(
+                                            fs,
+                                            cx,
+                                            move |settings| {
+                                                settings.search_contributions =
+                                                    Some(!search_contributions)
+                                            },
+                                        );
+                                    })
+                                    .ok();
+                            }
+                        },
+                    );
+                    let is_compact = ExtensionsPageSettings::get_global(cx).list_density
+                        == ListDensity::Compact;
+                    let menu = menu.separator().header("Display Options").custom_entry(
+                        move |_| {
+                            h_flex()
+                                .gap_2()
+                                .child(Checkbox::new(
+                                    "compact-list",
+                                    if is_compact {
+                                        Selection::Selected
+                                    } else {
+                                        Selection::Unselected
+                                    },
+                                ))
+                                .child(Label::new("Compact List"))
+                                .into_any_element()
+                        },
+                        {
+                            let workspace = workspace.clone();
+                            move |cx| {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        let fs = workspace.app_state().fs.clone();
+                                        settings::update_settings_file::<ExtensionsPageSettings>(
+                                            fs,
+                                            cx,
+                                            move |settings| {
+                                                settings.list_density = Some(if is_compact {
+                                                    ListDensity::Comfortable
+                                                } else {
+                                                    ListDensity::Compact
+                                                })
+                                            },
+                                        );
+                                    })
+                                    .ok();
+                            }
+                        },
+                    );
+                    let group_by_category =
+                        ExtensionsPageSettings::get_global(cx).group_by_category;
+                    let menu = menu.custom_entry(
+                        move |_| {
+                            h_flex()
+                                .gap_2()
+                                .child(Checkbox::new(
+                                    "group-by-category",
+                                    if group_by_category {
+                                        Selection::Selected
+                                    } else {
+                                        Selection::Unselected
+                                    },
+                                ))
+                                .child(Label::new("Group by Category"))
+                                .into_any_element()
+                        },
+                        {
+                            let workspace = workspace.clone();
+                            move |cx| {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        let fs = workspace.app_state().fs.clone();
+                                        settings::update_settings_file::<ExtensionsPageSettings>(
+                                            fs,
+                                            cx,
+                                            move |settings| {
+                                                settings.group_by_category =
+                                                    Some(!group_by_category)
+                                            },
+                                        );
+                                    })
+                                    .ok();
+                            }
+                        },
+                    );
+                    CardMetadataField::ALL.into_iter().fold(
+                        menu,
+                        |menu, field| {
+                            let is_shown = field.is_shown(&card_metadata);
+                            let workspace = workspace.clone();
+                            menu.custom_entry(
+                                move |_| {
+                                    h_flex()
+                                        .gap_2()
+                                        .child(Checkbox::new(
+                                            SharedString::from(format!(
+                                                "card-metadata-{field:?}"
+                                            )),
+                                            if is_shown {
+                                                Selection::Selected
+                                            } else {
+                                                Selection::Unselected
+                                            },
+                                        ))
+                                        .child(Label::new(field.label()))
+                                        .into_any_element()
+                                },
+                                move |cx| {
+                                    workspace
+                                        .update(cx, |workspace, cx| {
+                                            let fs = workspace.app_state().fs.clone();
+                                            settings::update_settings_file::<ExtensionsPageSettings>(
+                                                fs,
+                                                cx,
+                                                move |settings| {
+                                                    field.set_shown(
+                                                        settings
+                                                            .card_metadata
+                                                            .get_or_insert_with(Default::default),
+                                                        !is_shown,
+                                                    )
+                                                },
+                                            );
+                                        })
+                                        .ok();
+                                },
+                            )
+                        },
+                    )
+                    .separator()
+                    .header("Extension List")
+                    .entry(
+                        "Import…",
+                        None,
+                        cx.handler_for(&this, |this, cx| this.import_extension_list(cx)),
+                    )
+                    .entry(
+                        "Export…",
+                        None,
+                        cx.handler_for(&this, |this, cx| this.export_extension_list(cx)),
+                    )
+                    .separator()
+                    .action("Install from File…", Box::new(InstallExtensionFromFile))
+                    .action("Install from Git…", Box::new(InstallExtensionFromGit))
+                    .action(
+                        "Install from GitHub Release…",
+                        Box::new(InstallExtensionFromGithubRelease),
+                    )
+                    .action("Clean Up Leftover Files", Box::new(CleanUpExtensions))
+                    .action("Repair All Extensions", Box::new(RepairAll));
+                    let registries = ExtensionSettings::get_global(cx).registries.clone();
+                    if registries.is_empty() {
+                        return menu;
+                    }
+                    registries.into_iter().enumerate().fold(
+                        menu.separator().header("Registries"),
+                        |menu, (ix, registry)| {
+                            let workspace = workspace.clone();
+                            menu.custom_entry(
+                                move |_| {
+                                    h_flex()
+                                        .gap_2()
+                                        .child(Checkbox::new(
+                                            SharedString::from(format!("registry-{ix}")),
+                                            if registry.enabled {
+                                                Selection::Selected
+                                            } else {
+                                                Selection::Unselected
+                                            },
+                                        ))
+                                        .child(Label::new(registry.name.clone()))
+                                        .into_any_element()
+                                },
+                                move |cx| {
+                                    workspace
+                                        .update(cx, |workspace, cx| {
+                                            let fs = workspace.app_state().fs.clone();
+                                            settings::update_settings_file::<ExtensionSettings>(
+                                                fs,
+                                                cx,
+                                                move |settings| {
+                                                    if let Some(registry) = settings
+                                                        .registries
+                                                        .as_mut()
+                                                        .and_then(|registries| {
+                                                            registries.get_mut(ix)
+                                                        })
+                                                    {
+                                                        registry.enabled = !registry.enabled;
+                                                    }
+                                                },
+                                            );
+                                        })
+                                        .ok();
+                                },
+                            )
+                        },
+                    )
+                }))
+            })
+    }
+
+    fn render_download_progress(
+        &self,
+        extension_id: &Arc<str>,
+        progress: DownloadProgress,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let is_queued = ExtensionStore::global(cx)
+            .read(cx)
+            .download_queue_position(extension_id)
+            .is_some();
+        let label = match progress.total_bytes {
+            _ if is_queued => "Queued".to_string(),
+            Some(total_bytes) => format!(
+                "{} / {}",
+                format_data_size(progress.downloaded_bytes),
+                format_data_size(total_bytes)
+            ),
+            None => format_data_size(progress.downloaded_bytes),
+        };
+
+        h_flex()
+            .gap_2()
+            .child(
+                div()
+                    .w(rems(8.))
+                    .h(rems(0.375))
+                    .rounded_md()
+                    .bg(cx.theme().colors().element_background)
+                    .child(
+                        div()
+                            .h_full()
+                            .w(relative(progress.fraction().unwrap_or(0.)))
+                            .rounded_md()
+                            .bg(Color::Accent.color(cx)),
+                    ),
+            )
+            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+            .child(
+                IconButton::new(
+                    SharedString::from(format!("cancel-download-{extension_id}")),
+                    IconName::Close,
+                )
+                .icon_size(IconSize::Small)
+                .on_click(cx.listener({
+                    let extension_id = extension_id.clone();
+                    move |_, _, cx| {
+                        ExtensionStore::global(cx)
+                            .update(cx, |store, _| store.cancel_download(&extension_id));
+                    }
+                }))
+                .tooltip(|cx| Tooltip::text("Cancel", cx)),
+            )
+    }
+
+    /// Explains why the registry blocked the installed version of an
+    /// extension, with buttons that downgrade it to the version the registry
+    /// recommends, if any, or uninstall it.
+    fn render_blocked_version(
+        &self,
+        blocked: BlockedExtensionVersion,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let downgrade_id = blocked.extension_id.clone();
+        let uninstall_id = blocked.extension_id.clone();
+        let is_required =
+            ExtensionSettings::get_global(cx).requires_extension(&blocked.extension_id);
+        v_flex()
+            .gap_1()
+            .child(
+                Label::new(format!(
+                    "v{} has been blocked by the extension registry: {}",
+                    blocked.version, blocked.reason
+                ))
+                .size(LabelSize::Small)
+                .color(Color::Error),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .children(blocked.safe_version.map(|safe_version| {
+                        Button::new("downgrade-blocked", format!("Downgrade to v{safe_version}"))
+                            .on_click(cx.listener(move |_, _, cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store
+                                        .downgrade_blocked_extension(downgrade_id.clone(), cx)
+                                        .detach_and_log_err(cx)
+                                });
+                            }))
+                    }))
+                    .when(!is_required, |this| {
+                        this.child(Button::new("uninstall-blocked", "Uninstall").on_click(
+                            cx.listener(move |_, _, cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.uninstall_extension(uninstall_id.clone(), cx)
+                                });
+                            }),
+                        ))
+                    }),
+            )
+    }
+
+    /// Renders a chip showing the version the extension is pinned to, with a
+    /// button that unpins it.
+    fn render_pin_indicator(
+        &self,
+        extension_id: Arc<str>,
+        version: &str,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .gap_1()
+            .px_1()
+            .rounded_md()
+            .bg(cx.theme().colors().element_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Icon::new(IconName::Pin)
+                    .size(IconSize::XSmall)
+                    .color(Color::Muted),
+            )
+            .child(
+                Label::new(format!("Pinned v{version}"))
+                    .size(LabelSize::XSmall)
+                    .color(Color::Muted),
+            )
+            .child(
+                IconButton::new(
+                    SharedString::from(format!("unpin-{extension_id}")),
+                    IconName::Close,
+                )
+                .icon_size(IconSize::XSmall)
+                .icon_color(Color::Muted)
+                .on_click(cx.listener(move |_, _, cx| {
+                    ExtensionStore::global(cx)
+                        .update(cx, |store, cx| store.unpin_extension(&extension_id, cx));
+                }))
+                .tooltip(|cx| Tooltip::text("Unpin", cx)),
+            )
+    }
+
+    /// Renders a button that opens the settings for what the extension
+    /// provides, if it is installed and provides anything configurable.
+    fn render_settings_button(
+        &self,
+        extension_id: &Arc<str>,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<IconButton> {
+        if extension_settings_keys(extension_id, cx).is_empty() {
+            return None;
+        }
+
+        let extension_id = extension_id.clone();
+        Some(
+            IconButton::new(
+                SharedString::from(format!("settings-{extension_id}")),
+                IconName::Settings,
+            )
+            .icon_size(IconSize::Small)
+            .icon_color(Color::Muted)
+            .on_click(cx.listener(move |this, _, cx| {
+                this.workspace
+                    .update(cx, |workspace, cx| {
+                        open_extension_settings(workspace, &extension_id, cx)
+                    })
+                    .ok();
+            }))
+            .tooltip(|cx| Tooltip::text("Open Settings", cx)),
+        )
+    }
+
+    fn render_extension_menu(
+        &self,
+        extension_id: Arc<str>,
+        extension_name: String,
+        is_published: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let has_data = self.extension_data_size(&extension_id, cx).is_some();
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let has_build_log = !is_published
+            && extension_store
+                .dev_extension_build_log(&extension_id)
+                .is_some();
+        let is_installed = matches!(
+            extension_store.extension_status(&extension_id),
+            ExtensionStatus::Installed(_)
+        );
+        let is_disabled = extension_store
+            .extension_disabled_until(&extension_id)
+            .is_some();
+        let is_hidden = is_published && Self::is_hidden(&extension_id, cx);
+        let is_tracked = extension_store.is_tracked(&extension_id);
+        let can_reinstall = is_published
+            && is_installed
+            && extension_store.extension_source(&extension_id).is_none();
+        let auto_updates = ExtensionSettings::get_global(cx).auto_updates(&extension_id);
+        let sync_extensions = ExtensionSettings::get_global(cx).sync_extensions;
+        let is_synced = ExtensionSettings::get_global(cx).syncs(&extension_id);
+        let is_pinned = extension_store.pinned_version(&extension_id).is_some();
+        let installed_dir = extension_store.installed_extension_dir(&extension_id);
+        let can_view_logs = installed_dir.is_some();
+        let remote_extension = is_published
+            .then(|| extension_store.remote_extension(&extension_id))
+            .flatten();
+        let repository_url = remote_extension.map(|extension| extension.repository.clone());
+        let published_version = remote_extension.map(|extension| extension.version.clone());
+        let this = cx.view().clone();
+        popover_menu(SharedString::from(format!("extension-menu-{extension_id}")))
+            .trigger(IconButton::new(
+                SharedString::from(format!("extension-menu-trigger-{extension_id}")),
+                IconName::Ellipsis,
+            ))
+            .menu(move |cx| {
+                let extension_id = extension_id.clone();
+                let extension_name = extension_name.clone();
+                let this = this.clone();
+                let installed_dir = installed_dir.clone();
+                let published_version = published_version.clone();
+                Some(ContextMenu::build(cx, move |menu, cx| {
+                    menu.when(is_published, |menu| {
+                        let extension_id = extension_id.clone();
+                        menu.entry("View on Web", None, move |cx| {
+                            cx.open_url(&extension_web_url(&extension_id, cx))
+                        })
+                    })
+                    .when_some(published_version.clone(), |menu, version| {
+                        let extension_id = extension_id.clone();
+                        let extension_name = extension_name.clone();
+                        menu.entry(
+                            "Preview",
+                            None,
+                            cx.handler_for(&this, move |this, cx| {
+                                this.preview_install(
+                                    extension_id.clone(),
+                                    extension_name.clone().into(),
+                                    version.clone(),
+                                    cx,
+                                )
+                            }),
+                        )
+                    })
+                    .when_some(installed_dir, |menu, installed_dir| {
+                        menu.entry("Reveal in File Manager", None, move |cx| {
+                            cx.reveal_path(&installed_dir)
+                        })
+                    })
+                    .when(can_reinstall, |menu| {
+                        let extension_id = extension_id.clone();
+                        menu.entry("Reinstall", None, move |cx| {
+                            ExtensionStore::global(cx).update(cx, |store, cx| {
+                                store
+                                    .reinstall_extension(extension_id.clone(), cx)
+                                    .detach_and_log_err(cx)
+                            })
+                        })
+                    })
+                    .when(is_published, |menu| {
+                        let extension_id = extension_id.clone();
+                        let extension_name = extension_name.clone();
+                        menu.entry(
+                            "Report...",
+                            None,
+                            cx.handler_for(&this, move |this, cx| {
+                                this.report_extension(
+                                    extension_id.clone(),
+                                    extension_name.clone().into(),
+                                    cx,
+                                )
+                            }),
+                        )
+                    })
+                    .when(is_published, |menu| {
+                        let extension_id = extension_id.clone();
+                        menu.entry(
+                            if is_hidden { "Unhide" } else { "Hide" },
+                            None,
+                            cx.handler_for(&this, move |this, cx| {
+                                this.toggle_hidden(extension_id.clone(), cx)
+                            }),
+                        )
+                    })
+                    .when(is_installed, |menu| {
+                        if is_disabled {
+                            let extension_id = extension_id.clone();
+                            menu.entry("Enable", None, move |cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.enable_extension(&extension_id, cx)
+                                })
+                            })
+                        } else {
+                            let disable_id = extension_id.clone();
+                            let disable_for_hour_id = extension_id.clone();
+                            let disable_until_restart_id = extension_id.clone();
+                            menu.entry("Disable", None, move |cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.disable_extension(
+                                        disable_id.clone(),
+                                        DisabledUntil::Reenabled,
+                                        cx,
+                                    )
+                                })
+                            })
+                            .entry("Disable for 1 Hour", None, move |cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.disable_extension(
+                                        disable_for_hour_id.clone(),
+                                        DisabledUntil::Time(
+                                            Instant::now() + TRIAL_DISABLE_DURATION,
+                                        ),
+                                        cx,
+                                    )
+                                })
+                            })
+                            .entry(
+                                "Disable Until Restart",
+                                None,
+                                move |cx| {
+                                    ExtensionStore::global(cx).update(cx, |store, cx| {
+                                        store.disable_extension(
+                                            disable_until_restart_id.clone(),
+                                            DisabledUntil::Restart,
+                                            cx,
+                                        )
+                                    })
+                                },
+                            )
+                        }
+                    })
+                    .when(is_published && is_installed && !is_tracked, |menu| {
+                        let extension_id = extension_id.clone();
+                        menu.custom_entry(
+                            move |_| {
+                                h_flex()
+                                    .gap_2()
+                                    .child(Checkbox::new(
+                                        "auto-update",
+                                        if auto_updates {
+                                            Selection::Selected
+                                        } else {
+                                            Selection::Unselected
+                                        },
+                                    ))
+                                    .child(Label::new("Auto-update"))
+                                    .into_any_element()
+                            },
+                            cx.handler_for(&this, move |this, cx| {
+                                this.set_auto_update(extension_id.clone(), !auto_updates, cx)
+                            }),
+                        )
+                    })
+                    .when(is_published && is_installed && !is_tracked, |menu| {
+                        let extension_id = extension_id.clone();
+                        menu.entry(
+                            if is_pinned {
+                                "Unpin Version"
+                            } else {
+                                "Pin Version"
+                            },
+                            None,
+                            move |cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    if is_pinned {
+                                        store.unpin_extension(&extension_id, cx)
+                                    } else {
+                                        store.pin_extension(&extension_id, cx)
+                                    }
+                                })
+                            },
+                        )
+                    })
+                    .when(
+                        sync_extensions && is_published && is_installed && !is_tracked,
+                        |menu| {
+                            let extension_id = extension_id.clone();
+                            menu.custom_entry(
+                                move |_| {
+                                    h_flex()
+                                        .gap_2()
+                                        .child(Checkbox::new(
+                                            "sync",
+                                            if is_synced {
+                                                Selection::Selected
+                                            } else {
+                                                Selection::Unselected
+                                            },
+                                        ))
+                                        .child(Label::new("Sync to Other Machines"))
+                                        .into_any_element()
+                                },
+                                move |cx| {
+                                    ExtensionStore::global(cx).update(cx, |store, cx| {
+                                        store.set_extension_synced(&extension_id, !is_synced, cx)
+                                    })
+                                },
+                            )
+                        },
+                    )
+                    .map(|menu| {
+                        let extension_id = extension_id.clone();
+                        if is_tracked {
+                            menu.entry(
+                                "Stop Tracking Main Branch",
+                                None,
+                                cx.handler_for(&this, move |this, cx| {
+                                    this.set_tracked_repository(extension_id.clone(), None, cx)
+                                }),
+                            )
+                        } else if let Some(repository_url) =
+                            repository_url.clone().filter(|_| is_installed)
+                        {
+                            menu.entry(
+                                "Track Main Branch",
+                                None,
+                                cx.handler_for(&this, move |this, cx| {
+                                    this.set_tracked_repository(
+                                        extension_id.clone(),
+                                        Some(repository_url.clone()),
+                                        cx,
+                                    )
+                                }),
+                            )
+                        } else {
+                            menu
+                        }
+                    })
+                    .when(has_build_log, |menu| {
+                        let extension_id = extension_id.clone();
+                        let extension_name = extension_name.clone();
+                        menu.entry(
+                            "View Build Log",
+                            None,
+                            cx.handler_for(&this, move |this, cx| {
+                                this.view_build_log(extension_id.clone(), &extension_name, cx)
+                            }),
+                        )
+                    })
+                    .when(can_view_logs, |menu| {
+                        let extension_id = extension_id.clone();
+                        let extension_name = extension_name.clone();
+                        menu.entry(
+                            "View Logs",
+                            None,
+                            cx.handler_for(&this, move |this, cx| {
+                                this.view_logs(extension_id.clone(), &extension_name, cx)
+                            }),
+                        )
+                    })
+                    .when(has_data, |menu| {
+                        menu.entry(
+                            "Clear Extension Data",
+                            None,
+                            cx.handler_for(&this, move |this, cx| {
+                                this.clear_extension_data(extension_id.clone(), &extension_name, cx)
+                            }),
+                        )
+                    })
+                }))
+            })
+    }
+
+    fn open_extension_api_reference(&mut self, cx: &mut ViewContext<Self>) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let existing_view = workspace
+                    .items_of_type::<ExtensionApiReferenceView>(cx)
+                    .next();
+                if let Some(view) = existing_view {
+                    workspace.activate_item(&view, cx);
+                } else {
+                    let view = cx.new_view(ExtensionApiReferenceView::new);
+                    workspace.add_item_to_active_pane(Box::new(view), cx);
+                }
+            })
+            .log_err();
+    }
+
+    /// Opens the output of the most recent build of the given dev extension.
+    fn view_build_log(
+        &mut self,
+        extension_id: Arc<str>,
+        extension_name: &str,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.open_build_log(
+            BuildLogSource::DevExtension(extension_id),
+            extension_name.to_string(),
+            cx,
+        );
+    }
+
+    fn open_build_log(
+        &mut self,
+        source: BuildLogSource,
+        title: String,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let existing_log_view = workspace
+                    .items_of_type::<BuildLogView>(cx)
+                    .find(|log_view| log_view.read(cx).source() == &source);
+                if let Some(log_view) = existing_log_view {
+                    workspace.activate_item(&log_view, cx);
+                } else {
+                    let log_view = cx.new_view(|cx| BuildLogView::new(source, title, cx));
+                    workspace.add_item_to_active_pane(Box::new(log_view), cx);
+                }
+            })
+            .log_err();
+    }
+
+    /// Opens the recent output of the given extension's wasm module and
+    /// language servers.
+    fn view_logs(
+        &mut self,
+        extension_id: Arc<str>,
+        extension_name: &str,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let extension_name = extension_name.to_string();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let existing_log_view = workspace
+                    .items_of_type::<ExtensionLogView>(cx)
+                    .find(|log_view| log_view.read(cx).extension_id() == &extension_id);
+                if let Some(log_view) = existing_log_view {
+                    workspace.activate_item(&log_view, cx);
+                } else {
+                    let log_view =
+                        cx.new_view(|cx| ExtensionLogView::new(extension_id, extension_name, cx));
+                    workspace.add_item_to_active_pane(Box::new(log_view), cx);
+                }
+            })
+            .log_err();
+    }
+
+    fn render_data_size_label(&self, extension_id: &str, cx: &AppContext) -> Option<Label> {
+        if !ExtensionsPageSettings::get_global(cx)
+            .card_metadata
+            .data_size
+        {
+            return None;
+        }
+        // Installed extensions show all of the space they use, and others
+        // only the data that they left behind.
+        if let Some(usage) = ExtensionStore::global(cx)
+            .read(cx)
+            .extension_disk_usage(extension_id)
+        {
+            return Some(
+                Label::new(format!("On disk: {}", format_data_size(usage))).size(LabelSize::Small),
+            );
+        }
+        let size = self.extension_data_size(extension_id, cx)?;
+        Some(Label::new(format!("Data: {}", format_data_size(size))).size(LabelSize::Small))
+    }
+
+    /// Returns the size of the data stored by the given extension, if it has any.
+    fn extension_data_size(&self, extension_id: &str, cx: &AppContext) -> Option<u64> {
+        ExtensionStore::global(cx)
+            .read(cx)
+            .extension_data_size(extension_id)
+            .filter(|size| *size > 0)
+    }
+
+    fn refresh_extension_data_sizes(&mut self, cx: &mut ViewContext<Self>) {
+        let extension_ids = self
+            .dev_extension_entries
+            .iter()
+            .map(|extension| extension.id.clone())
+            .chain(self.remote_extension_ids.iter().cloned())
+            .collect::<Vec<_>>();
+        ExtensionStore::global(cx).update(cx, |store, cx| {
+            store
+                .refresh_extension_data_sizes(extension_ids, cx)
+                .detach_and_log_err(cx);
+            store.refresh_disk_usage(cx).detach_and_log_err(cx);
+        });
+    }
+
+    fn clear_extension_data(
+        &mut self,
+        extension_id: Arc<str>,
+        extension_name: &str,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let size = self
+            .extension_data_size(&extension_id, cx)
+            .unwrap_or_default();
+        let answer = cx.prompt(
+            PromptLevel::Warning,
+            &format!("Clear the data stored by \"{extension_name}\"?"),
+            Some(
+                format!(
+                    "This removes {} of caches and configuration, including any downloaded language servers.",
+                    format_data_size(size)
+                )
+                .as_str(),
+            ),
+            &["Clear Data", "Cancel"],
+        );
+        cx.spawn(|this, mut cx| async move {
+            if answer.await? != 0 {
+                return Ok(());
+            }
+            this.update(&mut cx, |_, cx| {
+                ExtensionStore::global(cx).update(cx, |store, cx| {
+                    store.clear_extension_data(&extension_id, cx)
+                })
+            })?
+            .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_theme_preview_controls(
+        &self,
+        extension: &ExtensionApiResponse,
+        status: &ExtensionStatus,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        if let Some(preview) = self
+            .theme_preview
+            .as_ref()
+            .filter(|preview| preview.extension_id == extension.id)
+        {
+            let Some(theme_names) = preview.theme_names.as_ref() else {
+                return Some(
+                    Button::new(
+                        SharedString::from(format!("preview-{}", extension.id)),
+                        "Loading Preview...",
                     )
-                    .child(
-                        Label::new(format!("Downloads: {}", extension.download_count))
-                            .size(LabelSize::Small),
-                    ),
-            )
-            .child(
+                    .disabled(true)
+                    .into_any_element(),
+                );
+            };
+
+            let theme_name = theme_names.get(preview.active_theme_ix)?.clone();
+            return Some(
                 h_flex()
                     .gap_2()
-                    .justify_between()
-                    .children(extension.description.as_ref().map(|description| {
-                        h_flex().overflow_x_hidden().child(
-                            Label::new(description.clone())
-                                .size(LabelSize::Small)
-                                .color(Color::Default),
+                    .child(
+                        Label::new(format!("Previewing {theme_name}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .when(theme_names.len() > 1, |this| {
+                        this.child(
+                            IconButton::new(
+                                SharedString::from(format!("preview-next-{}", extension.id)),
+                                IconName::ChevronRight,
+                            )
+                            .icon_size(IconSize::Small)
+                            .on_click(cx.listener(|this, _, cx| this.preview_next_theme(cx)))
+                            .tooltip(|cx| Tooltip::text("Preview Next Theme", cx)),
                         )
-                    }))
+                    })
                     .child(
-                        IconButton::new(
-                            SharedString::from(format!("repository-{}", extension.id)),
-                            IconName::Github,
+                        Button::new(
+                            SharedString::from(format!("end-preview-{}", extension.id)),
+                            "End Preview",
                         )
-                        .icon_color(Color::Accent)
-                        .icon_size(IconSize::Small)
-                        .style(ButtonStyle::Filled)
-                        .on_click(cx.listener({
-                            let repository_url = repository_url.clone();
-                            move |_, _, cx| {
-                                cx.open_url(&repository_url);
-                            }
+                        .on_click(cx.listener(|this, _, cx| {
+                            this.end_theme_preview(cx);
+                            cx.notify();
                         }))
-                        .tooltip(move |cx| Tooltip::text(repository_url.clone(), cx)),
-                    ),
+                        .tooltip(|cx| Tooltip::text("Go back to your previous theme", cx)),
+                    )
+                    .into_any_element(),
+            );
+        }
+
+        if extension.themes.is_empty() || !matches!(status, ExtensionStatus::NotInstalled) {
+            return None;
+        }
+
+        Some(
+            Button::new(
+                SharedString::from(format!("preview-{}", extension.id)),
+                "Preview",
             )
+            .on_click(cx.listener({
+                let extension = extension.clone();
+                move |this, _, cx| this.preview_themes(&extension, cx)
+            }))
+            .tooltip(|cx| Tooltip::text("Try this extension's themes without installing it", cx))
+            .into_any_element(),
+        )
+    }
+
+    fn preview_themes(&mut self, extension: &ExtensionApiResponse, cx: &mut ViewContext<Self>) {
+        self.end_theme_preview(cx);
+
+        let extension_id = extension.id.clone();
+        let load = ExtensionStore::global(cx).update(cx, |store, cx| {
+            store.load_theme_preview(
+                extension.id.clone(),
+                extension.version.clone(),
+                extension.themes.clone(),
+                cx,
+            )
+        });
+        self.theme_preview = Some(ThemePreview {
+            extension_id: extension_id.clone(),
+            original_theme: cx.theme().clone(),
+            theme_names: None,
+            active_theme_ix: 0,
+        });
+        cx.notify();
+
+        cx.spawn(|this, mut cx| async move {
+            let theme_names = match load.await {
+                Ok(theme_names) => theme_names,
+                Err(error) => {
+                    return this.update(&mut cx, |this, cx| {
+                        if this
+                            .theme_preview
+                            .as_ref()
+                            .map_or(false, |preview| preview.extension_id == extension_id)
+                        {
+                            this.theme_preview = None;
+                            cx.notify();
+                        }
+                        drop(cx.prompt(
+                            PromptLevel::Critical,
+                            "Failed to preview themes",
+                            Some(format!("{error:#}").as_str()),
+                            &["Ok"],
+                        ));
+                    });
+                }
+            };
+
+            // The preview may have been dismissed while the themes were downloading.
+            let is_previewing = this
+                .update(&mut cx, |this, cx| {
+                    let Some(preview) = this
+                        .theme_preview
+                        .as_mut()
+                        .filter(|preview| preview.extension_id == extension_id)
+                    else {
+                        return false;
+                    };
+                    preview.theme_names = Some(theme_names.clone());
+                    this.show_previewed_theme(cx);
+                    cx.notify();
+                    true
+                })
+                .unwrap_or(false);
+            if !is_previewing {
+                cx.update(|cx| {
+                    ExtensionStore::global(cx)
+                        .update(cx, |store, _| store.unload_theme_preview(&theme_names))
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn preview_next_theme(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(preview) = self.theme_preview.as_mut() else {
+            return;
+        };
+        let Some(theme_names) = preview.theme_names.as_ref() else {
+            return;
+        };
+        preview.active_theme_ix = (preview.active_theme_ix + 1) % theme_names.len();
+        self.show_previewed_theme(cx);
+        cx.notify();
+    }
+
+    fn show_previewed_theme(&self, cx: &mut AppContext) {
+        let Some(theme_name) = self.theme_preview.as_ref().and_then(|preview| {
+            preview
+                .theme_names
+                .as_ref()?
+                .get(preview.active_theme_ix)
+                .cloned()
+        }) else {
+            return;
+        };
+        if let Some(theme) = ThemeRegistry::global(cx).get(&theme_name).log_err() {
+            set_theme(theme, cx);
+        }
+    }
+
+    /// Restores the theme that was active before the preview started, and removes
+    /// the previewed themes from the registry.
+    fn end_theme_preview(&mut self, cx: &mut AppContext) {
+        let Some(preview) = self.theme_preview.take() else {
+            return;
+        };
+        if let Some(theme_names) = preview.theme_names {
+            set_theme(preview.original_theme, cx);
+            ExtensionStore::global(cx)
+                .update(cx, |store, _| store.unload_theme_preview(&theme_names));
+        }
     }
 
     fn buttons_for_entry(
@@ -425,57 +4798,119 @@ impl ExtensionsPage {
         status: &ExtensionStatus,
         cx: &mut ViewContext<Self>,
     ) -> (Button, Option<Button>) {
+        // Each button's ID and tooltip name the action and the extension, so
+        // that the buttons of different cards, and the buttons on one card, can
+        // be told apart.
+        let install_id = SharedString::from(format!("install-{}", extension.id));
+        let uninstall_id = SharedString::from(format!("uninstall-{}", extension.id));
+        let upgrade_id = SharedString::from(format!("upgrade-{}", extension.id));
+        let is_pinned = ExtensionStore::global(cx)
+            .read(cx)
+            .pinned_version(&extension.id)
+            .is_some();
+        let install_tooltip: SharedString =
+            format!("Install {} v{}", extension.name, extension.version).into();
+        let uninstall_tooltip: SharedString = format!("Uninstall {}", extension.name).into();
+        let settings = ExtensionSettings::get_global(cx);
+        let is_allowed = settings.allows_extension(&extension.id);
+        let is_required = settings.requires_extension(&extension.id);
+
         match status.clone() {
+            ExtensionStatus::NotInstalled if !is_allowed => (
+                Button::new(install_id, "Blocked by your organization")
+                    .disabled(true)
+                    .tooltip(|cx| {
+                        Tooltip::text(
+                            "Your organization's settings don't allow this extension",
+                            cx,
+                        )
+                    }),
+                None,
+            ),
             ExtensionStatus::NotInstalled => (
-                Button::new(SharedString::from(extension.id.clone()), "Install").on_click(
-                    cx.listener({
-                        let extension_id = extension.id.clone();
-                        let version = extension.version.clone();
+                Button::new(install_id, "Install")
+                    .on_click(cx.listener({
+                        let extension = extension.clone();
                         move |this, _, cx| {
-                            this.telemetry
-                                .report_app_event("extensions: install extension".to_string());
-                            ExtensionStore::global(cx).update(cx, |store, cx| {
-                                store.install_extension(extension_id.clone(), version.clone(), cx)
-                            });
+                            report_extension_event(
+                                &this.telemetry,
+                                "extensions: install extension".to_string(),
+                                cx,
+                            );
+                            if InstallConfirmationModal::is_needed(&extension, cx) {
+                                let extension = extension.clone();
+                                this.workspace
+                                    .update(cx, |workspace, cx| {
+                                        let fs = workspace.app_state().fs.clone();
+                                        workspace.toggle_modal(cx, |cx| {
+                                            InstallConfirmationModal::new(&extension, fs, cx)
+                                        })
+                                    })
+                                    .ok();
+                            } else {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.install_extension(
+                                        extension.id.clone(),
+                                        extension.version.clone(),
+                                        cx,
+                                    )
+                                });
+                            }
                         }
-                    }),
-                ),
+                    }))
+                    .tooltip(move |cx| Tooltip::text(install_tooltip.clone(), cx)),
                 None,
             ),
             ExtensionStatus::Installing => (
-                Button::new(SharedString::from(extension.id.clone()), "Install").disabled(true),
+                Button::new(install_id, "Install")
+                    .disabled(true)
+                    .tooltip(move |cx| {
+                        Tooltip::text(format!("{install_tooltip} (in progress)"), cx)
+                    }),
                 None,
             ),
             ExtensionStatus::Upgrading => (
-                Button::new(SharedString::from(extension.id.clone()), "Uninstall").disabled(true),
-                Some(
-                    Button::new(SharedString::from(extension.id.clone()), "Upgrade").disabled(true),
-                ),
+                Button::new(uninstall_id, "Uninstall").disabled(true),
+                Some(Button::new(upgrade_id, "Upgrade").disabled(true).tooltip({
+                    let tooltip = format!("Upgrading {} to v{}", extension.name, extension.version);
+                    move |cx| Tooltip::text(tooltip.clone(), cx)
+                })),
             ),
             ExtensionStatus::Installed(installed_version) => (
-                Button::new(SharedString::from(extension.id.clone()), "Uninstall").on_click(
-                    cx.listener({
-                        let extension_id = extension.id.clone();
-                        move |this, _, cx| {
-                            this.telemetry
-                                .report_app_event("extensions: uninstall extension".to_string());
-                            ExtensionStore::global(cx).update(cx, |store, cx| {
-                                store.uninstall_extension(extension_id.clone(), cx)
-                            });
-                        }
-                    }),
-                ),
-                if installed_version == extension.version {
+                if is_required {
+                    Button::new(uninstall_id, "Managed by your organization")
+                        .disabled(true)
+                        .tooltip(|cx| {
+                            Tooltip::text("Your organization requires this extension", cx)
+                        })
+                } else {
+                    Button::new(uninstall_id, "Uninstall")
+                        .on_click(cx.listener({
+                            let extension_id = extension.id.clone();
+                            let extension_name = extension.name.clone();
+                            move |this, _, cx| {
+                                this.uninstall_extension(extension_id.clone(), &extension_name, cx)
+                            }
+                        }))
+                        .tooltip(move |cx| Tooltip::text(uninstall_tooltip.clone(), cx))
+                },
+                if installed_version == extension.version || is_pinned || !is_allowed {
                     None
                 } else {
+                    let upgrade_tooltip = format!(
+                        "Upgrade {} from v{installed_version} to v{}",
+                        extension.name, extension.version
+                    );
                     Some(
-                        Button::new(SharedString::from(extension.id.clone()), "Upgrade").on_click(
-                            cx.listener({
+                        Button::new(upgrade_id, "Upgrade")
+                            .on_click(cx.listener({
                                 let extension_id = extension.id.clone();
                                 let version = extension.version.clone();
                                 move |this, _, cx| {
-                                    this.telemetry.report_app_event(
+                                    report_extension_event(
+                                        &this.telemetry,
                                         "extensions: install extension".to_string(),
+                                        cx,
                                     );
                                     ExtensionStore::global(cx).update(cx, |store, cx| {
                                         store.upgrade_extension(
@@ -485,13 +4920,17 @@ impl ExtensionsPage {
                                         )
                                     });
                                 }
-                            }),
-                        ),
+                            }))
+                            .tooltip(move |cx| Tooltip::text(upgrade_tooltip.clone(), cx)),
                     )
                 },
             ),
             ExtensionStatus::Removing => (
-                Button::new(SharedString::from(extension.id.clone()), "Uninstall").disabled(true),
+                Button::new(uninstall_id, "Uninstall")
+                    .disabled(true)
+                    .tooltip(move |cx| {
+                        Tooltip::text(format!("{uninstall_tooltip} (in progress)"), cx)
+                    }),
                 None,
             ),
         }
@@ -609,8 +5048,14 @@ impl ExtensionsPage {
     fn render_empty_state(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let has_search = self.search_query(cx).is_some();
 
-        let message = if self.is_fetching_extensions {
+        let message = if self.is_fetching_extensions
+            && ExtensionStore::global(cx).read(cx).is_registry_busy()
+        {
+            "Registry busy, retrying…"
+        } else if self.is_fetching_extensions {
             "Loading extensions..."
+        } else if self.fetch_error.is_some() && !self.is_showing_cached_extensions {
+            "Extensions could not be loaded."
         } else {
             match self.filter {
                 ExtensionFilter::All => {
@@ -634,6 +5079,13 @@ impl ExtensionsPage {
                         "No not installed extensions."
                     }
                 }
+                ExtensionFilter::Recommended => {
+                    if has_search {
+                        "No recommended extensions that match your search."
+                    } else {
+                        "This workspace doesn't recommend any extensions."
+                    }
+                }
             }
         };
 
@@ -641,11 +5093,61 @@ impl ExtensionsPage {
     }
 }
 
-impl Render for ExtensionsPage {
-    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+impl ExtensionsPage {
+    fn render_list(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let extension_store = ExtensionStore::global(cx).read(cx);
+        let has_collections = !extension_store.extension_collections().is_empty();
+        let has_extensions_requiring_restart = extension_store.has_extensions_requiring_restart();
+        let is_in_safe_mode = extension_store.is_in_safe_mode();
+        let bisect_banner = extension_store.extension_bisect().map(|bisect| {
+            format!(
+                "Bisecting extensions: {} of {} are disabled.",
+                bisect.disabled_extensions().len(),
+                bisect.candidate_count()
+            )
+        });
+        let pending_change_count = extension_store.pending_changes().len();
+        let show_dev_extensions =
+            self.filter.include_dev_extensions() && !self.dev_extension_entries.is_empty();
+        let show_recently_upgraded = matches!(
+            self.filter,
+            ExtensionFilter::All | ExtensionFilter::Installed
+        ) && extension_store
+            .recently_upgraded_extensions()
+            .next()
+            .is_some();
+        let unused_extensions = if self.filter == ExtensionFilter::Installed {
+            extension_store.unused_extensions(cx)
+        } else {
+            Vec::new()
+        };
+        let has_hidden_extensions = !ExtensionsPageSettings::get_global(cx)
+            .hidden_extensions
+            .is_empty();
+        let has_recommendations = !self.workspace_recommendations.is_empty();
+        let registries = if extension_store.registries().len() > 1 {
+            extension_store
+                .registries()
+                .iter()
+                .map(|registry| registry.name.as_ref())
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else {
+            extension_store.registry_url()
+        };
+        let disk_quota = extension_store.disk_quota(cx);
+        let total_disk_usage = extension_store.total_disk_usage();
+        let is_over_disk_quota = extension_store.would_exceed_disk_quota(0, cx);
+
         v_flex()
             .size_full()
             .bg(cx.theme().colors().editor_background)
+            .drag_over::<ExternalPaths>(|style, _, cx| {
+                style.bg(cx.theme().colors().drop_target_background)
+            })
+            .on_drop(cx.listener(|this, paths: &ExternalPaths, cx| {
+                this.install_dropped_paths(paths, cx)
+            }))
             .child(
                 v_flex()
                     .gap_4()
@@ -658,14 +5160,72 @@ impl Render for ExtensionsPage {
                             .w_full()
                             .gap_2()
                             .justify_between()
-                            .child(Headline::new("Extensions").size(HeadlineSize::XLarge))
                             .child(
-                                Button::new("add-dev-extension", "Add Dev Extension")
-                                    .style(ButtonStyle::Filled)
-                                    .size(ButtonSize::Large)
-                                    .on_click(|_event, cx| {
-                                        cx.dispatch_action(Box::new(InstallDevExtension))
+                                h_flex()
+                                    .gap_2()
+                                    .items_end()
+                                    .child(Headline::new("Extensions").size(HeadlineSize::XLarge))
+                                    .child(
+                                        Label::new(self.results_summary.clone())
+                                            .color(Color::Muted),
+                                    )
+                                    .child(
+                                        Label::new(format!("from {registries}"))
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                    .when(total_disk_usage > 0, |this| {
+                                        let usage = match disk_quota {
+                                            Some(quota) => format!(
+                                                "{} of {} on disk",
+                                                format_data_size(total_disk_usage),
+                                                format_data_size(quota)
+                                            ),
+                                            None => format!(
+                                                "{} on disk",
+                                                format_data_size(total_disk_usage)
+                                            ),
+                                        };
+                                        this.child(
+                                            Label::new(usage).size(LabelSize::Small).color(
+                                                if is_over_disk_quota {
+                                                    Color::Warning
+                                                } else {
+                                                    Color::Muted
+                                                },
+                                            ),
+                                        )
                                     }),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .when(self.is_guest, |this| {
+                                        this.child(
+                                            Button::new("host-extensions", "Host Extensions")
+                                                .style(ButtonStyle::Filled)
+                                                .size(ButtonSize::Large)
+                                                .on_click(|_event, cx| {
+                                                    cx.dispatch_action(Box::new(HostExtensions))
+                                                })
+                                                .tooltip(|cx| {
+                                                    Tooltip::text(
+                                                        "View the language extensions of this project's host",
+                                                        cx,
+                                                    )
+                                                }),
+                                        )
+                                    })
+                                    .child(
+                                        Button::new("add-dev-extension", "Add Dev Extension")
+                                            .style(ButtonStyle::Filled)
+                                            .size(ButtonSize::Large)
+                                            .on_click(|_event, cx| {
+                                                cx.dispatch_action(Box::new(InstallDevExtension))
+                                            }),
+                                    )
+                                    .child(self.render_install_queue(cx))
+                                    .child(self.render_page_menu(cx)),
                             ),
                     )
                     .child(
@@ -691,7 +5251,10 @@ impl Render for ExtensionsPage {
                                             .first(),
                                     )
                                     .child(
-                                        ToggleButton::new("filter-installed", "Installed")
+                                        ToggleButton::new(
+                                            "filter-installed",
+                                            format!("Installed ({})", self.installed_extension_count),
+                                        )
                                             .style(ButtonStyle::Filled)
                                             .size(ButtonSize::Large)
                                             .selected(self.filter == ExtensionFilter::Installed)
@@ -716,18 +5279,136 @@ impl Render for ExtensionsPage {
                                             .tooltip(move |cx| {
                                                 Tooltip::text("Show not installed extensions", cx)
                                             })
+                                            .map(|button| {
+                                                if has_recommendations {
+                                                    button.middle()
+                                                } else {
+                                                    button.last()
+                                                }
+                                            }),
+                                    )
+                                    .when(has_recommendations, |this| {
+                                        this.child(
+                                            ToggleButton::new(
+                                                "filter-recommended",
+                                                "Recommended by This Workspace",
+                                            )
+                                            .style(ButtonStyle::Filled)
+                                            .size(ButtonSize::Large)
+                                            .selected(
+                                                self.filter == ExtensionFilter::Recommended,
+                                            )
+                                            .on_click(cx.listener(|this, _event, cx| {
+                                                this.filter = ExtensionFilter::Recommended;
+                                                this.filter_extension_entries(cx);
+                                            }))
+                                            .tooltip(move |cx| {
+                                                Tooltip::text(
+                                                    "Show the extensions that this workspace's \
+                                                    .zed/extensions.json recommends",
+                                                    cx,
+                                                )
+                                            })
                                             .last(),
-                                    ),
+                                        )
+                                    }),
                             ),
-                    ),
+                    )
+                    .when(has_hidden_extensions, |this| {
+                        this.child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Checkbox::new(
+                                        "show-hidden-extensions",
+                                        if self.show_hidden_extensions {
+                                            Selection::Selected
+                                        } else {
+                                            Selection::Unselected
+                                        },
+                                    )
+                                    .on_click(cx.listener(
+                                        |this, selection: &Selection, cx| {
+                                            this.show_hidden_extensions =
+                                                *selection == Selection::Selected;
+                                            this.filter_extension_entries(cx);
+                                        },
+                                    )),
+                                )
+                                .child(Label::new("Show hidden").size(LabelSize::Small)),
+                        )
+                    })
+                    .when(self.filter == ExtensionFilter::Installed, |this| {
+                        this.child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Checkbox::new(
+                                        "sort-by-startup-impact",
+                                        if self.sort_by_startup_impact {
+                                            Selection::Selected
+                                        } else {
+                                            Selection::Unselected
+                                        },
+                                    )
+                                    .on_click(cx.listener(
+                                        |this, selection: &Selection, cx| {
+                                            this.sort_by_startup_impact =
+                                                *selection == Selection::Selected;
+                                            this.filter_extension_entries(cx);
+                                        },
+                                    )),
+                                )
+                                .child(
+                                    Label::new("Sort by startup impact").size(LabelSize::Small),
+                                ),
+                        )
+                    })
+                    .when(has_collections, |this| {
+                        this.child(self.render_collections(cx))
+                    }),
+            )
+            .when(is_in_safe_mode, |this| {
+                this.child(self.render_safe_mode_banner(cx))
+            })
+            .when_some(bisect_banner, |this, message| {
+                this.child(self.render_bisect_banner(message, cx))
+            })
+            .when_some(
+                self.fetch_error
+                    .clone()
+                    .filter(|_| !self.is_fetching_extensions),
+                |this, error| this.child(self.render_fetch_error_banner(error, cx)),
             )
+            .when(pending_change_count > 0, |this| {
+                this.child(self.render_pending_changes_banner(pending_change_count, cx))
+            })
+            .when(has_extensions_requiring_restart, |this| {
+                this.child(self.render_restart_banner(cx))
+            })
+            .when(
+                !self.selected_extension_ids.is_empty() || self.batch_outcome.is_some(),
+                |this| this.child(self.render_batch_bar(cx)),
+            )
+            .when(show_recently_upgraded, |this| {
+                this.child(div().px_4().child(self.render_recently_upgraded_section(cx)))
+            })
+            .when(!unused_extensions.is_empty(), |this| {
+                this.child(
+                    div()
+                        .px_4()
+                        .child(self.render_unused_extensions_section(unused_extensions, cx)),
+                )
+            })
+            .when(show_dev_extensions, |this| {
+                this.child(div().px_4().child(self.render_dev_extensions_section(cx)))
+            })
             .child(v_flex().px_4().size_full().overflow_y_hidden().map(|this| {
-                let mut count = self.filtered_remote_extension_indices.len();
-                if self.filter.include_dev_extensions() {
-                    count += self.dev_extension_entries.len();
-                }
-
+                let count = self.list_entries.len();
                 if count == 0 {
+                    if show_dev_extensions {
+                        return this;
+                    }
                     return this.py_4().child(self.render_empty_state(cx));
                 }
 
@@ -736,7 +5417,7 @@ impl Render for ExtensionsPage {
                 this.child(
                     canvas(
                         move |bounds, cx| {
-                            let mut list = uniform_list::<_, ExtensionCard, _>(
+                            let mut list = uniform_list::<_, AnyElement, _>(
                                 view,
                                 "entries",
                                 count,
@@ -757,11 +5438,292 @@ impl Render for ExtensionsPage {
     }
 }
 
+impl Render for ExtensionsPage {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .on_action(cx.listener(Self::go_back))
+            .on_action(cx.listener(Self::go_forward))
+            .map(|this| match self.location.clone() {
+                PageLocation::List => this.child(self.render_list(cx)),
+                PageLocation::Detail(extension_id) => {
+                    this.child(self.render_extension_detail(extension_id, cx))
+                }
+            })
+    }
+}
+
+/// Renders the header above the extensions in a category. Every row of the
+/// list has the same height, so the label sits at the bottom of the row, just
+/// above the first extension in the category.
+fn render_category_header(category: ExtensionCategory) -> impl IntoElement {
+    h_flex()
+        .size_full()
+        .items_end()
+        .pb_1()
+        .child(ListSubHeader::new(category.label()))
+}
+
+/// Lists the names of some of an extension's contributions in its detail view.
+fn render_detail_section(title: &'static str, items: &[String]) -> Option<impl IntoElement> {
+    if items.is_empty() {
+        return None;
+    }
+    Some(
+        v_flex()
+            .gap_1()
+            .child(Headline::new(title).size(HeadlineSize::XSmall))
+            .child(
+                h_flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .children(items.iter().map(|item| ExtensionBadge::new(item.clone()))),
+            ),
+    )
+}
+
+fn render_resource_usage(usage: ExtensionResourceUsage) -> impl IntoElement {
+    v_flex()
+        .gap_1()
+        .child(Headline::new("Resource Usage").size(HeadlineSize::XSmall))
+        .child(
+            h_flex()
+                .gap_4()
+                .child(
+                    Label::new(format!("Memory: {}", format_data_size(usage.wasm_memory)))
+                        .size(LabelSize::Small),
+                )
+                .child(
+                    Label::new(format!(
+                        "Busy time: {}",
+                        format_load_time(usage.wasm_busy_time)
+                    ))
+                    .size(LabelSize::Small),
+                )
+                .when(usage.server_processes > 0, |this| {
+                    this.child(
+                        Label::new(format!(
+                            "Processes: {} using {}, {:.1}% CPU",
+                            usage.server_processes,
+                            format_data_size(usage.server_memory),
+                            usage.server_cpu_usage
+                        ))
+                        .size(LabelSize::Small),
+                    )
+                }),
+        )
+}
+
+/// Reports an extensions event, unless extension telemetry is disabled.
+fn report_extension_event(telemetry: &Telemetry, event: String, cx: &AppContext) {
+    if ExtensionsPageSettings::get_global(cx).telemetry {
+        telemetry.report_app_event(event);
+    }
+}
+
+fn format_load_time(load_time: Duration) -> String {
+    if load_time < Duration::from_secs(1) {
+        format!("{} ms", load_time.as_millis())
+    } else {
+        format!("{:.1} s", load_time.as_secs_f64())
+    }
+}
+
+fn format_data_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.;
+    let mut unit_ix = 0;
+    while size >= 1024. && unit_ix + 1 < UNITS.len() {
+        size /= 1024.;
+        unit_ix += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit_ix])
+}
+
+fn render_kind_badge(kind: ExtensionKind) -> ExtensionBadge {
+    ExtensionBadge::new(kind.label()).color(Color::Accent)
+}
+
+/// Calls out the extension whose theme is selected, since it restyles the
+/// entire editor rather than a single language.
+fn render_active_theme_badge() -> ExtensionBadge {
+    ExtensionBadge::new("Active UI Theme").color(Color::Info)
+}
+
+fn render_restart_required_badge() -> ExtensionBadge {
+    ExtensionBadge::new("Restart Required").color(Color::Warning)
+}
+
+/// Shows that an extension was disabled because it crashed repeatedly, with
+/// its last crash in a tooltip and an action that enables it again.
+fn render_crashed_indicator(extension_id: Arc<str>, error: SharedString) -> impl IntoElement {
+    h_flex()
+        .gap_1()
+        .child(ExtensionBadge::new("Disabled after repeated crashes").color(Color::Error))
+        .child(
+            Button::new(
+                SharedString::from(format!("reenable-{extension_id}")),
+                "Re-enable",
+            )
+            .style(ButtonStyle::Subtle)
+            .label_size(LabelSize::Small)
+            .tooltip(move |cx| Tooltip::text(format!("Last crash: {error}"), cx))
+            .on_click(move |_, cx| {
+                ExtensionStore::global(cx)
+                    .update(cx, |store, cx| store.enable_extension(&extension_id, cx))
+            }),
+        )
+}
+
+fn render_funding_button(extension_id: &str, funding_url: Option<String>) -> Option<IconButton> {
+    let funding_url = SharedString::from(funding_url?);
+    Some(
+        IconButton::new(
+            SharedString::from(format!("funding-{extension_id}")),
+            IconName::Heart,
+        )
+        .icon_color(Color::Accent)
+        .icon_size(IconSize::Small)
+        .style(ButtonStyle::Filled)
+        .on_click({
+            let funding_url = funding_url.clone();
+            move |_, cx| cx.open_url(&funding_url)
+        })
+        .tooltip(move |cx| Tooltip::text(format!("Support the authors: {funding_url}"), cx)),
+    )
+}
+
+fn render_verified_publisher_badge(extension: &ExtensionApiResponse) -> Option<impl IntoElement> {
+    let publisher = extension
+        .publisher
+        .as_ref()
+        .filter(|publisher| publisher.verified)?;
+    let tooltip = format!(
+        "Published by {}, whose identity has been verified by the extension registry.",
+        publisher.name
+    );
+    Some(
+        div()
+            .id(SharedString::from(format!("verified-{}", extension.id)))
+            .child(
+                Icon::new(IconName::Check)
+                    .size(IconSize::Small)
+                    .color(Color::Accent),
+            )
+            .tooltip(move |cx| Tooltip::text(tooltip.clone(), cx)),
+    )
+}
+
+/// Shows which key signed an installed extension, if its signature was
+/// verified when it was installed.
+fn render_signer_label(signer: Option<&ExtensionSigner>) -> impl IntoElement {
+    let (label, tooltip) = match signer {
+        Some(signer) => (
+            format!("Signed by {}", signer.key_id),
+            match signer.source {
+                SigningKeySource::Registry => "Signed with a key that ships with Zed.",
+                SigningKeySource::User => {
+                    "Signed with a key trusted by the `trusted_signing_keys` setting."
+                }
+            },
+        ),
+        None => (
+            "Unsigned".to_string(),
+            "This extension's signature wasn't verified when it was installed.",
+        ),
+    };
+    div()
+        .id("extension-detail-signer")
+        .child(
+            Label::new(label)
+                .size(LabelSize::Small)
+                .color(if signer.is_some() {
+                    Color::Default
+                } else {
+                    Color::Warning
+                }),
+        )
+        .tooltip(move |cx| Tooltip::text(tooltip, cx))
+}
+
+fn render_status_indicator(status: ExtensionCardStatus) -> impl IntoElement {
+    h_flex()
+        .gap_1()
+        .child(
+            Icon::new(status.icon())
+                .size(IconSize::Small)
+                .color(status.color()),
+        )
+        .child(
+            Label::new(status.label())
+                .size(LabelSize::XSmall)
+                .color(status.color()),
+        )
+}
+
+/// Renders the status of an extension from the registry as a chip, which
+/// includes the installed version, and the latest one when it differs.
+fn render_status_chip(
+    card_status: ExtensionCardStatus,
+    status: &ExtensionStatus,
+    latest_version: &str,
+    cx: &WindowContext,
+) -> impl IntoElement {
+    let label = match (card_status, status) {
+        (ExtensionCardStatus::Installed, ExtensionStatus::Installed(version)) => {
+            format!("Installed v{version}")
+        }
+        (ExtensionCardStatus::UpdateAvailable, ExtensionStatus::Installed(version)) => {
+            format!("Update Available: v{version} → v{latest_version}")
+        }
+        (ExtensionCardStatus::Disabled, ExtensionStatus::Installed(version)) => {
+            format!("Disabled v{version}")
+        }
+        _ => card_status.label().to_string(),
+    };
+
+    h_flex()
+        .gap_1()
+        .px_1()
+        .rounded_md()
+        .bg(cx.theme().colors().element_background)
+        .border_1()
+        .border_color(card_status.color().color(cx))
+        .child(
+            Icon::new(card_status.icon())
+                .size(IconSize::XSmall)
+                .color(card_status.color()),
+        )
+        .child(
+            Label::new(label)
+                .size(LabelSize::XSmall)
+                .color(card_status.color()),
+        )
+}
+
+fn set_theme(theme: Arc<Theme>, cx: &mut AppContext) {
+    cx.update_global(|store: &mut SettingsStore, cx| {
+        let mut theme_settings = store.get::<ThemeSettings>(None).clone();
+        theme_settings.active_theme = theme;
+        theme_settings.apply_theme_overrides();
+        store.override_global(theme_settings);
+        cx.refresh();
+    });
+}
+
 impl EventEmitter<ItemEvent> for ExtensionsPage {}
 
 impl FocusableView for ExtensionsPage {
     fn focus_handle(&self, cx: &AppContext) -> gpui::FocusHandle {
-        self.query_editor.read(cx).focus_handle(cx)
+        match self.location {
+            PageLocation::List => self.query_editor.read(cx).focus_handle(cx),
+            PageLocation::Detail(_) => self.detail_focus_handle.clone(),
+        }
     }
 }
 
@@ -788,10 +5750,72 @@ impl Item for ExtensionsPage {
 
     fn clone_on_split(
         &self,
-        _workspace_id: WorkspaceId,
-        _: &mut ViewContext<Self>,
+        workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
     ) -> Option<View<Self>> {
-        None
+        let search_query = self.search_query(cx);
+        Some(cx.new_view(|cx| {
+            let mut page = Self::build(
+                self.workspace.clone(),
+                workspace_id,
+                self.is_guest,
+                self.telemetry.clone(),
+                search_query,
+                cx,
+            );
+            // The new page starts out with the same listing, without fetching it
+            // again, and is filtered and searched independently from then on.
+            page.remote_extension_ids = self.remote_extension_ids.clone();
+            page.dev_extension_entries = self.dev_extension_entries.clone();
+            page.fetch_error = self.fetch_error.clone();
+            page.is_showing_cached_extensions = self.is_showing_cached_extensions;
+            page.filter = self.filter;
+            page.selected_collection = self.selected_collection.clone();
+            page.show_hidden_extensions = self.show_hidden_extensions;
+            page.sort_by_startup_impact = self.sort_by_startup_impact;
+            page.workspace_recommendations = self.workspace_recommendations.clone();
+            page.filter_extension_entries(cx);
+            page
+        }))
+    }
+
+    fn serialized_item_kind() -> Option<&'static str> {
+        Some("ExtensionsPage")
+    }
+
+    fn deserialize(
+        _project: Model<Project>,
+        workspace: WeakView<Workspace>,
+        workspace_id: WorkspaceId,
+        item_id: ItemId,
+        cx: &mut ViewContext<Pane>,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|_, mut cx| async move {
+            let page_state = EXTENSIONS_PAGE_DB
+                .get_page_state(item_id, workspace_id)
+                .log_err()
+                .flatten();
+            workspace.update(&mut cx, |workspace, cx| {
+                let extensions_page = ExtensionsPage::new(workspace, cx);
+                if let Some((filter, search_query, collection)) = page_state {
+                    extensions_page.update(cx, |page, cx| {
+                        page.restore_page_state(&filter, search_query, collection, cx)
+                    });
+                }
+                extensions_page
+            })
+        })
+    }
+
+    fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
+        cx.background_executor()
+            .spawn(EXTENSIONS_PAGE_DB.update_workspace_id(
+                workspace.database_id(),
+                self.workspace_id,
+                cx.entity_id().as_u64(),
+            ))
+            .detach();
+        self.workspace_id = workspace.database_id();
     }
 
     fn to_item_events(event: &Self::Event, mut f: impl FnMut(workspace::item::ItemEvent)) {
@@ -0,0 +1,220 @@
+use anyhow::{Context as _, Result};
+use client::{proto, Client, TypedEnvelope};
+use extension::ExtensionStore;
+use gpui::{
+    AnyElement, AppContext, AsyncAppContext, EventEmitter, FocusHandle, FocusableView, Global,
+    Model, Subscription, Task, WindowContext,
+};
+use std::sync::Arc;
+use ui::prelude::*;
+use workspace::{
+    item::{Item, ItemEvent},
+    Workspace,
+};
+
+use crate::components::{ExtensionBadge, ExtensionCard};
+
+struct GlobalHostExtensionsHandler(#[allow(dead_code)] Subscription);
+
+impl Global for GlobalHostExtensionsHandler {}
+
+/// Answers guests' requests for the extensions that provide language support
+/// in the projects that this client is hosting.
+pub(crate) fn init(cx: &mut AppContext) {
+    let client = Client::global(cx);
+    let store = ExtensionStore::global(cx);
+    let subscription = client.add_request_handler(store.downgrade(), handle_get_project_extensions);
+    cx.set_global(GlobalHostExtensionsHandler(subscription));
+}
+
+async fn handle_get_project_extensions(
+    store: Model<ExtensionStore>,
+    _: TypedEnvelope<proto::GetProjectExtensions>,
+    _: Arc<Client>,
+    mut cx: AsyncAppContext,
+) -> Result<proto::GetProjectExtensionsResponse> {
+    store.update(&mut cx, |store, _| proto::GetProjectExtensionsResponse {
+        extensions: store
+            .loaded_extensions()
+            .filter(|manifest| {
+                !manifest.languages.is_empty()
+                    || !manifest.grammars.is_empty()
+                    || !manifest.language_servers.is_empty()
+            })
+            .map(|manifest| proto::ProjectExtension {
+                id: manifest.id.to_string(),
+                name: manifest.name.clone(),
+                version: manifest.version.to_string(),
+                description: manifest.description.clone(),
+                languages: store
+                    .extension_language_names(&manifest.id)
+                    .into_iter()
+                    .map(|name| name.to_string())
+                    .collect(),
+                language_servers: manifest
+                    .language_servers
+                    .keys()
+                    .map(|name| name.0.to_string())
+                    .collect(),
+            })
+            .collect(),
+    })
+}
+
+/// A read-only list of the language extensions that the host of a shared
+/// project has loaded, so that guests can tell what tooling is active.
+pub struct HostExtensionsView {
+    extensions: Vec<proto::ProjectExtension>,
+    is_loading: bool,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    _fetch_task: Task<()>,
+}
+
+impl HostExtensionsView {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let project = workspace.project().read(cx);
+        let client = project.client();
+        let project_id = project.remote_id();
+
+        let fetch_task = cx.spawn(|this, mut cx| async move {
+            let response = async {
+                let project_id = project_id.context("project is not shared")?;
+                client
+                    .request(proto::GetProjectExtensions { project_id })
+                    .await
+            }
+            .await;
+
+            this.update(&mut cx, |this, cx| {
+                match response {
+                    Ok(response) => this.extensions = response.extensions,
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                this.is_loading = false;
+                cx.notify();
+            })
+            .ok();
+        });
+
+        Self {
+            extensions: Vec::new(),
+            is_loading: true,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            _fetch_task: fetch_task,
+        }
+    }
+
+    fn render_extension(&self, extension: &proto::ProjectExtension) -> ExtensionCard {
+        ExtensionCard::new()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_end()
+                    .child(Headline::new(extension.name.clone()).size(HeadlineSize::Medium))
+                    .child(
+                        Headline::new(format!("v{}", extension.version)).size(HeadlineSize::XSmall),
+                    )
+                    .children(
+                        extension
+                            .languages
+                            .iter()
+                            .map(|language| ExtensionBadge::new(language.clone())),
+                    ),
+            )
+            .children(extension.description.as_ref().map(|description| {
+                Label::new(description.clone())
+                    .size(LabelSize::Small)
+                    .color(Color::Default)
+            }))
+            .when(!extension.language_servers.is_empty(), |this| {
+                this.child(
+                    Label::new(format!(
+                        "Language Servers: {}",
+                        extension.language_servers.join(", ")
+                    ))
+                    .size(LabelSize::Small),
+                )
+            })
+    }
+}
+
+impl Render for HostExtensionsView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("host-extensions")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_4()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(Headline::new("Host Extensions").size(HeadlineSize::XLarge))
+                    .child(
+                        Label::new(
+                            "The extensions providing language support in this shared project. \
+                             They run on the host, so they can't be changed from here.",
+                        )
+                        .color(Color::Muted),
+                    ),
+            )
+            .map(|this| {
+                if self.is_loading {
+                    this.child(Label::new("Loading extensions..."))
+                } else if let Some(error) = self.error.clone() {
+                    this.child(
+                        Label::new(format!("Failed to load the host's extensions: {error}"))
+                            .color(Color::Error),
+                    )
+                } else if self.extensions.is_empty() {
+                    this.child(Label::new(
+                        "The host has no extensions that provide language support.",
+                    ))
+                } else {
+                    this.children(
+                        self.extensions
+                            .iter()
+                            .map(|extension| self.render_extension(extension)),
+                    )
+                }
+            })
+    }
+}
+
+impl EventEmitter<ItemEvent> for HostExtensionsView {}
+
+impl FocusableView for HostExtensionsView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for HostExtensionsView {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, _: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        Label::new("Host Extensions")
+            .color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(workspace::item::ItemEvent)) {
+        f(*event)
+    }
+}
@@ -0,0 +1,213 @@
+use extension::{ExtensionApiResponse, ExtensionSettings, ExtensionStore};
+use fs::Fs;
+use gpui::{AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, MouseDownEvent};
+use settings::Settings;
+use std::{iter, sync::Arc};
+use ui::{prelude::*, CheckboxWithLabel, Selection};
+use workspace::ModalView;
+
+use crate::format_data_size;
+
+/// Asks for confirmation before installing an extension that pulls in more
+/// than its own archive, listing everything that will be downloaded, including
+/// the extensions it depends on, or that would take extensions over their disk
+/// quota.
+pub struct InstallConfirmationModal {
+    extension_id: Arc<str>,
+    extension_name: SharedString,
+    version: Arc<str>,
+    /// The names and versions of the dependencies that will be installed too.
+    dependencies: Vec<(SharedString, Arc<str>)>,
+    language_servers: Vec<SharedString>,
+    /// The disk space used by extensions and the quota on it, when installing
+    /// the extension would exceed the quota.
+    quota_warning: Option<(u64, u64)>,
+    /// Whether extensions are warmed up once they're installed, which is
+    /// saved to the settings when toggled.
+    warm_up_on_install: bool,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+}
+
+impl InstallConfirmationModal {
+    /// Whether installing the given extension will download anything besides
+    /// the extension itself, or would exceed the disk quota.
+    pub fn is_needed(extension: &ExtensionApiResponse, cx: &AppContext) -> bool {
+        let dependencies = Self::dependencies(extension, cx);
+        !extension.language_servers.is_empty()
+            || !dependencies.is_empty()
+            || Self::quota_warning(extension, &dependencies, cx).is_some()
+    }
+
+    fn dependencies(
+        extension: &ExtensionApiResponse,
+        cx: &AppContext,
+    ) -> Vec<ExtensionApiResponse> {
+        ExtensionStore::global(cx)
+            .read(cx)
+            .dependencies_to_install(extension)
+    }
+
+    fn quota_warning(
+        extension: &ExtensionApiResponse,
+        dependencies: &[ExtensionApiResponse],
+        cx: &AppContext,
+    ) -> Option<(u64, u64)> {
+        let store = ExtensionStore::global(cx).read(cx);
+        let quota = store.disk_quota(cx)?;
+        let download_size = iter::once(extension)
+            .chain(dependencies)
+            .filter_map(|extension| extension.archive_size)
+            .sum();
+        store
+            .would_exceed_disk_quota(download_size, cx)
+            .then(|| (store.total_disk_usage(), quota))
+    }
+
+    pub fn new(
+        extension: &ExtensionApiResponse,
+        fs: Arc<dyn Fs>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let dependencies = Self::dependencies(extension, cx);
+        Self {
+            warm_up_on_install: ExtensionSettings::get_global(cx).warm_up_on_install,
+            fs,
+            quota_warning: Self::quota_warning(extension, &dependencies, cx),
+            extension_id: extension.id.clone(),
+            extension_name: extension.name.clone().into(),
+            version: extension.version.clone(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|dependency| (dependency.name.into(), dependency.version))
+                .collect(),
+            language_servers: extension
+                .language_servers
+                .iter()
+                .cloned()
+                .map(SharedString::from)
+                .collect(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let extension_id = self.extension_id.clone();
+        let version = self.version.clone();
+        ExtensionStore::global(cx).update(cx, |store, cx| {
+            store.install_extension(extension_id, version, cx)
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn toggle_warm_up_on_install(&mut self, cx: &mut ViewContext<Self>) {
+        self.warm_up_on_install = !self.warm_up_on_install;
+        let warm_up_on_install = self.warm_up_on_install;
+        settings::update_settings_file::<ExtensionSettings>(self.fs.clone(), cx, move |settings| {
+            settings.warm_up_on_install = Some(warm_up_on_install)
+        });
+        cx.notify();
+    }
+}
+
+impl Render for InstallConfirmationModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("install-confirmation-modal")
+            .key_context("InstallConfirmationModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .capture_any_mouse_down(cx.listener(|this, _: &MouseDownEvent, cx| {
+                cx.focus(&this.focus_handle);
+                cx.stop_propagation();
+            }))
+            .elevation_3(cx)
+            .w(rems(28.))
+            .p_4()
+            .gap_3()
+            .child(
+                Headline::new(format!("Install {}?", self.extension_name))
+                    .size(HeadlineSize::Small),
+            )
+            .child(Label::new("The following will be installed:").color(Color::Muted))
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(self.extension_name.clone()))
+                            .child(
+                                Label::new(format!("v{}", self.version))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .children(self.dependencies.iter().map(|(name, version)| {
+                        h_flex().gap_2().child(Label::new(name.clone())).child(
+                            Label::new(format!("v{version}, required by this extension"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }))
+                    .children(self.language_servers.iter().map(|language_server| {
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(language_server.clone()))
+                            .child(
+                                Label::new("Language server, downloaded when first used")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                    })),
+            )
+            .when_some(self.quota_warning, |this, (usage, quota)| {
+                this.child(
+                    Label::new(format!(
+                        "Extensions already use {} of the {} disk quota.",
+                        format_data_size(usage),
+                        format_data_size(quota)
+                    ))
+                    .color(Color::Warning),
+                )
+            })
+            .child(CheckboxWithLabel::new(
+                "warm-up-on-install",
+                Label::new("Load languages and fetch language servers on install"),
+                if self.warm_up_on_install {
+                    Selection::Selected
+                } else {
+                    Selection::Unselected
+                },
+                cx.listener(|this, _, cx| this.toggle_warm_up_on_install(cx)),
+            ))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel-install", "Cancel")
+                            .on_click(cx.listener(|this, _, cx| this.cancel(&menu::Cancel, cx))),
+                    )
+                    .child(
+                        Button::new("confirm-install", "Install")
+                            .style(ButtonStyle::Filled)
+                            .on_click(cx.listener(|this, _, cx| this.confirm(&menu::Confirm, cx))),
+                    ),
+            )
+    }
+}
+
+impl EventEmitter<DismissEvent> for InstallConfirmationModal {}
+impl ModalView for InstallConfirmationModal {}
+
+impl FocusableView for InstallConfirmationModal {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
@@ -0,0 +1,163 @@
+use editor::Editor;
+use extension::ExtensionStore;
+use gpui::{AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, View};
+use ui::prelude::*;
+use workspace::ModalView;
+
+/// Where [`InstallFromRepositoryModal`] installs an extension from.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RepositoryInstallKind {
+    /// Builds the extension from the repository's default branch.
+    Git,
+    /// Downloads the packaged extension from the repository's latest release.
+    GithubRelease,
+}
+
+/// Asks for a repository, and installs the extension that it contains or
+/// publishes.
+pub struct InstallFromRepositoryModal {
+    kind: RepositoryInstallKind,
+    url_editor: View<Editor>,
+    is_installing: bool,
+    /// Why the most recent attempt to install the extension failed.
+    error: Option<SharedString>,
+}
+
+impl InstallFromRepositoryModal {
+    pub fn new(kind: RepositoryInstallKind, cx: &mut ViewContext<Self>) -> Self {
+        let url_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text(
+                match kind {
+                    RepositoryInstallKind::Git => "https://github.com/owner/extension",
+                    RepositoryInstallKind::GithubRelease => "owner/extension",
+                },
+                cx,
+            );
+            editor
+        });
+        Self {
+            kind,
+            url_editor,
+            is_installing: false,
+            error: None,
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let repository_url = self.url_editor.read(cx).text(cx).trim().to_string();
+        if repository_url.is_empty() || self.is_installing {
+            return;
+        }
+
+        let kind = self.kind;
+        let install = ExtensionStore::global(cx).update(cx, |store, cx| match kind {
+            RepositoryInstallKind::Git => store.install_from_git(repository_url, cx),
+            RepositoryInstallKind::GithubRelease => {
+                store.install_from_github_release(repository_url, cx)
+            }
+        });
+        self.is_installing = true;
+        self.error = None;
+        cx.notify();
+
+        cx.spawn(|this, mut cx| async move {
+            let result = install.await;
+            this.update(&mut cx, |this, cx| {
+                this.is_installing = false;
+                match result {
+                    // The install is reported by the usual toast.
+                    Ok(()) => cx.emit(DismissEvent),
+                    Err(error) => {
+                        let error = error.to_string();
+                        let message = error.lines().next().unwrap_or_default();
+                        this.error =
+                            Some(format!("Couldn't install the extension: {message}").into());
+                        cx.notify();
+                    }
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for InstallFromRepositoryModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let (headline, description) = match self.kind {
+            RepositoryInstallKind::Git => (
+                "Install Extension from Git",
+                "The repository is built like a dev extension, and checked for new commits when extensions are updated.",
+            ),
+            RepositoryInstallKind::GithubRelease => (
+                "Install Extension from GitHub Release",
+                "The packaged extension is downloaded from the repository's latest release, and newer releases are installed when extensions are updated.",
+            ),
+        };
+
+        v_flex()
+            .id("install-from-repository-modal")
+            .key_context("InstallFromRepositoryModal")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(28.))
+            .p_4()
+            .gap_3()
+            .child(Headline::new(headline).size(HeadlineSize::Small))
+            .child(
+                Label::new(description)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .child(self.url_editor.clone()),
+            )
+            .children(
+                self.error
+                    .clone()
+                    .map(|error| Label::new(error).size(LabelSize::Small).color(Color::Error)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel-install-from-repository", "Cancel")
+                            .on_click(cx.listener(|this, _, cx| this.cancel(&menu::Cancel, cx))),
+                    )
+                    .child(
+                        Button::new(
+                            "install-from-repository",
+                            if self.is_installing {
+                                "Installing..."
+                            } else {
+                                "Install"
+                            },
+                        )
+                        .style(ButtonStyle::Filled)
+                        .disabled(self.is_installing)
+                        .on_click(cx.listener(|this, _, cx| this.confirm(&menu::Confirm, cx))),
+                    ),
+            )
+    }
+}
+
+impl EventEmitter<DismissEvent> for InstallFromRepositoryModal {}
+impl ModalView for InstallFromRepositoryModal {}
+
+impl FocusableView for InstallFromRepositoryModal {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.url_editor.focus_handle(cx)
+    }
+}
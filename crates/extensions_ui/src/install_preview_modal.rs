@@ -0,0 +1,192 @@
+use extension::{ExtensionStore, InstallPreview};
+use gpui::{AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, MouseDownEvent};
+use std::sync::Arc;
+use ui::prelude::*;
+use workspace::ModalView;
+
+use crate::format_data_size;
+
+/// Shows what installing an extension would do, without installing it.
+pub struct InstallPreviewModal {
+    extension_id: Arc<str>,
+    extension_name: SharedString,
+    version: Arc<str>,
+    /// The preview, once the extension's archive has been downloaded and
+    /// inspected.
+    preview: Option<Result<InstallPreview, SharedString>>,
+    focus_handle: FocusHandle,
+}
+
+impl InstallPreviewModal {
+    pub fn new(
+        extension_id: Arc<str>,
+        extension_name: SharedString,
+        version: Arc<str>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let preview = ExtensionStore::global(cx).update(cx, |store, cx| {
+            store.preview_install(extension_id.clone(), version.clone(), cx)
+        });
+        cx.spawn(|this, mut cx| async move {
+            let preview = preview.await;
+            this.update(&mut cx, |this, cx| {
+                this.preview = Some(preview.map_err(|error| error.to_string().into()));
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+
+        Self {
+            extension_id,
+            extension_name,
+            version,
+            preview: None,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        if !matches!(self.preview, Some(Ok(_))) {
+            return;
+        }
+        let extension_id = self.extension_id.clone();
+        let version = self.version.clone();
+        ExtensionStore::global(cx).update(cx, |store, cx| {
+            store.install_extension(extension_id, version, cx)
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_preview(&self, preview: &InstallPreview) -> impl IntoElement {
+        v_flex()
+            .gap_3()
+            .child(
+                Label::new(format!(
+                    "Downloads {} and writes {} files totaling {}.",
+                    format_data_size(preview.download_size),
+                    preview.files.len(),
+                    format_data_size(preview.total_size())
+                ))
+                .size(LabelSize::Small),
+            )
+            .children(render_preview_section(
+                "Languages",
+                preview
+                    .languages
+                    .iter()
+                    .map(|language| language.to_string()),
+            ))
+            .children(render_preview_section(
+                "Themes",
+                preview.themes.iter().map(|theme| theme.to_string()),
+            ))
+            .children(render_preview_section(
+                "Grammars",
+                preview.grammars.iter().map(|grammar| grammar.to_string()),
+            ))
+            .children(render_preview_section(
+                "Language Servers (download their own binaries when first used)",
+                preview
+                    .language_servers
+                    .iter()
+                    .map(|language_server| language_server.0.to_string()),
+            ))
+            .children(render_preview_section(
+                "Files",
+                preview.files.iter().map(|(path, size)| {
+                    format!("{} ({})", path.display(), format_data_size(*size))
+                }),
+            ))
+    }
+}
+
+impl Render for InstallPreviewModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let can_install = matches!(self.preview, Some(Ok(_)));
+
+        v_flex()
+            .id("install-preview-modal")
+            .key_context("InstallPreviewModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .capture_any_mouse_down(cx.listener(|this, _: &MouseDownEvent, cx| {
+                cx.focus(&this.focus_handle);
+                cx.stop_propagation();
+            }))
+            .elevation_3(cx)
+            .w(rems(32.))
+            .max_h(rems(36.))
+            .p_4()
+            .gap_3()
+            .child(
+                Headline::new(format!("Install {} v{}", self.extension_name, self.version))
+                    .size(HeadlineSize::Small),
+            )
+            .child(
+                div()
+                    .id("install-preview")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .map(|this| match &self.preview {
+                        None => {
+                            this.child(Label::new("Downloading the extension…").color(Color::Muted))
+                        }
+                        Some(Err(error)) => this.child(
+                            Label::new(format!("Couldn't preview the installation: {error}"))
+                                .color(Color::Error),
+                        ),
+                        Some(Ok(preview)) => this.child(self.render_preview(preview)),
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("close-install-preview", "Close")
+                            .on_click(cx.listener(|this, _, cx| this.cancel(&menu::Cancel, cx))),
+                    )
+                    .child(
+                        Button::new("install-from-preview", "Install")
+                            .style(ButtonStyle::Filled)
+                            .disabled(!can_install)
+                            .on_click(cx.listener(|this, _, cx| this.confirm(&menu::Confirm, cx))),
+                    ),
+            )
+    }
+}
+
+/// Lists one kind of thing that an installation would add.
+fn render_preview_section(
+    title: &'static str,
+    items: impl Iterator<Item = String>,
+) -> Option<impl IntoElement> {
+    let items = items.collect::<Vec<_>>();
+    if items.is_empty() {
+        return None;
+    }
+    Some(
+        v_flex()
+            .gap_1()
+            .child(Headline::new(title).size(HeadlineSize::XSmall))
+            .children(
+                items
+                    .into_iter()
+                    .map(|item| Label::new(item).size(LabelSize::Small).color(Color::Muted)),
+            ),
+    )
+}
+
+impl EventEmitter<DismissEvent> for InstallPreviewModal {}
+impl ModalView for InstallPreviewModal {}
+
+impl FocusableView for InstallPreviewModal {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
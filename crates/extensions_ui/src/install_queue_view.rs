@@ -0,0 +1,185 @@
+use extension::{
+    ExtensionOperation, ExtensionOperationEntry, ExtensionOperationState, ExtensionStore,
+};
+use gpui::{AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Subscription};
+use ui::{prelude::*, Tooltip};
+
+use crate::format_data_size;
+
+/// Lists the extensions that are being installed, upgraded, or removed, along
+/// with the installs and upgrades that have failed.
+pub struct InstallQueueView {
+    focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl InstallQueueView {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let store = ExtensionStore::global(cx);
+        Self {
+            focus_handle: cx.focus_handle(),
+            _subscription: cx.observe(&store, |_, _, cx| cx.notify()),
+        }
+    }
+
+    fn render_entry(
+        &self,
+        entry: ExtensionOperationEntry,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let extension_name = ExtensionStore::global(cx)
+            .read(cx)
+            .remote_extension(&entry.extension_id)
+            .map_or_else(
+                || entry.extension_id.to_string(),
+                |extension| extension.name.clone(),
+            );
+        let extension_id = entry.extension_id.clone();
+        let is_failed = matches!(entry.state, ExtensionOperationState::Failed(_));
+        let can_cancel = entry.operation != ExtensionOperation::Remove
+            && matches!(
+                entry.state,
+                ExtensionOperationState::Queued(_)
+                    | ExtensionOperationState::Pending
+                    | ExtensionOperationState::Downloading(_)
+            );
+
+        let (description, color) = match &entry.state {
+            ExtensionOperationState::Queued(0) => {
+                ("Queued, downloading next".to_string(), Color::Muted)
+            }
+            ExtensionOperationState::Queued(ahead) => {
+                (format!("Queued, {ahead} ahead"), Color::Muted)
+            }
+            ExtensionOperationState::Pending => (
+                match entry.operation {
+                    ExtensionOperation::Install | ExtensionOperation::Upgrade => {
+                        "Waiting to download".to_string()
+                    }
+                    ExtensionOperation::Remove => "Removing".to_string(),
+                },
+                Color::Muted,
+            ),
+            ExtensionOperationState::Downloading(progress) => (
+                match progress.total_bytes {
+                    Some(total_bytes) => format!(
+                        "Downloading {} / {}",
+                        format_data_size(progress.downloaded_bytes),
+                        format_data_size(total_bytes)
+                    ),
+                    None => format!(
+                        "Downloading {}",
+                        format_data_size(progress.downloaded_bytes)
+                    ),
+                },
+                Color::Muted,
+            ),
+            ExtensionOperationState::Loading => ("Loading".to_string(), Color::Muted),
+            ExtensionOperationState::Failed(error) => (
+                match entry.operation {
+                    ExtensionOperation::Install => format!("Install failed: {error}"),
+                    ExtensionOperation::Upgrade => format!("Upgrade failed: {error}"),
+                    ExtensionOperation::Remove => format!("Removal failed: {error}"),
+                },
+                Color::Error,
+            ),
+        };
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .justify_between()
+            .child(
+                v_flex()
+                    .overflow_x_hidden()
+                    .child(Label::new(extension_name))
+                    .child(Label::new(description).size(LabelSize::Small).color(color)),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .when(can_cancel, |this| {
+                        let extension_id = extension_id.clone();
+                        this.child(
+                            IconButton::new(
+                                SharedString::from(format!("cancel-operation-{extension_id}")),
+                                IconName::Close,
+                            )
+                            .icon_size(IconSize::Small)
+                            .on_click(move |_, cx| {
+                                ExtensionStore::global(cx)
+                                    .update(cx, |store, _| store.cancel_download(&extension_id));
+                            })
+                            .tooltip(|cx| Tooltip::text("Cancel", cx)),
+                        )
+                    })
+                    .when(is_failed, |this| {
+                        let retry_extension_id = extension_id.clone();
+                        let dismiss_extension_id = extension_id.clone();
+                        this.child(
+                            IconButton::new(
+                                SharedString::from(format!("retry-operation-{extension_id}")),
+                                IconName::ArrowCircle,
+                            )
+                            .icon_size(IconSize::Small)
+                            .on_click(move |_, cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.retry_operation(retry_extension_id.clone(), cx)
+                                });
+                            })
+                            .tooltip(|cx| Tooltip::text("Retry", cx)),
+                        )
+                        .child(
+                            IconButton::new(
+                                SharedString::from(format!("dismiss-operation-{extension_id}")),
+                                IconName::Close,
+                            )
+                            .icon_size(IconSize::Small)
+                            .on_click(move |_, cx| {
+                                ExtensionStore::global(cx).update(cx, |store, cx| {
+                                    store.dismiss_failed_operation(&dismiss_extension_id, cx)
+                                });
+                            })
+                            .tooltip(|cx| Tooltip::text("Dismiss", cx)),
+                        )
+                    }),
+            )
+    }
+}
+
+impl Render for InstallQueueView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let operations = ExtensionStore::global(cx).read(cx).operations();
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .elevation_2(cx)
+            .w(rems(24.))
+            .p_2()
+            .gap_2()
+            .child(Label::new("Install Queue").color(Color::Muted))
+            .map(|this| {
+                if operations.is_empty() {
+                    this.child(
+                        Label::new("No extensions are being installed.")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                } else {
+                    this.children(
+                        operations
+                            .into_iter()
+                            .map(|entry| self.render_entry(entry, cx)),
+                    )
+                }
+            })
+    }
+}
+
+impl EventEmitter<DismissEvent> for InstallQueueView {}
+
+impl FocusableView for InstallQueueView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
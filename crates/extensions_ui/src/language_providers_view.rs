@@ -0,0 +1,281 @@
+use editor::Editor;
+use extension::ExtensionStore;
+use gpui::{
+    actions, AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, WeakView,
+    WindowContext,
+};
+use language::language_settings::{language_settings, Formatter};
+use std::sync::Arc;
+use ui::prelude::*;
+use workspace::{
+    item::{Item, ItemEvent},
+    Toast, Workspace,
+};
+
+actions!(extensions, [WhatsProvidingThis]);
+
+const NO_LANGUAGE_TOAST_ID: usize = 0x5c31b9e2;
+
+pub(crate) fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(open_language_providers);
+    })
+    .detach();
+}
+
+/// Where a feature of a language comes from.
+#[derive(Clone)]
+enum Provider {
+    BuiltIn,
+    Extension { id: Arc<str>, name: SharedString },
+    ExternalCommand(SharedString),
+}
+
+/// A feature of the active buffer's language, such as its grammar or one of
+/// its language servers, along with what provides it.
+struct ProvidedFeature {
+    feature: &'static str,
+    name: SharedString,
+    provider: Provider,
+}
+
+fn open_language_providers(
+    workspace: &mut Workspace,
+    _: &WhatsProvidingThis,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let Some(view) = LanguageProvidersView::for_active_editor(workspace, cx) else {
+        workspace.show_toast(
+            Toast::new(
+                NO_LANGUAGE_TOAST_ID,
+                "The active item isn't an editor for a file with a language.",
+            ),
+            cx,
+        );
+        return;
+    };
+    let view = cx.new_view(|_| view);
+    workspace.add_item_to_active_pane(Box::new(view), cx);
+}
+
+/// Lists the extensions that provide the grammar, language servers, and
+/// formatter for the language of the active editor's buffer.
+pub struct LanguageProvidersView {
+    workspace: WeakView<Workspace>,
+    language_name: Arc<str>,
+    features: Vec<ProvidedFeature>,
+    focus_handle: FocusHandle,
+}
+
+impl LanguageProvidersView {
+    fn for_active_editor(workspace: &Workspace, cx: &mut ViewContext<Workspace>) -> Option<Self> {
+        let editor = workspace.active_item_as::<Editor>(cx)?;
+        let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+        let buffer = buffer.read(cx);
+        let language = buffer.language()?.clone();
+        let language_name = language.name();
+
+        let store = ExtensionStore::global(cx).read(cx);
+        let providers = store.language_providers(&language_name);
+        let extension_provider = |extension_id: &Arc<str>| Provider::Extension {
+            id: extension_id.clone(),
+            name: store
+                .installed_extension(extension_id)
+                .map_or_else(
+                    || extension_id.to_string(),
+                    |manifest| manifest.name.clone(),
+                )
+                .into(),
+        };
+
+        let mut features = Vec::new();
+        features.push(ProvidedFeature {
+            feature: "Language",
+            name: language_name.to_string().into(),
+            provider: providers
+                .language
+                .as_ref()
+                .map_or(Provider::BuiltIn, extension_provider),
+        });
+        if providers.grammar.is_some() || language.grammar().is_some() {
+            features.push(ProvidedFeature {
+                feature: "Grammar",
+                name: language_name.to_string().into(),
+                provider: providers
+                    .grammar
+                    .as_ref()
+                    .map_or(Provider::BuiltIn, extension_provider),
+            });
+        }
+
+        let language_servers = workspace
+            .project()
+            .read(cx)
+            .languages()
+            .lsp_adapters(&language)
+            .into_iter()
+            .map(|adapter| {
+                let provider = providers
+                    .language_servers
+                    .iter()
+                    .find(|(name, _)| *name == adapter.name)
+                    .map_or(Provider::BuiltIn, |(_, extension_id)| {
+                        extension_provider(extension_id)
+                    });
+                (SharedString::from(adapter.name.0.to_string()), provider)
+            })
+            .collect::<Vec<_>>();
+
+        let settings = language_settings(Some(&language), buffer.file(), cx);
+        let formatter = match &settings.formatter {
+            Formatter::Prettier => Some(("Prettier".into(), Provider::BuiltIn)),
+            Formatter::External { command, .. } => Some((
+                command.to_string().into(),
+                Provider::ExternalCommand(command.to_string().into()),
+            )),
+            Formatter::LanguageServer | Formatter::Auto => {
+                language_servers.first().map(|(name, provider)| {
+                    let name = if matches!(settings.formatter, Formatter::Auto) {
+                        format!("Prettier or {name}").into()
+                    } else {
+                        name.clone()
+                    };
+                    (name, provider.clone())
+                })
+            }
+        };
+
+        features.extend(
+            language_servers
+                .into_iter()
+                .map(|(name, provider)| ProvidedFeature {
+                    feature: "Language Server",
+                    name,
+                    provider,
+                }),
+        );
+        features.extend(formatter.map(|(name, provider)| ProvidedFeature {
+            feature: "Formatter",
+            name,
+            provider,
+        }));
+
+        Some(Self {
+            workspace: cx.view().downgrade(),
+            language_name,
+            features,
+            focus_handle: cx.focus_handle(),
+        })
+    }
+
+    fn render_feature(&self, ix: usize, feature: &ProvidedFeature) -> impl IntoElement {
+        h_flex()
+            .gap_4()
+            .justify_between()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Label::new(feature.feature)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(Label::new(feature.name.clone())),
+            )
+            .child(match &feature.provider {
+                Provider::BuiltIn => Label::new("Built In")
+                    .color(Color::Muted)
+                    .into_any_element(),
+                Provider::ExternalCommand(command) => {
+                    Label::new(format!("External Command: {command}"))
+                        .color(Color::Muted)
+                        .into_any_element()
+                }
+                Provider::Extension { id, name } => {
+                    let workspace = self.workspace.clone();
+                    let extension_id = id.clone();
+                    Button::new(("view-extension", ix), name.clone())
+                        .on_click(move |_, cx| {
+                            workspace
+                                .update(cx, |workspace, cx| {
+                                    crate::open_extension(workspace, &extension_id, cx)
+                                })
+                                .ok();
+                        })
+                        .into_any_element()
+                }
+            })
+    }
+}
+
+impl Render for LanguageProvidersView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("language-providers")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_4()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(
+                        Headline::new(format!("What's Providing {}", self.language_name))
+                            .size(HeadlineSize::XLarge),
+                    )
+                    .child(
+                        Label::new("The extensions that provide support for this language.")
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(
+                v_flex().gap_2().children(
+                    self.features
+                        .iter()
+                        .enumerate()
+                        .map(|(ix, feature)| self.render_feature(ix, feature)),
+                ),
+            )
+            .child(
+                Label::new("Tasks and snippets can't be provided by extensions.")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+    }
+}
+
+impl EventEmitter<ItemEvent> for LanguageProvidersView {}
+
+impl FocusableView for LanguageProvidersView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for LanguageProvidersView {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, _: Option<usize>, selected: bool, _: &WindowContext) -> AnyElement {
+        Label::new(format!("{} Providers", self.language_name))
+            .color(if selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(workspace::item::ItemEvent)) {
+        f(*event)
+    }
+}
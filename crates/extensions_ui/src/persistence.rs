@@ -0,0 +1,53 @@
+use db::{define_connection, query, sqlez_macros::sql};
+use workspace::{ItemId, WorkspaceDb, WorkspaceId};
+
+define_connection! {
+    pub static ref EXTENSIONS_PAGE_DB: ExtensionsPageDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE extensions_pages (
+                workspace_id INTEGER,
+                item_id INTEGER,
+                filter TEXT NOT NULL,
+                search_query TEXT,
+                collection TEXT,
+                PRIMARY KEY(workspace_id, item_id),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        )];
+}
+
+impl ExtensionsPageDb {
+    query! {
+       pub async fn update_workspace_id(
+            new_id: WorkspaceId,
+            old_id: WorkspaceId,
+            item_id: ItemId
+        ) -> Result<()> {
+            UPDATE extensions_pages
+            SET workspace_id = ?
+            WHERE workspace_id = ? AND item_id = ?
+        }
+    }
+
+    query! {
+        pub async fn save_page_state(
+            item_id: ItemId,
+            workspace_id: WorkspaceId,
+            filter: String,
+            search_query: Option<String>,
+            collection: Option<String>
+        ) -> Result<()> {
+            INSERT OR REPLACE INTO extensions_pages(item_id, workspace_id, filter, search_query, collection)
+            VALUES (?, ?, ?, ?, ?)
+        }
+    }
+
+    query! {
+        pub fn get_page_state(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<(String, Option<String>, Option<String>)>> {
+            SELECT filter, search_query, collection
+            FROM extensions_pages
+            WHERE item_id = ? AND workspace_id = ?
+        }
+    }
+}
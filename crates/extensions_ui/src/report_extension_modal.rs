@@ -0,0 +1,186 @@
+use editor::Editor;
+use extension::{ExtensionReportReason, ExtensionStore};
+use gpui::{
+    AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, MouseDownEvent, View,
+    WeakView,
+};
+use std::sync::Arc;
+use ui::{prelude::*, ToggleButton};
+use workspace::{ModalView, Toast, Workspace};
+
+use crate::extension_toast_id;
+
+/// A form for reporting an extension in the registry to its operators.
+pub struct ReportExtensionModal {
+    workspace: WeakView<Workspace>,
+    extension_id: Arc<str>,
+    extension_name: SharedString,
+    reason: Option<ExtensionReportReason>,
+    details_editor: View<Editor>,
+    is_submitting: bool,
+    /// Why the most recent attempt to submit the report failed.
+    error: Option<SharedString>,
+}
+
+impl ReportExtensionModal {
+    pub fn new(
+        workspace: WeakView<Workspace>,
+        extension_id: Arc<str>,
+        extension_name: SharedString,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let details_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Describe the problem (optional)", cx);
+            editor
+        });
+        Self {
+            workspace,
+            extension_id,
+            extension_name,
+            reason: None,
+            details_editor,
+            is_submitting: false,
+            error: None,
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let Some(reason) = self.reason else {
+            return;
+        };
+        if self.is_submitting {
+            return;
+        }
+
+        let details = self.details_editor.read(cx).text(cx);
+        let report = ExtensionStore::global(cx).update(cx, |store, cx| {
+            store.report_extension(&self.extension_id, reason, details, cx)
+        });
+        self.is_submitting = true;
+        self.error = None;
+        cx.notify();
+
+        cx.spawn(|this, mut cx| async move {
+            let result = report.await;
+            this.update(&mut cx, |this, cx| {
+                this.is_submitting = false;
+                match result {
+                    Ok(()) => {
+                        let toast = Toast::new(
+                            extension_toast_id(&this.extension_id),
+                            format!("Thanks for reporting {}.", this.extension_name),
+                        );
+                        this.workspace
+                            .update(cx, |workspace, cx| workspace.show_toast(toast, cx))
+                            .ok();
+                        cx.emit(DismissEvent);
+                    }
+                    Err(error) => {
+                        this.error = Some(format!("Couldn't send the report: {error}").into());
+                        cx.notify();
+                    }
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for ReportExtensionModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let can_submit = self.reason.is_some() && !self.is_submitting;
+
+        v_flex()
+            .id("report-extension-modal")
+            .key_context("ReportExtensionModal")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .capture_any_mouse_down(cx.listener(|this, _: &MouseDownEvent, cx| {
+                cx.focus_view(&this.details_editor);
+            }))
+            .elevation_3(cx)
+            .w(rems(28.))
+            .p_4()
+            .gap_3()
+            .child(
+                Headline::new(format!("Report {}", self.extension_name)).size(HeadlineSize::Small),
+            )
+            .child(
+                Label::new("Why are you reporting this extension?")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(ExtensionReportReason::ALL.into_iter().enumerate().map(
+                        |(ix, reason)| {
+                            ToggleButton::new(("report-reason", ix), reason.label())
+                                .style(ButtonStyle::Filled)
+                                .selected(self.reason == Some(reason))
+                                .on_click(cx.listener(move |this, _, cx| {
+                                    this.reason = Some(reason);
+                                    cx.notify();
+                                }))
+                                .map(|button| match ix {
+                                    0 => button.first(),
+                                    ix if ix == ExtensionReportReason::ALL.len() - 1 => {
+                                        button.last()
+                                    }
+                                    _ => button.middle(),
+                                })
+                        },
+                    )),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .child(self.details_editor.clone()),
+            )
+            .children(
+                self.error
+                    .clone()
+                    .map(|error| Label::new(error).size(LabelSize::Small).color(Color::Error)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel-report", "Cancel")
+                            .on_click(cx.listener(|this, _, cx| this.cancel(&menu::Cancel, cx))),
+                    )
+                    .child(
+                        Button::new(
+                            "submit-report",
+                            if self.is_submitting {
+                                "Sending..."
+                            } else {
+                                "Send Report"
+                            },
+                        )
+                        .style(ButtonStyle::Filled)
+                        .disabled(!can_submit)
+                        .on_click(cx.listener(|this, _, cx| this.confirm(&menu::Confirm, cx))),
+                    ),
+            )
+    }
+}
+
+impl EventEmitter<DismissEvent> for ReportExtensionModal {}
+impl ModalView for ReportExtensionModal {}
+
+impl FocusableView for ReportExtensionModal {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.details_editor.focus_handle(cx)
+    }
+}
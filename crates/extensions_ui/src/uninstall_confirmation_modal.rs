@@ -0,0 +1,147 @@
+use client::telemetry::Telemetry;
+use extension::ExtensionStore;
+use gpui::{AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, MouseDownEvent};
+use std::sync::Arc;
+use ui::{prelude::*, CheckboxWithLabel, Selection};
+use workspace::ModalView;
+
+use crate::{format_data_size, report_extension_event};
+
+/// Asks for confirmation before uninstalling an extension, optionally also
+/// removing its settings and the data it has stored.
+pub struct UninstallConfirmationModal {
+    extension_id: Arc<str>,
+    extension_name: SharedString,
+    /// The size of the data the extension has stored, if any.
+    data_size: Option<u64>,
+    /// The installed extensions that depend on this one.
+    dependents: Vec<Arc<str>>,
+    purge_data: bool,
+    telemetry: Arc<Telemetry>,
+    focus_handle: FocusHandle,
+}
+
+impl UninstallConfirmationModal {
+    pub fn new(
+        extension_id: Arc<str>,
+        extension_name: SharedString,
+        data_size: Option<u64>,
+        telemetry: Arc<Telemetry>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let dependents = ExtensionStore::global(cx)
+            .read(cx)
+            .dependents(&extension_id);
+        Self {
+            extension_id,
+            extension_name,
+            data_size,
+            dependents,
+            purge_data: false,
+            telemetry,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        report_extension_event(
+            &self.telemetry,
+            "extensions: uninstall extension".to_string(),
+            cx,
+        );
+        let extension_id = self.extension_id.clone();
+        let purge_data = self.purge_data;
+        ExtensionStore::global(cx).update(cx, |store, cx| {
+            // The extension's data is purged first, while its manifest still
+            // records the language servers whose settings should be removed.
+            if purge_data {
+                store
+                    .purge_extension_data(&extension_id, cx)
+                    .detach_and_log_err(cx);
+            }
+            store.uninstall_extension(extension_id, cx);
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for UninstallConfirmationModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let purge_label = match self.data_size {
+            Some(size) => format!(
+                "Also remove this extension's settings and its {} of cached data",
+                format_data_size(size)
+            ),
+            None => "Also remove this extension's settings and cached data".to_string(),
+        };
+
+        v_flex()
+            .id("uninstall-confirmation-modal")
+            .key_context("UninstallConfirmationModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .capture_any_mouse_down(cx.listener(|this, _: &MouseDownEvent, cx| {
+                cx.focus(&this.focus_handle);
+                cx.stop_propagation();
+            }))
+            .elevation_3(cx)
+            .w(rems(28.))
+            .p_4()
+            .gap_3()
+            .child(
+                Headline::new(format!("Uninstall {}?", self.extension_name))
+                    .size(HeadlineSize::Small),
+            )
+            .when(!self.dependents.is_empty(), |this| {
+                this.child(
+                    Label::new(format!(
+                        "These extensions depend on it and may stop working: {}.",
+                        self.dependents.join(", ")
+                    ))
+                    .size(LabelSize::Small)
+                    .color(Color::Warning),
+                )
+            })
+            .child(CheckboxWithLabel::new(
+                "purge-extension-data",
+                Label::new(purge_label).size(LabelSize::Small),
+                if self.purge_data {
+                    Selection::Selected
+                } else {
+                    Selection::Unselected
+                },
+                cx.listener(|this, selection: &Selection, cx| {
+                    this.purge_data = *selection == Selection::Selected;
+                    cx.notify();
+                }),
+            ))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel-uninstall", "Cancel")
+                            .on_click(cx.listener(|this, _, cx| this.cancel(&menu::Cancel, cx))),
+                    )
+                    .child(
+                        Button::new("confirm-uninstall", "Uninstall")
+                            .style(ButtonStyle::Filled)
+                            .on_click(cx.listener(|this, _, cx| this.confirm(&menu::Confirm, cx))),
+                    ),
+            )
+    }
+}
+
+impl EventEmitter<DismissEvent> for UninstallConfirmationModal {}
+impl ModalView for UninstallConfirmationModal {}
+
+impl FocusableView for UninstallConfirmationModal {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
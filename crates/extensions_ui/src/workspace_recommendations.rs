@@ -0,0 +1,121 @@
+use collections::HashSet;
+use extension::ExtensionStore;
+use fs::Fs;
+use gpui::{AppContext, Model, Task};
+use project::Project;
+use serde::Deserialize;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+use util::{paths::LOCAL_EXTENSIONS_RELATIVE_PATH, ResultExt as _};
+use workspace::{Toast, Workspace};
+
+const RECOMMENDATIONS_TOAST_ID: usize = 0x5d13a7e4;
+
+/// The contents of a project's `.zed/extensions.json`.
+#[derive(Default, Deserialize)]
+struct WorkspaceExtensions {
+    /// The IDs of the extensions that people working on the project should
+    /// install.
+    #[serde(default)]
+    recommendations: Vec<Arc<str>>,
+}
+
+/// Offers to install the extensions that a workspace's project recommends,
+/// whenever a worktree is added to it.
+pub(crate) fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, cx| {
+        // The recommendations that have been offered in this workspace, so
+        // that adding another worktree doesn't offer them again.
+        let offered = Rc::new(RefCell::new(HashSet::<Arc<str>>::default()));
+        cx.subscribe(workspace.project(), move |workspace, project, event, cx| {
+            if !matches!(event, project::Event::WorktreeAdded) {
+                return;
+            }
+            let recommendations =
+                load_recommendations(&project, workspace.app_state().fs.clone(), cx);
+            let offered = offered.clone();
+            cx.spawn(|workspace, mut cx| async move {
+                let recommendations = recommendations.await;
+                workspace.update(&mut cx, |workspace, cx| {
+                    let store = ExtensionStore::global(cx).read(cx);
+                    let missing = recommendations
+                        .into_iter()
+                        .filter(|extension_id| {
+                            store.installed_extension(extension_id).is_none()
+                                && offered.borrow_mut().insert(extension_id.clone())
+                        })
+                        .collect::<Vec<_>>();
+                    if missing.is_empty() {
+                        return;
+                    }
+
+                    let names = missing
+                        .iter()
+                        .map(|extension_id| {
+                            store
+                                .remote_extension(extension_id)
+                                .map(|extension| extension.name.clone())
+                                .unwrap_or_else(|| extension_id.to_string())
+                        })
+                        .collect::<Vec<_>>();
+                    let message = format!(
+                        "This workspace recommends extensions that aren't installed: {}.",
+                        names.join(", ")
+                    );
+                    workspace.show_toast(
+                        Toast::new(RECOMMENDATIONS_TOAST_ID, message).on_click(
+                            "Install",
+                            move |cx| {
+                                ExtensionStore::global(cx)
+                                    .update(cx, |store, cx| {
+                                        store.install_latest_extensions(missing.clone(), cx)
+                                    })
+                                    .detach()
+                            },
+                        ),
+                        cx,
+                    );
+                })
+            })
+            .detach_and_log_err(cx);
+        })
+        .detach();
+    })
+    .detach();
+}
+
+/// Loads the IDs of the extensions recommended by the `.zed/extensions.json`
+/// files in the roots of the project's visible local worktrees.
+pub(crate) fn load_recommendations(
+    project: &Model<Project>,
+    fs: Arc<dyn Fs>,
+    cx: &AppContext,
+) -> Task<Vec<Arc<str>>> {
+    let paths = project
+        .read(cx)
+        .visible_worktrees(cx)
+        .filter_map(|worktree| {
+            let worktree = worktree.read(cx);
+            worktree
+                .is_local()
+                .then(|| worktree.abs_path().join(*LOCAL_EXTENSIONS_RELATIVE_PATH))
+        })
+        .collect::<Vec<_>>();
+    cx.background_executor().spawn(async move {
+        let mut recommendations = Vec::new();
+        for path in paths {
+            let Ok(content) = fs.load(&path).await else {
+                continue;
+            };
+            let Some(extensions) = serde_json::from_str::<WorkspaceExtensions>(&content).log_err()
+            else {
+                continue;
+            };
+            for extension_id in extensions.recommendations {
+                if !recommendations.contains(&extension_id) {
+                    recommendations.push(extension_id);
+                }
+            }
+        }
+        recommendations
+    })
+}
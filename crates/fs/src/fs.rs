@@ -109,6 +109,8 @@ pub struct Metadata {
     pub mtime: SystemTime,
     pub is_symlink: bool,
     pub is_dir: bool,
+    /// The size of the file, in bytes.
+    pub len: u64,
 }
 
 pub struct RealFs;
@@ -300,6 +302,7 @@ impl Fs for RealFs {
             mtime: metadata.modified().unwrap(),
             is_symlink,
             is_dir: metadata.file_type().is_dir(),
+            len: metadata.len(),
         }))
     }
 
@@ -1213,7 +1216,12 @@ impl Fs for FakeFs {
                 }
             }
             btree_map::Entry::Occupied(e) => {
-                e.get().lock().file_content(&path)?;
+                {
+                    let entry = e.get().lock();
+                    if !entry.is_symlink() {
+                        entry.file_content(&path)?;
+                    }
+                }
                 e.remove();
             }
         }
@@ -1294,17 +1302,23 @@ impl Fs for FakeFs {
 
             let entry = entry.lock();
             Ok(Some(match &*entry {
-                FakeFsEntry::File { inode, mtime, .. } => Metadata {
+                FakeFsEntry::File {
+                    inode,
+                    mtime,
+                    content,
+                } => Metadata {
                     inode: *inode,
                     mtime: *mtime,
                     is_dir: false,
                     is_symlink,
+                    len: content.len() as u64,
                 },
                 FakeFsEntry::Dir { inode, mtime, .. } => Metadata {
                     inode: *inode,
                     mtime: *mtime,
                     is_dir: true,
                     is_symlink,
+                    len: 0,
                 },
                 FakeFsEntry::Symlink { .. } => unreachable!(),
             }))
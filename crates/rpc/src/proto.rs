@@ -187,6 +187,8 @@ messages!(
     (GetNotificationsResponse, Foreground),
     (GetPrivateUserInfo, Foreground),
     (GetPrivateUserInfoResponse, Foreground),
+    (GetProjectExtensions, Background),
+    (GetProjectExtensionsResponse, Background),
     (GetProjectSymbols, Background),
     (GetProjectSymbolsResponse, Background),
     (GetReferences, Background),
@@ -321,6 +323,7 @@ request_messages!(
     (GetHover, GetHoverResponse),
     (GetNotifications, GetNotificationsResponse),
     (GetPrivateUserInfo, GetPrivateUserInfoResponse),
+    (GetProjectExtensions, GetProjectExtensionsResponse),
     (GetProjectSymbols, GetProjectSymbolsResponse),
     (GetReferences, GetReferencesResponse),
     (GetTypeDefinition, GetTypeDefinitionResponse),
@@ -397,6 +400,7 @@ entity_messages!(
     GetImplementation,
     GetDocumentHighlights,
     GetHover,
+    GetProjectExtensions,
     GetProjectSymbols,
     GetReferences,
     GetTypeDefinition,
@@ -91,6 +91,37 @@ pub fn handle_settings_file_changes(
     .detach();
 }
 
+/// Applies the contents of the managed settings file, which an administrator
+/// can deploy to enforce settings across an organization.
+pub fn handle_managed_settings_file_changes(
+    mut managed_settings_file_rx: mpsc::UnboundedReceiver<String>,
+    cx: &mut AppContext,
+) {
+    let managed_settings_content = cx
+        .background_executor()
+        .block(managed_settings_file_rx.next())
+        .unwrap();
+    cx.update_global(|store: &mut SettingsStore, cx| {
+        store
+            .set_managed_settings(&managed_settings_content, cx)
+            .log_err();
+    });
+    cx.spawn(move |mut cx| async move {
+        while let Some(managed_settings_content) = managed_settings_file_rx.next().await {
+            let result = cx.update_global(|store: &mut SettingsStore, cx| {
+                store
+                    .set_managed_settings(&managed_settings_content, cx)
+                    .log_err();
+                cx.refresh();
+            });
+            if result.is_err() {
+                break; // App dropped
+            }
+        }
+    })
+    .detach();
+}
+
 async fn load_settings(fs: &Arc<dyn Fs>) -> Result<String> {
     match fs.load(&paths::SETTINGS).await {
         result @ Ok(_) => result,
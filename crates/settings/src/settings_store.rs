@@ -136,6 +136,7 @@ pub struct SettingsStore {
     setting_values: HashMap<TypeId, Box<dyn AnySettingValue>>,
     raw_default_settings: serde_json::Value,
     raw_user_settings: serde_json::Value,
+    raw_managed_settings: serde_json::Value,
     raw_local_settings: BTreeMap<(usize, Arc<Path>), serde_json::Value>,
     tab_size_callback: Option<(
         TypeId,
@@ -151,6 +152,7 @@ impl Default for SettingsStore {
             setting_values: Default::default(),
             raw_default_settings: serde_json::json!({}),
             raw_user_settings: serde_json::json!({}),
+            raw_managed_settings: serde_json::json!({}),
             raw_local_settings: Default::default(),
             tab_size_callback: Default::default(),
         }
@@ -225,6 +227,13 @@ impl SettingsStore {
                 }
             }
 
+            if let Some(managed_settings) = setting_value
+                .deserialize_setting(&self.raw_managed_settings)
+                .log_err()
+            {
+                user_values_stack.push(managed_settings);
+            }
+
             if let Some(setting) = setting_value
                 .load_setting(&default_settings, &user_values_stack, cx)
                 .context("A default setting must be added to the `default.json` file")
@@ -407,6 +416,29 @@ impl SettingsStore {
         }
     }
 
+    /// Sets the managed settings via a JSON string.
+    ///
+    /// Managed settings are deployed by an administrator, and take precedence
+    /// over the user's settings and any local settings.
+    pub fn set_managed_settings(
+        &mut self,
+        managed_settings_content: &str,
+        cx: &mut AppContext,
+    ) -> Result<()> {
+        let settings: serde_json::Value = if managed_settings_content.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            parse_json_with_comments(managed_settings_content)?
+        };
+        if settings.is_object() {
+            self.raw_managed_settings = settings;
+            self.recompute_values(None, cx)?;
+            Ok(())
+        } else {
+            Err(anyhow!("settings must be an object"))
+        }
+    }
+
     /// Add or remove a set of local settings via a JSON string.
     pub fn set_local_settings(
         &mut self,
@@ -535,6 +567,11 @@ impl SettingsStore {
         for setting_value in self.setting_values.values_mut() {
             let default_settings = setting_value.deserialize_setting(&self.raw_default_settings)?;
 
+            // Managed settings are applied on top of every other layer.
+            let mut managed_settings = setting_value
+                .deserialize_setting(&self.raw_managed_settings)
+                .log_err();
+
             user_settings_stack.clear();
             paths_stack.clear();
 
@@ -561,12 +598,17 @@ impl SettingsStore {
 
             // If the global settings file changed, reload the global value for the field.
             if changed_local_path.is_none() {
+                let has_managed_settings = managed_settings.is_some();
+                user_settings_stack.extend(managed_settings.take());
                 if let Some(value) = setting_value
                     .load_setting(&default_settings, &user_settings_stack, cx)
                     .log_err()
                 {
                     setting_value.set_global_value(value);
                 }
+                if has_managed_settings {
+                    managed_settings = user_settings_stack.pop();
+                }
             }
 
             // Reload the local values for the setting.
@@ -597,12 +639,17 @@ impl SettingsStore {
                         continue;
                     }
 
+                    let has_managed_settings = managed_settings.is_some();
+                    user_settings_stack.extend(managed_settings.take());
                     if let Some(value) = setting_value
                         .load_setting(&default_settings, &user_settings_stack, cx)
                         .log_err()
                     {
                         setting_value.set_local_value(*root_id, path.clone(), value);
                     }
+                    if has_managed_settings {
+                        managed_settings = user_settings_stack.pop();
+                    }
                 }
             }
         }
@@ -623,6 +670,7 @@ impl Debug for SettingsStore {
             )
             .field("default_settings", &self.raw_default_settings)
             .field("user_settings", &self.raw_user_settings)
+            .field("managed_settings", &self.raw_managed_settings)
             .field("local_settings", &self.raw_local_settings)
             .finish_non_exhaustive()
     }
@@ -1102,6 +1150,75 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_managed_settings_take_precedence(cx: &mut AppContext) {
+        let mut store = SettingsStore::default();
+        store.register_setting::<UserSettings>(cx);
+        store.register_setting::<TurboSetting>(cx);
+        store
+            .set_default_settings(
+                r#"{
+                    "turbo": false,
+                    "user": {
+                        "name": "John Doe",
+                        "age": 30,
+                        "staff": false
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+        store
+            .set_managed_settings(r#"{ "turbo": true, "user": { "staff": true } }"#, cx)
+            .unwrap();
+        store
+            .set_user_settings(r#"{ "turbo": false, "user": { "age": 31 } }"#, cx)
+            .unwrap();
+        store
+            .set_local_settings(
+                1,
+                Path::new("/root1").into(),
+                Some(r#"{ "user": { "staff": false, "name": "Jane Doe" } }"#),
+                cx,
+            )
+            .unwrap();
+
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(true));
+        assert_eq!(
+            store.get::<UserSettings>(None),
+            &UserSettings {
+                name: "John Doe".to_string(),
+                age: 31,
+                staff: true,
+            }
+        );
+        assert_eq!(
+            store.get::<UserSettings>(Some(SettingsLocation {
+                worktree_id: 1,
+                path: Path::new("/root1/something"),
+            })),
+            &UserSettings {
+                name: "Jane Doe".to_string(),
+                age: 31,
+                staff: true,
+            }
+        );
+
+        store.set_managed_settings("", cx).unwrap();
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(false));
+        assert_eq!(
+            store.get::<UserSettings>(Some(SettingsLocation {
+                worktree_id: 1,
+                path: Path::new("/root1/something"),
+            })),
+            &UserSettings {
+                name: "Jane Doe".to_string(),
+                age: 31,
+                staff: false,
+            }
+        );
+    }
+
     #[gpui::test]
     fn test_setting_store_update(cx: &mut AppContext) {
         let mut store = SettingsStore::default();
@@ -254,6 +254,13 @@ impl ThemeRegistry {
         Ok(theme)
     }
 
+    /// Parses a user theme from the contents of its JSON file.
+    pub fn parse_user_theme(theme_json: &[u8]) -> Result<ThemeFamilyContent> {
+        let theme = serde_json_lenient::from_slice(theme_json)?;
+
+        Ok(theme)
+    }
+
     /// Loads the user theme from the specified path and adds it to the registry.
     pub async fn load_user_theme(&self, theme_path: &Path, fs: Arc<dyn Fs>) -> Result<()> {
         let theme = Self::read_user_theme(theme_path, fs).await?;
@@ -79,6 +79,7 @@ pub enum IconName {
     FolderX,
     Github,
     Hash,
+    Heart,
     InlayHint,
     Link,
     MagicWand,
@@ -93,6 +94,7 @@ pub enum IconName {
     Option,
     PageDown,
     PageUp,
+    Pin,
     Play,
     Plus,
     Public,
@@ -104,10 +106,13 @@ pub enum IconName {
     ReplyArrow,
     Screen,
     SelectAll,
+    Settings,
     Shift,
     Snip,
     Space,
     Split,
+    Star,
+    StarFilled,
     Tab,
     Terminal,
     Update,
@@ -173,6 +178,7 @@ impl IconName {
             IconName::FolderX => "icons/stop_sharing.svg",
             IconName::Github => "icons/github.svg",
             IconName::Hash => "icons/hash.svg",
+            IconName::Heart => "icons/heart.svg",
             IconName::InlayHint => "icons/inlay_hint.svg",
             IconName::Link => "icons/link.svg",
             IconName::MagicWand => "icons/magic_wand.svg",
@@ -187,6 +193,7 @@ impl IconName {
             IconName::Option => "icons/option.svg",
             IconName::PageDown => "icons/page_down.svg",
             IconName::PageUp => "icons/page_up.svg",
+            IconName::Pin => "icons/pin.svg",
             IconName::Play => "icons/play.svg",
             IconName::Plus => "icons/plus.svg",
             IconName::Public => "icons/public.svg",
@@ -198,10 +205,13 @@ impl IconName {
             IconName::ReplyArrow => "icons/reply_arrow.svg",
             IconName::Screen => "icons/desktop.svg",
             IconName::SelectAll => "icons/select_all.svg",
+            IconName::Settings => "icons/settings.svg",
             IconName::Shift => "icons/shift.svg",
             IconName::Snip => "icons/snip.svg",
             IconName::Space => "icons/space.svg",
             IconName::Split => "icons/split.svg",
+            IconName::Star => "icons/star.svg",
+            IconName::StarFilled => "icons/star_filled.svg",
             IconName::Tab => "icons/tab.svg",
             IconName::Terminal => "icons/terminal.svg",
             IconName::Update => "icons/update.svg",
@@ -56,6 +56,16 @@ lazy_static::lazy_static! {
         CRASHES_DIR.join("retired")
     };
     pub static ref SETTINGS: PathBuf = CONFIG_DIR.join("settings.json");
+    pub static ref MANAGED_SETTINGS: PathBuf = if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/Zed/managed_settings.json")
+    } else if cfg!(target_os = "windows") {
+        std::env::var_os("ProgramData")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("C:\\ProgramData"))
+            .join("Zed/managed_settings.json")
+    } else {
+        PathBuf::from("/etc/zed/managed_settings.json")
+    };
     pub static ref KEYMAP: PathBuf = CONFIG_DIR.join("keymap.json");
     pub static ref TASKS: PathBuf = CONFIG_DIR.join("tasks.json");
     pub static ref LAST_USERNAME: PathBuf = CONFIG_DIR.join("last-username.txt");
@@ -63,6 +73,8 @@ lazy_static::lazy_static! {
     pub static ref OLD_LOG: PathBuf = LOGS_DIR.join("Zed.log.old");
     pub static ref LOCAL_SETTINGS_RELATIVE_PATH: &'static Path = Path::new(".zed/settings.json");
     pub static ref LOCAL_TASKS_RELATIVE_PATH: &'static Path = Path::new(".zed/tasks.json");
+    pub static ref LOCAL_EXTENSIONS_RELATIVE_PATH: &'static Path =
+        Path::new(".zed/extensions.json");
     pub static ref TEMP_DIR: PathBuf = if cfg!(target_os = "widows") {
         dirs::data_local_dir()
             .expect("failed to determine LocalAppData directory")
@@ -4640,6 +4640,20 @@ pub fn join_in_room_project(
 }
 
 pub fn restart(_: &Restart, cx: &mut AppContext) {
+    let confirm = confirm_restart(cx);
+    cx.spawn(|cx| async move {
+        if confirm.await? {
+            cx.update(|cx| cx.restart())?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Asks the user to confirm a restart, if configured to, and prepares every
+/// workspace to close. Resolves to `false` if any of those prompts were
+/// cancelled, in which case the app should stay open.
+pub fn confirm_restart(cx: &mut AppContext) -> Task<Result<bool>> {
     let should_confirm = WorkspaceSettings::get_global(cx).confirm_quit;
     let mut workspace_windows = cx
         .windows()
@@ -4669,7 +4683,7 @@ pub fn restart(_: &Restart, cx: &mut AppContext) {
         if let Some(prompt) = prompt {
             let answer = prompt.await?;
             if answer != 0 {
-                return Ok(());
+                return Ok(false);
             }
         }
 
@@ -4679,14 +4693,13 @@ pub fn restart(_: &Restart, cx: &mut AppContext) {
                 workspace.prepare_to_close(true, cx)
             }) {
                 if !should_close.await? {
-                    return Ok(());
+                    return Ok(false);
                 }
             }
         }
 
-        cx.update(|cx| cx.restart())
+        Ok(true)
     })
-    .detach_and_log_err(cx);
 }
 
 fn parse_pixel_position_env_var(value: &str) -> Option<Point<GlobalPixels>> {
@@ -4,7 +4,7 @@
 use anyhow::{anyhow, Context as _, Result};
 use backtrace::Backtrace;
 use chrono::Utc;
-use cli::FORCE_CLI_MODE_ENV_VAR_NAME;
+use cli::{DISABLE_EXTENSIONS_ARG, FORCE_CLI_MODE_ENV_VAR_NAME};
 use client::{parse_zed_link, Client, UserStore};
 use collab_ui::channel_view::ChannelView;
 use db::kvp::KEY_VALUE_STORE;
@@ -24,7 +24,8 @@ use parking_lot::Mutex;
 use release_channel::{AppCommitSha, ReleaseChannel, RELEASE_CHANNEL};
 use serde::{Deserialize, Serialize};
 use settings::{
-    default_settings, handle_settings_file_changes, watch_config_file, Settings, SettingsStore,
+    default_settings, handle_managed_settings_file_changes, handle_settings_file_changes,
+    watch_config_file, Settings, SettingsStore,
 };
 use simplelog::ConfigBuilder;
 use smol::process::Command;
@@ -88,6 +89,11 @@ fn main() {
         fs.clone(),
         paths::SETTINGS.clone(),
     );
+    let managed_settings_file_rx = watch_config_file(
+        &app.background_executor(),
+        fs.clone(),
+        paths::MANAGED_SETTINGS.clone(),
+    );
     let user_keymap_file_rx = watch_config_file(
         &app.background_executor(),
         fs.clone(),
@@ -133,6 +139,7 @@ fn main() {
             .unwrap();
         cx.set_global(store);
         handle_settings_file_changes(user_settings_file_rx, cx);
+        handle_managed_settings_file_changes(managed_settings_file_rx, cx);
         handle_keymap_file_changes(user_keymap_file_rx, cx);
         client::init_settings(cx);
 
@@ -178,6 +185,7 @@ fn main() {
             node_runtime.clone(),
             languages.clone(),
             ThemeRegistry::global(cx),
+            env::args().any(|arg| arg == DISABLE_EXTENSIONS_ARG),
             cx,
         );
 
@@ -343,6 +351,22 @@ fn handle_open_request(
         }));
     }
 
+    if let Some(extension_id) = request.open_extension {
+        let task = task.take();
+        let app_state = app_state.clone();
+        cx.spawn(|mut cx| async move {
+            if let Some(task) = task {
+                task.await?;
+            }
+            let workspace_window =
+                workspace::get_any_active_workspace(app_state, cx.clone()).await?;
+            workspace_window.update(&mut cx, |workspace, cx| {
+                extensions_ui::open_extension(workspace, &extension_id, cx)
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     if !request.open_channel_notes.is_empty() || request.join_channel.is_some() {
         cx.spawn(|mut cx| async move {
             if let Some(task) = task {
@@ -898,6 +922,7 @@ fn stdout_is_a_pty() -> bool {
 fn collect_url_args(cx: &AppContext) -> Vec<String> {
     env::args()
         .skip(1)
+        .filter(|arg| arg != DISABLE_EXTENSIONS_ARG)
         .filter_map(|arg| match std::fs::canonicalize(Path::new(&arg)) {
             Ok(path) => Some(format!("file://{}", path.to_string_lossy())),
             Err(error) => {
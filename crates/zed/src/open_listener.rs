@@ -26,6 +26,7 @@ pub struct OpenRequest {
     pub open_paths: Vec<PathLikeWithPosition<PathBuf>>,
     pub open_channel_notes: Vec<(u64, Option<String>)>,
     pub join_channel: Option<u64>,
+    pub open_extension: Option<String>,
 }
 
 impl OpenRequest {
@@ -60,7 +61,14 @@ impl OpenRequest {
 
     fn parse_request_path(&mut self, request_path: &str) -> Result<()> {
         let mut parts = request_path.split('/');
-        if parts.next() == Some("channel") {
+        let first_part = parts.next();
+        if first_part == Some("extensions") {
+            if let Some(extension_id) = parts.next().filter(|id| !id.is_empty()) {
+                self.open_extension = Some(extension_id.to_string());
+                return Ok(());
+            }
+        }
+        if first_part == Some("channel") {
             if let Some(slug) = parts.next() {
                 if let Some(id_str) = slug.split('-').last() {
                     if let Ok(channel_id) = id_str.parse::<u64>() {